@@ -0,0 +1,413 @@
+//! Sources of the blocks that are replayed by the benchmarks.
+//!
+//! Blocks are either fetched from an RPC endpoint, or read from local data: a directory of Era1
+//! files, an RLP encoded chain file as used by `reth import`, or the datadir of another reth node.
+//! The local sources only support Ethereum blocks and make the benchmarks reproducible without
+//! network access.
+
+use crate::{
+    bench_mode::BenchMode,
+    valid_payload::{block_to_new_payload, consensus_block_to_new_payload},
+};
+use alloy_primitives::{address, BlockNumber, B256};
+use alloy_provider::{network::AnyNetwork, Provider, RootProvider};
+use alloy_rpc_client::ClientBuilder;
+use clap::Args;
+use eyre::{ensure, OptionExt};
+use reth_chainspec::ChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_consensus::noop::NoopConsensus;
+use reth_db::DatabaseEnv;
+use reth_downloaders::file_client::ChunkedFileReader;
+use reth_era::{era1_file::Era1Reader, execution_types::MAX_BLOCKS_PER_ERA1, DecodeCompressed};
+use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+use reth_ethereum_primitives::{Block, BlockBody};
+use reth_node_api::{EngineApiMessageVersion, NodeTypesWithDBAdapter};
+use reth_node_ethereum::EthereumNode;
+use reth_provider::{
+    providers::ReadOnlyConfig, BlockHashReader, BlockNumReader, BlockReader, ProviderFactory,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+/// Number of blocks below the benchmarked range that are read from local sources, so that the
+/// safe and finalized blocks of the forkchoice updates are known.
+const FORKCHOICE_DEPTH: u64 = 64;
+
+/// Arguments selecting where the replayed blocks are read from.
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Block source")]
+pub struct BlockSourceArgs {
+    #[command(flatten)]
+    source: SourceArgs,
+
+    /// The chain of the datadir given with `--datadir`.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        default_value = EthereumChainSpecParser::SUPPORTED_CHAINS[0],
+        value_parser = EthereumChainSpecParser::parser(),
+        requires = "datadir"
+    )]
+    chain: Arc<ChainSpec>,
+}
+
+/// The source of the replayed blocks, exactly one of them must be given.
+#[derive(Debug, Args)]
+#[group(required = true, multiple = false)]
+struct SourceArgs {
+    /// The RPC url to use for getting data.
+    #[arg(long, value_name = "RPC_URL", verbatim_doc_comment)]
+    rpc_url: Option<String>,
+
+    /// Directory of Era1 files to read the blocks from.
+    #[arg(long, value_name = "ERA1_DIR", verbatim_doc_comment)]
+    era1_dir: Option<PathBuf>,
+
+    /// RLP encoded chain file to read the blocks from, as used by `reth import`.
+    #[arg(long, value_name = "CHAIN_FILE", verbatim_doc_comment)]
+    chain_file: Option<PathBuf>,
+
+    /// Datadir of a reth node to read the blocks from.
+    ///
+    /// The database and static files are opened read-only.
+    #[arg(long, value_name = "DATADIR", verbatim_doc_comment)]
+    datadir: Option<PathBuf>,
+}
+
+/// A block converted into the `engine_newPayload` call that replays it.
+#[derive(Debug)]
+pub(crate) struct BenchPayload {
+    /// The number of the block.
+    pub(crate) number: BlockNumber,
+    /// The hash of the block.
+    pub(crate) hash: B256,
+    /// The gas used by the block.
+    pub(crate) gas_used: u64,
+    /// The version of the `engine_newPayload` call.
+    pub(crate) version: EngineApiMessageVersion,
+    /// The parameters of the `engine_newPayload` call.
+    pub(crate) params: serde_json::Value,
+}
+
+impl BenchPayload {
+    /// Converts a block of a local source.
+    fn new(hash: B256, block: &Block) -> eyre::Result<Self> {
+        let (version, params) = consensus_block_to_new_payload(block, false)?;
+        Ok(Self { number: block.number, hash, gas_used: block.gas_used, version, params })
+    }
+}
+
+/// Where the replayed blocks are read from.
+#[derive(Debug)]
+pub(crate) enum BlockSource {
+    /// Blocks fetched from an RPC endpoint.
+    Rpc {
+        /// The provider of the RPC endpoint.
+        provider: RootProvider<AnyNetwork>,
+        /// Whether the chain is an OP rollup.
+        is_optimism: bool,
+    },
+    /// Blocks read from Era1 files or an RLP chain file.
+    File(FileBlocks),
+    /// Blocks read from the datadir of a reth node.
+    Datadir(ProviderFactory<NodeTypesWithDBAdapter<EthereumNode, Arc<DatabaseEnv>>>),
+}
+
+impl BlockSource {
+    /// Creates the block source selected by the [`BlockSourceArgs`].
+    ///
+    /// Local sources require a closed range of blocks, and fail if a block of the range is
+    /// missing.
+    pub(crate) async fn new(args: &BlockSourceArgs, mode: &BenchMode) -> eyre::Result<Self> {
+        if let Some(rpc_url) = &args.source.rpc_url {
+            info!("Running benchmark using data from RPC URL: {}", rpc_url);
+            return Self::rpc(rpc_url).await
+        }
+
+        let BenchMode::Range(range) = mode else {
+            eyre::bail!(
+                "`--from` and `--to` must be provided when replaying blocks from local data"
+            )
+        };
+        let blocks = range.start().saturating_sub(FORKCHOICE_DEPTH)..=*range.end();
+
+        let source = if let Some(dir) = &args.source.era1_dir {
+            info!("Running benchmark using blocks from Era1 files in {:?}", dir);
+            Self::File(FileBlocks::from_era1_dir(dir, &blocks)?)
+        } else if let Some(path) = &args.source.chain_file {
+            info!("Running benchmark using blocks from chain file {:?}", path);
+            Self::File(FileBlocks::from_chain_file(path, &blocks).await?)
+        } else {
+            let datadir = args.source.datadir.as_ref().ok_or_eyre("no block source provided")?;
+            info!("Running benchmark using blocks from datadir {:?}", datadir);
+            let factory = EthereumNode::provider_factory_builder()
+                .open_read_only(args.chain.clone(), ReadOnlyConfig::from_datadir(datadir))?;
+            let last_block = factory.last_block_number()?;
+            ensure!(
+                last_block >= *range.end(),
+                "datadir only contains blocks up to {last_block}, requested up to {}",
+                range.end()
+            );
+            Self::Datadir(factory)
+        };
+
+        if let Self::File(file_blocks) = &source {
+            file_blocks.ensure_range(range)?;
+        }
+
+        Ok(source)
+    }
+
+    /// Creates a block source for the given RPC url, checking whether the chain is an OP rollup.
+    async fn rpc(rpc_url: &str) -> eyre::Result<Self> {
+        let client = ClientBuilder::default().http(rpc_url.parse()?);
+        let provider = RootProvider::<AnyNetwork>::new(client);
+
+        // Check if this is an OP chain by checking code at a predeploy address.
+        let is_optimism = !provider
+            .get_code_at(address!("0x420000000000000000000000000000000000000F"))
+            .await?
+            .is_empty();
+
+        Ok(Self::Rpc { provider, is_optimism })
+    }
+
+    /// Returns the number of the latest block of the source.
+    pub(crate) async fn latest_block_number(&self) -> eyre::Result<BlockNumber> {
+        match self {
+            Self::Rpc { provider, .. } => Ok(provider.get_block_number().await?),
+            Self::File(blocks) => blocks.blocks.keys().next_back().copied().ok_or_eyre("no blocks"),
+            Self::Datadir(factory) => Ok(factory.last_block_number()?),
+        }
+    }
+
+    /// Returns the `engine_newPayload` call replaying the given block.
+    pub(crate) async fn payload(&self, number: BlockNumber) -> eyre::Result<BenchPayload> {
+        match self {
+            Self::Rpc { provider, is_optimism } => {
+                let block = provider
+                    .get_block_by_number(number.into())
+                    .full()
+                    .await?
+                    .ok_or_else(|| eyre::eyre!("block {number} not found"))?;
+                let (hash, gas_used) = (block.header.hash, block.header.gas_used);
+                let (version, params) = block_to_new_payload(block, *is_optimism)?;
+                Ok(BenchPayload { number, hash, gas_used, version, params })
+            }
+            Self::File(blocks) => {
+                let (hash, block) = blocks
+                    .blocks
+                    .get(&number)
+                    .ok_or_else(|| eyre::eyre!("block {number} not found"))?;
+                BenchPayload::new(*hash, block)
+            }
+            Self::Datadir(factory) => {
+                let block = factory
+                    .block_by_number(number)?
+                    .ok_or_else(|| eyre::eyre!("block {number} not found"))?;
+                BenchPayload::new(block.header.hash_slow(), &block)
+            }
+        }
+    }
+
+    /// Returns the hash of the given block.
+    ///
+    /// Blocks below the first block read from a file resolve to the first block, so that the
+    /// forkchoice updates of the first benchmarked blocks have safe and finalized blocks.
+    pub(crate) async fn block_hash(&self, number: BlockNumber) -> eyre::Result<B256> {
+        match self {
+            Self::Rpc { provider, .. } => Ok(provider
+                .get_block_by_number(number.into())
+                .await?
+                .ok_or_else(|| eyre::eyre!("block {number} not found"))?
+                .header
+                .hash),
+            Self::File(blocks) => blocks
+                .blocks
+                .range(number..)
+                .next()
+                .map(|(_, (hash, _))| *hash)
+                .ok_or_else(|| eyre::eyre!("block {number} not found")),
+            Self::Datadir(factory) => {
+                factory.block_hash(number)?.ok_or_else(|| eyre::eyre!("block {number} not found"))
+            }
+        }
+    }
+}
+
+/// Blocks read from a file into memory, with their hashes.
+#[derive(Debug, Default)]
+pub(crate) struct FileBlocks {
+    blocks: BTreeMap<BlockNumber, (B256, Block)>,
+}
+
+impl FileBlocks {
+    /// Reads the blocks of the given range from the Era1 files in the directory.
+    fn from_era1_dir(dir: &Path, range: &RangeInclusive<BlockNumber>) -> eyre::Result<Self> {
+        let mut files = Vec::new();
+        for entry in reth_fs_util::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension() == Some("era1".as_ref()) {
+                files.push(path);
+            }
+        }
+        // Era1 file names contain the zero-padded era number, so they sort by block number
+        files.sort();
+
+        let mut file_blocks = Self::default();
+        for path in files {
+            for (index, block) in Era1Reader::new(reth_fs_util::open(&path)?).iter().enumerate() {
+                let block = block?;
+                let header: alloy_consensus::Header = block.header.decode()?;
+
+                if header.number < *range.start() {
+                    if index == 0 && header.number + MAX_BLOCKS_PER_ERA1 as u64 <= *range.start() {
+                        // the whole file is below the range
+                        break
+                    }
+                    continue
+                }
+                if header.number > *range.end() {
+                    return Ok(file_blocks)
+                }
+
+                let body: BlockBody = block.body.decode()?;
+                file_blocks.insert(Block::new(header, body));
+            }
+        }
+
+        Ok(file_blocks)
+    }
+
+    /// Reads the blocks of the given range from an RLP encoded chain file.
+    async fn from_chain_file(
+        path: &Path,
+        range: &RangeInclusive<BlockNumber>,
+    ) -> eyre::Result<Self> {
+        let mut reader = ChunkedFileReader::new(path, None).await?;
+
+        let mut file_blocks = Self::default();
+        while let Some(mut file_client) =
+            reader.next_chunk::<Block>(NoopConsensus::arc(), None).await?
+        {
+            let mut headers = file_client
+                .headers_iter()
+                .filter(|header| range.contains(&header.number))
+                .map(|header| (header.number, header.clone()))
+                .collect::<HashMap<_, _>>();
+            for (number, body) in file_client.bodies_iter_mut() {
+                if let Some(header) = headers.remove(&number) {
+                    file_blocks.insert(Block::new(header, std::mem::take(body)));
+                }
+            }
+
+            if file_client.max_block().is_some_and(|max| max >= *range.end()) {
+                break
+            }
+        }
+
+        Ok(file_blocks)
+    }
+
+    /// Inserts the block with its hash.
+    fn insert(&mut self, block: Block) {
+        self.blocks.insert(block.number, (block.header.hash_slow(), block));
+    }
+
+    /// Returns an error if a block of the range is missing.
+    fn ensure_range(&self, range: &RangeInclusive<BlockNumber>) -> eyre::Result<()> {
+        for number in range.clone() {
+            ensure!(self.blocks.contains_key(&number), "block {number} is missing from the file");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::U256;
+    use alloy_rlp::Encodable;
+    use reth_era::{
+        e2s_types::IndexEntry,
+        era1_file::{Era1File, Era1Writer},
+        era1_types::{BlockIndex, Era1Group, Era1Id},
+        execution_types::{Accumulator, BlockTuple},
+    };
+
+    fn chain(blocks: u64) -> Vec<Block> {
+        let mut parent_hash = B256::ZERO;
+        (0..blocks)
+            .map(|number| {
+                let header =
+                    Header { number, parent_hash, gas_used: number * 21_000, ..Default::default() };
+                parent_hash = header.hash_slow();
+                Block::new(header, BlockBody::default())
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn read_chain_file() {
+        let blocks = chain(20);
+        let mut encoded = Vec::new();
+        for block in &blocks {
+            block.encode(&mut encoded);
+        }
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("chain.rlp");
+        reth_fs_util::write(&path, encoded).unwrap();
+
+        let file_blocks = FileBlocks::from_chain_file(&path, &(5..=10)).await.unwrap();
+        assert_eq!(
+            file_blocks.blocks.keys().copied().collect::<Vec<_>>(),
+            (5..=10).collect::<Vec<_>>()
+        );
+        file_blocks.ensure_range(&(5..=10)).unwrap();
+        assert!(file_blocks.ensure_range(&(5..=11)).is_err());
+
+        let source = BlockSource::File(file_blocks);
+        let payload = source.payload(7).await.unwrap();
+        assert_eq!(payload.hash, blocks[7].header.hash_slow());
+        assert_eq!(payload.gas_used, 7 * 21_000);
+        assert_eq!(payload.version, EngineApiMessageVersion::V1);
+
+        // Blocks below the file resolve to the first block
+        assert_eq!(source.block_hash(0).await.unwrap(), blocks[5].header.hash_slow());
+        assert!(source.block_hash(11).await.is_err());
+    }
+
+    #[test]
+    fn read_era1_dir() {
+        let blocks = chain(20);
+        let tuples = blocks
+            .iter()
+            .map(|block| {
+                BlockTuple::from_alloy_block(block, &Vec::<u8>::new(), U256::ZERO).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let group = Era1Group::new(
+            tuples,
+            Accumulator::new(B256::ZERO),
+            BlockIndex::new(0, vec![0; blocks.len()]),
+        );
+        let temp_dir = tempfile::tempdir().unwrap();
+        let id = Era1Id::new("test", 0, blocks.len() as u32);
+        Era1Writer::create(temp_dir.path().join(id.to_file_name()), &Era1File::new(group, id))
+            .unwrap();
+
+        let file_blocks = FileBlocks::from_era1_dir(temp_dir.path(), &(3..=8)).unwrap();
+        file_blocks.ensure_range(&(3..=8)).unwrap();
+        assert_eq!(file_blocks.blocks.len(), 6);
+        assert_eq!(file_blocks.blocks[&8].0, blocks[8].header.hash_slow());
+    }
+}
@@ -1,41 +1,42 @@
 //! This contains the [`BenchContext`], which is information that all replay-based benchmarks need.
 //! The initialization code is also the same, so this can be shared across benchmark commands.
 
-use crate::{authenticated_transport::AuthenticatedTransportConnect, bench_mode::BenchMode};
-use alloy_eips::BlockNumberOrTag;
-use alloy_primitives::address;
-use alloy_provider::{network::AnyNetwork, Provider, RootProvider};
+use crate::{
+    authenticated_transport::AuthenticatedTransportConnect,
+    bench::block_source::{BlockSource, BlockSourceArgs},
+    bench_mode::BenchMode,
+};
+use alloy_provider::{network::AnyNetwork, RootProvider};
 use alloy_rpc_client::ClientBuilder;
 use alloy_rpc_types_engine::JwtSecret;
 use reqwest::Url;
 use reth_node_core::args::BenchmarkArgs;
 use tracing::info;
 
-/// This is intended to be used by benchmarks that replay blocks from an RPC.
+/// This is intended to be used by benchmarks that replay blocks from an RPC or from local data.
 ///
-/// It contains an authenticated provider for engine API queries, a block provider for block
+/// It contains an authenticated provider for engine API queries, a [`BlockSource`] for block
 /// queries, a [`BenchMode`] to determine whether the benchmark should run for a closed or open
 /// range of blocks, and the next block to fetch.
 pub(crate) struct BenchContext {
     /// The auth provider is used for engine API queries.
     pub(crate) auth_provider: RootProvider<AnyNetwork>,
-    /// The block provider is used for block queries.
-    pub(crate) block_provider: RootProvider<AnyNetwork>,
+    /// The block source is used for block queries.
+    pub(crate) block_source: BlockSource,
     /// The benchmark mode, which defines whether the benchmark should run for a closed or open
     /// range of blocks.
     pub(crate) benchmark_mode: BenchMode,
     /// The next block to fetch.
     pub(crate) next_block: u64,
-    /// Whether the chain is an OP rollup.
-    pub(crate) is_optimism: bool,
 }
 
 impl BenchContext {
     /// This is the initialization code for most benchmarks, taking in a [`BenchmarkArgs`] and
     /// returning the providers needed to run a benchmark.
-    pub(crate) async fn new(bench_args: &BenchmarkArgs, rpc_url: String) -> eyre::Result<Self> {
-        info!("Running benchmark using data from RPC URL: {}", rpc_url);
-
+    pub(crate) async fn new(
+        bench_args: &BenchmarkArgs,
+        source_args: &BlockSourceArgs,
+    ) -> eyre::Result<Self> {
         // Ensure that output directory exists and is a directory
         if let Some(output) = &bench_args.output {
             if output.is_file() {
@@ -48,20 +49,12 @@ impl BenchContext {
             }
         }
 
-        // set up alloy client for blocks
-        let client = ClientBuilder::default().http(rpc_url.parse()?);
-        let block_provider = RootProvider::<AnyNetwork>::new(client);
-
-        // Check if this is an OP chain by checking code at a predeploy address.
-        let is_optimism = !block_provider
-            .get_code_at(address!("0x420000000000000000000000000000000000000F"))
-            .await?
-            .is_empty();
-
         // If neither `--from` nor `--to` are provided, we will run the benchmark continuously,
         // starting at the latest block.
         let mut benchmark_mode = BenchMode::new(bench_args.from, bench_args.to)?;
 
+        let block_source = BlockSource::new(source_args, &benchmark_mode).await?;
+
         // construct the authenticated provider
         let auth_jwt = bench_args
             .auth_jwtsecret
@@ -86,28 +79,19 @@ impl BenchContext {
         let first_block = match benchmark_mode {
             BenchMode::Continuous => {
                 // fetch Latest block
-                block_provider.get_block_by_number(BlockNumberOrTag::Latest).full().await?.unwrap()
+                block_source.latest_block_number().await?
             }
-            BenchMode::Range(ref mut range) => {
-                match range.next() {
-                    Some(block_number) => {
-                        // fetch first block in range
-                        block_provider
-                            .get_block_by_number(block_number.into())
-                            .full()
-                            .await?
-                            .unwrap()
-                    }
-                    None => {
-                        return Err(eyre::eyre!(
-                            "Benchmark mode range is empty, please provide a larger range"
-                        ));
-                    }
+            BenchMode::Range(ref mut range) => match range.next() {
+                Some(block_number) => block_number,
+                None => {
+                    return Err(eyre::eyre!(
+                        "Benchmark mode range is empty, please provide a larger range"
+                    ));
                 }
-            }
+            },
         };
 
-        let next_block = first_block.header.number + 1;
-        Ok(Self { auth_provider, block_provider, benchmark_mode, next_block, is_optimism })
+        let next_block = first_block + 1;
+        Ok(Self { auth_provider, block_source, benchmark_mode, next_block })
     }
 }
@@ -5,10 +5,12 @@ use reth_cli_runner::CliContext;
 use reth_node_core::args::LogArgs;
 use reth_tracing::FileWorkerGuard;
 
+mod block_source;
 mod context;
 mod new_payload_fcu;
 mod new_payload_only;
 mod output;
+mod rpc_load;
 mod send_payload;
 
 /// `reth bench` command
@@ -30,6 +32,10 @@ pub enum Subcommands {
     /// Benchmark which only calls subsequent `newPayload` calls.
     NewPayloadOnly(new_payload_only::Command),
 
+    /// Benchmark which replays a recorded RPC workload and reports the latency percentiles of each
+    /// RPC method.
+    RpcLoad(rpc_load::Command),
+
     /// Command for generating and sending an `engine_newPayload` request constructed from an RPC
     /// block.
     ///
@@ -52,6 +58,7 @@ impl BenchmarkCommand {
         match self.command {
             Subcommands::NewPayloadFcu(command) => command.execute(ctx).await,
             Subcommands::NewPayloadOnly(command) => command.execute(ctx).await,
+            Subcommands::RpcLoad(command) => command.execute(ctx).await,
             Subcommands::SendPayload(command) => command.execute(ctx).await,
         }
     }
@@ -3,15 +3,15 @@
 
 use crate::{
     bench::{
+        block_source::BlockSourceArgs,
         context::BenchContext,
         output::{
             CombinedResult, NewPayloadResult, TotalGasOutput, TotalGasRow, COMBINED_OUTPUT_SUFFIX,
             GAS_OUTPUT_SUFFIX,
         },
     },
-    valid_payload::{block_to_new_payload, call_forkchoice_updated, call_new_payload},
+    valid_payload::{call_forkchoice_updated, call_new_payload},
 };
-use alloy_provider::Provider;
 use alloy_rpc_types_engine::ForkchoiceState;
 use clap::Parser;
 use csv::Writer;
@@ -24,9 +24,8 @@ use tracing::{debug, info};
 /// `reth benchmark new-payload-fcu` command
 #[derive(Debug, Parser)]
 pub struct Command {
-    /// The RPC url to use for getting data.
-    #[arg(long, value_name = "RPC_URL", verbatim_doc_comment)]
-    rpc_url: String,
+    #[command(flatten)]
+    source: BlockSourceArgs,
 
     /// How long to wait after a forkchoice update before sending the next payload.
     #[arg(long, value_name = "WAIT_TIME", value_parser = parse_duration, verbatim_doc_comment)]
@@ -39,47 +38,25 @@ pub struct Command {
 impl Command {
     /// Execute `benchmark new-payload-fcu` command
     pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
-        let BenchContext {
-            benchmark_mode,
-            block_provider,
-            auth_provider,
-            mut next_block,
-            is_optimism,
-        } = BenchContext::new(&self.benchmark, self.rpc_url).await?;
+        let BenchContext { benchmark_mode, block_source, auth_provider, mut next_block } =
+            BenchContext::new(&self.benchmark, &self.source).await?;
 
         let (sender, mut receiver) = tokio::sync::mpsc::channel(1000);
         tokio::task::spawn(async move {
             while benchmark_mode.contains(next_block) {
-                let block_res = block_provider.get_block_by_number(next_block.into()).full().await;
-                let block = block_res.unwrap().unwrap();
-                let header = block.header.clone();
-
-                let (version, params) = block_to_new_payload(block, is_optimism).unwrap();
-                let head_block_hash = header.hash;
-                let safe_block_hash =
-                    block_provider.get_block_by_number(header.number.saturating_sub(32).into());
+                let payload = block_source.payload(next_block).await.unwrap();
 
+                let safe_block_hash = block_source.block_hash(payload.number.saturating_sub(32));
                 let finalized_block_hash =
-                    block_provider.get_block_by_number(header.number.saturating_sub(64).into());
+                    block_source.block_hash(payload.number.saturating_sub(64));
 
                 let (safe, finalized) = tokio::join!(safe_block_hash, finalized_block_hash,);
 
-                let safe_block_hash = safe.unwrap().expect("finalized block exists").header.hash;
-                let finalized_block_hash =
-                    finalized.unwrap().expect("finalized block exists").header.hash;
+                let safe_block_hash = safe.expect("safe block exists");
+                let finalized_block_hash = finalized.expect("finalized block exists");
 
                 next_block += 1;
-                sender
-                    .send((
-                        header,
-                        version,
-                        params,
-                        head_block_hash,
-                        safe_block_hash,
-                        finalized_block_hash,
-                    ))
-                    .await
-                    .unwrap();
+                sender.send((payload, safe_block_hash, finalized_block_hash)).await.unwrap();
             }
         });
 
@@ -88,27 +65,28 @@ impl Command {
         let total_benchmark_duration = Instant::now();
         let mut total_wait_time = Duration::ZERO;
 
-        while let Some((header, version, params, head, safe, finalized)) = {
+        while let Some((payload, safe, finalized)) = {
             let wait_start = Instant::now();
             let result = receiver.recv().await;
             total_wait_time += wait_start.elapsed();
             result
         } {
             // just put gas used here
-            let gas_used = header.gas_used;
-            let block_number = header.number;
+            let gas_used = payload.gas_used;
+            let block_number = payload.number;
+            let version = payload.version;
 
             debug!(target: "reth-bench", ?block_number, "Sending payload",);
 
             // construct fcu to call
             let forkchoice_state = ForkchoiceState {
-                head_block_hash: head,
+                head_block_hash: payload.hash,
                 safe_block_hash: safe,
                 finalized_block_hash: finalized,
             };
 
             let start = Instant::now();
-            call_new_payload(&auth_provider, version, params).await?;
+            call_new_payload(&auth_provider, version, payload.params).await?;
 
             let new_payload_result = NewPayloadResult { gas_used, latency: start.elapsed() };
 
@@ -2,15 +2,15 @@
 
 use crate::{
     bench::{
+        block_source::BlockSourceArgs,
         context::BenchContext,
         output::{
             NewPayloadResult, TotalGasOutput, TotalGasRow, GAS_OUTPUT_SUFFIX,
             NEW_PAYLOAD_OUTPUT_SUFFIX,
         },
     },
-    valid_payload::{block_to_new_payload, call_new_payload},
+    valid_payload::call_new_payload,
 };
-use alloy_provider::Provider;
 use clap::Parser;
 use csv::Writer;
 use reth_cli_runner::CliContext;
@@ -21,9 +21,8 @@ use tracing::{debug, info};
 /// `reth benchmark new-payload-only` command
 #[derive(Debug, Parser)]
 pub struct Command {
-    /// The RPC url to use for getting data.
-    #[arg(long, value_name = "RPC_URL", verbatim_doc_comment)]
-    rpc_url: String,
+    #[command(flatten)]
+    source: BlockSourceArgs,
 
     #[command(flatten)]
     benchmark: BenchmarkArgs,
@@ -32,25 +31,16 @@ pub struct Command {
 impl Command {
     /// Execute `benchmark new-payload-only` command
     pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
-        let BenchContext {
-            benchmark_mode,
-            block_provider,
-            auth_provider,
-            mut next_block,
-            is_optimism,
-        } = BenchContext::new(&self.benchmark, self.rpc_url).await?;
+        let BenchContext { benchmark_mode, block_source, auth_provider, mut next_block } =
+            BenchContext::new(&self.benchmark, &self.source).await?;
 
         let (sender, mut receiver) = tokio::sync::mpsc::channel(1000);
         tokio::task::spawn(async move {
             while benchmark_mode.contains(next_block) {
-                let block_res = block_provider.get_block_by_number(next_block.into()).full().await;
-                let block = block_res.unwrap().unwrap();
-                let header = block.header.clone();
-
-                let (version, params) = block_to_new_payload(block, is_optimism).unwrap();
+                let payload = block_source.payload(next_block).await.unwrap();
 
                 next_block += 1;
-                sender.send((header, version, params)).await.unwrap();
+                sender.send(payload).await.unwrap();
             }
         });
 
@@ -59,25 +49,25 @@ impl Command {
         let total_benchmark_duration = Instant::now();
         let mut total_wait_time = Duration::ZERO;
 
-        while let Some((header, version, params)) = {
+        while let Some(payload) = {
             let wait_start = Instant::now();
             let result = receiver.recv().await;
             total_wait_time += wait_start.elapsed();
             result
         } {
             // just put gas used here
-            let gas_used = header.gas_used;
+            let gas_used = payload.gas_used;
 
-            let block_number = header.number;
+            let block_number = payload.number;
 
             debug!(
                 target: "reth-bench",
-                number=?header.number,
+                number=?payload.number,
                 "Sending payload to engine",
             );
 
             let start = Instant::now();
-            call_new_payload(&auth_provider, version, params).await?;
+            call_new_payload(&auth_provider, payload.version, payload.params).await?;
 
             let new_payload_result = NewPayloadResult { gas_used, latency: start.elapsed() };
             info!(%new_payload_result);
@@ -3,7 +3,7 @@
 
 use reth_primitives_traits::constants::GIGAGAS;
 use serde::{ser::SerializeStruct, Serialize};
-use std::time::Duration;
+use std::{collections::BTreeMap, time::Duration};
 
 /// This is the suffix for gas output csv files.
 pub(crate) const GAS_OUTPUT_SUFFIX: &str = "total_gas.csv";
@@ -14,6 +14,12 @@ pub(crate) const COMBINED_OUTPUT_SUFFIX: &str = "combined_latency.csv";
 /// This is the suffix for new payload output csv files.
 pub(crate) const NEW_PAYLOAD_OUTPUT_SUFFIX: &str = "new_payload_latency.csv";
 
+/// This is the suffix for RPC request output csv files.
+pub(crate) const RPC_CALL_OUTPUT_SUFFIX: &str = "rpc_call_latency.csv";
+
+/// This is the suffix for RPC method output csv files.
+pub(crate) const RPC_METHOD_OUTPUT_SUFFIX: &str = "rpc_method_latency.csv";
+
 /// This represents the results of a single `newPayload` call in the benchmark, containing the gas
 /// used and the `newPayload` latency.
 #[derive(Debug)]
@@ -180,6 +186,121 @@ impl Serialize for TotalGasRow {
     }
 }
 
+/// This represents the result of a single request replayed by the `rpc-load` benchmark.
+#[derive(Debug)]
+pub(crate) struct RpcCallResult {
+    /// The RPC method of the request.
+    pub(crate) method: String,
+    /// The latency of the request.
+    pub(crate) latency: Duration,
+    /// Whether the request succeeded.
+    pub(crate) success: bool,
+}
+
+/// This serializes the `latency` field of the [`RpcCallResult`] to microseconds, for the csv
+/// writer.
+impl Serialize for RpcCallResult {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        let mut state = serializer.serialize_struct("RpcCallResult", 3)?;
+        state.serialize_field("method", &self.method)?;
+        state.serialize_field("latency", &self.latency.as_micros())?;
+        state.serialize_field("success", &self.success)?;
+        state.end()
+    }
+}
+
+/// This represents the latency percentiles of the requests of a single RPC method.
+///
+/// The percentiles only include the successful requests.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct RpcMethodSummary {
+    /// The RPC method.
+    pub(crate) method: String,
+    /// The number of requests.
+    pub(crate) requests: u64,
+    /// The number of failed requests.
+    pub(crate) errors: u64,
+    /// The median latency.
+    pub(crate) p50: Duration,
+    /// The 90th percentile latency.
+    pub(crate) p90: Duration,
+    /// The 99th percentile latency.
+    pub(crate) p99: Duration,
+    /// The maximum latency.
+    pub(crate) max: Duration,
+}
+
+impl RpcMethodSummary {
+    /// Summarizes the results per method, sorted by method.
+    pub(crate) fn from_results(results: &[RpcCallResult]) -> Vec<Self> {
+        let mut methods = BTreeMap::<&str, (Vec<Duration>, u64)>::new();
+        for result in results {
+            let (latencies, errors) = methods.entry(&result.method).or_default();
+            if result.success {
+                latencies.push(result.latency);
+            } else {
+                *errors += 1;
+            }
+        }
+
+        methods
+            .into_iter()
+            .map(|(method, (mut latencies, errors))| {
+                latencies.sort_unstable();
+                Self {
+                    method: method.to_string(),
+                    requests: latencies.len() as u64 + errors,
+                    errors,
+                    p50: percentile(&latencies, 50),
+                    p90: percentile(&latencies, 90),
+                    p99: percentile(&latencies, 99),
+                    max: latencies.last().copied().unwrap_or_default(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns the nearest-rank percentile of the sorted latencies, or zero if there are none.
+fn percentile(sorted: &[Duration], percentile: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO
+    }
+    let rank = (sorted.len() * percentile).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+impl std::fmt::Display for RpcMethodSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} requests, {} errors. Latency p50: {:?}, p90: {:?}, p99: {:?}, max: {:?}",
+            self.method, self.requests, self.errors, self.p50, self.p90, self.p99, self.max
+        )
+    }
+}
+
+/// This serializes the latencies of the [`RpcMethodSummary`] to microseconds, for the csv writer.
+impl Serialize for RpcMethodSummary {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        let mut state = serializer.serialize_struct("RpcMethodSummary", 7)?;
+        state.serialize_field("method", &self.method)?;
+        state.serialize_field("requests", &self.requests)?;
+        state.serialize_field("errors", &self.errors)?;
+        state.serialize_field("p50", &self.p50.as_micros())?;
+        state.serialize_field("p90", &self.p90.as_micros())?;
+        state.serialize_field("p99", &self.p99.as_micros())?;
+        state.serialize_field("max", &self.max.as_micros())?;
+        state.end()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,4 +327,57 @@ mod tests {
         let second_line = result.next().unwrap().unwrap();
         assert_eq!(second_line, expected_second_line);
     }
+
+    #[test]
+    fn test_rpc_method_summary() {
+        let mut results = (1..=100)
+            .map(|millis| RpcCallResult {
+                method: "eth_call".to_string(),
+                latency: Duration::from_millis(millis),
+                success: true,
+            })
+            .collect::<Vec<_>>();
+        results.push(RpcCallResult {
+            method: "eth_call".to_string(),
+            latency: Duration::from_secs(10),
+            success: false,
+        });
+        results.push(RpcCallResult {
+            method: "debug_traceTransaction".to_string(),
+            latency: Duration::from_millis(5),
+            success: false,
+        });
+
+        let summaries = RpcMethodSummary::from_results(&results);
+        assert_eq!(
+            summaries,
+            vec![
+                RpcMethodSummary {
+                    method: "debug_traceTransaction".to_string(),
+                    requests: 1,
+                    errors: 1,
+                    p50: Duration::ZERO,
+                    p90: Duration::ZERO,
+                    p99: Duration::ZERO,
+                    max: Duration::ZERO,
+                },
+                RpcMethodSummary {
+                    method: "eth_call".to_string(),
+                    requests: 101,
+                    errors: 1,
+                    p50: Duration::from_millis(50),
+                    p90: Duration::from_millis(90),
+                    p99: Duration::from_millis(99),
+                    max: Duration::from_millis(100),
+                },
+            ]
+        );
+
+        let mut writer = Writer::from_writer(vec![]);
+        writer.serialize(&summaries[1]).unwrap();
+        let result = writer.into_inner().unwrap();
+        let mut result = result.as_slice().lines();
+        assert_eq!(result.next().unwrap().unwrap(), "method,requests,errors,p50,p90,p99,max");
+        assert_eq!(result.next().unwrap().unwrap(), "eth_call,101,1,50000,90000,99000,100000");
+    }
 }
@@ -0,0 +1,266 @@
+//! Runs the `reth bench rpc-load` command, replaying a recorded RPC workload against a node and
+//! reporting the latency percentiles of each RPC method.
+
+use crate::bench::output::{
+    RpcCallResult, RpcMethodSummary, RPC_CALL_OUTPUT_SUFFIX, RPC_METHOD_OUTPUT_SUFFIX,
+};
+use alloy_pubsub::PubSubConnect;
+use alloy_rpc_client::{ClientBuilder, RpcClient};
+use alloy_transport_ipc::IpcConnect;
+use alloy_transport_ws::WsConnect;
+use clap::Parser;
+use csv::Writer;
+use reqwest::Url;
+use reth_cli_runner::CliContext;
+use serde::{de::IgnoredAny, Deserialize};
+use std::{
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::Semaphore,
+    task::JoinSet,
+    time::{interval, MissedTickBehavior},
+};
+use tracing::{debug, info};
+
+/// `reth benchmark rpc-load` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The RPC url of the node to benchmark.
+    ///
+    /// HTTP, `WebSocket` and IPC are supported, e.g. `http://localhost:8545`,
+    /// `ws://localhost:8546` or `file:///tmp/reth.ipc`.
+    #[arg(long, value_name = "RPC_URL", verbatim_doc_comment)]
+    rpc_url: Url,
+
+    /// Path to the recorded workload, a JSONL file with one JSON-RPC request per line.
+    ///
+    /// Only the `method` and `params` of the requests are used.
+    #[arg(long, value_name = "WORKLOAD", verbatim_doc_comment)]
+    workload: PathBuf,
+
+    /// The maximum number of requests in flight.
+    #[arg(
+        long,
+        value_name = "CONCURRENCY",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    concurrency: u32,
+
+    /// The maximum number of requests sent per second.
+    ///
+    /// If not set, requests are sent as fast as the concurrency allows.
+    #[arg(
+        long,
+        value_name = "RATE",
+        value_parser = clap::value_parser!(u32).range(1..),
+        verbatim_doc_comment
+    )]
+    rate: Option<u32>,
+
+    /// How many times the workload is replayed.
+    #[arg(long, value_name = "REPEAT", default_value_t = 1)]
+    repeat: usize,
+
+    /// The path to the output directory for granular benchmark results.
+    #[arg(long, short, value_name = "BENCHMARK_OUTPUT", verbatim_doc_comment)]
+    output: Option<PathBuf>,
+}
+
+/// A request of the recorded workload.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct RecordedRequest {
+    /// The RPC method.
+    method: String,
+    /// The parameters of the request.
+    #[serde(default = "empty_params")]
+    params: serde_json::Value,
+}
+
+fn empty_params() -> serde_json::Value {
+    serde_json::Value::Array(Vec::new())
+}
+
+impl Command {
+    /// Execute `benchmark rpc-load` command
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        // Ensure that output directory exists and is a directory
+        if let Some(output) = &self.output {
+            if output.is_file() {
+                return Err(eyre::eyre!("Output path must be a directory"));
+            }
+            // Create the directory if it doesn't exist
+            if !output.exists() {
+                std::fs::create_dir_all(output)?;
+                info!("Created output directory: {:?}", output);
+            }
+        }
+
+        let requests = read_workload(&self.workload)?;
+        if requests.is_empty() {
+            return Err(eyre::eyre!("Workload {:?} contains no requests", self.workload));
+        }
+        info!(
+            requests = requests.len(),
+            repeat = self.repeat,
+            concurrency = self.concurrency,
+            rate = ?self.rate,
+            "Replaying RPC workload against {}",
+            self.rpc_url
+        );
+
+        let client = connect(&self.rpc_url).await?;
+        let semaphore = Arc::new(Semaphore::new(self.concurrency as usize));
+        let mut rate_limit = self.rate.map(|rate| {
+            let mut interval = interval(Duration::from_secs(1) / rate);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            interval
+        });
+
+        let mut results = Vec::with_capacity(requests.len() * self.repeat);
+        let mut tasks = JoinSet::new();
+        let total_benchmark_duration = Instant::now();
+
+        for request in requests.iter().cycle().take(requests.len() * self.repeat) {
+            if let Some(rate_limit) = &mut rate_limit {
+                rate_limit.tick().await;
+            }
+            let permit = semaphore.clone().acquire_owned().await?;
+
+            let client = client.clone();
+            let RecordedRequest { method, params } = request.clone();
+            tasks.spawn(async move {
+                let start = Instant::now();
+                let response = client.request::<_, IgnoredAny>(method.clone(), params).await;
+                let latency = start.elapsed();
+                drop(permit);
+
+                if let Err(err) = &response {
+                    debug!(target: "reth-bench", %method, %err, "RPC request failed");
+                }
+                RpcCallResult { method, latency, success: response.is_ok() }
+            });
+
+            while let Some(result) = tasks.try_join_next() {
+                results.push(result?);
+            }
+        }
+        while let Some(result) = tasks.join_next().await {
+            results.push(result?);
+        }
+
+        let total_duration = total_benchmark_duration.elapsed();
+        let summaries = RpcMethodSummary::from_results(&results);
+        for summary in &summaries {
+            info!(%summary);
+        }
+
+        // write the csv output to files
+        if let Some(path) = self.output {
+            // first write the latency of each request to a file
+            let output_path = path.join(RPC_CALL_OUTPUT_SUFFIX);
+            info!("Writing RPC request latency output to file: {:?}", output_path);
+            let mut writer = Writer::from_path(output_path)?;
+            for result in &results {
+                writer.serialize(result)?;
+            }
+            writer.flush()?;
+
+            // now write the percentiles of each method to a file
+            let output_path = path.join(RPC_METHOD_OUTPUT_SUFFIX);
+            info!("Writing RPC method latency output to file: {:?}", output_path);
+            let mut writer = Writer::from_path(output_path)?;
+            for summary in &summaries {
+                writer.serialize(summary)?;
+            }
+            writer.flush()?;
+
+            info!("Finished writing benchmark output files to {:?}.", path);
+        }
+
+        let errors = results.iter().filter(|result| !result.success).count();
+        info!(
+            total_duration=?total_duration,
+            requests=results.len(),
+            errors,
+            "Requests per second: {:.2}",
+            results.len() as f64 / total_duration.as_secs_f64()
+        );
+
+        Ok(())
+    }
+}
+
+/// Connects to the HTTP, `WebSocket` or IPC endpoint at the given url.
+async fn connect(url: &Url) -> eyre::Result<RpcClient> {
+    Ok(match url.scheme() {
+        "http" | "https" => ClientBuilder::default().http(url.clone()),
+        "ws" | "wss" => {
+            let transport = WsConnect::new(url.clone()).into_service().await?;
+            ClientBuilder::default().transport(transport, false)
+        }
+        "file" => {
+            let transport = IpcConnect::new(url.path().to_string()).into_service().await?;
+            ClientBuilder::default().transport(transport, true)
+        }
+        scheme => return Err(eyre::eyre!("Unsupported RPC url scheme: {scheme}")),
+    })
+}
+
+/// Reads the requests of a JSONL workload, skipping empty lines.
+fn read_workload(path: &Path) -> eyre::Result<Vec<RecordedRequest>> {
+    let reader = BufReader::new(reth_fs_util::open(path)?);
+    let mut requests = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue
+        }
+        let request = serde_json::from_str(&line)
+            .map_err(|err| eyre::eyre!("Invalid request on line {}: {err}", index + 1))?;
+        requests.push(request);
+    }
+    Ok(requests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_workload() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("workload.jsonl");
+        reth_fs_util::write(
+            &path,
+            r#"{"jsonrpc":"2.0","id":1,"method":"eth_call","params":[{"to":"0x0000000000000000000000000000000000000000"},"latest"]}
+
+{"method":"eth_blockNumber"}
+"#,
+        )
+        .unwrap();
+
+        let requests = read_workload(&path).unwrap();
+        assert_eq!(
+            requests,
+            vec![
+                RecordedRequest {
+                    method: "eth_call".to_string(),
+                    params: serde_json::json!([
+                        {"to": "0x0000000000000000000000000000000000000000"},
+                        "latest"
+                    ]),
+                },
+                RecordedRequest { method: "eth_blockNumber".to_string(), params: empty_params() },
+            ]
+        );
+
+        reth_fs_util::write(&path, "{\"method\":\"eth_call\"}\nnot json\n").unwrap();
+        let err = read_workload(&path).unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{err}");
+    }
+}
@@ -2,7 +2,8 @@
 //! response. This is useful for benchmarking, as it allows us to wait for a payload to be valid
 //! before sending additional calls.
 
-use alloy_eips::eip7685::Requests;
+use alloy_consensus::{Block, Transaction};
+use alloy_eips::{eip2718::Encodable2718, eip7685::Requests};
 use alloy_provider::{ext::EngineApi, network::AnyRpcBlock, Network, Provider};
 use alloy_rpc_types_engine::{
     ExecutionPayload, ExecutionPayloadInputV2, ForkchoiceState, ForkchoiceUpdated,
@@ -133,6 +134,7 @@ where
     }
 }
 
+/// Converts an RPC block into the `engine_newPayload` version and parameters to send it with.
 pub(crate) fn block_to_new_payload(
     block: AnyRpcBlock,
     is_optimism: bool,
@@ -146,8 +148,19 @@ pub(crate) fn block_to_new_payload(
         })?
         .into_consensus();
 
+    consensus_block_to_new_payload(&block, is_optimism)
+}
+
+/// Converts a consensus block into the `engine_newPayload` version and parameters to send it with.
+pub(crate) fn consensus_block_to_new_payload<T>(
+    block: &Block<T>,
+    is_optimism: bool,
+) -> eyre::Result<(EngineApiMessageVersion, serde_json::Value)>
+where
+    T: Encodable2718 + Transaction,
+{
     // Convert to execution payload
-    let (payload, sidecar) = ExecutionPayload::from_block_slow(&block);
+    let (payload, sidecar) = ExecutionPayload::from_block_slow(block);
 
     let (version, params) = match payload {
         ExecutionPayload::V3(payload) => {
@@ -0,0 +1,103 @@
+//! `reth exex wal` command.
+
+use crate::common::{CliNodeTypes, EnvironmentArgs};
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_exex::Wal;
+use reth_node_api::NodeTypes;
+use std::sync::Arc;
+
+/// `reth exex wal` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(subcommand)]
+    command: Subcommands<C>,
+}
+
+/// `reth exex wal` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// Lists the notifications in the ExEx WAL, or dumps one of them
+    Inspect(InspectCommand<C>),
+}
+
+/// `reth exex wal inspect` command
+///
+/// Opening the WAL migrates it from the legacy format, and truncates incomplete records, so the
+/// node must not be running at the same time.
+#[derive(Debug, Parser)]
+pub struct InspectCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// ID of the notification to dump. If not set, all notifications are listed.
+    #[arg(long)]
+    id: Option<u32>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `exex wal` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Inspect(command) => command.execute::<N>(),
+        }
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        match &self.command {
+            Subcommands::Inspect(command) => Some(&command.env.chain),
+        }
+    }
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> InspectCommand<C> {
+    /// Execute `exex wal inspect` command
+    pub fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain());
+        let wal_path = data_dir.exex_wal();
+        eyre::ensure!(wal_path.is_dir(), "ExEx WAL does not exist: {wal_path:?}");
+
+        let wal = Wal::<<N as NodeTypes>::Primitives>::new(&wal_path)?;
+
+        if let Some(id) = self.id {
+            let notification = wal
+                .get_notification(id)?
+                .ok_or_else(|| eyre::eyre!("Notification {id} not found in the WAL"))?;
+            println!("{notification:#?}");
+            return Ok(())
+        }
+
+        let records = wal.records()?;
+        println!(
+            "{:>10} {:>10} {:>12} {:>10} {:>12} {:>25}",
+            "id", "segment", "offset", "size", "max block", "committed blocks"
+        );
+        for record in &records {
+            println!(
+                "{:>10} {:>10} {:>12} {:>10} {:>12} {:>25}",
+                record.id,
+                record.location.segment,
+                record.location.offset,
+                record.location.size,
+                record.blocks.max_block.map(|block| block.to_string()).unwrap_or_default(),
+                record
+                    .blocks
+                    .committed_range()
+                    .map(|range| format!("{}..={}", range.start(), range.end()))
+                    .unwrap_or_default(),
+            );
+        }
+        println!(
+            "{} notifications, {} committed blocks, {} bytes",
+            records.len(),
+            wal.num_blocks(),
+            records.iter().map(|record| record.location.size).sum::<u64>()
+        );
+
+        Ok(())
+    }
+}
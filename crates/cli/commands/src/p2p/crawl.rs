@@ -0,0 +1,702 @@
+//! `reth p2p crawl` command.
+
+use alloy_rlp::Decodable;
+use clap::Parser;
+use futures::{
+    future::BoxFuture,
+    stream::{self, BoxStream, Fuse, FusedStream, FuturesUnordered},
+    FutureExt, StreamExt,
+};
+use reth_chainspec::{EnrForkIdEntry, EthChainSpec, ForkCondition, ForkFilter, Hardforks, Head};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_util::{get_secret_key, parse_duration_from_secs};
+use reth_discv4::{DiscoveryUpdate, Discv4, Discv4Config};
+use reth_discv5::{discv5, Discv5};
+use reth_dns_discovery::tree::{DnsTree, LinkEntry};
+use reth_ecies::stream::ECIESStream;
+use reth_eth_wire::{
+    Capability, EthNetworkPrimitives, HelloMessage, UnauthedEthStream, UnauthedP2PStream,
+    UnifiedStatus,
+};
+use reth_net_nat::NatResolver;
+use reth_network::config::rng_secret_key;
+use reth_network_peers::{pk2id, Enr, NodeRecord, PeerId};
+use secp256k1::{SecretKey, SECP256K1};
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt,
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::net::TcpStream;
+use tracing::{debug, info};
+
+/// Crawls the network for nodes of the chain and prints them as a signed EIP-1459 DNS node list.
+///
+/// Nodes are discovered via discv4, and optionally discv5. Every discovered node is handshaken,
+/// and only the nodes with a compatible fork ID that support the required capabilities are
+/// included in the list. The list is printed as DNS zone file records.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    /// The chain to crawl.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        default_value = C::SUPPORTED_CHAINS[0],
+        value_parser = C::parser()
+    )]
+    pub chain: Arc<C::ChainSpec>,
+
+    /// Listen address of the discv4 crawler.
+    #[arg(long, default_value = "0.0.0.0:30301")]
+    pub addr: SocketAddr,
+
+    /// Listen address of the discv5 crawler. If not set, only discv4 is crawled.
+    #[arg(long)]
+    pub v5_addr: Option<SocketAddr>,
+
+    /// Comma separated enode URLs of the nodes to start crawling from. Defaults to the boot nodes
+    /// of the chain.
+    #[arg(long, value_delimiter = ',')]
+    pub bootnodes: Vec<NodeRecord>,
+
+    /// NAT resolution method (any|none|upnp|publicip|extip:\<IP\>)
+    #[arg(long, default_value = "any")]
+    pub nat: NatResolver,
+
+    /// Capability the nodes must support, e.g. `snap/1`. Can be repeated.
+    #[arg(long = "capability", value_name = "NAME/VERSION", value_parser = parse_capability)]
+    pub capabilities: Vec<Capability>,
+
+    /// How long to crawl for, in seconds.
+    #[arg(long, default_value = "600", value_parser = parse_duration_from_secs)]
+    pub duration: Duration,
+
+    /// Stop crawling once this many nodes have been found.
+    #[arg(long)]
+    pub max_nodes: Option<usize>,
+
+    /// Maximum number of concurrent handshakes.
+    #[arg(long, default_value_t = CrawlerConfig::DEFAULT_CONCURRENCY)]
+    pub concurrency: usize,
+
+    /// Timeout of a handshake with a node, in seconds.
+    #[arg(long, default_value = "10", value_parser = parse_duration_from_secs)]
+    pub handshake_timeout: Duration,
+
+    /// Domain the node list is published at, e.g. `nodes.example.org`.
+    #[arg(long)]
+    pub domain: String,
+
+    /// Path to the secret key that signs the node list. A new key is created if the file doesn't
+    /// exist.
+    #[arg(long, value_name = "PATH")]
+    pub tree_key: PathBuf,
+
+    /// Sequence number of the node list. Defaults to the current UNIX timestamp.
+    #[arg(long)]
+    pub seq: Option<u64>,
+
+    /// Links to other node lists to include, e.g. `enrtree://<key>@nodes.example.org`. Can be
+    /// repeated.
+    #[arg(long = "link")]
+    pub links: Vec<LinkEntry>,
+
+    /// TTL of the DNS records, in seconds.
+    #[arg(long, default_value = "3600")]
+    pub ttl: u64,
+
+    /// File to write the zone file records to. If not set, the records are printed to stdout.
+    #[arg(long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks>> Command<C> {
+    /// Execute `p2p crawl` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let tree_key = get_secret_key(&self.tree_key)?;
+        let boot_nodes = if self.bootnodes.is_empty() {
+            self.chain.bootnodes().unwrap_or_default()
+        } else {
+            self.bootnodes.clone()
+        };
+        eyre::ensure!(!boot_nodes.is_empty(), "No boot nodes to crawl from, set `--bootnodes`");
+
+        let mut config = CrawlerConfig::new(&*self.chain, rng_secret_key(), self.addr);
+        config.discv4_config = Discv4Config::builder()
+            .add_boot_nodes(boot_nodes.clone())
+            .external_ip_resolver(Some(self.nat))
+            .build();
+        config.discv5_config = self.v5_addr.map(|addr| {
+            reth_discv5::Config::builder(self.addr)
+                .discv5_config(discv5::ConfigBuilder::new(discv5::ListenConfig::from(addr)).build())
+                .add_unsigned_boot_nodes(boot_nodes)
+                .build()
+        });
+        config.capabilities = self.capabilities.clone();
+        config.concurrency = self.concurrency;
+        config.handshake_timeout = self.handshake_timeout;
+
+        let mut crawler = Crawler::start(config).await?;
+        info!(
+            target: "reth::cli",
+            record = %crawler.local_node_record(),
+            duration = ?self.duration,
+            "Started crawler"
+        );
+
+        let mut nodes = Vec::new();
+        let mut rejected = 0usize;
+        let deadline = tokio::time::sleep(self.duration);
+        tokio::pin!(deadline);
+        loop {
+            let outcome = tokio::select! {
+                _ = &mut deadline => break,
+                outcome = crawler.next_outcome() => outcome,
+            };
+            match outcome {
+                Some(CrawlOutcome::Accepted(node)) => {
+                    info!(
+                        target: "reth::cli",
+                        peer_id = %node.peer_id(),
+                        client = %node.client_version,
+                        "Found node"
+                    );
+                    nodes.push(node.enr);
+                    if self.max_nodes.is_some_and(|max| nodes.len() >= max) {
+                        break
+                    }
+                }
+                Some(CrawlOutcome::Rejected { peer_id, reason }) => {
+                    debug!(target: "reth::cli", %peer_id, %reason, "Rejected node");
+                    rejected += 1;
+                }
+                None => break,
+            }
+        }
+        info!(target: "reth::cli", nodes = nodes.len(), rejected, "Finished crawling");
+
+        let sequence_number = match self.seq {
+            Some(seq) => seq,
+            None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        };
+        let mut tree = DnsTree::new(sequence_number, nodes, self.links);
+        tree.sign(&tree_key).map_err(|err| eyre::eyre!("Failed to sign node list: {err}"))?;
+
+        let link = LinkEntry::<SecretKey> {
+            domain: self.domain.trim_end_matches('.').to_string(),
+            pubkey: tree_key.public_key(SECP256K1),
+        };
+        let zone = tree.to_zone_file(&self.domain, self.ttl);
+        match &self.output {
+            Some(path) => {
+                reth_fs_util::write(path, zone)?;
+                info!(target: "reth::cli", ?path, %link, "Wrote node list");
+            }
+            None => {
+                print!("{zone}");
+                info!(target: "reth::cli", %link, "Created node list");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a capability in the `<name>/<version>` format, e.g. `snap/1`.
+fn parse_capability(value: &str) -> eyre::Result<Capability> {
+    let (name, version) = value
+        .split_once('/')
+        .ok_or_else(|| eyre::eyre!("invalid capability {value}, expected <name>/<version>"))?;
+    Ok(Capability::new(name.to_string(), version.parse()?))
+}
+
+/// Configuration of the [`Crawler`].
+#[derive(Debug)]
+pub struct CrawlerConfig {
+    /// Key of the crawler's discovery nodes and of its handshakes.
+    pub secret_key: SecretKey,
+    /// Listen address of the discv4 node.
+    pub discv4_addr: SocketAddr,
+    /// Configuration of the discv4 node, including the boot nodes.
+    pub discv4_config: Discv4Config,
+    /// Configuration of the discv5 node. If not set, only discv4 is crawled.
+    pub discv5_config: Option<reth_discv5::Config>,
+    /// Status sent to the nodes in the eth handshake.
+    pub status: UnifiedStatus,
+    /// Filter of the fork IDs of compatible nodes.
+    pub fork_filter: ForkFilter,
+    /// Capabilities, besides `eth`, the nodes must support.
+    pub capabilities: Vec<Capability>,
+    /// Maximum number of concurrent handshakes.
+    pub concurrency: usize,
+    /// Timeout of a handshake with a node.
+    pub handshake_timeout: Duration,
+}
+
+impl CrawlerConfig {
+    /// Default maximum number of concurrent handshakes.
+    pub const DEFAULT_CONCURRENCY: usize = 32;
+
+    /// Creates a config for crawling the network of the given chain with the default discv4
+    /// configuration and without discv5.
+    ///
+    /// The crawler handshakes nodes as a node at the latest fork that is active at the current
+    /// time, so that nodes that haven't followed the chain's forks are rejected.
+    pub fn new<Spec>(chain_spec: &Spec, secret_key: SecretKey, discv4_addr: SocketAddr) -> Self
+    where
+        Spec: EthChainSpec + Hardforks,
+    {
+        let head = latest_head(chain_spec);
+
+        Self {
+            secret_key,
+            discv4_addr,
+            discv4_config: Discv4Config::default(),
+            discv5_config: None,
+            status: UnifiedStatus::spec_builder(chain_spec, &head),
+            fork_filter: chain_spec.fork_filter(head),
+            capabilities: Vec::new(),
+            concurrency: Self::DEFAULT_CONCURRENCY,
+            handshake_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Returns a head past all block based forks of the chain, at the current time.
+fn latest_head<Spec>(chain_spec: &Spec) -> Head
+where
+    Spec: EthChainSpec + Hardforks,
+{
+    let number = chain_spec
+        .forks_iter()
+        .filter_map(|(_, condition)| match condition {
+            ForkCondition::Block(block) => Some(block),
+            ForkCondition::TTD { fork_block, activation_block_number, .. } => {
+                Some(fork_block.unwrap_or(activation_block_number))
+            }
+            _ => None,
+        })
+        .max()
+        .unwrap_or_default();
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+    Head {
+        hash: chain_spec.genesis_hash(),
+        number,
+        timestamp: timestamp.max(chain_spec.genesis().timestamp),
+        difficulty: chain_spec.genesis().difficulty,
+        total_difficulty: chain_spec
+            .final_paris_total_difficulty()
+            .unwrap_or(chain_spec.genesis().difficulty),
+    }
+}
+
+/// A node that passed the filters of the [`Crawler`].
+#[derive(Debug, Clone)]
+pub struct CrawledNode {
+    /// Signed record of the node.
+    pub enr: Enr<SecretKey>,
+    /// Client version of the node, from its hello message.
+    pub client_version: String,
+    /// Capabilities of the node, from its hello message.
+    pub capabilities: Vec<Capability>,
+    /// Status of the node, from the eth handshake.
+    pub status: UnifiedStatus,
+}
+
+impl CrawledNode {
+    /// Returns the ID of the node.
+    pub fn peer_id(&self) -> PeerId {
+        pk2id(&self.enr.public_key())
+    }
+}
+
+/// Outcome of the handshake with a discovered node.
+#[derive(Debug)]
+pub enum CrawlOutcome {
+    /// The node is compatible with the chain and supports the required capabilities.
+    Accepted(CrawledNode),
+    /// The node is incompatible, or the handshake failed.
+    Rejected {
+        /// ID of the node.
+        peer_id: PeerId,
+        /// Why the node was rejected.
+        reason: String,
+    },
+}
+
+/// A node found by the discovery nodes of the [`Crawler`].
+#[derive(Debug)]
+enum Discovered {
+    /// Signed record of the node.
+    Record(Enr<SecretKey>),
+    /// A node that wasn't added to the full discv4 table, so its record isn't requested by the
+    /// discv4 node itself.
+    AtCapacity(NodeRecord),
+}
+
+/// Settings of the handshakes with discovered nodes.
+#[derive(Debug)]
+struct HandshakeConfig {
+    secret_key: SecretKey,
+    status: UnifiedStatus,
+    fork_filter: ForkFilter,
+    capabilities: Vec<Capability>,
+    timeout: Duration,
+}
+
+/// Network crawler that discovers nodes and handshakes them, to find the nodes of a chain.
+///
+/// Every node is handshaken once, the outcomes are returned by [`Crawler::next_outcome`].
+pub struct Crawler {
+    discv4: Discv4,
+    _discv5: Option<Discv5>,
+    /// Nodes discovered via discv4 and discv5.
+    discovered: Fuse<BoxStream<'static, Discovered>>,
+    handshake: Arc<HandshakeConfig>,
+    concurrency: usize,
+    /// Nodes that were discovered before.
+    seen: HashSet<PeerId>,
+    /// Nodes outside the discv4 table whose records were requested.
+    requested: HashSet<PeerId>,
+    /// Outcomes of nodes rejected before the handshake.
+    outcomes: VecDeque<CrawlOutcome>,
+    /// Nodes waiting for a handshake.
+    queued: VecDeque<Enr<SecretKey>>,
+    handshakes: FuturesUnordered<BoxFuture<'static, CrawlOutcome>>,
+}
+
+impl Crawler {
+    /// Starts the discovery nodes of the crawler.
+    pub async fn start(config: CrawlerConfig) -> eyre::Result<Self> {
+        let CrawlerConfig {
+            secret_key,
+            discv4_addr,
+            discv4_config,
+            discv5_config,
+            status,
+            fork_filter,
+            capabilities,
+            concurrency,
+            handshake_timeout,
+        } = config;
+
+        let local_record = NodeRecord::from_secret_key(discv4_addr, &secret_key);
+        let (discv4, mut discv4_service) =
+            Discv4::bind(discv4_addr, local_record, secret_key, discv4_config).await?;
+        let mut discovered = discv4_service
+            .update_stream()
+            .flat_map(|update| stream::iter(discv4_records(update)))
+            .boxed();
+        discv4_service.spawn();
+
+        let discv5 = match discv5_config {
+            Some(config) => {
+                let (discv5, updates, _) = Discv5::start(&secret_key, config).await?;
+                let discv5_discovered = tokio_stream::wrappers::ReceiverStream::new(updates)
+                    .filter_map(|event| {
+                        futures::future::ready(discv5_record(event).map(Discovered::Record))
+                    });
+                discovered = stream::select(discovered, discv5_discovered).boxed();
+                Some(discv5)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            discv4,
+            _discv5: discv5,
+            discovered: discovered.fuse(),
+            handshake: Arc::new(HandshakeConfig {
+                secret_key,
+                status,
+                fork_filter,
+                capabilities,
+                timeout: handshake_timeout,
+            }),
+            concurrency: concurrency.max(1),
+            seen: HashSet::default(),
+            requested: HashSet::default(),
+            outcomes: VecDeque::new(),
+            queued: VecDeque::new(),
+            handshakes: FuturesUnordered::new(),
+        })
+    }
+
+    /// Returns the record of the crawler's discv4 node.
+    pub fn local_node_record(&self) -> NodeRecord {
+        self.discv4.node_record()
+    }
+
+    /// Waits for the outcome of the next node.
+    ///
+    /// Returns `None` once the discovery nodes stopped and all handshakes finished.
+    pub async fn next_outcome(&mut self) -> Option<CrawlOutcome> {
+        loop {
+            if let Some(outcome) = self.outcomes.pop_front() {
+                return Some(outcome)
+            }
+            while self.handshakes.len() < self.concurrency {
+                let Some(enr) = self.queued.pop_front() else { break };
+                self.handshakes.push(handshake(enr, self.handshake.clone()).boxed());
+            }
+            if self.discovered.is_terminated() && self.handshakes.is_empty() {
+                return None
+            }
+
+            tokio::select! {
+                Some(outcome) = self.handshakes.next(), if !self.handshakes.is_empty() => {
+                    return Some(outcome)
+                }
+                Some(discovered) = self.discovered.next(), if !self.discovered.is_terminated() => {
+                    match discovered {
+                        Discovered::Record(enr) => self.on_discovered(enr),
+                        Discovered::AtCapacity(record) => self.on_discovered_at_capacity(record),
+                    }
+                }
+                // the discovery stream ended
+                else => {}
+            }
+        }
+    }
+
+    /// Queues a newly discovered node for a handshake, unless its record advertises an
+    /// incompatible fork ID.
+    fn on_discovered(&mut self, enr: Enr<SecretKey>) {
+        let peer_id = pk2id(&enr.public_key());
+        if !self.seen.insert(peer_id) {
+            return
+        }
+
+        if let Some(Ok(fork_id)) = enr.get_decodable::<EnrForkIdEntry>(b"eth") {
+            if let Err(err) = self.handshake.fork_filter.validate(fork_id.into()) {
+                self.outcomes.push_back(CrawlOutcome::Rejected {
+                    peer_id,
+                    reason: format!("incompatible fork ID in record: {err}"),
+                });
+                return
+            }
+        }
+
+        self.queued.push_back(enr);
+    }
+
+    /// Requests the record of a node that discv4 discovered but didn't add to its full table.
+    fn on_discovered_at_capacity(&mut self, record: NodeRecord) {
+        if !self.seen.contains(&record.id) && self.requested.insert(record.id) {
+            self.discv4.request_enr(record);
+        }
+    }
+}
+
+impl fmt::Debug for Crawler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Crawler")
+            .field("local_node_record", &self.local_node_record())
+            .field("seen", &self.seen.len())
+            .field("queued", &self.queued.len())
+            .field("handshakes", &self.handshakes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns the nodes contained in the discv4 update.
+fn discv4_records(update: DiscoveryUpdate) -> Vec<Discovered> {
+    match update {
+        DiscoveryUpdate::Enr(_, enr) => vec![Discovered::Record(enr)],
+        DiscoveryUpdate::DiscoveredAtCapacity(record) => vec![Discovered::AtCapacity(record)],
+        DiscoveryUpdate::Batch(updates) => updates.into_iter().flat_map(discv4_records).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the record of the node discovered via discv5, if it's a secp256k1 record.
+fn discv5_record(event: discv5::Event) -> Option<Enr<SecretKey>> {
+    let enr = match event {
+        discv5::Event::Discovered(enr) |
+        discv5::Event::SessionEstablished(enr, _) |
+        discv5::Event::UnverifiableEnr { enr, .. } => enr,
+        _ => return None,
+    };
+    Enr::decode(&mut alloy_rlp::encode(&enr).as_slice()).ok()
+}
+
+/// Handshakes the node and checks that it's compatible with the chain and supports the required
+/// capabilities.
+async fn handshake(enr: Enr<SecretKey>, config: Arc<HandshakeConfig>) -> CrawlOutcome {
+    let peer_id = pk2id(&enr.public_key());
+    let result = tokio::time::timeout(config.timeout, try_handshake(&enr, peer_id, &config))
+        .await
+        .unwrap_or_else(|_| Err(eyre::eyre!("handshake timed out")));
+
+    match result {
+        Ok((hello, status)) => CrawlOutcome::Accepted(CrawledNode {
+            enr,
+            client_version: hello.client_version,
+            capabilities: hello.capabilities,
+            status,
+        }),
+        Err(err) => CrawlOutcome::Rejected { peer_id, reason: err.to_string() },
+    }
+}
+
+/// Performs the p2p and eth handshakes with the node, and returns its hello message and status.
+async fn try_handshake(
+    enr: &Enr<SecretKey>,
+    peer_id: PeerId,
+    config: &HandshakeConfig,
+) -> eyre::Result<(HelloMessage, UnifiedStatus)> {
+    let addr = match (enr.ip4(), enr.tcp4(), enr.ip6(), enr.tcp6()) {
+        (Some(ip), Some(port), _, _) => SocketAddr::new(IpAddr::V4(ip), port),
+        (_, _, Some(ip), Some(port)) => SocketAddr::new(IpAddr::V6(ip), port),
+        _ => eyre::bail!("record has no TCP endpoint"),
+    };
+
+    let outgoing = TcpStream::connect(addr).await?;
+    let ecies_stream = ECIESStream::connect(outgoing, config.secret_key, peer_id).await?;
+    let hello = HelloMessage::builder(pk2id(&config.secret_key.public_key(SECP256K1))).build();
+    let (p2p_stream, their_hello) = UnauthedP2PStream::new(ecies_stream).handshake(hello).await?;
+
+    if let Some(missing) =
+        config.capabilities.iter().find(|cap| !their_hello.capabilities.contains(cap))
+    {
+        eyre::bail!("missing capability {missing}")
+    }
+
+    let status = UnifiedStatus {
+        version: p2p_stream.shared_capabilities().eth()?.version().try_into()?,
+        ..config.status
+    };
+    let (_, their_status) = UnauthedEthStream::new(p2p_stream)
+        .handshake::<EthNetworkPrimitives>(status, config.fork_filter.clone())
+        .await?;
+
+    Ok((their_hello, their_status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::{ChainSpec, ForkHash, ForkId, MAINNET, SEPOLIA};
+    use reth_network::{NetworkConfigBuilder, NetworkManager, PeersInfo};
+    use std::net::Ipv4Addr;
+
+    /// Spawns a node of the given chain that runs discv4 on localhost.
+    async fn spawn_node(chain_spec: Arc<ChainSpec>, boot_nodes: Vec<NodeRecord>) -> NodeRecord {
+        let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+        let mut discv4 = Discv4Config::builder();
+        discv4.external_ip_resolver(None);
+        let config = NetworkConfigBuilder::<EthNetworkPrimitives>::new(rng_secret_key())
+            .listener_addr(addr)
+            .discovery_addr(addr)
+            .discovery(discv4)
+            .disable_dns_discovery()
+            .disable_nat()
+            .boot_nodes(boot_nodes)
+            .build_with_noop_provider(chain_spec);
+        let network = NetworkManager::new(config).await.unwrap();
+        let record = network.handle().local_node_record();
+        tokio::spawn(network);
+        record
+    }
+
+    #[test]
+    fn config_at_latest_fork() {
+        let config = CrawlerConfig::new(
+            MAINNET.as_ref(),
+            rng_secret_key(),
+            SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+        );
+
+        // a node that never left the frontier fork is rejected
+        let frontier = ForkId { hash: ForkHash([0xfc, 0x64, 0xec, 0x04]), next: 0 };
+        assert_ne!(config.fork_filter.current().hash, frontier.hash);
+        assert!(config.fork_filter.validate(frontier).is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn crawl_local_network() {
+        reth_tracing::init_test_tracing();
+
+        let bootnode = spawn_node(MAINNET.clone(), Vec::new()).await;
+        let mut mainnet_nodes = HashSet::new();
+        for _ in 0..3 {
+            mainnet_nodes.insert(spawn_node(MAINNET.clone(), vec![bootnode]).await.id);
+        }
+        let sepolia_node = spawn_node(SEPOLIA.clone(), vec![bootnode]).await.id;
+
+        let mut config = CrawlerConfig::new(
+            MAINNET.as_ref(),
+            rng_secret_key(),
+            SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+        );
+        config.discv4_config = Discv4Config::builder()
+            .add_boot_node(bootnode)
+            .external_ip_resolver(None)
+            .lookup_interval(Duration::from_millis(500))
+            .build();
+        let mut crawler = Crawler::start(config).await.unwrap();
+
+        let mut accepted = HashSet::new();
+        let mut rejected = HashSet::new();
+        tokio::time::timeout(Duration::from_secs(60), async {
+            while !mainnet_nodes.is_subset(&accepted) || !rejected.contains(&sepolia_node) {
+                match crawler.next_outcome().await.unwrap() {
+                    CrawlOutcome::Accepted(node) => {
+                        assert_eq!(node.status.genesis, MAINNET.genesis_hash());
+                        assert!(node.capabilities.iter().any(|cap| cap.name == "eth"));
+                        accepted.insert(node.peer_id());
+                    }
+                    CrawlOutcome::Rejected { peer_id, .. } => {
+                        rejected.insert(peer_id);
+                    }
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert!(!accepted.contains(&sepolia_node));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn crawl_required_capabilities() {
+        reth_tracing::init_test_tracing();
+
+        let bootnode = spawn_node(MAINNET.clone(), Vec::new()).await;
+        let node = spawn_node(MAINNET.clone(), vec![bootnode]).await.id;
+
+        let mut config = CrawlerConfig::new(
+            MAINNET.as_ref(),
+            rng_secret_key(),
+            SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+        );
+        config.discv4_config = Discv4Config::builder()
+            .add_boot_node(bootnode)
+            .external_ip_resolver(None)
+            .lookup_interval(Duration::from_millis(500))
+            .build();
+        config.capabilities = vec![parse_capability("snap/1").unwrap()];
+        let mut crawler = Crawler::start(config).await.unwrap();
+
+        let reason = tokio::time::timeout(Duration::from_secs(60), async {
+            loop {
+                match crawler.next_outcome().await.unwrap() {
+                    CrawlOutcome::Rejected { peer_id, reason } if peer_id == node => return reason,
+                    CrawlOutcome::Accepted(crawled) => {
+                        assert_ne!(crawled.peer_id(), node)
+                    }
+                    _ => {}
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(reason, "missing capability snap/1");
+    }
+}
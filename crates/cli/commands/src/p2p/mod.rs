@@ -18,6 +18,7 @@ use reth_node_core::{
 };
 
 pub mod bootnode;
+pub mod crawl;
 pub mod rlpx;
 
 /// `reth p2p` command
@@ -85,6 +86,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             Subcommands::Bootnode(command) => {
                 command.execute().await?;
             }
+            Subcommands::Crawl(command) => {
+                command.execute().await?;
+            }
         }
 
         Ok(())
@@ -99,6 +103,7 @@ impl<C: ChainSpecParser> Command<C> {
             Subcommands::Body { args, .. } => Some(&args.chain),
             Subcommands::Rlpx(_) => None,
             Subcommands::Bootnode(_) => None,
+            Subcommands::Crawl(command) => Some(&command.chain),
         }
     }
 }
@@ -126,6 +131,8 @@ pub enum Subcommands<C: ChainSpecParser> {
     Rlpx(rlpx::Command),
     /// Bootnode command
     Bootnode(bootnode::Command),
+    /// Crawl the network and create a signed EIP-1459 DNS node list
+    Crawl(crawl::Command<C>),
 }
 
 #[derive(Debug, Clone, Parser)]
@@ -226,4 +233,33 @@ mod tests {
         let _args: Command<EthereumChainSpecParser> =
             Command::parse_from(["reth", "body", "--chain", "mainnet", "1000"]);
     }
+
+    #[test]
+    fn parse_crawl_cmd() {
+        let args: Command<EthereumChainSpecParser> = Command::parse_from([
+            "reth",
+            "crawl",
+            "--chain",
+            "sepolia",
+            "--domain",
+            "nodes.example.org",
+            "--tree-key",
+            "tree.key",
+            "--capability",
+            "snap/1",
+        ]);
+        let Subcommands::Crawl(command) = args.command else { panic!("expected crawl command") };
+        assert_eq!(command.capabilities, vec![reth_eth_wire::Capability::new_static("snap", 1)]);
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "crawl",
+            "--domain",
+            "nodes.example.org",
+            "--tree-key",
+            "tree.key",
+            "--capability",
+            "snap",
+        ])
+        .is_err());
+    }
 }
@@ -0,0 +1,87 @@
+//! Command that runs pruning without any limits.
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_primitives::BlockNumber;
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db::DatabaseEnv;
+use reth_node_builder::NodeTypesWithDBAdapter;
+use reth_provider::ProviderFactory;
+use reth_prune::{PruneModes, PrunerBuilder};
+use reth_static_file::StaticFileProducer;
+use std::sync::Arc;
+use tracing::info;
+
+mod reconfigure;
+
+/// Prunes according to the configuration without any limits
+#[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct PruneCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    #[command(subcommand)]
+    command: Option<Subcommands<C>>,
+}
+
+/// `reth prune` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// Migrates the datadir to a changed prune configuration, pruning or rebuilding segments
+    Reconfigure(reconfigure::Command<C>),
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> PruneCommand<C> {
+    /// Execute the `prune` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        if let Some(Subcommands::Reconfigure(command)) = self.command {
+            return command.execute::<N>()
+        }
+
+        let Environment { config, provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+        let prune_config = config.prune.unwrap_or_default();
+
+        let lowest_static_file_height =
+            copy_to_static_files(&provider_factory, prune_config.segments.clone())?;
+
+        // Delete data which has been copied to static files.
+        if let Some(prune_tip) = lowest_static_file_height {
+            info!(target: "reth::cli", ?prune_tip, ?prune_config, "Pruning data from database...");
+            // Run the pruner according to the configuration, and don't enforce any limits on it
+            let mut pruner = PrunerBuilder::new(prune_config)
+                .delete_limit(usize::MAX)
+                .build_with_provider_factory(provider_factory);
+
+            pruner.run(prune_tip)?;
+            info!(target: "reth::cli", "Pruned data from database");
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> PruneCommand<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        match &self.command {
+            Some(Subcommands::Reconfigure(command)) => Some(command.chain_spec()),
+            None => Some(&self.env.chain),
+        }
+    }
+}
+
+/// Copies data from database to static files, and returns the lowest block height of the static
+/// files, which is the highest block that can be pruned from the database.
+fn copy_to_static_files<N: CliNodeTypes>(
+    provider_factory: &ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
+    prune_modes: PruneModes,
+) -> eyre::Result<Option<BlockNumber>> {
+    info!(target: "reth::cli", "Copying data from database to static files...");
+    let static_file_producer = StaticFileProducer::new(provider_factory.clone(), prune_modes);
+    let lowest_static_file_height =
+        static_file_producer.lock().copy_to_static_files()?.min_block_num();
+    info!(target: "reth::cli", ?lowest_static_file_height, "Copied data from database to static files");
+
+    Ok(lowest_static_file_height)
+}
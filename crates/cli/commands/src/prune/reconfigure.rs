@@ -0,0 +1,322 @@
+//! Command that migrates the datadir to a new prune configuration.
+use super::copy_to_static_files;
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_primitives::{BlockNumber, TxHash, TxNumber};
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_config::{config::EtlConfig, Config};
+use reth_db::DatabaseEnv;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    tables,
+    transaction::{DbTx, DbTxMut},
+    RawKey, RawValue,
+};
+use reth_etl::Collector;
+use reth_node_builder::NodeTypesWithDBAdapter;
+use reth_node_core::args::PruningArgs;
+use reth_primitives_traits::{SignedTransaction, SignerRecoverable};
+use reth_provider::{
+    BlockNumReader, BlockReader, DBProvider, ProviderFactory, PruneCheckpointReader,
+    PruneCheckpointWriter, TransactionsProvider, TransactionsProviderExt,
+};
+use reth_prune::{
+    PruneCheckpoint, PruneMode, PruneReconfiguration, PruneSegment, PrunerBuilder,
+    SegmentReconfiguration,
+};
+use reth_stages::{
+    stages::{IndexAccountHistoryStage, IndexStorageHistoryStage},
+    ExecInput, Stage, StageCheckpoint,
+};
+use std::{ops::RangeInclusive, sync::Arc};
+use tracing::info;
+
+/// Number of blocks to rebuild a segment for before committing.
+const REBUILD_COMMIT_THRESHOLD: u64 = 10_000;
+
+/// `reth prune reconfigure` command
+///
+/// Compares the stored prune checkpoints with the prune configuration, and brings the datadir in
+/// line with it: segments that the configuration prunes further are pruned, and segments that were
+/// pruned further than the configuration allows are rebuilt from static files or changesets where
+/// possible.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The new prune configuration. Any configuration set here takes precedence over the one
+    /// set in `reth.toml`.
+    #[command(flatten)]
+    pruning: PruningArgs,
+
+    /// Only print the changes, without applying them.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `prune reconfigure` command
+    pub fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let Environment { config, provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+        // Any configuration set in CLI takes precedence over the one set in toml
+        let mut prune_config = self.pruning.prune_config(&*self.env.chain).unwrap_or_default();
+        prune_config.merge(config.prune.clone());
+
+        let provider = provider_factory.provider()?;
+        let tip = provider.last_block_number()?;
+        let checkpoints = provider.get_prune_checkpoints()?;
+        drop(provider);
+
+        let reconfiguration = PruneReconfiguration::new(&checkpoints, &prune_config.segments, tip)?;
+        for (segment, mode, action) in &reconfiguration.segments {
+            info!(target: "reth::cli", %segment, ?mode, ?action, "Prune segment reconfiguration");
+        }
+
+        let mut unrecoverable = reconfiguration
+            .unrecoverable()
+            .map(|(segment, blocks)| (segment, blocks.clone()))
+            .collect::<Vec<_>>();
+        // History indices can only be rebuilt for the blocks that still have changesets
+        for (segment, _, action) in &reconfiguration.segments {
+            if let SegmentReconfiguration::Rebuild { blocks, .. } = action {
+                if let Some(missing) = missing_changesets(&provider_factory, *segment, blocks)? {
+                    unrecoverable.push((*segment, missing));
+                }
+            }
+        }
+        if !unrecoverable.is_empty() {
+            eyre::bail!(
+                "The following segments were pruned further than the new configuration allows and \
+                 can't be restored without a resync: {}",
+                unrecoverable
+                    .iter()
+                    .map(|(segment, blocks)| format!(
+                        "{segment} ({}..={})",
+                        blocks.start(),
+                        blocks.end()
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+
+        if reconfiguration.is_unchanged() {
+            info!(target: "reth::cli", "Datadir already matches the prune configuration");
+            return Ok(())
+        }
+        if self.dry_run {
+            return Ok(())
+        }
+
+        let lowest_static_file_height =
+            copy_to_static_files(&provider_factory, prune_config.segments.clone())?;
+
+        let mut prune = false;
+        for (segment, mode, action) in reconfiguration.segments {
+            match action {
+                SegmentReconfiguration::Rebuild { blocks, pruned_to } => rebuild_segment(
+                    &provider_factory,
+                    &config,
+                    segment,
+                    blocks,
+                    pruned_to,
+                    mode,
+                    tip,
+                )?,
+                SegmentReconfiguration::Prune { .. } => prune = true,
+                SegmentReconfiguration::Unchanged |
+                SegmentReconfiguration::Unrecoverable { .. } => {}
+            }
+        }
+
+        // Delete data which has been copied to static files.
+        if let Some(prune_tip) = lowest_static_file_height.filter(|_| prune) {
+            info!(target: "reth::cli", ?prune_tip, "Pruning data from database...");
+            // Run the pruner according to the new configuration, and don't enforce any limits on it
+            let mut pruner = PrunerBuilder::new(prune_config)
+                .delete_limit(usize::MAX)
+                .build_with_provider_factory(provider_factory);
+            pruner.run(prune_tip)?;
+            info!(target: "reth::cli", "Pruned data from database");
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub const fn chain_spec(&self) -> &Arc<C::ChainSpec> {
+        &self.env.chain
+    }
+}
+
+/// Returns the blocks of a history segment rebuild that no longer have changesets, and can't be
+/// indexed again.
+fn missing_changesets<N: CliNodeTypes>(
+    provider_factory: &ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
+    segment: PruneSegment,
+    blocks: &RangeInclusive<BlockNumber>,
+) -> eyre::Result<Option<RangeInclusive<BlockNumber>>> {
+    let provider = provider_factory.provider()?;
+    let first_changeset = match segment {
+        PruneSegment::AccountHistory => provider
+            .tx_ref()
+            .cursor_read::<tables::AccountChangeSets>()?
+            .first()?
+            .map(|(block_number, _)| block_number),
+        PruneSegment::StorageHistory => provider
+            .tx_ref()
+            .cursor_read::<tables::StorageChangeSets>()?
+            .first()?
+            .map(|(key, _)| key.block_number()),
+        _ => return Ok(None),
+    };
+
+    Ok(match first_changeset {
+        Some(first) if first <= *blocks.start() => None,
+        Some(first) => Some(*blocks.start()..=(first - 1).min(*blocks.end())),
+        None => Some(blocks.clone()),
+    })
+}
+
+/// Rebuilds the pruned blocks of a segment, and updates its checkpoint to the blocks that stay
+/// pruned.
+fn rebuild_segment<N: CliNodeTypes>(
+    provider_factory: &ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
+    config: &Config,
+    segment: PruneSegment,
+    blocks: RangeInclusive<u64>,
+    pruned_to: Option<u64>,
+    mode: Option<PruneMode>,
+    tip: BlockNumber,
+) -> eyre::Result<()> {
+    info!(target: "reth::cli", %segment, ?blocks, "Rebuilding prune segment");
+
+    let etl_config = config.stages.etl.clone();
+    match segment {
+        PruneSegment::SenderRecovery => rebuild_senders(provider_factory, blocks)?,
+        PruneSegment::TransactionLookup => {
+            rebuild_transaction_lookup(provider_factory, &etl_config, blocks)?
+        }
+        PruneSegment::AccountHistory | PruneSegment::StorageHistory => {
+            // The stages clear the indices and rebuild them from all changesets on the first sync
+            let input = ExecInput { target: Some(tip), checkpoint: Some(StageCheckpoint::new(0)) };
+            let provider = provider_factory.database_provider_rw()?;
+            if segment == PruneSegment::AccountHistory {
+                IndexAccountHistoryStage::new(
+                    config.stages.index_account_history,
+                    etl_config,
+                    None,
+                )
+                .execute(&provider, input)?;
+            } else {
+                IndexStorageHistoryStage::new(
+                    config.stages.index_storage_history,
+                    etl_config,
+                    None,
+                )
+                .execute(&provider, input)?;
+            }
+            provider.commit()?;
+        }
+        _ => eyre::bail!("segment {segment} can't be rebuilt"),
+    }
+    info!(target: "reth::cli", %segment, "Rebuilt prune segment");
+
+    let provider = provider_factory.database_provider_rw()?;
+    match pruned_to.zip(mode) {
+        Some((block_number, prune_mode)) => {
+            let tx_number =
+                provider.block_body_indices(block_number)?.map(|indices| indices.last_tx_num());
+            provider.save_prune_checkpoint(
+                segment,
+                PruneCheckpoint { block_number: Some(block_number), tx_number, prune_mode },
+            )?;
+        }
+        None => {
+            provider.tx_ref().delete::<tables::PruneCheckpoints>(segment, None)?;
+        }
+    }
+    provider.commit()?;
+
+    Ok(())
+}
+
+/// Recovers the senders of the transactions in `blocks` from static files.
+///
+/// Senders are appended if no later transactions have senders stored, and inserted otherwise.
+fn rebuild_senders<N: CliNodeTypes>(
+    provider_factory: &ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
+    blocks: RangeInclusive<u64>,
+) -> eyre::Result<()> {
+    for start in blocks.clone().step_by(REBUILD_COMMIT_THRESHOLD as usize) {
+        let end = (start + REBUILD_COMMIT_THRESHOLD - 1).min(*blocks.end());
+        let provider = provider_factory.database_provider_rw()?;
+
+        let tx_range = provider.transaction_range_by_block_range(start..=end)?;
+        let transactions = provider.transactions_by_tx_range(tx_range.clone())?;
+
+        let mut cursor = provider.tx_ref().cursor_write::<tables::TransactionSenders>()?;
+        let append_only = cursor.last()?.is_none_or(|(tx_number, _)| tx_number < *tx_range.start());
+        for (tx_number, transaction) in tx_range.zip(transactions) {
+            let sender = transaction.recover_signer_unchecked()?;
+            if append_only {
+                cursor.append(tx_number, &sender)?
+            } else {
+                cursor.insert(tx_number, &sender)?
+            }
+        }
+        drop(cursor);
+
+        provider.commit()?;
+        info!(target: "reth::cli", block = end, "Rebuilt sender recovery");
+    }
+
+    Ok(())
+}
+
+/// Hashes the transactions in `blocks` from static files, and writes the hashes sorted through an
+/// ETL collector.
+fn rebuild_transaction_lookup<N: CliNodeTypes>(
+    provider_factory: &ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
+    etl_config: &EtlConfig,
+    blocks: RangeInclusive<u64>,
+) -> eyre::Result<()> {
+    let mut collector: Collector<TxHash, TxNumber> =
+        Collector::new(etl_config.file_size, etl_config.dir.clone());
+
+    let provider = provider_factory.provider()?;
+    for start in blocks.clone().step_by(REBUILD_COMMIT_THRESHOLD as usize) {
+        let end = (start + REBUILD_COMMIT_THRESHOLD - 1).min(*blocks.end());
+        let tx_range = provider.transaction_range_by_block_range(start..=end)?;
+        for (hash, tx_number) in
+            provider.transaction_hashes_by_range(*tx_range.start()..*tx_range.end() + 1)?
+        {
+            collector.insert(hash, tx_number)?;
+        }
+        info!(target: "reth::cli", block = end, "Hashed transactions");
+    }
+    drop(provider);
+
+    let provider = provider_factory.database_provider_rw()?;
+    let append_only = provider.tx_ref().entries::<tables::TransactionHashNumbers>()? == 0;
+    let mut cursor =
+        provider.tx_ref().cursor_write::<tables::RawTable<tables::TransactionHashNumbers>>()?;
+    for entry in collector.iter()? {
+        let (hash, tx_number) = entry?;
+        let key = RawKey::<TxHash>::from_vec(hash);
+        let value = RawValue::<TxNumber>::from_vec(tx_number);
+        if append_only {
+            cursor.append(key, &value)?
+        } else {
+            cursor.insert(key, &value)?
+        }
+    }
+    drop(cursor);
+    provider.commit()?;
+
+    Ok(())
+}
@@ -149,6 +149,14 @@ impl<C: ChainSpecParser> Command<C> {
 
                 insert_genesis_history(&provider_rw, self.env.chain.genesis().alloc.iter())?;
             }
+            StageEnum::LogIndex => {
+                tx.clear::<tables::LogAddressHistory>()?;
+                tx.clear::<tables::LogTopicHistory>()?;
+
+                // The log index is optional, so it's no longer maintained until the stage is run
+                // again.
+                tx.delete::<tables::StageCheckpoints>(StageId::IndexLogs.to_string(), None)?;
+            }
             StageEnum::TxLookup => {
                 tx.clear::<tables::TransactionHashNumbers>()?;
                 reset_prune_checkpoint(tx, PruneSegment::TransactionLookup)?;
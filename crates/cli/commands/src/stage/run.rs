@@ -39,8 +39,8 @@ use reth_provider::{
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, HeaderStage, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        IndexLogsStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
+        StorageHashingStage, TransactionLookupStage,
     },
     ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageExt, UnwindInput, UnwindOutput,
 };
@@ -319,6 +319,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     )),
                     None,
                 ),
+                StageEnum::LogIndex => {
+                    (Box::new(IndexLogsStage::new(config.stages.index_logs, etl_config)), None)
+                }
                 _ => return Ok(()),
             };
         if let Some(unwind_stage) = &unwind_stage {
@@ -22,6 +22,13 @@ cfg_if::cfg_if! {
     }
 }
 
+// Heap profiles require jemalloc to be initialized with profiling enabled. Sampling an allocation
+// every 2^19 bytes (512KiB) on average keeps the overhead low enough for production nodes.
+#[cfg(all(feature = "jemalloc-prof", unix))]
+#[allow(non_upper_case_globals)]
+#[export_name = "_rjem_malloc_conf"]
+static malloc_conf: &[u8] = b"prof:true,prof_active:true,lg_prof_sample:19\0";
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "tracy-allocator")] {
         type AllocatorWrapper = tracy_client::ProfiledAllocator<AllocatorInner>;
@@ -125,6 +125,8 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexLogsConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -426,6 +428,23 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Log index stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexLogsConfig {
+    /// Whether to maintain the index of log addresses and topics used to speed up `eth_getLogs`.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -464,6 +483,7 @@ impl PruneConfig {
                     account_history,
                     storage_history,
                     bodies_history,
+                    trie_changesets,
                     receipts_log_filter,
                 },
         } = other;
@@ -480,6 +500,7 @@ impl PruneConfig {
         self.segments.account_history = self.segments.account_history.or(account_history);
         self.segments.storage_history = self.segments.storage_history.or(storage_history);
         self.segments.bodies_history = self.segments.bodies_history.or(bodies_history);
+        self.segments.trie_changesets = self.segments.trie_changesets.or(trie_changesets);
 
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
@@ -1001,6 +1022,7 @@ receipts = 'full'
                 account_history: None,
                 storage_history: Some(PruneMode::Before(5000)),
                 bodies_history: None,
+                trie_changesets: None,
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([(
                     Address::random(),
                     PruneMode::Full,
@@ -1017,6 +1039,7 @@ receipts = 'full'
                 account_history: Some(PruneMode::Distance(2000)),
                 storage_history: Some(PruneMode::Distance(3000)),
                 bodies_history: None,
+                trie_changesets: Some(PruneMode::Distance(4000)),
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
@@ -1035,6 +1058,7 @@ receipts = 'full'
         assert_eq!(config1.segments.receipts, Some(PruneMode::Distance(1000)));
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
+        assert_eq!(config1.segments.trie_changesets, Some(PruneMode::Distance(4000)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
     }
 
@@ -37,7 +37,7 @@ use reth_trie::{updates::TrieUpdates, HashedPostState, TrieInput};
 use reth_trie_db::{DatabaseHashedPostState, StateCommitment};
 use reth_trie_parallel::root::{ParallelStateRoot, ParallelStateRootError};
 use std::{collections::HashMap, sync::Arc, time::Instant};
-use tracing::{debug, error, info, trace, warn};
+use tracing::{debug, debug_span, error, info, instrument, trace, warn};
 
 /// Context providing access to tree state during validation.
 ///
@@ -214,6 +214,7 @@ where
     /// - Block execution
     /// - State root computation
     /// - Fork detection
+    #[instrument(level = "debug", skip_all, fields(block = ?block.num_hash()), target = "engine::tree")]
     pub fn validate_block_with_state<T: PayloadTypes<BuiltPayload: BuiltPayload<Primitives = N>>>(
         &mut self,
         block: RecoveredBlock<N::Block>,
@@ -395,6 +396,8 @@ where
             return Err((err.into(), block))
         }
 
+        let state_root_span =
+            debug_span!(target: "engine::tree", "state_root", block = ?block_num_hash).entered();
         debug!(target: "engine::tree", block=?block_num_hash, "Calculating block state root");
 
         let root_time = Instant::now();
@@ -471,6 +474,7 @@ where
 
         self.metrics.block_validation.record_state_root(&trie_output, root_elapsed.as_secs_f64());
         debug!(target: "engine::tree", ?root_elapsed, block=?block_num_hash, "Calculated state root");
+        drop(state_root_span);
 
         // ensure state root matches
         if state_root != block.header().state_root() {
@@ -546,6 +550,7 @@ where
     }
 
     /// Executes a block with the given state provider
+    #[instrument(level = "debug", skip_all, fields(block = ?block.num_hash()), target = "engine::tree")]
     fn execute_block<S: StateProvider>(
         &mut self,
         state_provider: S,
@@ -6,7 +6,8 @@ use reth_chainspec::{ChainSpec, EthChainSpec, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
     common::{CliComponentsBuilder, CliHeader, CliNodeTypes},
-    config_cmd, db, download, dump_genesis, export_era, import, import_era, init_cmd, init_state,
+    config_cmd, db, download, dump_genesis, exex, export_era, import, import_era, init_cmd,
+    init_state,
     launcher::FnLauncher,
     node::{self, NoArgs},
     p2p, prune, re_execute, recover, stage,
@@ -223,6 +224,7 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Cli<C, Ext> {
                 runner.run_command_until_exit(|ctx| command.execute::<N>(ctx))
             }
             Commands::Prune(command) => runner.run_until_ctrl_c(command.execute::<N>()),
+            Commands::Exex(command) => runner.run_until_ctrl_c(command.execute::<N>()),
             Commands::ReExecute(command) => {
                 runner.run_until_ctrl_c(command.execute::<N>(components))
             }
@@ -287,6 +289,9 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand<C>),
+    /// Execution extension utilities
+    #[command(name = "exex")]
+    Exex(exex::Command<C>),
     /// Re-execute blocks in parallel to verify historical sync correctness.
     #[command(name = "re-execute")]
     ReExecute(re_execute::Command<C>),
@@ -312,6 +317,7 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Commands<C, Ext> {
             Self::Config(_) => None,
             Self::Recover(cmd) => cmd.chain_spec(),
             Self::Prune(cmd) => cmd.chain_spec(),
+            Self::Exex(cmd) => cmd.chain_spec(),
             Self::ReExecute(cmd) => cmd.chain_spec(),
         }
     }
@@ -330,6 +336,26 @@ mod tests {
         assert_eq!(reth.logs.color, ColorMode::Always);
     }
 
+    #[test]
+    fn parse_tracing_otlp() {
+        let reth = Cli::try_parse_args_from(["reth", "node"]).unwrap();
+        assert_eq!(reth.logs.tracing_otlp, None);
+
+        let reth = Cli::try_parse_args_from(["reth", "node", "--tracing-otlp"]).unwrap();
+        assert_eq!(reth.logs.tracing_otlp.unwrap().as_str(), "http://localhost:4318/v1/traces");
+
+        let reth = Cli::try_parse_args_from([
+            "reth",
+            "node",
+            "--tracing-otlp=http://collector:4318/v1/traces",
+            "--tracing-otlp.sample-ratio",
+            "0.1",
+        ])
+        .unwrap();
+        assert_eq!(reth.logs.tracing_otlp.unwrap().as_str(), "http://collector:4318/v1/traces");
+        assert_eq!(reth.logs.tracing_otlp_sample_ratio, 0.1);
+    }
+
     /// Tests that the help message is parsed correctly. This ensures that clap args are configured
     /// correctly and no conflicts are introduced via attributes that would result in a panic at
     /// runtime
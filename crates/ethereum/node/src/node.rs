@@ -24,8 +24,9 @@ use reth_node_api::{
 };
 use reth_node_builder::{
     components::{
-        BasicPayloadServiceBuilder, ComponentsBuilder, ConsensusBuilder, ExecutorBuilder,
-        NetworkBuilder, PoolBuilder, TxPoolBuilder,
+        load_inclusion_policy, spawn_inclusion_policy_reload_task, BasicPayloadServiceBuilder,
+        ComponentsBuilder, ConsensusBuilder, ExecutorBuilder, NetworkBuilder, PoolBuilder,
+        TxPoolBuilder,
     },
     node::{FullNodeTypes, NodeTypes},
     rpc::{
@@ -34,13 +35,17 @@ use reth_node_builder::{
     },
     BuilderContext, DebugNode, Node, NodeAdapter, PayloadBuilderConfig, PayloadTypes,
 };
-use reth_provider::{providers::ProviderFactoryBuilder, EthStorage};
+use reth_provider::{
+    providers::ProviderFactoryBuilder, DBProvider, DatabaseProviderFactory, EthStorage,
+};
 use reth_rpc::{
     eth::core::{EthApiFor, EthRpcConverterFor},
-    ValidationApi,
+    EthExtApi, ValidationApi,
+};
+use reth_rpc_api::servers::{BlockSubmissionValidationApiServer, L2EthApiExtServer};
+use reth_rpc_builder::{
+    archive::ArchiveRpc, config::RethRpcServerConfig, middleware::RethRpcMiddleware,
 };
-use reth_rpc_api::servers::BlockSubmissionValidationApiServer;
-use reth_rpc_builder::{config::RethRpcServerConfig, middleware::RethRpcMiddleware};
 use reth_rpc_eth_api::{
     helpers::pending_block::BuildPendingEnv, RpcConvert, RpcTypes, SignableTxRequest,
 };
@@ -48,7 +53,8 @@ use reth_rpc_eth_types::{error::FromEvmError, EthApiError};
 use reth_rpc_server_types::RethRpcModule;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, PoolPooledTx, PoolTransaction,
+    blobstore::DiskFileBlobStore, conditional::MaybeConditionalTransaction,
+    inclusion::evict_denied_transactions, EthTransactionPool, PoolPooledTx, PoolTransaction,
     TransactionPool, TransactionValidationTaskExecutor,
 };
 use reth_trie_db::MerklePatriciaTrie;
@@ -246,6 +252,7 @@ where
             Payload: EngineTypes<ExecutionData = ExecutionData>,
         >,
         Evm: ConfigureEvm<NextBlockEnvCtx = NextBlockEnvAttributes>,
+        Pool: TransactionPool<Transaction: MaybeConditionalTransaction>,
     >,
     EthB: EthApiBuilder<N>,
     EV: EngineValidatorBuilder<N>,
@@ -269,13 +276,41 @@ where
             Arc::new(EthereumEngineValidator::new(ctx.config.chain.clone())),
         );
 
+        let archive_rpc = ctx
+            .config
+            .rpc
+            .rpc_archive_upstream
+            .clone()
+            .map(|upstream| -> eyre::Result<_> {
+                let prune_modes =
+                    ctx.node.provider().database_provider_ro()?.prune_modes_ref().clone();
+                info!(target: "reth::cli", %upstream, "Forwarding pruned history to archive RPC");
+                Ok(ArchiveRpc::new(ctx.node.provider().clone(), upstream, prune_modes))
+            })
+            .transpose()?;
+
+        let tx_conditional_ext = ctx
+            .config
+            .rpc
+            .rpc_enable_tx_conditional
+            .then(|| EthExtApi::new(ctx.node.pool().clone(), ctx.node.provider().clone()));
+
         self.inner
+            .option_layer_rpc_middleware(archive_rpc)
             .launch_add_ons_with(ctx, move |container| {
                 container.modules.merge_if_module_configured(
                     RethRpcModule::Flashbots,
                     validation_api.into_rpc(),
                 )?;
 
+                if let Some(tx_conditional_ext) = tx_conditional_ext {
+                    // extend the eth namespace if configured in the regular http server
+                    container.modules.merge_if_module_configured(
+                        RethRpcModule::Eth,
+                        tx_conditional_ext.into_rpc(),
+                    )?;
+                }
+
                 Ok(())
             })
             .await
@@ -291,6 +326,7 @@ where
             Payload: EngineTypes<ExecutionData = ExecutionData>,
         >,
         Evm: ConfigureEvm<NextBlockEnvCtx = NextBlockEnvAttributes>,
+        Pool: TransactionPool<Transaction: MaybeConditionalTransaction>,
     >,
     EthB: EthApiBuilder<N>,
     EV: EngineValidatorBuilder<N>,
@@ -432,6 +468,8 @@ where
         let blob_store =
             reth_node_builder::components::create_blob_store_with_cache(ctx, blob_cache_size)?;
 
+        let inclusion_policy = load_inclusion_policy(ctx)?;
+
         let validator = TransactionValidationTaskExecutor::eth_builder(ctx.provider().clone())
             .with_head_timestamp(ctx.head().timestamp)
             .with_max_tx_input_bytes(ctx.config().txpool.max_tx_input_bytes)
@@ -441,6 +479,7 @@ where
             .with_max_tx_gas_limit(ctx.config().txpool.max_tx_gas_limit)
             .with_minimum_priority_fee(ctx.config().txpool.minimum_priority_fee)
             .with_additional_tasks(ctx.config().txpool.additional_validation_tasks)
+            .with_inclusion_policy(inclusion_policy.clone())
             .build_with_tasks(ctx.task_executor().clone(), blob_store.clone());
 
         if validator.validator().eip4844() {
@@ -458,6 +497,14 @@ where
             .with_validator(validator)
             .build_and_spawn_maintenance_task(blob_store, pool_config)?;
 
+        if let Some(inclusion_policy) = inclusion_policy {
+            // evict transactions that are denied by updated policies
+            let pool = transaction_pool.clone();
+            spawn_inclusion_policy_reload_task(ctx, inclusion_policy, move |policy| {
+                evict_denied_transactions(&pool, policy)
+            });
+        }
+
         info!(target: "reth::cli", "Transaction pool initialized");
         debug!(target: "reth::cli", "Spawned txpool maintenance task");
 
@@ -11,7 +11,9 @@ use reth_node_api::{FullNodeTypes, NodeTypes, PrimitivesTy, TxTy};
 use reth_node_builder::{
     components::PayloadBuilderBuilder, BuilderContext, PayloadBuilderConfig, PayloadTypes,
 };
-use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use reth_transaction_pool::{
+    conditional::MaybeConditionalTransaction, PoolTransaction, TransactionPool,
+};
 
 /// A basic ethereum payload service.
 #[derive(Clone, Default, Debug)]
@@ -22,8 +24,10 @@ impl<Types, Node, Pool, Evm> PayloadBuilderBuilder<Node, Pool, Evm> for Ethereum
 where
     Types: NodeTypes<ChainSpec: EthereumHardforks, Primitives = EthPrimitives>,
     Node: FullNodeTypes<Types = Types>,
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
-        + Unpin
+    Pool: TransactionPool<
+            Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>
+                             + MaybeConditionalTransaction,
+        > + Unpin
         + 'static,
     Evm: ConfigureEvm<
             Primitives = PrimitivesTy<Types>,
@@ -48,11 +52,16 @@ where
         let chain = ctx.chain_spec().chain();
         let gas_limit = conf.gas_limit_for(chain);
 
+        // share the pool's policy so reloads apply to both the pool and built payloads
+        let inclusion_policy = pool.inclusion_policy();
+
         Ok(reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
             ctx.provider().clone(),
             pool,
             evm_config,
-            EthereumBuilderConfig::new().with_gas_limit(gas_limit),
+            EthereumBuilderConfig::new()
+                .with_gas_limit(gas_limit)
+                .with_inclusion_policy(inclusion_policy),
         ))
     }
 }
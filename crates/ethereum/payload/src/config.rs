@@ -1,5 +1,6 @@
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
 use reth_primitives_traits::constants::GAS_LIMIT_BOUND_DIVISOR;
+use reth_transaction_pool::inclusion::InclusionPolicyHandle;
 
 /// Settings for the Ethereum builder.
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -9,6 +10,8 @@ pub struct EthereumBuilderConfig {
     /// Waits for the first payload to be built if there is no payload built when the payload is
     /// being resolved.
     pub await_payload_on_missing: bool,
+    /// Address deny-list that transactions must satisfy to be included in the payload.
+    pub inclusion_policy: Option<InclusionPolicyHandle>,
 }
 
 impl Default for EthereumBuilderConfig {
@@ -20,7 +23,11 @@ impl Default for EthereumBuilderConfig {
 impl EthereumBuilderConfig {
     /// Create new payload builder config.
     pub const fn new() -> Self {
-        Self { desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M, await_payload_on_missing: true }
+        Self {
+            desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            await_payload_on_missing: true,
+            inclusion_policy: None,
+        }
     }
 
     /// Set desired gas limit.
@@ -35,6 +42,15 @@ impl EthereumBuilderConfig {
         self.await_payload_on_missing = await_payload_on_missing;
         self
     }
+
+    /// Sets the inclusion policy that is enforced for transactions included in the payload.
+    pub fn with_inclusion_policy(
+        mut self,
+        inclusion_policy: Option<InclusionPolicyHandle>,
+    ) -> Self {
+        self.inclusion_policy = inclusion_policy;
+        self
+    }
 }
 
 impl EthereumBuilderConfig {
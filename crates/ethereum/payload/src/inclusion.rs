@@ -0,0 +1,108 @@
+//! Call tracing used to enforce the call targets of an inclusion policy.
+
+use alloy_primitives::Address;
+use parking_lot::Mutex;
+use revm::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, InterpreterTypes},
+    Inspector,
+};
+use std::{collections::HashSet, sync::Arc};
+
+/// An [`Inspector`] that records every account a transaction calls or creates.
+///
+/// This records the target and the code address of every `CALL`, `CALLCODE`, `DELEGATECALL` and
+/// `STATICCALL`, including the top-level call of the transaction, and the address of every
+/// contract deployed via `CREATE` or `CREATE2`.
+///
+/// The recorded targets are shared between clones, so they can be read while the inspector is
+/// owned by the EVM.
+#[derive(Debug, Clone, Default)]
+pub struct CallTargetInspector {
+    /// Accounts called or created since the targets were last taken.
+    targets: Arc<Mutex<HashSet<Address>>>,
+}
+
+impl CallTargetInspector {
+    /// Returns all recorded targets and resets the inspector.
+    pub fn take(&self) -> HashSet<Address> {
+        std::mem::take(&mut *self.targets.lock())
+    }
+
+    /// Discards all recorded targets.
+    pub fn clear(&self) {
+        self.targets.lock().clear();
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for CallTargetInspector {
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let mut targets = self.targets.lock();
+        targets.insert(inputs.target_address);
+        // the code of delegated calls is loaded from a different account
+        targets.insert(inputs.bytecode_address);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        if let Some(address) = outcome.address {
+            self.targets.lock().insert(address);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, Bytes, TxKind};
+    use reth_evm::{ConfigureEvm, Evm, EvmEnv};
+    use reth_evm_ethereum::EthEvmConfig;
+    use revm::{
+        bytecode::Bytecode,
+        context::TxEnv,
+        database::{CacheDB, EmptyDB},
+        state::AccountInfo,
+    };
+
+    #[test]
+    fn records_internal_call_targets() {
+        let contract = address!("0x0000000000000000000000000000000000000003");
+        let denied = address!("0x0000000000000000000000000000000000000004");
+
+        // STATICCALL(gas, denied, 0, 0, 0, 0) without emitting any logs
+        let mut code = vec![0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73];
+        code.extend_from_slice(denied.as_slice());
+        code.extend_from_slice(&[0x5a, 0xfa, 0x00]);
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_raw(Bytes::from(code))),
+        );
+
+        let inspector = CallTargetInspector::default();
+        let mut evm = EthEvmConfig::mainnet().evm_with_env_and_inspector(
+            db,
+            EvmEnv::default(),
+            inspector.clone(),
+        );
+        let res = evm
+            .transact(TxEnv {
+                kind: TxKind::Call(contract),
+                gas_limit: 1_000_000,
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(res.result.is_success());
+        assert!(res.result.logs().is_empty());
+
+        let targets = inspector.take();
+        assert!(targets.contains(&contract));
+        assert!(targets.contains(&denied));
+        assert!(inspector.take().is_empty());
+    }
+}
@@ -9,8 +9,8 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![allow(clippy::useless_let_if_seq)]
 
-use alloy_consensus::Transaction;
-use alloy_primitives::U256;
+use alloy_consensus::{conditional::BlockConditionalAttributes, Transaction};
+use alloy_primitives::{Address, B256, U256};
 use reth_basic_payload_builder::{
     is_better_payload, BuildArguments, BuildOutcome, MissingPayloadBehaviour, PayloadBuilder,
     PayloadConfig,
@@ -19,6 +19,7 @@ use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_errors::{BlockExecutionError, BlockValidationError};
 use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
 use reth_evm::{
+    block::CommitChanges,
     execute::{BlockBuilder, BlockBuilderOutcome},
     ConfigureEvm, Evm, NextBlockEnvAttributes,
 };
@@ -28,19 +29,25 @@ use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadBuilderAttributes;
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
 use reth_revm::{database::StateProviderDatabase, db::State};
-use reth_storage_api::StateProviderFactory;
+use reth_storage_api::{errors::ProviderError, StateProvider, StateProviderFactory};
 use reth_transaction_pool::{
+    conditional::{validate_conditional, KnownAccountsState, MaybeConditionalTransaction},
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
+    pool::BestTransactionsWithInclusionPolicy,
     BestTransactions, BestTransactionsAttributes, PoolTransaction, TransactionPool,
     ValidPoolTransaction,
 };
-use revm::context_interface::Block as _;
+use reth_trie_common::HashedStorage;
+use revm::{context_interface::Block as _, Database};
 use std::sync::Arc;
 use tracing::{debug, trace, warn};
 
 mod config;
 pub use config::*;
 
+mod inclusion;
+pub use inclusion::CallTargetInspector;
+
 pub mod validator;
 pub use validator::EthereumExecutionPayloadValidator;
 
@@ -78,7 +85,9 @@ impl<Pool, Client, EvmConfig> PayloadBuilder for EthereumPayloadBuilder<Pool, Cl
 where
     EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks> + Clone,
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+    Pool: TransactionPool<
+        Transaction: PoolTransaction<Consensus = TransactionSigned> + MaybeConditionalTransaction,
+    >,
 {
     type Attributes = EthPayloadBuilderAttributes;
     type BuiltPayload = EthBuiltPayload;
@@ -144,7 +153,9 @@ pub fn default_ethereum_payload<EvmConfig, Client, Pool, F>(
 where
     EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks>,
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+    Pool: TransactionPool<
+        Transaction: PoolTransaction<Consensus = TransactionSigned> + MaybeConditionalTransaction,
+    >,
     F: FnOnce(BestTransactionsAttributes) -> BestTransactionsIter<Pool>,
 {
     let BuildArguments { mut cached_reads, config, cancel, best_payload } = args;
@@ -155,20 +166,28 @@ where
     let mut db =
         State::builder().with_database(cached_reads.as_db_mut(state)).with_bundle_update().build();
 
-    let mut builder = evm_config
-        .builder_for_next_block(
-            &mut db,
-            &parent_header,
-            NextBlockEnvAttributes {
-                timestamp: attributes.timestamp(),
-                suggested_fee_recipient: attributes.suggested_fee_recipient(),
-                prev_randao: attributes.prev_randao(),
-                gas_limit: builder_config.gas_limit(parent_header.gas_limit),
-                parent_beacon_block_root: attributes.parent_beacon_block_root(),
-                withdrawals: Some(attributes.withdrawals().clone()),
-            },
-        )
+    // use the same policy snapshot for the entire payload
+    let inclusion_policy = builder_config.inclusion_policy.as_ref().map(|policy| policy.current());
+    // call targets can only be checked by tracing the execution
+    let call_target_policy = inclusion_policy.clone().filter(|policy| policy.has_call_targets());
+    let call_targets = CallTargetInspector::default();
+
+    let next_attributes = NextBlockEnvAttributes {
+        timestamp: attributes.timestamp(),
+        suggested_fee_recipient: attributes.suggested_fee_recipient(),
+        prev_randao: attributes.prev_randao(),
+        gas_limit: builder_config.gas_limit(parent_header.gas_limit),
+        parent_beacon_block_root: attributes.parent_beacon_block_root(),
+        withdrawals: Some(attributes.withdrawals().clone()),
+    };
+    let evm_env = evm_config
+        .next_evm_env(&parent_header, &next_attributes)
         .map_err(PayloadBuilderError::other)?;
+    let mut evm = evm_config.evm_with_env_and_inspector(&mut db, evm_env, call_targets.clone());
+    // only pay for tracing if there are call targets to check
+    evm.set_inspector_enabled(call_target_policy.is_some());
+    let ctx = evm_config.context_for_next_block(&parent_header, next_attributes);
+    let mut builder = evm_config.create_block_builder(evm, &parent_header, ctx);
 
     let chain_spec = client.chain_spec();
 
@@ -181,6 +200,11 @@ where
         base_fee,
         builder.evm_mut().block().blob_gasprice().map(|gasprice| gasprice as u64),
     ));
+
+    if let Some(policy) = inclusion_policy {
+        best_txs = Box::new(BestTransactionsWithInclusionPolicy::new(policy, best_txs));
+    }
+
     let mut total_fees = U256::ZERO;
 
     builder.apply_pre_execution_changes().map_err(|err| {
@@ -198,6 +222,11 @@ where
     let max_blob_count =
         blob_params.as_ref().map(|params| params.max_blob_count).unwrap_or_default();
 
+    let block_attr = BlockConditionalAttributes {
+        number: parent_header.number + 1,
+        timestamp: attributes.timestamp,
+    };
+
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
@@ -216,6 +245,20 @@ where
             return Ok(BuildOutcome::Cancelled)
         }
 
+        // skip transactions whose conditional doesn't hold for this block, known accounts are
+        // checked against the state after the transactions executed so far
+        if let Some(conditional) = pool_tx.transaction.conditional() {
+            let state = PendingBlockState {
+                db: builder.evm_mut().db_mut(),
+                parent: state_provider.as_ref(),
+            };
+            if let Err(err) = validate_conditional(state, conditional, &block_attr) {
+                trace!(target: "payload_builder", %err, tx=?pool_tx.hash(), "skipping transaction with failed conditional");
+                best_txs.mark_invalid(&pool_tx, InvalidPoolTransactionError::other(err));
+                continue
+            }
+        }
+
         // convert tx to a signed transaction
         let tx = pool_tx.to_consensus();
 
@@ -272,8 +315,29 @@ where
             };
         }
 
-        let gas_used = match builder.execute_transaction(tx.clone()) {
-            Ok(gas_used) => gas_used,
+        // drop targets recorded by system calls or transactions that failed validation
+        call_targets.clear();
+        let mut call_target_violation = None;
+        let gas_used = match builder.execute_transaction_with_commit_condition(tx.clone(), |_| {
+            if let Some(Err(err)) = call_target_policy
+                .as_ref()
+                .map(|policy| policy.check_call_targets(&call_targets.take()))
+            {
+                call_target_violation = Some(err);
+                return CommitChanges::No
+            }
+            CommitChanges::Yes
+        }) {
+            Ok(Some(gas_used)) => gas_used,
+            Ok(None) => {
+                if let Some(err) = call_target_violation {
+                    // a denied account was called or created, skip the transaction and all of its
+                    // descendants
+                    trace!(target: "payload_builder", %err, ?tx, "skipping transaction denied by inclusion policy");
+                    best_txs.mark_invalid(&pool_tx, err.into());
+                }
+                continue
+            }
             Err(BlockExecutionError::Validation(BlockValidationError::InvalidTx {
                 error, ..
             })) => {
@@ -342,3 +406,93 @@ where
 
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
+
+/// State of the block being built, that the known accounts of conditional transactions are
+/// validated against.
+struct PendingBlockState<'a, DB> {
+    /// State with the changes of the transactions executed so far.
+    db: &'a mut State<DB>,
+    /// State of the parent block.
+    parent: &'a dyn StateProvider,
+}
+
+impl<DB: Database<Error = ProviderError>> KnownAccountsState for PendingBlockState<'_, DB> {
+    fn storage_value(&mut self, address: Address, slot: U256) -> Result<U256, ProviderError> {
+        self.db.storage(address, slot)
+    }
+
+    fn storage_root(&mut self, address: Address) -> Result<B256, ProviderError> {
+        // overlay the storage of the account in this block on the parent state
+        let hashed_storage = self
+            .db
+            .cache
+            .accounts
+            .get(&address)
+            .map(|account| {
+                HashedStorage::from_plain_storage(
+                    account.status,
+                    account.account.iter().flat_map(|account| &account.storage),
+                )
+            })
+            .unwrap_or_default();
+        self.parent.storage_root(address, hashed_storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_eth::erc4337::{AccountStorage, TransactionConditional};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_transaction_pool::conditional::TransactionConditionalError;
+    use revm::{
+        state::{Account, EvmState, EvmStorageSlot},
+        DatabaseCommit,
+    };
+
+    #[test]
+    fn conditional_checked_against_block_state() {
+        let address = Address::random();
+        let slot = U256::from(1);
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(1, U256::from(1))
+                .extend_storage([(B256::from(slot), U256::from(1))]),
+        );
+        let conditional = TransactionConditional {
+            known_accounts: [(
+                address,
+                AccountStorage::Slots([(slot, B256::from(U256::from(1)))].into_iter().collect()),
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        let block_attr = BlockConditionalAttributes { number: 1, timestamp: 0 };
+
+        let state_provider: Box<dyn StateProvider> = Box::new(provider);
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(&state_provider))
+            .with_bundle_update()
+            .build();
+
+        // the known accounts match the parent state
+        let state = PendingBlockState { db: &mut db, parent: state_provider.as_ref() };
+        assert!(validate_conditional(state, &conditional, &block_attr).is_ok());
+
+        // a transaction earlier in the block changes the known slot
+        let mut account = Account::from(db.basic(address).unwrap().unwrap());
+        account.mark_touch();
+        account.storage.insert(slot, EvmStorageSlot::new_changed(U256::from(1), U256::from(2), 0));
+        db.commit(EvmState::from_iter([(address, account)]));
+
+        // the transaction is skipped, even though the parent state still matches
+        let state = PendingBlockState { db: &mut db, parent: state_provider.as_ref() };
+        assert!(matches!(
+            validate_conditional(state, &conditional, &block_attr),
+            Err(TransactionConditionalError::StorageValueMismatch { .. })
+        ));
+        assert!(validate_conditional(state_provider.as_ref(), &conditional, &block_attr).is_ok());
+    }
+}
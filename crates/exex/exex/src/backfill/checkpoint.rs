@@ -0,0 +1,137 @@
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{keccak256, BlockNumber, B256};
+use std::{
+    io::{self, Write},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+/// Size of an encoded checkpoint: the first block of the range, followed by the block number and
+/// hash.
+const CHECKPOINT_SIZE: usize = 8 + 8 + 32;
+
+/// Persisted progress of a [`StreamBackfillJob`](crate::StreamBackfillJob) for a single ExEx.
+///
+/// The checkpoint is the last block that the ExEx has processed from the backfill stream, together
+/// with the first block of the backfill job. It's stored in a separate file per ExEx, usually in
+/// [`ChainPath::exex_backfill`](reth_node_core::dirs::ChainPath::exex_backfill), so that a backfill
+/// job started again from the same block after a crash or a restart skips the blocks that have
+/// already been processed, even if it runs to a newer tip.
+#[derive(Debug, Clone)]
+pub struct BackfillCheckpoint {
+    exex_id: String,
+    path: PathBuf,
+}
+
+impl BackfillCheckpoint {
+    /// Creates a new checkpoint for the ExEx with the given ID, stored in the given directory.
+    ///
+    /// The file is named after the hash of the ID, so that any ID maps to a file in the directory.
+    /// The directory is created if it doesn't exist.
+    pub fn new(directory: impl AsRef<Path>, exex_id: impl Into<String>) -> io::Result<Self> {
+        let directory = directory.as_ref();
+        reth_fs_util::create_dir_all(directory).map_err(io::Error::other)?;
+
+        let exex_id = exex_id.into();
+        let path = directory.join(format!("{:x}.checkpoint", keccak256(&exex_id)));
+        Ok(Self { exex_id, path })
+    }
+
+    /// Returns the ID of the ExEx this checkpoint belongs to.
+    pub fn exex_id(&self) -> &str {
+        &self.exex_id
+    }
+
+    /// Returns the path of the checkpoint file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the last processed block to resume a job with the given range from, if any.
+    ///
+    /// The checkpoint is only returned if the job it was saved by started at or before the start
+    /// of the range, so that all blocks of the range up to the checkpoint have been processed, and
+    /// the checkpoint lies inside the range.
+    pub fn load(&self, range: &RangeInclusive<BlockNumber>) -> io::Result<Option<BlockNumHash>> {
+        let data = match std::fs::read(&self.path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        if data.len() != CHECKPOINT_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid backfill checkpoint size {} at {:?}", data.len(), self.path),
+            ))
+        }
+
+        let number_at = |offset: usize| {
+            u64::from_le_bytes(data[offset..offset + 8].try_into().expect("8 bytes"))
+        };
+        let block = BlockNumHash::new(number_at(8), B256::from_slice(&data[16..]));
+        if number_at(0) > *range.start() || !range.contains(&block.number) {
+            return Ok(None)
+        }
+        Ok(Some(block))
+    }
+
+    /// Atomically stores the last processed block of a job that started at the given block.
+    pub fn save(&self, start: BlockNumber, block: BlockNumHash) -> io::Result<()> {
+        reth_fs_util::atomic_write_file(&self.path, |file| {
+            file.write_all(&start.to_le_bytes())?;
+            file.write_all(&block.number.to_le_bytes())?;
+            file.write_all(block.hash.as_slice())
+        })
+        .map_err(io::Error::other)
+    }
+
+    /// Removes the checkpoint, so that the next backfill job starts from the beginning of its
+    /// range.
+    pub fn clear(&self) -> io::Result<()> {
+        match std::fs::remove_file(&self.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkpoint_roundtrip() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let checkpoint = BackfillCheckpoint::new(temp_dir.path().join("backfill"), "test")?;
+        let range = 1..=100;
+        assert_eq!(checkpoint.load(&range)?, None);
+
+        let block = BlockNumHash::new(10, B256::repeat_byte(0x01));
+        checkpoint.save(*range.start(), block)?;
+        assert_eq!(checkpoint.load(&range)?, Some(block));
+
+        // A job from the same start to a newer tip, or from a later start, resumes
+        assert_eq!(checkpoint.load(&(1..=200))?, Some(block));
+        assert_eq!(checkpoint.load(&(5..=200))?, Some(block));
+
+        // The checkpoint is ignored if the job starts earlier, or it's outside the range
+        assert_eq!(checkpoint.load(&(0..=100))?, None);
+        assert_eq!(checkpoint.load(&(1..=9))?, None);
+        assert_eq!(checkpoint.load(&(11..=100))?, None);
+
+        // Checkpoints of other ExExes are independent
+        let other = BackfillCheckpoint::new(temp_dir.path().join("backfill"), "other")?;
+        assert_eq!(other.load(&range)?, None);
+
+        // IDs can't escape the directory
+        let escaping = BackfillCheckpoint::new(temp_dir.path().join("backfill"), "../../escape")?;
+        assert_eq!(escaping.path().parent(), Some(temp_dir.path().join("backfill").as_path()));
+
+        checkpoint.clear()?;
+        assert_eq!(checkpoint.load(&range)?, None);
+        checkpoint.clear()?;
+
+        Ok(())
+    }
+}
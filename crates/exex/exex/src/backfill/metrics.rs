@@ -0,0 +1,20 @@
+use metrics::{Counter, Gauge};
+use reth_metrics::Metrics;
+
+/// Metrics for the [`StreamBackfillJob`](crate::StreamBackfillJob)
+#[derive(Metrics)]
+#[metrics(scope = "exex.backfill")]
+pub(super) struct Metrics {
+    /// Number of blocks executed and delivered by the backfill stream
+    pub blocks_executed: Counter,
+    /// Number of backfill tasks currently running
+    pub active_tasks: Gauge,
+    /// Highest block delivered by the backfill stream
+    pub delivered_block: Gauge,
+    /// Last block of the backfill range
+    pub target_block: Gauge,
+    /// Number of blocks left to deliver
+    pub remaining_blocks: Gauge,
+    /// Last block persisted to the backfill checkpoint
+    pub checkpoint_block: Gauge,
+}
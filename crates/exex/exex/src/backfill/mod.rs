@@ -1,9 +1,12 @@
+mod checkpoint;
 mod factory;
 mod job;
+mod metrics;
 mod stream;
 #[cfg(test)]
 mod test_utils;
 
+pub use checkpoint::BackfillCheckpoint;
 pub use factory::BackfillJobFactory;
 pub use job::{BackfillJob, SingleBlockBackfillJob};
 pub use stream::StreamBackfillJob;
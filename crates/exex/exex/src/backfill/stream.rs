@@ -1,9 +1,10 @@
-use super::job::BackfillJobResult;
-use crate::{BackfillJob, SingleBlockBackfillJob};
+use super::{job::BackfillJobResult, metrics::Metrics};
+use crate::{BackfillCheckpoint, BackfillJob, SingleBlockBackfillJob};
+use alloy_eips::BlockNumHash;
 use alloy_primitives::BlockNumber;
 use futures::{
     stream::{FuturesOrdered, Stream},
-    StreamExt,
+    FutureExt, StreamExt,
 };
 use reth_ethereum_primitives::EthPrimitives;
 use reth_evm::{
@@ -12,11 +13,12 @@ use reth_evm::{
 };
 use reth_node_api::NodePrimitives;
 use reth_primitives_traits::RecoveredBlock;
-use reth_provider::{BlockReader, Chain, StateProviderFactory};
+use reth_provider::{BlockHashReader, BlockReader, Chain, StateProviderFactory};
 use reth_prune_types::PruneModes;
 use reth_stages_api::ExecutionStageThresholds;
-use reth_tracing::tracing::debug;
+use reth_tracing::tracing::{debug, warn};
 use std::{
+    io,
     ops::RangeInclusive,
     pin::Pin,
     task::{ready, Context, Poll},
@@ -51,6 +53,11 @@ type BatchBlockStreamItem<N = EthPrimitives> = Chain<N>;
 ///
 /// This struct manages the execution of [`SingleBlockBackfillJob`] tasks, allowing blocks to be
 /// processed asynchronously but in order within a specified range.
+///
+/// The range is split into batches that are executed by up to
+/// [`parallelism`](Self::with_parallelism) blocking tasks at once, and the results are delivered in
+/// order. With a [`BackfillCheckpoint`], the progress is persisted, so that the stream resumes
+/// from the last processed block when it's created again for the same range.
 #[derive(Debug)]
 pub struct StreamBackfillJob<E, P, T> {
     evm_config: E,
@@ -61,6 +68,16 @@ pub struct StreamBackfillJob<E, P, T> {
     parallelism: usize,
     batch_size: usize,
     thresholds: ExecutionStageThresholds,
+    checkpoint: Option<BackfillCheckpoint>,
+    /// The first block of the job, which the checkpoint is keyed by.
+    checkpoint_start: BlockNumber,
+    /// Whether the checkpoint has been loaded and applied to the range.
+    checkpoint_loaded: bool,
+    /// Last block delivered by the stream, persisted to the checkpoint on the next poll.
+    pending_checkpoint: Option<BlockNumHash>,
+    /// Write of the checkpoint in progress.
+    checkpoint_write: Option<JoinHandle<io::Result<BlockNumHash>>>,
+    metrics: Metrics,
 }
 
 impl<E, P, T> StreamBackfillJob<E, P, T>
@@ -79,6 +96,27 @@ where
         self
     }
 
+    /// Configures the checkpoint to resume from and to persist the progress to.
+    ///
+    /// If the checkpoint was saved by a job that started at or before the start of the range, and
+    /// it lies inside the range, only the blocks after it are backfilled. Then, the last delivered
+    /// block is persisted once the stream is polled again, i.e. once the consumer has processed
+    /// it.
+    pub fn with_checkpoint(mut self, checkpoint: BackfillCheckpoint) -> Self {
+        self.metrics = Metrics::new_with_labels(&[("exex", checkpoint.exex_id().to_string())]);
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// Records the last block delivered by the stream.
+    fn on_delivered(&mut self, block: BlockNumHash, blocks: usize) {
+        self.pending_checkpoint = Some(block);
+        self.metrics.blocks_executed.increment(blocks as u64);
+        self.metrics.delivered_block.set(block.number as f64);
+        self.metrics.remaining_blocks.set(self.range.end().saturating_sub(block.number) as f64);
+        self.metrics.active_tasks.set(self.tasks.len() as f64);
+    }
+
     /// Spawns a new task calling the [`BackfillTaskIterator::next`] method and pushes it to the end
     /// of the [`BackfillTasks`] queue.
     fn push_back(&mut self, mut job: BackfillTaskIterator<T>) {
@@ -116,6 +154,56 @@ where
     }
 }
 
+impl<E, P, T> StreamBackfillJob<E, P, T>
+where
+    P: BlockHashReader,
+{
+    /// Skips the blocks up to the checkpoint on the first call, and persists the last delivered
+    /// block in a blocking task.
+    ///
+    /// Only one write is in progress at a time, and the blocks delivered in the meantime are
+    /// coalesced into the next write. Returns [`Poll::Ready`] once all delivered blocks are
+    /// persisted, which is only awaited before the stream ends.
+    fn poll_checkpoint(&mut self, cx: &mut Context<'_>) -> Poll<BackfillJobResult<()>> {
+        let Some(checkpoint) = &self.checkpoint else { return Poll::Ready(Ok(())) };
+
+        if !self.checkpoint_loaded {
+            self.checkpoint_loaded = true;
+            self.metrics.target_block.set(*self.range.end() as f64);
+
+            let block = checkpoint.load(&self.range).map_err(BlockExecutionError::other)?;
+            if let Some(block) = block {
+                let canonical_hash =
+                    self.provider.block_hash(block.number).map_err(BlockExecutionError::other)?;
+                if canonical_hash == Some(block.hash) {
+                    debug!(target: "exex::backfill", exex_id = %checkpoint.exex_id(), ?block, range = ?self.range, "Resuming backfill from checkpoint");
+                    self.range = block.number + 1..=*self.range.end();
+                    self.metrics.checkpoint_block.set(block.number as f64);
+                } else {
+                    warn!(target: "exex::backfill", exex_id = %checkpoint.exex_id(), ?block, ?canonical_hash, "Backfill checkpoint is not canonical, backfilling the whole range");
+                }
+            }
+        }
+
+        loop {
+            if let Some(write) = &mut self.checkpoint_write {
+                let res = ready!(write.poll_unpin(cx));
+                self.checkpoint_write = None;
+                let block =
+                    res.map_err(BlockExecutionError::other)?.map_err(BlockExecutionError::other)?;
+                self.metrics.checkpoint_block.set(block.number as f64);
+            }
+
+            let Some(block) = self.pending_checkpoint.take() else { return Poll::Ready(Ok(())) };
+            let checkpoint = checkpoint.clone();
+            let start = self.checkpoint_start;
+            self.checkpoint_write = Some(tokio::task::spawn_blocking(move || {
+                checkpoint.save(start, block).map(|()| block)
+            }));
+        }
+    }
+}
+
 impl<E, P> Stream for StreamBackfillJob<E, P, SingleBlockStreamItem<E::Primitives>>
 where
     E: ConfigureEvm<Primitives: NodePrimitives<Block = P::Block>> + 'static,
@@ -126,6 +214,10 @@ where
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.get_mut();
 
+        if let Poll::Ready(Err(err)) = this.poll_checkpoint(cx) {
+            return Poll::Ready(Some(Err(err)))
+        }
+
         // Spawn new tasks only if we are below the parallelism configured.
         while this.tasks.len() < this.parallelism {
             // Get the next block number from the range. If it is empty, we are done.
@@ -145,7 +237,15 @@ where
             this.push_back(job);
         }
 
-        this.poll_next_task(cx)
+        let res = ready!(this.poll_next_task(cx));
+        match &res {
+            Some(Ok((block, _))) => this.on_delivered(block.num_hash(), 1),
+            // persist the last delivered block before the stream ends
+            None => ready!(this.poll_checkpoint(cx))?,
+            _ => {}
+        }
+
+        Poll::Ready(res)
     }
 }
 
@@ -159,6 +259,10 @@ where
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.get_mut();
 
+        if let Poll::Ready(Err(err)) = this.poll_checkpoint(cx) {
+            return Poll::Ready(Some(Err(err)))
+        }
+
         loop {
             // Spawn new tasks only if we are below the parallelism configured.
             while this.tasks.len() < this.parallelism {
@@ -189,11 +293,16 @@ where
             let res = ready!(this.poll_next_task(cx));
 
             if res.is_some() {
+                if let Some(Ok(chain)) = &res {
+                    this.on_delivered(chain.tip().num_hash(), chain.len());
+                }
                 return Poll::Ready(res);
             }
 
             if this.range.is_empty() {
-                // only terminate the stream if there are no more blocks to process
+                // only terminate the stream if there are no more blocks to process, once the last
+                // delivered block is persisted
+                ready!(this.poll_checkpoint(cx))?;
                 return Poll::Ready(None);
             }
         }
@@ -206,11 +315,17 @@ impl<E, P> From<SingleBlockBackfillJob<E, P>> for StreamBackfillJob<E, P, Single
             evm_config: job.evm_config,
             provider: job.provider,
             prune_modes: PruneModes::default(),
-            range: job.range,
+            range: job.range.clone(),
             tasks: FuturesOrdered::new(),
             parallelism: job.stream_parallelism,
             batch_size: 1,
             thresholds: ExecutionStageThresholds { max_blocks: Some(1), ..Default::default() },
+            checkpoint: None,
+            checkpoint_start: *job.range.start(),
+            checkpoint_loaded: false,
+            pending_checkpoint: None,
+            checkpoint_write: None,
+            metrics: Metrics::default(),
         }
     }
 }
@@ -225,7 +340,7 @@ where
             evm_config: job.evm_config,
             provider: job.provider,
             prune_modes: job.prune_modes,
-            range: job.range,
+            range: job.range.clone(),
             tasks: FuturesOrdered::new(),
             parallelism: job.stream_parallelism,
             batch_size,
@@ -233,6 +348,12 @@ where
                 max_blocks: Some(batch_size as u64),
                 ..job.thresholds
             },
+            checkpoint: None,
+            checkpoint_start: *job.range.start(),
+            checkpoint_loaded: false,
+            pending_checkpoint: None,
+            checkpoint_write: None,
+            metrics: Metrics::default(),
         }
     }
 }
@@ -243,7 +364,7 @@ mod tests {
         backfill::test_utils::{
             blocks_and_execution_outcome, blocks_and_execution_outputs, chain_spec,
         },
-        BackfillJobFactory,
+        BackfillCheckpoint, BackfillJobFactory,
     };
     use futures::StreamExt;
     use reth_db_common::init::init_genesis;
@@ -327,4 +448,66 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_checkpoint() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        // Create a key pair for the sender
+        let key_pair = generators::generate_key(&mut generators::rng());
+        let address = public_key_to_address(key_pair.public_key());
+
+        let chain_spec = chain_spec(address);
+
+        let executor = EthEvmConfig::ethereum(chain_spec.clone());
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&provider_factory)?;
+        let blockchain_db = BlockchainProvider::new(provider_factory.clone())?;
+
+        // Create first 2 blocks
+        let (blocks, _) = blocks_and_execution_outcome(provider_factory, chain_spec, key_pair)?;
+
+        let temp_dir = tempfile::tempdir()?;
+        let checkpoint = BackfillCheckpoint::new(temp_dir.path(), "test")?;
+
+        // Backfill the blocks one by one
+        let factory = BackfillJobFactory::new(executor.clone(), blockchain_db.clone())
+            .with_thresholds(ExecutionStageThresholds { max_blocks: Some(1), ..Default::default() })
+            .with_stream_parallelism(2);
+        let mut backfill_stream =
+            factory.backfill(1..=2).into_stream().with_checkpoint(checkpoint.clone());
+        let chain = backfill_stream.next().await.unwrap()?;
+        assert!(chain.blocks_iter().eq(&blocks[..1]));
+
+        // The delivered block is only persisted once the stream is polled again
+        assert_eq!(checkpoint.load(&(1..=2))?, None);
+        let chain = backfill_stream.next().await.unwrap()?;
+        assert!(chain.blocks_iter().eq(&blocks[1..]));
+
+        // The last delivered block is persisted before the stream ends
+        assert!(backfill_stream.next().await.is_none());
+        assert_eq!(checkpoint.load(&(1..=2))?, Some(blocks[1].num_hash()));
+
+        // The restarted stream resumes after the checkpoint of a job to an older tip
+        checkpoint.save(1, blocks[0].num_hash())?;
+        let mut backfill_stream =
+            factory.backfill(1..=2).into_stream().with_checkpoint(checkpoint.clone());
+        let chain = backfill_stream.next().await.unwrap()?;
+        assert!(chain.blocks_iter().eq(&blocks[1..]));
+        assert!(backfill_stream.next().await.is_none());
+        assert_eq!(checkpoint.load(&(1..=2))?, Some(blocks[1].num_hash()));
+
+        // The whole range has been processed
+        let mut backfill_stream =
+            factory.backfill(1..=2).into_stream().with_checkpoint(checkpoint.clone());
+        assert!(backfill_stream.next().await.is_none());
+
+        // A job whose range ends before the checkpoint ignores it
+        let mut backfill_stream = factory.backfill(1..=1).into_stream().with_checkpoint(checkpoint);
+        let chain = backfill_stream.next().await.unwrap()?;
+        assert!(chain.blocks_iter().eq(&blocks[..1]));
+        assert!(backfill_stream.next().await.is_none());
+
+        Ok(())
+    }
 }
@@ -1,4 +1,6 @@
-use crate::{ExExContextDyn, ExExEvent, ExExNotifications, ExExNotificationsStream};
+use crate::{
+    BackfillCheckpoint, ExExContextDyn, ExExEvent, ExExNotifications, ExExNotificationsStream,
+};
 use alloy_eips::BlockNumHash;
 use reth_exex_types::ExExHead;
 use reth_node_api::{FullNodeComponents, NodePrimitives, NodeTypes, PrimitivesTy};
@@ -107,6 +109,15 @@ where
         self.components.task_executor()
     }
 
+    /// Returns the [`BackfillCheckpoint`] of the ExEx with the given ID, stored in the node's data
+    /// directory.
+    pub fn backfill_checkpoint(
+        &self,
+        exex_id: impl Into<String>,
+    ) -> std::io::Result<BackfillCheckpoint> {
+        BackfillCheckpoint::new(self.config.datadir().exex_backfill(), exex_id)
+    }
+
     /// Sets notifications stream to [`crate::ExExNotificationsWithoutHead`], a stream of
     /// notifications without a head.
     pub fn set_notifications_without_head(&mut self) {
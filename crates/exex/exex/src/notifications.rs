@@ -1,4 +1,6 @@
-use crate::{BackfillJobFactory, ExExNotification, StreamBackfillJob, WalHandle};
+use crate::{
+    BackfillCheckpoint, BackfillJobFactory, ExExNotification, StreamBackfillJob, WalHandle,
+};
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumHash;
 use futures::{Stream, StreamExt};
@@ -100,6 +102,21 @@ where
             )),
         }
     }
+
+    /// Sets the [`BackfillCheckpoint`] that the backfill of a stream with a head resumes from, and
+    /// persists its progress to, see [`StreamBackfillJob::with_checkpoint`].
+    pub fn with_backfill_checkpoint(mut self, checkpoint: BackfillCheckpoint) -> Self {
+        match &mut self.inner {
+            ExExNotificationsInner::WithoutHead(notifications) => {
+                notifications.backfill_checkpoint = Some(checkpoint)
+            }
+            ExExNotificationsInner::WithHead(notifications) => {
+                notifications.backfill_checkpoint = Some(checkpoint)
+            }
+            ExExNotificationsInner::Invalid => unreachable!(),
+        }
+        self
+    }
 }
 
 impl<P, E> ExExNotificationsStream<E::Primitives> for ExExNotifications<P, E>
@@ -111,13 +128,14 @@ where
         let current = std::mem::replace(&mut self.inner, ExExNotificationsInner::Invalid);
         self.inner = ExExNotificationsInner::WithoutHead(match current {
             ExExNotificationsInner::WithoutHead(notifications) => notifications,
-            ExExNotificationsInner::WithHead(notifications) => ExExNotificationsWithoutHead::new(
-                notifications.initial_local_head,
-                notifications.provider,
-                notifications.evm_config,
-                notifications.notifications,
-                notifications.wal_handle,
-            ),
+            ExExNotificationsInner::WithHead(notifications) => ExExNotificationsWithoutHead {
+                node_head: notifications.initial_local_head,
+                provider: notifications.provider,
+                evm_config: notifications.evm_config,
+                notifications: notifications.notifications,
+                wal_handle: notifications.wal_handle,
+                backfill_checkpoint: notifications.backfill_checkpoint,
+            },
             ExExNotificationsInner::Invalid => unreachable!(),
         });
     }
@@ -135,6 +153,7 @@ where
                     notifications.evm_config,
                     notifications.notifications,
                     notifications.wal_handle,
+                    notifications.backfill_checkpoint,
                     exex_head,
                 ))
             }
@@ -184,6 +203,8 @@ where
     evm_config: E,
     notifications: Receiver<ExExNotification<E::Primitives>>,
     wal_handle: WalHandle<E::Primitives>,
+    /// The checkpoint of the backfill, once the stream is configured with a head.
+    backfill_checkpoint: Option<BackfillCheckpoint>,
 }
 
 impl<P: Debug, E> Debug for ExExNotificationsWithoutHead<P, E>
@@ -211,7 +232,14 @@ where
         notifications: Receiver<ExExNotification<E::Primitives>>,
         wal_handle: WalHandle<E::Primitives>,
     ) -> Self {
-        Self { node_head, provider, evm_config, notifications, wal_handle }
+        Self {
+            node_head,
+            provider,
+            evm_config,
+            notifications,
+            wal_handle,
+            backfill_checkpoint: None,
+        }
     }
 
     /// Subscribe to notifications with the given head.
@@ -222,6 +250,7 @@ where
             self.evm_config,
             self.notifications,
             self.wal_handle,
+            self.backfill_checkpoint,
             head,
         )
     }
@@ -257,6 +286,8 @@ where
     evm_config: E,
     notifications: Receiver<ExExNotification<E::Primitives>>,
     wal_handle: WalHandle<E::Primitives>,
+    /// The checkpoint that the backfill resumes from and persists its progress to.
+    backfill_checkpoint: Option<BackfillCheckpoint>,
     /// The exex head at launch
     initial_exex_head: ExExHead,
 
@@ -281,6 +312,7 @@ where
         evm_config: E,
         notifications: Receiver<ExExNotification<E::Primitives>>,
         wal_handle: WalHandle<E::Primitives>,
+        backfill_checkpoint: Option<BackfillCheckpoint>,
         exex_head: ExExHead,
     ) -> Self {
         Self {
@@ -289,6 +321,7 @@ where
             evm_config,
             notifications,
             wal_handle,
+            backfill_checkpoint,
             initial_exex_head: exex_head,
             pending_check_canonical: true,
             pending_check_backfill: true,
@@ -365,11 +398,14 @@ where
             std::cmp::Ordering::Less => {
                 // ExEx is behind the node head, start backfill
                 debug!(target: "exex::notifications", "ExEx is behind the node head and on the canonical chain, starting backfill");
-                let backfill = backfill_job_factory
+                let mut backfill = backfill_job_factory
                     .backfill(
                         self.initial_exex_head.block.number + 1..=self.initial_local_head.number,
                     )
                     .into_stream();
+                if let Some(checkpoint) = &self.backfill_checkpoint {
+                    backfill = backfill.with_checkpoint(checkpoint.clone());
+                }
                 self.backfill_job = Some(backfill);
             }
             std::cmp::Ordering::Equal => {
@@ -539,6 +575,73 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn exex_notifications_behind_head_resumes_from_checkpoint() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path().join("wal")).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&provider_factory)?;
+        let genesis_block = provider_factory
+            .block(genesis_hash.into())?
+            .ok_or_else(|| eyre::eyre!("genesis block not found"))?;
+
+        let provider = BlockchainProvider::new(provider_factory.clone())?;
+
+        let node_head_block = random_block(
+            &mut rng,
+            genesis_block.number + 1,
+            BlockParams { parent: Some(genesis_hash), tx_count: Some(0), ..Default::default() },
+        );
+        let provider_rw = provider_factory.provider_rw()?;
+        provider_rw
+            .insert_block(node_head_block.clone().try_recover()?, StorageLocation::Database)?;
+        provider_rw.commit()?;
+
+        let node_head = node_head_block.num_hash();
+        let exex_head =
+            ExExHead { block: BlockNumHash { number: genesis_block.number, hash: genesis_hash } };
+
+        // The previous run backfilled the node head before it was interrupted
+        let checkpoint = BackfillCheckpoint::new(temp_dir.path().join("backfill"), "test")?;
+        checkpoint.save(node_head.number, node_head)?;
+
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(
+                vec![random_block(
+                    &mut rng,
+                    node_head.number + 1,
+                    BlockParams { parent: Some(node_head.hash), ..Default::default() },
+                )
+                .try_recover()?],
+                Default::default(),
+                None,
+            )),
+        };
+
+        let (notifications_tx, notifications_rx) = mpsc::channel(1);
+
+        notifications_tx.send(notification.clone()).await?;
+
+        let mut notifications = ExExNotificationsWithoutHead::new(
+            node_head,
+            provider,
+            EthEvmConfig::mainnet(),
+            notifications_rx,
+            wal.handle(),
+        );
+        notifications.backfill_checkpoint = Some(checkpoint);
+        let mut notifications = notifications.with_head(exex_head);
+
+        // The backfill resumes after the node head, so the first notification is the actual
+        // notification that we sent before
+        assert_eq!(notifications.next().await.transpose()?, Some(notification));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn exex_notifications_same_head_canonical() -> eyre::Result<()> {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -1,6 +1,7 @@
 use std::{
     cmp::Reverse,
     collections::{BinaryHeap, HashSet},
+    ops::RangeInclusive,
 };
 
 use alloy_consensus::BlockHeader;
@@ -98,25 +99,21 @@ impl BlockCache {
         file_id: u32,
         notification: &ExExNotification<N>,
     ) {
-        let reverted_chain = notification.reverted_chain();
-        let committed_chain = notification.committed_chain();
+        self.insert_blocks_with_file_id(file_id, &NotificationBlocks::new(notification));
+    }
 
-        let max_block =
-            reverted_chain.iter().chain(&committed_chain).map(|chain| chain.tip().number()).max();
-        if let Some(max_block) = max_block {
+    /// Inserts the blocks of a notification into the cache with the given file ID.
+    pub(super) fn insert_blocks_with_file_id(&mut self, file_id: u32, blocks: &NotificationBlocks) {
+        if let Some(max_block) = blocks.max_block {
             self.notification_max_blocks.push(Reverse((max_block, file_id)));
         }
 
-        if let Some(committed_chain) = &committed_chain {
-            for block in committed_chain.blocks().values() {
-                let cached_block = CachedBlock {
-                    block: (block.number(), block.hash()).into(),
-                    parent_hash: block.parent_hash(),
-                };
-                self.committed_blocks.insert(block.hash(), (file_id, cached_block));
-            }
+        for block in &blocks.committed {
+            self.committed_blocks.insert(block.block.hash, (file_id, *block));
+        }
 
-            self.highest_committed_block_height = Some(committed_chain.tip().number());
+        if let Some(tip) = blocks.committed.last() {
+            self.highest_committed_block_height = Some(tip.block.number);
         }
     }
 
@@ -143,9 +140,83 @@ impl BlockCache {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(super) struct CachedBlock {
+pub struct CachedBlock {
     /// The block number and hash of the block.
-    pub(super) block: BlockNumHash,
+    pub block: BlockNumHash,
     /// The hash of the parent block.
-    pub(super) parent_hash: B256,
+    pub parent_hash: B256,
+}
+
+/// Blocks of a notification that are stored in the [`BlockCache`].
+///
+/// They are persisted next to every notification in the [`Storage`](super::Storage), so that the
+/// cache can be filled without decoding the notifications.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NotificationBlocks {
+    /// Highest block in the notification, between both the committed and reverted chains.
+    pub max_block: Option<BlockNumber>,
+    /// Committed blocks in ascending order.
+    pub committed: Vec<CachedBlock>,
+}
+
+impl NotificationBlocks {
+    /// Size of an encoded [`CachedBlock`].
+    const ENCODED_BLOCK_LENGTH: usize = 8 + 32 + 32;
+
+    /// Collects the blocks of the notification.
+    pub fn new<N: NodePrimitives>(notification: &ExExNotification<N>) -> Self {
+        let reverted_chain = notification.reverted_chain();
+        let committed_chain = notification.committed_chain();
+
+        let max_block =
+            reverted_chain.iter().chain(&committed_chain).map(|chain| chain.tip().number()).max();
+        let committed = committed_chain
+            .iter()
+            .flat_map(|chain| chain.blocks().values())
+            .map(|block| CachedBlock {
+                block: (block.number(), block.hash()).into(),
+                parent_hash: block.parent_hash(),
+            })
+            .collect();
+
+        Self { max_block, committed }
+    }
+
+    /// Returns the range of committed blocks, if any.
+    pub fn committed_range(&self) -> Option<RangeInclusive<BlockNumber>> {
+        Some(self.committed.first()?.block.number..=self.committed.last()?.block.number)
+    }
+
+    /// Encodes the blocks as `max_block + 1` (zero if none), followed by the number, hash and
+    /// parent hash of every committed block.
+    pub(super) fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.max_block.map_or(0, |block| block + 1).to_le_bytes());
+        for block in &self.committed {
+            buf.extend_from_slice(&block.block.number.to_le_bytes());
+            buf.extend_from_slice(block.block.hash.as_slice());
+            buf.extend_from_slice(block.parent_hash.as_slice());
+        }
+    }
+
+    /// Decodes the blocks encoded with [`NotificationBlocks::encode`].
+    pub(super) fn decode(buf: &[u8]) -> Option<Self> {
+        let (max_block, blocks) = buf.split_first_chunk::<8>()?;
+        let max_block = u64::from_le_bytes(*max_block).checked_sub(1);
+
+        if blocks.len() % Self::ENCODED_BLOCK_LENGTH != 0 {
+            return None
+        }
+        let committed = blocks
+            .chunks_exact(Self::ENCODED_BLOCK_LENGTH)
+            .map(|block| CachedBlock {
+                block: BlockNumHash::new(
+                    u64::from_le_bytes(block[..8].try_into().unwrap()),
+                    B256::from_slice(&block[8..40]),
+                ),
+                parent_hash: B256::from_slice(&block[40..]),
+            })
+            .collect();
+
+        Some(Self { max_block, committed })
+    }
 }
@@ -26,4 +26,13 @@ pub enum WalError {
     /// Decode error
     #[error("failed to decode notification {0} from {1}: {2}")]
     Decode(u32, PathBuf, rmp_serde::decode::Error),
+    /// Encode error
+    #[error("failed to encode notification {0}: {1}")]
+    Encode(u32, rmp_serde::encode::Error),
+    /// Invalid record in a segment file
+    #[error("corrupted record in {0} at offset {1}")]
+    Corrupted(PathBuf, u64),
+    /// Checksum mismatch of a notification
+    #[error("checksum mismatch for notification {0}")]
+    Checksum(u32),
 }
@@ -1,11 +1,11 @@
 #![allow(dead_code)]
 
 mod cache;
-pub use cache::BlockCache;
+pub use cache::{BlockCache, CachedBlock, NotificationBlocks};
 mod storage;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_node_api::NodePrimitives;
-pub use storage::Storage;
+pub use storage::{RecordLocation, Storage, WalRecord};
 mod metrics;
 use metrics::Metrics;
 mod error;
@@ -27,9 +27,9 @@ use reth_tracing::tracing::{debug, instrument};
 
 /// WAL is a write-ahead log (WAL) that stores the notifications sent to ExExes.
 ///
-/// WAL is backed by a directory of binary files represented by [`Storage`] and a block cache
-/// represented by [`BlockCache`]. The role of the block cache is to avoid walking the WAL directory
-/// and decoding notifications every time we want to iterate or finalize the WAL.
+/// WAL is backed by a directory of append-only segment files represented by [`Storage`] and a block
+/// cache represented by [`BlockCache`]. The role of the block cache is to avoid reading and
+/// decoding notifications every time we want to iterate or finalize the WAL.
 ///
 /// The expected mode of operation is as follows:
 /// 1. On every new canonical chain notification, call [`Wal::commit`].
@@ -78,13 +78,23 @@ where
     pub fn num_blocks(&self) -> usize {
         self.inner.block_cache().num_blocks()
     }
+
+    /// Returns all notification records in the WAL, without decoding the notifications.
+    pub fn records(&self) -> WalResult<Vec<WalRecord>> {
+        self.inner.storage.records()
+    }
+
+    /// Returns the notification with the given ID, if it exists.
+    pub fn get_notification(&self, id: u32) -> WalResult<Option<ExExNotification<N>>> {
+        Ok(self.inner.storage.read_notification(id)?.map(|(notification, _)| notification))
+    }
 }
 
 /// Inner type for the WAL.
 #[derive(Debug)]
 struct WalInner<N: NodePrimitives> {
     next_file_id: AtomicU32,
-    /// The underlying WAL storage backed by a file.
+    /// The underlying WAL storage backed by segment files.
     storage: Storage<N>,
     /// WAL block cache. See [`cache::BlockCache`] docs for more details.
     block_cache: RwLock<BlockCache>,
@@ -110,32 +120,27 @@ where
         self.block_cache.read()
     }
 
-    /// Fills the block cache with the notifications from the storage.
+    /// Fills the block cache with the blocks of the notifications from the storage.
     #[instrument(skip(self))]
     fn fill_block_cache(&self) -> WalResult<()> {
-        let Some(files_range) = self.storage.files_range()? else { return Ok(()) };
-        self.next_file_id.store(files_range.end() + 1, Ordering::Relaxed);
+        let Some(last_id) = self.storage.last_id() else { return Ok(()) };
+        self.next_file_id.store(last_id + 1, Ordering::Relaxed);
 
         let mut block_cache = self.block_cache.write();
         let mut notifications_size = 0;
 
-        for entry in self.storage.iter_notifications(files_range) {
-            let (file_id, size, notification) = entry?;
-
-            notifications_size += size;
-
-            let committed_chain = notification.committed_chain();
-            let reverted_chain = notification.reverted_chain();
+        for WalRecord { id, location, blocks } in self.storage.records()? {
+            notifications_size += location.size;
 
             debug!(
                 target: "exex::wal",
-                ?file_id,
-                reverted_block_range = ?reverted_chain.as_ref().map(|chain| chain.range()),
-                committed_block_range = ?committed_chain.as_ref().map(|chain| chain.range()),
+                ?id,
+                max_block = ?blocks.max_block,
+                committed_block_range = ?blocks.committed_range(),
                 "Inserting block cache entries"
             );
 
-            block_cache.insert_notification_blocks_with_file_id(file_id, &notification);
+            block_cache.insert_blocks_with_file_id(id, &blocks);
         }
 
         self.update_metrics(&block_cache, notifications_size as i64);
@@ -176,6 +181,11 @@ where
         debug!(target: "exex::wal", ?removed_notifications, ?removed_size, "Storage was finalized");
 
         self.update_metrics(&block_cache, -(removed_size as i64));
+        drop(block_cache);
+
+        // Compact the segments without blocking the commits.
+        let compacted_segments = self.storage.compact()?;
+        debug!(target: "exex::wal", ?compacted_segments, "Storage was compacted");
 
         Ok(())
     }
@@ -198,11 +208,7 @@ where
     fn iter_notifications(
         &self,
     ) -> WalResult<Box<dyn Iterator<Item = WalResult<ExExNotification<N>>> + '_>> {
-        let Some(range) = self.storage.files_range()? else {
-            return Ok(Box::new(std::iter::empty()))
-        };
-
-        Ok(Box::new(self.storage.iter_notifications(range).map(|entry| Ok(entry?.2))))
+        Ok(Box::new(self.storage.iter_notifications().map(|entry| Ok(entry?.2))))
     }
 }
 
@@ -246,13 +252,7 @@ mod tests {
     use std::sync::Arc;
 
     fn read_notifications(wal: &Wal) -> WalResult<Vec<ExExNotification>> {
-        wal.inner.storage.files_range()?.map_or(Ok(Vec::new()), |range| {
-            wal.inner
-                .storage
-                .iter_notifications(range)
-                .map(|entry| entry.map(|(_, _, n)| n))
-                .collect()
-        })
+        wal.inner.storage.iter_notifications().map(|entry| entry.map(|(_, _, n)| n)).collect()
     }
 
     fn sort_committed_blocks(
@@ -1,123 +1,567 @@
 use std::{
-    fs::File,
-    ops::RangeInclusive,
+    collections::{BTreeMap, BTreeSet},
+    fs::OpenOptions,
+    io::{BufReader, Read, Seek, SeekFrom, Write},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
-use crate::wal::{WalError, WalResult};
+use crate::wal::{NotificationBlocks, WalError, WalResult};
+use parking_lot::{Mutex, RwLock};
 use reth_ethereum_primitives::EthPrimitives;
 use reth_exex_types::ExExNotification;
 use reth_node_api::NodePrimitives;
-use reth_tracing::tracing::debug;
+use reth_tracing::tracing::{debug, info, warn};
 use tracing::instrument;
 
-static FILE_EXTENSION: &str = "wal";
+static SEGMENT_FILE_EXTENSION: &str = "seg";
+static LEGACY_FILE_EXTENSION: &str = "wal";
+static TOMBSTONES_FILE_NAME: &str = "removed.ids";
 
-/// The underlying WAL storage backed by a directory of files.
+/// Size of a segment file after which a new segment is started.
+const DEFAULT_MAX_SEGMENT_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Percentage of the size of a segment taken by removed records, at which the segment is
+/// compacted.
+const COMPACTION_THRESHOLD_PERCENT: u64 = 50;
+
+/// Size of the record header: notification ID, length of the encoded blocks, length of the encoded
+/// notification, checksum of the header and the blocks, and checksum of the notification.
+const RECORD_HEADER_SIZE: usize = 20;
+
+/// The underlying WAL storage backed by a directory of append-only segment files.
+///
+/// Each notification is appended as a record to the latest segment, until the segment grows past
+/// the maximum size and a new one is started. A record consists of:
+/// - the notification ID,
+/// - the [`NotificationBlocks`], which are used to fill the block cache without decoding the
+///   notification,
+/// - the MessagePack-encoded notification,
+///
+/// and CRC32 checksums of both the blocks and the notification. Segments are named after the ID of
+/// the first notification written to them.
+///
+/// Removed notifications are recorded in a tombstones file, and segments are deleted once no
+/// notifications are left in them. Segments in which removed records take a large part of the
+/// size are compacted by rewriting them without the removed records, see [`Self::compact`].
 ///
-/// Each notification is represented by a single file that contains a MessagePack-encoded
-/// notification.
+/// Notifications stored in the legacy format of one MessagePack file per notification are migrated
+/// to segments on startup.
 #[derive(Debug, Clone)]
 pub struct Storage<N: NodePrimitives = EthPrimitives> {
-    /// The path to the WAL file.
+    /// The path to the WAL directory.
     path: PathBuf,
+    /// Size of a segment file after which a new segment is started.
+    max_segment_size: u64,
+    /// Index of the segments.
+    segments: Arc<RwLock<Segments>>,
+    /// Lock that serializes the compactions of segments.
+    compaction: Arc<Mutex<()>>,
     _pd: std::marker::PhantomData<N>,
 }
 
+/// Index of the segment files.
+#[derive(Debug, Default)]
+struct Segments {
+    /// Size of every segment file, by the segment ID.
+    sizes: BTreeMap<u32, u64>,
+    /// Location of every notification, by the notification ID.
+    records: BTreeMap<u32, RecordLocation>,
+    /// Location of every removed notification whose record is still in a segment file, by the
+    /// notification ID.
+    removed: BTreeMap<u32, RecordLocation>,
+}
+
+impl Segments {
+    /// Returns the notifications in the given segment.
+    fn segment_records(&self, segment: u32) -> impl Iterator<Item = (u32, RecordLocation)> + '_ {
+        self.records
+            .iter()
+            .filter(move |(_, location)| location.segment == segment)
+            .map(|(id, location)| (*id, *location))
+    }
+
+    /// Returns the total size of the removed records in the given segment.
+    fn removed_size(&self, segment: u32) -> u64 {
+        self.removed
+            .values()
+            .filter(|location| location.segment == segment)
+            .map(|location| location.size)
+            .sum()
+    }
+}
+
+/// Location of a notification record in the segment files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordLocation {
+    /// ID of the segment.
+    pub segment: u32,
+    /// Offset of the record in the segment file.
+    pub offset: u64,
+    /// Size of the record in bytes.
+    pub size: u64,
+}
+
+/// A notification record in the WAL, see [`Wal::records`](super::Wal::records).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalRecord {
+    /// ID of the notification.
+    pub id: u32,
+    /// Location of the record.
+    pub location: RecordLocation,
+    /// Blocks of the notification.
+    pub blocks: NotificationBlocks,
+}
+
+/// Header of a notification record.
+#[derive(Debug, Clone, Copy)]
+struct RecordHeader {
+    id: u32,
+    blocks_len: u32,
+    notification_len: u32,
+    blocks_checksum: u32,
+    notification_checksum: u32,
+}
+
+impl RecordHeader {
+    fn new(id: u32, blocks: &[u8], notification: &[u8]) -> Self {
+        let mut header = Self {
+            id,
+            blocks_len: blocks.len() as u32,
+            notification_len: notification.len() as u32,
+            blocks_checksum: 0,
+            notification_checksum: crc32fast::hash(notification),
+        };
+        header.blocks_checksum = header.compute_blocks_checksum(blocks);
+        header
+    }
+
+    /// Checksum of the first three header fields and the encoded blocks.
+    fn compute_blocks_checksum(&self, blocks: &[u8]) -> u32 {
+        let mut hasher = crc32fast::Hasher::new();
+        hasher.update(&self.encode()[..12]);
+        hasher.update(blocks);
+        hasher.finalize()
+    }
+
+    /// Size of the whole record.
+    const fn record_size(&self) -> u64 {
+        RECORD_HEADER_SIZE as u64 + self.blocks_len as u64 + self.notification_len as u64
+    }
+
+    fn encode(&self) -> [u8; RECORD_HEADER_SIZE] {
+        let mut buf = [0; RECORD_HEADER_SIZE];
+        buf[..4].copy_from_slice(&self.id.to_le_bytes());
+        buf[4..8].copy_from_slice(&self.blocks_len.to_le_bytes());
+        buf[8..12].copy_from_slice(&self.notification_len.to_le_bytes());
+        buf[12..16].copy_from_slice(&self.blocks_checksum.to_le_bytes());
+        buf[16..].copy_from_slice(&self.notification_checksum.to_le_bytes());
+        buf
+    }
+
+    fn decode(buf: &[u8; RECORD_HEADER_SIZE]) -> Self {
+        let field = |i: usize| u32::from_le_bytes(buf[i * 4..(i + 1) * 4].try_into().unwrap());
+        Self {
+            id: field(0),
+            blocks_len: field(1),
+            notification_len: field(2),
+            blocks_checksum: field(3),
+            notification_checksum: field(4),
+        }
+    }
+}
+
 impl<N> Storage<N>
 where
     N: NodePrimitives,
 {
-    /// Creates a new instance of [`Storage`] backed by the file at the given path and creates
+    /// Creates a new instance of [`Storage`] backed by the directory at the given path and creates
     /// it doesn't exist.
+    ///
+    /// Notifications in the legacy format are migrated to segments.
     pub(super) fn new(path: impl AsRef<Path>) -> WalResult<Self> {
+        Self::with_max_segment_size(path, DEFAULT_MAX_SEGMENT_SIZE)
+    }
+
+    /// Creates a new instance of [`Storage`] with the given maximum segment size.
+    pub(super) fn with_max_segment_size(
+        path: impl AsRef<Path>,
+        max_segment_size: u64,
+    ) -> WalResult<Self> {
         reth_fs_util::create_dir_all(&path)?;
 
-        Ok(Self { path: path.as_ref().to_path_buf(), _pd: std::marker::PhantomData })
+        let storage = Self {
+            path: path.as_ref().to_path_buf(),
+            max_segment_size,
+            segments: Default::default(),
+            compaction: Default::default(),
+            _pd: std::marker::PhantomData,
+        };
+        storage.load_segments()?;
+        storage.load_tombstones()?;
+        storage.migrate_legacy_files()?;
+
+        Ok(storage)
     }
 
-    fn file_path(&self, id: u32) -> PathBuf {
-        self.path.join(format!("{id}.{FILE_EXTENSION}"))
+    fn segment_path(&self, segment: u32) -> PathBuf {
+        self.path.join(format!("{segment}.{SEGMENT_FILE_EXTENSION}"))
     }
 
-    fn parse_filename(filename: &str) -> WalResult<u32> {
+    fn compaction_path(&self, segment: u32) -> PathBuf {
+        self.path.join(format!("{segment}.{SEGMENT_FILE_EXTENSION}.tmp"))
+    }
+
+    fn tombstones_path(&self) -> PathBuf {
+        self.path.join(TOMBSTONES_FILE_NAME)
+    }
+
+    fn legacy_file_path(&self, id: u32) -> PathBuf {
+        self.path.join(format!("{id}.{LEGACY_FILE_EXTENSION}"))
+    }
+
+    fn parse_filename(filename: &str, extension: &str) -> WalResult<u32> {
         filename
-            .strip_suffix(".wal")
+            .strip_suffix(extension)
+            .and_then(|s| s.strip_suffix('.'))
             .and_then(|s| s.parse().ok())
             .ok_or_else(|| WalError::Parse(filename.to_string()))
     }
 
-    /// Removes notification for the given file ID from the storage.
-    ///
-    /// # Returns
+    /// Returns the sorted IDs of the files in the WAL directory with the given extension.
+    fn list_files(&self, extension: &str) -> WalResult<Vec<u32>> {
+        let mut ids = Vec::new();
+        for entry in reth_fs_util::read_dir(&self.path)? {
+            let entry = entry.map_err(|err| WalError::DirEntry(self.path.clone(), err))?;
+
+            if entry.path().extension() == Some(extension.as_ref()) {
+                ids.push(Self::parse_filename(&entry.file_name().to_string_lossy(), extension)?);
+            }
+        }
+        ids.sort_unstable();
+
+        Ok(ids)
+    }
+
+    /// Scans the headers of all segments and fills the index.
     ///
-    /// The size of the file that was removed in bytes, if any.
+    /// A record that was only partially written to the last segment, e.g. because of a crash, is
+    /// truncated.
     #[instrument(skip(self))]
-    fn remove_notification(&self, file_id: u32) -> Option<u64> {
-        let path = self.file_path(file_id);
-        let size = path.metadata().ok()?.len();
-
-        match reth_fs_util::remove_file(self.file_path(file_id)) {
-            Ok(()) => {
-                debug!(target: "exex::wal::storage", "Notification was removed from the storage");
-                Some(size)
-            }
-            Err(err) => {
-                debug!(target: "exex::wal::storage", ?err, "Failed to remove notification from the storage");
-                None
+    fn load_segments(&self) -> WalResult<()> {
+        let mut segments = self.segments.write();
+
+        let segment_ids = self.list_files(SEGMENT_FILE_EXTENSION)?;
+        for (i, &segment) in segment_ids.iter().enumerate() {
+            let path = self.segment_path(segment);
+            let mut file = reth_fs_util::open(&path)?;
+            let len = file.metadata().map_err(|err| WalError::FileMetadata(segment, err))?.len();
+
+            let mut reader = BufReader::new(&mut file);
+            let mut offset = 0;
+            while offset < len {
+                match read_header(&mut reader, len - offset)
+                    .map_err(|err| reth_fs_util::FsPathError::read(err, &path))?
+                {
+                    Some(header) => {
+                        let location =
+                            RecordLocation { segment, offset, size: header.record_size() };
+                        reader
+                            .seek_relative(header.notification_len as i64)
+                            .map_err(|err| reth_fs_util::FsPathError::read(err, &path))?;
+                        segments.records.insert(header.id, location);
+                        offset += location.size;
+                    }
+                    None if i == segment_ids.len() - 1 => {
+                        warn!(target: "exex::wal::storage", ?path, offset, "Truncating incomplete record");
+                        OpenOptions::new()
+                            .write(true)
+                            .open(&path)
+                            .and_then(|file| file.set_len(offset))
+                            .map_err(|err| reth_fs_util::FsPathError::write(err, &path))?;
+                        break
+                    }
+                    None => return Err(WalError::Corrupted(path, offset)),
+                }
             }
+
+            segments.sizes.insert(segment, offset);
         }
+
+        Ok(())
     }
 
-    /// Returns the range of file IDs in the storage.
+    /// Marks the notifications in the tombstones file as removed.
     ///
-    /// If there are no files in the storage, returns `None`.
-    pub(super) fn files_range(&self) -> WalResult<Option<RangeInclusive<u32>>> {
-        let mut min_id = None;
-        let mut max_id = None;
+    /// The tombstones file is rewritten with only the notifications that are still in the
+    /// segments, which also drops an ID that was only partially written.
+    #[instrument(skip(self))]
+    fn load_tombstones(&self) -> WalResult<()> {
+        let path = self.tombstones_path();
+        if !path.exists() {
+            return Ok(())
+        }
 
-        for entry in reth_fs_util::read_dir(&self.path)? {
-            let entry = entry.map_err(|err| WalError::DirEntry(self.path.clone(), err))?;
+        let tombstones = reth_fs_util::read(&path)?;
+        let mut segments = self.segments.write();
+        for id in tombstones.chunks_exact(4).map(|id| u32::from_le_bytes(id.try_into().unwrap())) {
+            if let Some(location) = segments.records.remove(&id) {
+                segments.removed.insert(id, location);
+            }
+        }
+        self.write_tombstones(&segments)
+    }
+
+    /// Replaces the tombstones file with the IDs of the removed notifications that are still in
+    /// the segments.
+    fn write_tombstones(&self, segments: &Segments) -> WalResult<()> {
+        let tombstones =
+            segments.removed.keys().flat_map(|id| id.to_le_bytes()).collect::<Vec<_>>();
+        reth_fs_util::atomic_write_file(&self.tombstones_path(), |file| {
+            file.write_all(&tombstones)
+        })?;
+        Ok(())
+    }
 
-            if entry.path().extension() == Some(FILE_EXTENSION.as_ref()) {
-                let file_name = entry.file_name();
-                let file_id = Self::parse_filename(&file_name.to_string_lossy())?;
+    /// Moves notifications from the legacy format of one file per notification to segments.
+    #[instrument(skip(self))]
+    fn migrate_legacy_files(&self) -> WalResult<()> {
+        let ids = self.list_files(LEGACY_FILE_EXTENSION)?;
+        if ids.is_empty() {
+            return Ok(())
+        }
 
-                min_id = min_id.map_or(Some(file_id), |min_id: u32| Some(min_id.min(file_id)));
-                max_id = max_id.map_or(Some(file_id), |max_id: u32| Some(max_id.max(file_id)));
+        info!(target: "exex::wal::storage", files = ids.len(), "Migrating WAL files to segments");
+        for &id in &ids {
+            // The file may have been migrated already if the previous migration was interrupted.
+            if self.segments.read().records.contains_key(&id) {
+                continue
             }
+
+            let path = self.legacy_file_path(id);
+            let mut file = reth_fs_util::open(&path)?;
+            let notification: reth_exex_types::serde_bincode_compat::ExExNotification<'_, N> =
+                rmp_serde::decode::from_read(&mut file)
+                    .map_err(|err| WalError::Decode(id, path, err))?;
+            self.write_notification(id, &notification.into())?;
+        }
+
+        for id in ids {
+            reth_fs_util::remove_file(self.legacy_file_path(id))?;
         }
 
-        Ok(min_id.zip(max_id).map(|(min_id, max_id)| min_id..=max_id))
+        Ok(())
+    }
+
+    /// Returns the highest notification ID in the storage, including removed notifications whose
+    /// records weren't compacted yet, so that their IDs aren't reused.
+    ///
+    /// If there are no notifications in the storage, returns `None`.
+    pub(super) fn last_id(&self) -> Option<u32> {
+        let segments = self.segments.read();
+        let last_id =
+            |records: &BTreeMap<u32, RecordLocation>| records.last_key_value().map(|(id, _)| *id);
+        last_id(&segments.records).max(last_id(&segments.removed))
+    }
+
+    /// Returns the number of segment files.
+    pub(super) fn num_segments(&self) -> usize {
+        self.segments.read().sizes.len()
     }
 
-    /// Removes notifications from the storage according to the given list of file IDs.
+    /// Removes notifications from the storage according to the given list of IDs.
+    ///
+    /// The removals are appended to the tombstones file, while the removed records stay in their
+    /// segments until they're compacted, see [`Self::compact`]. Segments without notifications
+    /// left are deleted.
     ///
     /// # Returns
     ///
-    /// Number of removed notifications and the total size of the removed files in bytes.
+    /// Number of removed notifications and the total size of the removed records in bytes.
+    #[instrument(skip_all)]
     pub(super) fn remove_notifications(
         &self,
-        file_ids: impl IntoIterator<Item = u32>,
+        ids: impl IntoIterator<Item = u32>,
     ) -> WalResult<(usize, u64)> {
-        let mut deleted_total = 0;
+        let mut segments = self.segments.write();
+
+        let removed = ids
+            .into_iter()
+            .filter_map(|id| Some((id, *segments.records.get(&id)?)))
+            .collect::<BTreeMap<_, _>>();
+        if removed.is_empty() {
+            return Ok((0, 0))
+        }
+
+        // Persist the removals first, so that the notifications aren't loaded after a restart.
+        let path = self.tombstones_path();
+        let tombstones = removed.keys().flat_map(|id| id.to_le_bytes()).collect::<Vec<_>>();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|err| reth_fs_util::FsPathError::open(err, &path))?;
+        let len =
+            file.metadata().map_err(|err| reth_fs_util::FsPathError::metadata(err, &path))?.len();
+        if let Err(err) = file.write_all(&tombstones).and_then(|_| file.sync_data()) {
+            let _ = file.set_len(len);
+            return Err(reth_fs_util::FsPathError::write(err, &path).into())
+        }
+
         let mut deleted_size = 0;
+        for (&id, &location) in &removed {
+            segments.records.remove(&id);
+            segments.removed.insert(id, location);
+            deleted_size += location.size;
+        }
+
+        let empty_segments = removed
+            .values()
+            .map(|location| location.segment)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|&segment| segments.segment_records(segment).next().is_none())
+            .collect::<Vec<_>>();
+        if !empty_segments.is_empty() {
+            for &segment in &empty_segments {
+                let path = self.segment_path(segment);
+                reth_fs_util::remove_file(&path)?;
+                segments.sizes.remove(&segment);
+                segments.removed.retain(|_, location| location.segment != segment);
+                debug!(target: "exex::wal::storage", ?path, "Segment was removed");
+            }
+            self.write_tombstones(&segments)?;
+        }
+
+        Ok((removed.len(), deleted_size))
+    }
+
+    /// Compacts the segments in which the removed records take at least
+    /// [`COMPACTION_THRESHOLD_PERCENT`] of the size, by rewriting them without the removed
+    /// records.
+    ///
+    /// The records are copied without holding the lock of the index, so that notifications can be
+    /// read and written during the compaction. A compacted segment only replaces the old one if no
+    /// records were appended to it in the meantime, otherwise it's compacted by a later call.
+    ///
+    /// # Returns
+    ///
+    /// Number of compacted segments.
+    #[instrument(skip_all)]
+    pub(super) fn compact(&self) -> WalResult<usize> {
+        let _compaction = self.compaction.lock();
+
+        let candidates = {
+            let segments = self.segments.read();
+            segments
+                .sizes
+                .iter()
+                .filter(|&(&segment, &size)| {
+                    let removed_size = segments.removed_size(segment);
+                    removed_size > 0 && removed_size * 100 >= size * COMPACTION_THRESHOLD_PERCENT
+                })
+                .map(|(&segment, &size)| {
+                    (segment, size, segments.segment_records(segment).collect::<Vec<_>>())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut compacted = 0;
+        for (segment, size, records) in candidates {
+            if self.compact_segment(segment, size, records)? {
+                compacted += 1;
+            }
+        }
+
+        Ok(compacted)
+    }
+
+    /// Rewrites the segment with only the given records, if its size is still the given size.
+    ///
+    /// Returns whether the compacted segment replaced the old one.
+    fn compact_segment(
+        &self,
+        segment: u32,
+        size: u64,
+        records: Vec<(u32, RecordLocation)>,
+    ) -> WalResult<bool> {
+        let path = self.segment_path(segment);
+        let compaction_path = self.compaction_path(segment);
+
+        // Copy the records to a new segment file that replaces the old one.
+        let mut new_locations = BTreeMap::new();
+        let mut offset = 0;
+        if !records.is_empty() {
+            let mut file = reth_fs_util::open(&path)?;
+            let mut buf = Vec::new();
+            for (id, location) in records {
+                let start = buf.len();
+                buf.resize(start + location.size as usize, 0);
+                file.seek(SeekFrom::Start(location.offset))
+                    .and_then(|_| file.read_exact(&mut buf[start..]))
+                    .map_err(|err| reth_fs_util::FsPathError::read(err, &path))?;
+                new_locations.insert(id, RecordLocation { offset, ..location });
+                offset += location.size;
+            }
+            let mut compacted = reth_fs_util::create_file(&compaction_path)?;
+            compacted
+                .write_all(&buf)
+                .and_then(|_| compacted.sync_all())
+                .map_err(|err| reth_fs_util::FsPathError::write(err, &compaction_path))?;
+        }
 
-        for id in file_ids {
-            if let Some(size) = self.remove_notification(id) {
-                deleted_total += 1;
-                deleted_size += size;
+        let mut segments = self.segments.write();
+        if segments.sizes.get(&segment) != Some(&size) {
+            debug!(target: "exex::wal::storage", ?path, "Segment was modified during compaction");
+            if !new_locations.is_empty() {
+                reth_fs_util::remove_file(&compaction_path)?;
             }
+            return Ok(false)
+        }
+
+        if new_locations.is_empty() {
+            reth_fs_util::remove_file(&path)?;
+            segments.sizes.remove(&segment);
+        } else {
+            reth_fs_util::rename(&compaction_path, &path)?;
+            segments.sizes.insert(segment, offset);
         }
 
-        Ok((deleted_total, deleted_size))
+        // Notifications removed during the compaction are still in the compacted segment.
+        let Segments { records, removed, .. } = &mut *segments;
+        removed
+            .retain(|id, location| location.segment != segment || new_locations.contains_key(id));
+        for (id, location) in new_locations {
+            if let Some(current) = records.get_mut(&id).or_else(|| removed.get_mut(&id)) {
+                *current = location;
+            }
+        }
+        self.write_tombstones(&segments)?;
+        debug!(target: "exex::wal::storage", ?path, "Segment was compacted");
+
+        Ok(true)
+    }
+
+    /// Returns the records of all notifications in the storage, without decoding the
+    /// notifications.
+    pub(super) fn records(&self) -> WalResult<Vec<WalRecord>> {
+        let segments = self.segments.read();
+        segments
+            .records
+            .iter()
+            .map(|(&id, &location)| {
+                let (_, blocks) = self.read_record(location, false)?;
+                Ok(WalRecord { id, location, blocks })
+            })
+            .collect()
     }
 
+    /// Returns an iterator over all notifications in the storage.
     pub(super) fn iter_notifications(
         &self,
-        range: RangeInclusive<u32>,
     ) -> impl Iterator<Item = WalResult<(u32, u64, ExExNotification<N>)>> + '_ {
-        range.map(move |id| {
+        let ids = self.segments.read().records.keys().copied().collect::<Vec<_>>();
+        ids.into_iter().map(move |id| {
             let (notification, size) =
                 self.read_notification(id)?.ok_or(WalError::FileNotFound(id))?;
 
@@ -125,63 +569,178 @@ where
         })
     }
 
-    /// Reads the notification from the file with the given ID.
+    /// Reads the notification with the given ID.
     #[instrument(skip(self))]
     pub(super) fn read_notification(
         &self,
-        file_id: u32,
+        id: u32,
     ) -> WalResult<Option<(ExExNotification<N>, u64)>> {
-        let file_path = self.file_path(file_id);
-        debug!(target: "exex::wal::storage", ?file_path, "Reading notification from WAL");
+        let segments = self.segments.read();
+        let Some(&location) = segments.records.get(&id) else { return Ok(None) };
+        debug!(target: "exex::wal::storage", ?location, "Reading notification from WAL");
 
-        let mut file = match File::open(&file_path) {
-            Ok(file) => file,
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
-            Err(err) => return Err(reth_fs_util::FsPathError::open(err, &file_path).into()),
-        };
-        let size = file.metadata().map_err(|err| WalError::FileMetadata(file_id, err))?.len();
+        let (notification, _) = self.read_record(location, true)?;
+        let notification = notification.expect("notification is read");
 
         // Deserialize using the bincode- and msgpack-compatible serde wrapper
         let notification: reth_exex_types::serde_bincode_compat::ExExNotification<'_, N> =
-            rmp_serde::decode::from_read(&mut file)
-                .map_err(|err| WalError::Decode(file_id, file_path, err))?;
+            rmp_serde::decode::from_slice(&notification)
+                .map_err(|err| WalError::Decode(id, self.segment_path(location.segment), err))?;
 
-        Ok(Some((notification.into(), size)))
+        Ok(Some((notification.into(), location.size)))
     }
 
-    /// Writes the notification to the file with the given ID.
+    /// Reads the record at the given location and verifies its checksums.
+    ///
+    /// The encoded notification is only read if `with_notification` is set.
+    fn read_record(
+        &self,
+        location: RecordLocation,
+        with_notification: bool,
+    ) -> WalResult<(Option<Vec<u8>>, NotificationBlocks)> {
+        let path = self.segment_path(location.segment);
+        let corrupted = || WalError::Corrupted(path.clone(), location.offset);
+
+        let mut file = reth_fs_util::open(&path)?;
+        file.seek(SeekFrom::Start(location.offset))
+            .map_err(|err| reth_fs_util::FsPathError::read(err, &path))?;
+        let header = read_header(&mut file, location.size)
+            .map_err(|err| reth_fs_util::FsPathError::read(err, &path))?
+            .ok_or_else(corrupted)?;
+
+        let mut blocks = vec![0; header.blocks_len as usize];
+        file.read_exact(&mut blocks).map_err(|err| reth_fs_util::FsPathError::read(err, &path))?;
+        let blocks = NotificationBlocks::decode(&blocks).ok_or_else(corrupted)?;
+
+        let notification = if with_notification {
+            let mut notification = vec![0; header.notification_len as usize];
+            file.read_exact(&mut notification)
+                .map_err(|err| reth_fs_util::FsPathError::read(err, &path))?;
+            if crc32fast::hash(&notification) != header.notification_checksum {
+                return Err(WalError::Checksum(header.id))
+            }
+            Some(notification)
+        } else {
+            None
+        };
+
+        Ok((notification, blocks))
+    }
+
+    /// Appends the notification with the given ID to the latest segment.
     ///
     /// # Returns
     ///
-    /// The size of the file that was written in bytes.
+    /// The size of the record that was written in bytes.
     #[instrument(skip(self, notification))]
     pub(super) fn write_notification(
         &self,
-        file_id: u32,
+        id: u32,
         notification: &ExExNotification<N>,
     ) -> WalResult<u64> {
-        let file_path = self.file_path(file_id);
-        debug!(target: "exex::wal::storage", ?file_path, "Writing notification to WAL");
+        let mut segments = self.segments.write();
 
         // Serialize using the bincode- and msgpack-compatible serde wrapper
-        let notification =
-            reth_exex_types::serde_bincode_compat::ExExNotification::<N>::from(notification);
+        let encoded_notification = rmp_serde::encode::to_vec(
+            &reth_exex_types::serde_bincode_compat::ExExNotification::<N>::from(notification),
+        )
+        .map_err(|err| WalError::Encode(id, err))?;
+        let mut encoded_blocks = Vec::new();
+        NotificationBlocks::new(notification).encode(&mut encoded_blocks);
+
+        let header = RecordHeader::new(id, &encoded_blocks, &encoded_notification);
+        let mut record = Vec::with_capacity(header.record_size() as usize);
+        record.extend_from_slice(&header.encode());
+        record.extend_from_slice(&encoded_blocks);
+        record.extend_from_slice(&encoded_notification);
+
+        let (segment, offset) = match segments.sizes.last_key_value() {
+            Some((&segment, &size)) if size < self.max_segment_size => (segment, size),
+            _ => (id, 0),
+        };
+        let path = self.segment_path(segment);
+        debug!(target: "exex::wal::storage", ?path, offset, "Writing notification to WAL");
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|err| reth_fs_util::FsPathError::open(err, &path))?;
+        if let Err(err) = file.write_all(&record).and_then(|_| file.sync_data()) {
+            // Don't leave a partial record behind, so that the next one is appended correctly.
+            let _ = file.set_len(offset);
+            return Err(reth_fs_util::FsPathError::write(err, &path).into())
+        }
 
-        reth_fs_util::atomic_write_file(&file_path, |file| {
-            rmp_serde::encode::write(file, &notification)
-        })?;
+        let location = RecordLocation { segment, offset, size: header.record_size() };
+        segments.sizes.insert(segment, offset + location.size);
+        segments.records.insert(id, location);
 
-        Ok(file_path.metadata().map_err(|err| WalError::FileMetadata(file_id, err))?.len())
+        Ok(location.size)
     }
 }
 
+/// Reads and verifies the record header and returns it, if the record fits into the remaining
+/// `len` bytes.
+///
+/// The reader is positioned at the start of the encoded blocks afterwards.
+fn read_header(reader: &mut impl Read, len: u64) -> std::io::Result<Option<RecordHeader>> {
+    if len < RECORD_HEADER_SIZE as u64 {
+        return Ok(None)
+    }
+
+    let mut buf = [0; RECORD_HEADER_SIZE];
+    reader.read_exact(&mut buf)?;
+    let header = RecordHeader::decode(&buf);
+    if header.record_size() > len {
+        return Ok(None)
+    }
+
+    let mut blocks = vec![0; header.blocks_len as usize];
+    reader.read_exact(&mut blocks)?;
+    if header.compute_blocks_checksum(&blocks) != header.blocks_checksum {
+        return Ok(None)
+    }
+
+    Ok(Some(header))
+}
+
 #[cfg(test)]
 mod tests {
     use super::Storage;
+    use crate::wal::WalError;
     use reth_exex_types::ExExNotification;
     use reth_provider::Chain;
-    use reth_testing_utils::generators::{self, random_block};
-    use std::{fs::File, sync::Arc};
+    use reth_testing_utils::generators::{
+        self, random_block, random_block_range, BlockRangeParams,
+    };
+    use std::{
+        fs::{File, OpenOptions},
+        io::Write,
+        sync::Arc,
+    };
+
+    /// Returns a committed notification for each of the given number of blocks.
+    fn notifications(count: u64) -> eyre::Result<Vec<ExExNotification>> {
+        notifications_with_params(count, BlockRangeParams::default())
+    }
+
+    /// Returns a committed notification for each of the given number of blocks, generated with the
+    /// given parameters.
+    fn notifications_with_params(
+        count: u64,
+        params: BlockRangeParams,
+    ) -> eyre::Result<Vec<ExExNotification>> {
+        let mut rng = generators::rng();
+        random_block_range(&mut rng, 0..=count - 1, params)
+            .into_iter()
+            .map(|block| {
+                Ok(ExExNotification::ChainCommitted {
+                    new: Arc::new(Chain::new(vec![block.try_recover()?], Default::default(), None)),
+                })
+            })
+            .collect()
+    }
 
     // wal with 1 block and tx
     // <https://github.com/paradigmxyz/reth/issues/15012>
@@ -230,21 +789,122 @@ mod tests {
     }
 
     #[test]
-    fn test_files_range() -> eyre::Result<()> {
+    fn test_segments() -> eyre::Result<()> {
         let temp_dir = tempfile::tempdir()?;
+        let notifications = notifications(4)?;
+
+        // The first two records are written to the same segment, and the next ones start a new
+        // segment each
+        let storage: Storage = Storage::new(&temp_dir)?;
+        storage.write_notification(0, &notifications[0])?;
+        storage.write_notification(1, &notifications[1])?;
+        drop(storage);
+        let storage: Storage = Storage::with_max_segment_size(&temp_dir, 1)?;
+        storage.write_notification(2, &notifications[2])?;
+        storage.write_notification(3, &notifications[3])?;
+        assert_eq!(storage.num_segments(), 3);
+        assert_eq!(storage.last_id(), Some(3));
+
+        // Removing a record keeps the segment, removing all records deletes it
+        assert_eq!(storage.remove_notifications([1])?.0, 1);
+        assert_eq!(storage.num_segments(), 3);
+        assert_eq!(storage.remove_notifications([0, 2])?.0, 2);
+        assert_eq!(storage.num_segments(), 1);
+        assert_eq!(
+            storage
+                .iter_notifications()
+                .map(|entry| Ok(entry?.2))
+                .collect::<eyre::Result<Vec<_>>>()?,
+            vec![notifications[3].clone()]
+        );
+
+        // A partially written record is truncated on startup
+        let segment_path = storage.segment_path(3);
+        drop(storage);
+        OpenOptions::new().append(true).open(&segment_path)?.write_all(&[1, 2, 3])?;
         let storage: Storage = Storage::new(&temp_dir)?;
+        assert_eq!(storage.records()?.iter().map(|record| record.id).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(std::fs::metadata(&segment_path)?.len(), storage.records()?[0].location.size);
+
+        // A corrupted notification fails the checksum
+        let mut segment = std::fs::read(&segment_path)?;
+        *segment.last_mut().unwrap() ^= 1;
+        std::fs::write(&segment_path, segment)?;
+        assert!(matches!(storage.read_notification(3), Err(WalError::Checksum(3))));
 
-        // Create WAL files
-        File::create(storage.file_path(1))?;
-        File::create(storage.file_path(2))?;
-        File::create(storage.file_path(3))?;
+        Ok(())
+    }
 
-        // Create non-WAL files that should be ignored
+    #[test]
+    fn test_compaction() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        // Blocks without transactions, so that the records are of similar size
+        let notifications = notifications_with_params(
+            4,
+            BlockRangeParams { tx_count: 0..1, ..Default::default() },
+        )?;
+
+        let storage: Storage = Storage::new(&temp_dir)?;
+        for (id, notification) in notifications.iter().enumerate() {
+            storage.write_notification(id as u32, notification)?;
+        }
+        let segment_path = storage.segment_path(0);
+        let segment_size = std::fs::metadata(&segment_path)?.len();
+
+        // A removed record stays in the segment below the threshold, and isn't loaded on startup
+        assert_eq!(storage.remove_notifications([0])?.0, 1);
+        assert_eq!(storage.compact()?, 0);
+        assert_eq!(std::fs::metadata(&segment_path)?.len(), segment_size);
+        drop(storage);
+        let storage: Storage = Storage::new(&temp_dir)?;
+        assert_eq!(
+            storage.records()?.iter().map(|record| record.id).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(storage.last_id(), Some(3));
+
+        // The segment is compacted once the removed records reach the threshold
+        assert_eq!(storage.remove_notifications([1, 2])?.0, 2);
+        assert_eq!(storage.compact()?, 1);
+        let records = storage.records()?;
+        assert_eq!(records.iter().map(|record| record.id).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(records[0].location.offset, 0);
+        assert_eq!(std::fs::metadata(&segment_path)?.len(), records[0].location.size);
+        assert_eq!(storage.read_notification(3)?.map(|(n, _)| n), Some(notifications[3].clone()));
+
+        // The compacted segment and the tombstones are consistent after a restart
+        drop(storage);
+        let storage: Storage = Storage::new(&temp_dir)?;
+        assert_eq!(storage.records()?, records);
+        assert_eq!(std::fs::metadata(storage.tombstones_path())?.len(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_migrate_legacy_files() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let notifications = notifications(3)?;
+
+        for (id, notification) in notifications.iter().enumerate() {
+            let notification =
+                reth_exex_types::serde_bincode_compat::ExExNotification::from(notification);
+            let mut file = File::create(temp_dir.path().join(format!("{id}.wal")))?;
+            rmp_serde::encode::write(&mut file, &notification)?;
+        }
+        // Non-WAL files should be ignored
         File::create(temp_dir.path().join("0.tmp"))?;
-        File::create(temp_dir.path().join("4.tmp"))?;
 
-        // Check files range
-        assert_eq!(storage.files_range()?, Some(1..=3));
+        let storage: Storage = Storage::new(&temp_dir)?;
+        assert_eq!(
+            storage
+                .iter_notifications()
+                .map(|entry| Ok(entry?.2))
+                .collect::<eyre::Result<Vec<_>>>()?,
+            notifications
+        );
+        assert!(!temp_dir.path().join("0.wal").exists());
+        assert!(temp_dir.path().join("0.tmp").exists());
 
         Ok(())
     }
@@ -0,0 +1,67 @@
+use crate::{
+    protocol::{ClientMessage, NotificationMessage, Transport, PROTOCOL_VERSION},
+    RemoteExExEndpoint, RemoteExExError,
+};
+use alloy_eips::BlockNumHash;
+use reth_ethereum_primitives::EthPrimitives;
+use reth_exex::{ExExHead, ExExNotification};
+use reth_primitives_traits::NodePrimitives;
+use std::marker::PhantomData;
+
+/// Client of a [`RemoteExEx`](crate::RemoteExEx), receiving [`ExExNotification`]s in a separate
+/// process.
+///
+/// The client is expected to process notifications in order, and to acknowledge them with
+/// [`RemoteExExClient::send_finished_height`], same as an in-process `ExEx` emits
+/// [`ExExEvent::FinishedHeight`](reth_exex::ExExEvent::FinishedHeight). Notifications that weren't
+/// acknowledged are sent again after reconnecting, so the client should persist its head and pass
+/// it to [`RemoteExExClient::connect`] to skip the ones it has already processed.
+#[derive(Debug)]
+pub struct RemoteExExClient<N: NodePrimitives = EthPrimitives> {
+    transport: Transport,
+    /// ID of the last received notification.
+    last_id: Option<u64>,
+    _pd: PhantomData<N>,
+}
+
+impl<N: NodePrimitives> RemoteExExClient<N> {
+    /// Connects to the server and subscribes to notifications after the given head.
+    ///
+    /// Without a head, the server streams notifications for all new blocks.
+    pub async fn connect(
+        endpoint: &RemoteExExEndpoint,
+        head: Option<ExExHead>,
+    ) -> Result<Self, RemoteExExError> {
+        let mut transport = Transport::connect(endpoint).await?;
+        transport
+            .send(&ClientMessage::Subscribe {
+                version: PROTOCOL_VERSION,
+                head: head.map(|head| head.block),
+            })
+            .await?;
+
+        Ok(Self { transport, last_id: None, _pd: PhantomData })
+    }
+
+    /// Receives the next notification, returning `None` if the server closed the connection.
+    ///
+    /// This method is cancel safe.
+    pub async fn recv(&mut self) -> Option<Result<ExExNotification<N>, RemoteExExError>> {
+        let message = match self.transport.recv::<NotificationMessage<N>>().await? {
+            Ok(message) => message,
+            Err(err) => return Some(Err(err)),
+        };
+        self.last_id = Some(message.id);
+
+        Some(Ok(message.notification))
+    }
+
+    /// Acknowledges all notifications received so far, and lets the node know that the blocks up
+    /// to and including `height` have been processed.
+    pub async fn send_finished_height(
+        &mut self,
+        height: BlockNumHash,
+    ) -> Result<(), RemoteExExError> {
+        self.transport.send(&ClientMessage::FinishedHeight { id: self.last_id, height }).await
+    }
+}
@@ -0,0 +1,16 @@
+/// Errors of the remote `ExEx` transport.
+#[derive(Debug, thiserror::Error)]
+pub enum RemoteExExError {
+    /// I/O error on the underlying socket.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Failed to encode a message.
+    #[error("failed to encode message: {0}")]
+    Encode(#[from] rmp_serde::encode::Error),
+    /// Failed to decode a message.
+    #[error("failed to decode message: {0}")]
+    Decode(#[from] rmp_serde::decode::Error),
+    /// The peer violated the protocol.
+    #[error("protocol error: {0}")]
+    Protocol(String),
+}
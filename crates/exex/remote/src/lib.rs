@@ -0,0 +1,164 @@
+//! Out-of-process execution extensions (`ExEx`).
+//!
+//! A [`RemoteExEx`] is installed into the node like any other `ExEx`, and serves the
+//! [`ExExNotification`](reth_exex::ExExNotification)s to a single [`RemoteExExClient`] over a Unix
+//! domain socket or a TCP connection. This allows consumers such as indexers to be deployed and
+//! restarted independently of the node.
+//!
+//! The client acknowledges the processed blocks, which the server forwards to the node as
+//! [`ExExEvent::FinishedHeight`](reth_exex::ExExEvent::FinishedHeight), and the server stops
+//! consuming notifications once too many of them are unacknowledged. Notifications are therefore
+//! kept in the WAL until the client has processed them, and the client can resume from its
+//! [`ExExHead`](reth_exex::ExExHead) after a restart of either side.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! let endpoint: RemoteExExEndpoint = "/tmp/reth-exex.ipc".parse()?;
+//!
+//! // Node
+//! builder.install_exex("remote", move |ctx| async move {
+//!     Ok(RemoteExEx::new(ctx.notifications, ctx.events, RemoteExExConfig::new(endpoint)).run())
+//! });
+//!
+//! // Consumer
+//! let mut client = RemoteExExClient::<EthPrimitives>::connect(&endpoint, head).await?;
+//! while let Some(notification) = client.recv().await.transpose()? {
+//!     if let Some(committed) = notification.committed_chain() {
+//!         client.send_finished_height(committed.tip().num_hash()).await?;
+//!     }
+//! }
+//! ```
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+mod client;
+pub use client::RemoteExExClient;
+
+mod error;
+pub use error::RemoteExExError;
+
+pub mod protocol;
+pub use protocol::RemoteExExEndpoint;
+
+mod server;
+pub use server::{RemoteExEx, RemoteExExConfig, DEFAULT_MAX_UNACKNOWLEDGED_NOTIFICATIONS};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::BlockNumHash;
+    use futures::Stream;
+    use reth_exex::{ExExEvent, ExExHead, ExExNotification, ExExNotificationsStream};
+    use reth_provider::Chain;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use std::{
+        pin::Pin,
+        sync::{Arc, Mutex},
+        task::{Context, Poll},
+    };
+    use tokio::sync::mpsc;
+
+    /// Notifications stream backed by a channel, recording the head it was configured with.
+    struct TestNotifications {
+        rx: mpsc::UnboundedReceiver<ExExNotification>,
+        head: Arc<Mutex<Option<Option<ExExHead>>>>,
+    }
+
+    impl Stream for TestNotifications {
+        type Item = eyre::Result<ExExNotification>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.rx.poll_recv(cx).map(|notification| notification.map(Ok))
+        }
+    }
+
+    impl ExExNotificationsStream for TestNotifications {
+        fn set_without_head(&mut self) {
+            *self.head.lock().unwrap() = Some(None);
+        }
+
+        fn set_with_head(&mut self, exex_head: ExExHead) {
+            *self.head.lock().unwrap() = Some(Some(exex_head));
+        }
+
+        fn without_head(mut self) -> Self {
+            self.set_without_head();
+            self
+        }
+
+        fn with_head(mut self, exex_head: ExExHead) -> Self {
+            self.set_with_head(exex_head);
+            self
+        }
+    }
+
+    #[tokio::test]
+    async fn remote_exex() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let notifications = random_block_range(&mut rng, 0..=2, BlockRangeParams::default())
+            .into_iter()
+            .map(|block| {
+                Ok(ExExNotification::ChainCommitted {
+                    new: Arc::new(Chain::new(vec![block.try_recover()?], Default::default(), None)),
+                })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+        let num_hash = |i: usize| notifications[i].committed_chain().unwrap().tip().num_hash();
+
+        let temp_dir = tempfile::tempdir()?;
+        let endpoint = RemoteExExEndpoint::Ipc(temp_dir.path().join("exex.ipc"));
+
+        let (notifications_tx, notifications_rx) = mpsc::unbounded_channel();
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let head = Arc::new(Mutex::new(None));
+        let exex = RemoteExEx::new(
+            TestNotifications { rx: notifications_rx, head: head.clone() },
+            events_tx,
+            RemoteExExConfig::new(endpoint.clone()).with_max_unacknowledged(2),
+        );
+        let server = tokio::spawn(exex.run());
+        while !temp_dir.path().join("exex.ipc").exists() {
+            tokio::task::yield_now().await;
+        }
+
+        for notification in &notifications {
+            notifications_tx.send(notification.clone())?;
+        }
+
+        // The head of the first subscription is applied to the notifications stream
+        let exex_head = ExExHead::new(BlockNumHash::default());
+        let mut client = RemoteExExClient::connect(&endpoint, Some(exex_head)).await?;
+        assert_eq!(client.recv().await.transpose()?.as_ref(), Some(&notifications[0]));
+        assert_eq!(*head.lock().unwrap(), Some(Some(exex_head)));
+        assert_eq!(client.recv().await.transpose()?.as_ref(), Some(&notifications[1]));
+
+        // The third notification is only sent once the first two are acknowledged
+        assert!(tokio::time::timeout(std::time::Duration::from_millis(100), client.recv())
+            .await
+            .is_err());
+        client.send_finished_height(num_hash(1)).await?;
+        assert_eq!(events_rx.recv().await, Some(ExExEvent::FinishedHeight(num_hash(1))));
+        assert_eq!(client.recv().await.transpose()?.as_ref(), Some(&notifications[2]));
+
+        // Unacknowledged notifications are sent again after reconnecting
+        drop(client);
+        let mut client =
+            RemoteExExClient::connect(&endpoint, Some(ExExHead::new(num_hash(1)))).await?;
+        assert_eq!(client.recv().await.transpose()?.as_ref(), Some(&notifications[2]));
+        client.send_finished_height(num_hash(2)).await?;
+        assert_eq!(events_rx.recv().await, Some(ExExEvent::FinishedHeight(num_hash(2))));
+
+        // The server stops once the notifications stream ends
+        drop(notifications_tx);
+        assert!(client.recv().await.is_none());
+        server.await??;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+//! Wire protocol spoken between the [`RemoteExEx`](crate::RemoteExEx) and the
+//! [`RemoteExExClient`](crate::RemoteExExClient).
+//!
+//! Every message is a length-delimited frame containing a MessagePack-encoded payload. The client
+//! opens the connection with a [`ClientMessage::Subscribe`], after which the server streams
+//! [`NotificationMessage`]s and the client acknowledges them with
+//! [`ClientMessage::FinishedHeight`].
+
+use crate::RemoteExExError;
+use alloy_eips::BlockNumHash;
+use bytes::Bytes;
+use futures::{SinkExt, StreamExt};
+use reth_exex::{serde_bincode_compat, ExExNotification};
+use reth_primitives_traits::NodePrimitives;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_with::serde_as;
+use std::{fmt, net::SocketAddr, path::PathBuf, pin::Pin, str::FromStr};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
+
+/// Version of the protocol, sent by the client on subscription.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Maximum size of a single frame. Notifications produced by backfill can be large, so this is the
+/// maximum that fits into the frame length prefix.
+const MAX_FRAME_LENGTH: usize = u32::MAX as usize;
+
+/// Messages sent from the client to the server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClientMessage {
+    /// Subscribes to notifications. Must be the first message on the connection.
+    Subscribe {
+        /// Protocol version of the client, see [`PROTOCOL_VERSION`].
+        version: u32,
+        /// Highest block processed by the client, if any.
+        ///
+        /// The server will only send notifications for blocks after the head.
+        head: Option<BlockNumHash>,
+    },
+    /// Highest block processed by the client, equivalent to
+    /// [`ExExEvent::FinishedHeight`](reth_exex::ExExEvent::FinishedHeight).
+    FinishedHeight {
+        /// ID of the last notification received by the client, if any. All notifications up to
+        /// and including this ID are acknowledged.
+        id: Option<u64>,
+        /// The finished height.
+        height: BlockNumHash,
+    },
+}
+
+/// Notification sent from the server to the client.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct NotificationMessage<N: NodePrimitives> {
+    /// ID of the notification, increasing with every notification sent by the server.
+    pub id: u64,
+    /// The notification.
+    #[serde_as(as = "serde_bincode_compat::ExExNotification<'_, N>")]
+    pub notification: ExExNotification<N>,
+}
+
+/// Endpoint the [`RemoteExEx`](crate::RemoteExEx) listens on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteExExEndpoint {
+    /// Unix domain socket at the given path.
+    #[cfg(unix)]
+    Ipc(PathBuf),
+    /// TCP socket at the given address.
+    Tcp(SocketAddr),
+}
+
+impl fmt::Display for RemoteExExEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(unix)]
+            Self::Ipc(path) => write!(f, "{}", path.display()),
+            Self::Tcp(addr) => write!(f, "{addr}"),
+        }
+    }
+}
+
+impl FromStr for RemoteExExEndpoint {
+    type Err = RemoteExExError;
+
+    /// Parses a socket address as a TCP endpoint, and anything else as the path to a Unix domain
+    /// socket.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(addr) = s.parse() {
+            return Ok(Self::Tcp(addr))
+        }
+
+        #[cfg(unix)]
+        return Ok(Self::Ipc(PathBuf::from(s)));
+
+        #[cfg(not(unix))]
+        Err(RemoteExExError::Protocol(format!("invalid socket address: {s}")))
+    }
+}
+
+/// Byte stream of a connection.
+pub(crate) trait Connection: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> Connection for T {}
+
+/// Framed connection between the client and the server.
+pub(crate) struct Transport {
+    inner: Framed<Pin<Box<dyn Connection>>, LengthDelimitedCodec>,
+}
+
+impl fmt::Debug for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transport").finish_non_exhaustive()
+    }
+}
+
+impl Transport {
+    /// Wraps the byte stream into length-delimited frames.
+    pub(crate) fn new(connection: impl Connection + 'static) -> Self {
+        let codec = LengthDelimitedCodec::builder().max_frame_length(MAX_FRAME_LENGTH).new_codec();
+        Self { inner: Framed::new(Box::pin(connection), codec) }
+    }
+
+    /// Connects to the endpoint.
+    pub(crate) async fn connect(endpoint: &RemoteExExEndpoint) -> Result<Self, RemoteExExError> {
+        Ok(match endpoint {
+            #[cfg(unix)]
+            RemoteExExEndpoint::Ipc(path) => {
+                Self::new(tokio::net::UnixStream::connect(path).await?)
+            }
+            RemoteExExEndpoint::Tcp(addr) => Self::new(tokio::net::TcpStream::connect(addr).await?),
+        })
+    }
+
+    /// Encodes and sends the message.
+    pub(crate) async fn send<T: Serialize>(&mut self, message: &T) -> Result<(), RemoteExExError> {
+        let bytes = rmp_serde::to_vec(message)?;
+        self.inner.send(Bytes::from(bytes)).await?;
+        Ok(())
+    }
+
+    /// Receives and decodes the next message, returning `None` if the connection was closed.
+    ///
+    /// This method is cancel safe.
+    pub(crate) async fn recv<T: DeserializeOwned>(&mut self) -> Option<Result<T, RemoteExExError>> {
+        let frame = match self.inner.next().await? {
+            Ok(frame) => frame,
+            Err(err) => return Some(Err(err.into())),
+        };
+        Some(rmp_serde::from_slice(&frame).map_err(Into::into))
+    }
+}
+
+/// Listener accepting connections on a [`RemoteExExEndpoint`].
+#[derive(Debug)]
+pub(crate) enum Listener {
+    #[cfg(unix)]
+    Ipc(tokio::net::UnixListener),
+    Tcp(tokio::net::TcpListener),
+}
+
+impl Listener {
+    /// Binds to the endpoint. An existing Unix domain socket at the path is removed first.
+    pub(crate) async fn bind(endpoint: &RemoteExExEndpoint) -> Result<Self, RemoteExExError> {
+        Ok(match endpoint {
+            #[cfg(unix)]
+            RemoteExExEndpoint::Ipc(path) => {
+                if path.exists() {
+                    std::fs::remove_file(path)?;
+                }
+                Self::Ipc(tokio::net::UnixListener::bind(path)?)
+            }
+            RemoteExExEndpoint::Tcp(addr) => Self::Tcp(tokio::net::TcpListener::bind(addr).await?),
+        })
+    }
+
+    /// Accepts the next connection.
+    pub(crate) async fn accept(&self) -> Result<Transport, RemoteExExError> {
+        Ok(match self {
+            #[cfg(unix)]
+            Self::Ipc(listener) => Transport::new(listener.accept().await?.0),
+            Self::Tcp(listener) => Transport::new(listener.accept().await?.0),
+        })
+    }
+}
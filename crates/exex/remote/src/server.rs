@@ -0,0 +1,212 @@
+use crate::{
+    protocol::{ClientMessage, Listener, NotificationMessage, Transport, PROTOCOL_VERSION},
+    RemoteExExEndpoint, RemoteExExError,
+};
+use alloy_eips::BlockNumHash;
+use futures::StreamExt;
+use reth_ethereum_primitives::EthPrimitives;
+use reth_exex::{ExExEvent, ExExHead, ExExNotification, ExExNotificationsStream};
+use reth_primitives_traits::NodePrimitives;
+use reth_tracing::tracing::{debug, info, warn};
+use std::collections::VecDeque;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Default maximum number of notifications sent to the client without an acknowledgement.
+pub const DEFAULT_MAX_UNACKNOWLEDGED_NOTIFICATIONS: usize = 64;
+
+/// Configuration of the [`RemoteExEx`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteExExConfig {
+    /// Endpoint to listen on.
+    pub endpoint: RemoteExExEndpoint,
+    /// Maximum number of notifications sent to the client without an acknowledgement. Once
+    /// reached, no more notifications are consumed from the node until the client catches up.
+    pub max_unacknowledged: usize,
+}
+
+impl RemoteExExConfig {
+    /// Creates a new configuration listening on the given endpoint.
+    pub const fn new(endpoint: RemoteExExEndpoint) -> Self {
+        Self { endpoint, max_unacknowledged: DEFAULT_MAX_UNACKNOWLEDGED_NOTIFICATIONS }
+    }
+
+    /// Sets the maximum number of notifications sent to the client without an acknowledgement.
+    pub const fn with_max_unacknowledged(mut self, max_unacknowledged: usize) -> Self {
+        self.max_unacknowledged = max_unacknowledged;
+        self
+    }
+}
+
+/// An `ExEx` that serves [`ExExNotification`]s to an out-of-process consumer, see
+/// [`RemoteExExClient`](crate::RemoteExExClient).
+///
+/// The server serves a single client at a time. Notifications are kept in memory until the client
+/// acknowledges them with a finished height, which is then forwarded to the node as
+/// [`ExExEvent::FinishedHeight`]. Because of that, the node doesn't prune nor finalize the WAL past
+/// the blocks that the client has processed:
+/// - If the client reconnects, the unacknowledged notifications are sent again.
+/// - If the node restarts, the head sent by the client on subscription is used to catch up from the
+///   WAL and the database, same as for an in-process `ExEx`.
+pub struct RemoteExEx<S, N: NodePrimitives = EthPrimitives> {
+    notifications: S,
+    events: UnboundedSender<ExExEvent>,
+    config: RemoteExExConfig,
+    /// Whether the notifications stream has been configured by the first subscription.
+    subscribed: bool,
+    /// ID of the next notification.
+    next_id: u64,
+    /// Notifications that were sent to the client, but not acknowledged yet.
+    unacknowledged: VecDeque<NotificationMessage<N>>,
+}
+
+impl<S, N: NodePrimitives> std::fmt::Debug for RemoteExEx<S, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteExEx")
+            .field("events", &self.events)
+            .field("config", &self.config)
+            .field("subscribed", &self.subscribed)
+            .field("next_id", &self.next_id)
+            .field("unacknowledged", &self.unacknowledged.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, N> RemoteExEx<S, N>
+where
+    S: ExExNotificationsStream<N>,
+    N: NodePrimitives,
+{
+    /// Creates a new [`RemoteExEx`] serving the notifications stream of the `ExEx` context, and
+    /// sending the acknowledged heights to its events channel.
+    ///
+    /// ```ignore
+    /// let exex = RemoteExEx::new(ctx.notifications, ctx.events, config);
+    /// ```
+    pub fn new(
+        notifications: S,
+        events: UnboundedSender<ExExEvent>,
+        config: RemoteExExConfig,
+    ) -> Self {
+        Self {
+            notifications,
+            events,
+            config,
+            subscribed: false,
+            next_id: 0,
+            unacknowledged: VecDeque::new(),
+        }
+    }
+
+    /// Listens on the configured endpoint and serves clients until the notifications stream ends.
+    ///
+    /// Connection errors are logged and the server waits for the next client.
+    pub async fn run(mut self) -> eyre::Result<()> {
+        let listener = Listener::bind(&self.config.endpoint).await?;
+        info!(target: "exex::remote", endpoint = %self.config.endpoint, "Remote ExEx listening");
+
+        loop {
+            let mut transport = listener.accept().await?;
+            debug!(target: "exex::remote", "Remote ExEx client connected");
+
+            match self.serve(&mut transport).await {
+                Ok(true) => {
+                    debug!(target: "exex::remote", "Remote ExEx client disconnected");
+                }
+                Ok(false) => {
+                    info!(target: "exex::remote", "Notifications stream ended, stopping remote ExEx");
+                    return Ok(())
+                }
+                Err(err) => match err.downcast::<RemoteExExError>() {
+                    Ok(err) => {
+                        warn!(target: "exex::remote", %err, "Remote ExEx client connection failed");
+                    }
+                    Err(err) => return Err(err),
+                },
+            }
+        }
+    }
+
+    /// Serves the connection until either the client disconnects, which returns `true`, or the
+    /// notifications stream ends, which returns `false`.
+    ///
+    /// Errors of the connection are returned as [`RemoteExExError`].
+    async fn serve(&mut self, transport: &mut Transport) -> eyre::Result<bool> {
+        let head = match transport.recv::<ClientMessage>().await.transpose()? {
+            Some(ClientMessage::Subscribe { version, head }) => {
+                if version != PROTOCOL_VERSION {
+                    return Err(RemoteExExError::Protocol(format!(
+                        "unsupported protocol version {version}, expected {PROTOCOL_VERSION}"
+                    ))
+                    .into())
+                }
+                head
+            }
+            Some(message) => {
+                return Err(RemoteExExError::Protocol(format!(
+                    "expected subscription, got {message:?}"
+                ))
+                .into())
+            }
+            None => return Ok(true),
+        };
+
+        if !self.subscribed {
+            // The head can only be applied to the notifications stream once, which is enough to
+            // catch up after a node restart. Subsequent subscriptions are served from the
+            // unacknowledged notifications.
+            match head {
+                Some(head) => self.notifications.set_with_head(ExExHead::new(head)),
+                None => self.notifications.set_without_head(),
+            }
+            self.subscribed = true;
+        }
+
+        for message in &self.unacknowledged {
+            if head.is_some_and(|head| is_processed(&message.notification, head)) {
+                continue
+            }
+            transport.send(message).await?;
+        }
+
+        loop {
+            tokio::select! {
+                message = transport.recv::<ClientMessage>() => {
+                    let Some(message) = message else { return Ok(true) };
+                    match message? {
+                        ClientMessage::FinishedHeight { id, height } => {
+                            if let Some(id) = id {
+                                while self.unacknowledged.front().is_some_and(|message| message.id <= id) {
+                                    self.unacknowledged.pop_front();
+                                }
+                            }
+                            self.events.send(ExExEvent::FinishedHeight(height))?;
+                        }
+                        message @ ClientMessage::Subscribe { .. } => {
+                            return Err(RemoteExExError::Protocol(format!(
+                                "already subscribed, got {message:?}"
+                            ))
+                            .into())
+                        }
+                    }
+                }
+                notification = self.notifications.next(), if self.unacknowledged.len() < self.config.max_unacknowledged => {
+                    let Some(notification) = notification.transpose()? else { return Ok(false) };
+
+                    let message = NotificationMessage { id: self.next_id, notification };
+                    self.next_id += 1;
+                    self.unacknowledged.push_back(message);
+                    transport.send(self.unacknowledged.back().expect("just pushed")).await?;
+                }
+            }
+        }
+    }
+}
+
+/// Returns `true` if the notification only commits blocks at or below the head, same as the
+/// notifications skipped by [`ExExNotificationsWithHead`](reth_exex::ExExNotificationsWithHead).
+fn is_processed<N: NodePrimitives>(notification: &ExExNotification<N>, head: BlockNumHash) -> bool {
+    match notification {
+        ExExNotification::ChainCommitted { new } => *new.range().end() <= head.number,
+        _ => false,
+    }
+}
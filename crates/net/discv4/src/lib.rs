@@ -346,6 +346,15 @@ impl Discv4 {
         self.send_to_service(cmd);
     }
 
+    /// Requests the signed [`Enr`] of the node via EIP-868, without adding it to the table.
+    ///
+    /// The node is pinged first if its endpoint is not proven yet. The record is emitted as
+    /// [`DiscoveryUpdate::Enr`] once the node responds.
+    pub fn request_enr(&self, node_record: NodeRecord) {
+        let cmd = Discv4Command::RequestEnr(node_record);
+        self.send_to_service(cmd);
+    }
+
     /// Adds the peer and id to the ban list.
     ///
     /// This will prevent any future inclusion in the table
@@ -1169,6 +1178,12 @@ impl Discv4Service {
         });
         self.send_packet(pong, remote_addr);
 
+        // an ENR request that reached the node before our pong was dropped, resend it now that
+        // the node has our endpoint proof
+        if self.pending_enr_requests.contains_key(&record.id) {
+            self.send_enr_request(record);
+        }
+
         // if node was absent also send a ping to establish the endpoint proof from our end
         if is_new_insert {
             self.try_ping(record, PingReason::InitialInsert);
@@ -1249,6 +1264,15 @@ impl Discv4Service {
         echo_hash
     }
 
+    /// Requests the ENR of the node, after proving its endpoint if there's no bond with it yet.
+    fn request_enr(&mut self, node: NodeRecord) {
+        if self.has_bond(node.id, node.address) {
+            self.send_enr_request(node)
+        } else {
+            self.try_ping(node, PingReason::RequestEnr)
+        }
+    }
+
     /// Sends an enr request message to the node's UDP address.
     ///
     /// Returns the echo hash of the ping message.
@@ -1263,7 +1287,7 @@ impl Discv4Service {
         let echo_hash = self.send_packet(Message::EnrRequest(enr_request), remote_addr);
 
         self.pending_enr_requests
-            .insert(node.id, EnrRequestState { sent_at: Instant::now(), echo_hash });
+            .insert(node.id, EnrRequestState { sent_at: Instant::now(), echo_hash, node });
     }
 
     /// Message handler for an incoming `Pong`.
@@ -1300,6 +1324,9 @@ impl Discv4Service {
             PingReason::RePing => {
                 self.update_on_reping(node, pong.enr_sq);
             }
+            PingReason::RequestEnr => {
+                self.send_enr_request(node);
+            }
             PingReason::Lookup(node, ctx) => {
                 self.update_on_pong(node, pong.enr_sq);
                 // insert node and assoc. lookup_context into the pending_lookup table to complete
@@ -1349,8 +1376,14 @@ impl Discv4Service {
                         let id = entry.value().update_with_fork_id(fork_id);
                         (entry.value().record, id)
                     }
-                    _ => return,
+                    kbucket::Entry::Absent(_) => {
+                        // requested for a node outside the table
+                        self.notify(DiscoveryUpdate::Enr(resp.node, msg.enr));
+                        return
+                    }
+                    kbucket::Entry::SelfEntry => return,
                 };
+                self.notify(DiscoveryUpdate::Enr(record, msg.enr));
                 match (fork_id, old_fork_id) {
                     (Some(new), Some(old)) => {
                         if new != old {
@@ -1728,6 +1761,9 @@ impl Discv4Service {
                     Discv4Command::Add(enr) => {
                         self.add_node(enr);
                     }
+                    Discv4Command::RequestEnr(record) => {
+                        self.request_enr(record);
+                    }
                     Discv4Command::Lookup { node_id, tx } => {
                         let node_id = node_id.unwrap_or(self.local_node_record.id);
                         self.lookup_with(node_id, tx);
@@ -2044,6 +2080,7 @@ impl Default for ReceiveCache {
 /// The commands sent from the frontend [Discv4] to the service [`Discv4Service`].
 enum Discv4Command {
     Add(NodeRecord),
+    RequestEnr(NodeRecord),
     SetTcpPort(u16),
     SetEIP868RLPPair { key: Vec<u8>, rlp: Bytes },
     Ban(PeerId, IpAddr),
@@ -2291,6 +2328,8 @@ struct EnrRequestState {
     sent_at: Instant,
     // Hash sent in the Ping request
     echo_hash: B256,
+    // The node the request was sent to.
+    node: NodeRecord,
 }
 
 /// Stored node info.
@@ -2386,6 +2425,8 @@ enum PingReason {
     EstablishBond,
     /// Re-ping a peer.
     RePing,
+    /// A ping to a peer outside the table to establish a bond before requesting its ENR.
+    RequestEnr,
     /// Part of a lookup to ensure endpoint is proven before we can send a `FindNode` request.
     Lookup(NodeRecord, LookupContext),
 }
@@ -2397,6 +2438,8 @@ pub enum DiscoveryUpdate {
     Added(NodeRecord),
     /// A new node was discovered but _not_ added to the table because it is currently full.
     DiscoveredAtCapacity(NodeRecord),
+    /// Received the signed [`Enr`] of the given [`NodeRecord`] via EIP-868.
+    Enr(NodeRecord, Enr<SecretKey>),
     /// Received a [`ForkId`] via EIP-868 for the given [`NodeRecord`].
     EnrForkId(NodeRecord, ForkId),
     /// Node that was removed from the table
@@ -2989,6 +3032,58 @@ mod tests {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_enr_update() {
+        reth_tracing::init_test_tracing();
+
+        let (_discv4, mut service_1) = create_discv4().await;
+        let (_discv4, service_2) = create_discv4().await;
+        let expected = service_2.local_eip_868_enr.clone();
+
+        let mut updates = service_1.update_stream();
+        service_1.add_node(service_2.local_node_record);
+        service_1.spawn();
+        service_2.spawn();
+
+        // the ENR is requested once the endpoint of the node is proven
+        let enr = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(DiscoveryUpdate::Enr(_, enr)) = updates.next().await {
+                    return enr
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(enr, expected);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_request_enr() {
+        reth_tracing::init_test_tracing();
+
+        let (discv4, mut service_1) = create_discv4().await;
+        let (_discv4, service_2) = create_discv4().await;
+        let expected = service_2.local_eip_868_enr.clone();
+
+        let mut updates = service_1.update_stream();
+        service_1.spawn();
+        service_2.spawn();
+
+        // the node is not in the table, the ENR is requested after a bond is established
+        discv4.request_enr(service_2.local_node_record);
+        let enr = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(DiscoveryUpdate::Enr(_, enr)) = updates.next().await {
+                    return enr
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(enr, expected);
+    }
+
     #[test]
     fn test_insert() {
         let local_node_record = rng_record(&mut rand_08::thread_rng());
@@ -410,7 +410,7 @@ fn convert_enr_node_record(enr: &Enr<SecretKey>) -> Option<DnsNodeRecordUpdate>
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tree::TreeRootEntry;
+    use crate::tree::{DnsTree, TreeRootEntry};
     use alloy_chains::Chain;
     use alloy_rlp::{Decodable, Encodable};
     use enr::EnrKey;
@@ -551,6 +551,43 @@ mod tests {
         task.await.unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_sync_built_tree() {
+        reth_tracing::init_test_tracing();
+
+        let nodes = (0..20u16)
+            .map(|port| {
+                Enr::builder()
+                    .ip4(Ipv4Addr::LOCALHOST)
+                    .udp4(30303 + port)
+                    .tcp4(30303 + port)
+                    .build(&SecretKey::new(&mut thread_rng()))
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let secret_key = SecretKey::new(&mut thread_rng());
+        let mut tree = DnsTree::new(1, nodes.clone(), Vec::<LinkEntry>::new());
+        tree.sign(&secret_key).unwrap();
+
+        let link =
+            LinkEntry { domain: "nodes.example.org".to_string(), pubkey: secret_key.public() };
+        let resolver = MapResolver::default();
+        for (name, content) in tree.records(&link.domain) {
+            resolver.insert(name, content);
+        }
+
+        let mut service = DnsDiscoveryService::new(Arc::new(resolver), Default::default());
+        service.sync_tree_with_link(link);
+
+        let mut discovered = HashSet::new();
+        while discovered.len() < nodes.len() {
+            let DnsDiscoveryEvent::Enr(enr) = poll_fn(|cx| service.poll(cx)).await;
+            discovered.insert(enr.node_id());
+        }
+        assert_eq!(discovered, nodes.iter().map(|enr| enr.node_id()).collect());
+    }
+
     #[tokio::test]
     async fn test_recheck_tree() {
         reth_tracing::init_test_tracing();
@@ -21,14 +21,16 @@ use crate::error::{
     ParseDnsEntryError::{FieldNotFound, UnknownEntry},
     ParseEntryResult,
 };
-use alloy_primitives::{hex, Bytes};
+use alloy_primitives::{hex, keccak256, Bytes};
 use data_encoding::{BASE32_NOPAD, BASE64URL_NOPAD};
 use enr::{Enr, EnrKey, EnrKeyUnambiguous, EnrPublicKey, Error as EnrError};
 use secp256k1::SecretKey;
 #[cfg(feature = "serde")]
 use serde_with::{DeserializeFromStr, SerializeDisplay};
 use std::{
+    collections::BTreeMap,
     fmt,
+    fmt::Write,
     hash::{Hash, Hasher},
     str::FromStr,
 };
@@ -41,6 +43,13 @@ const LINK_PREFIX: &str = "enrtree://";
 const BRANCH_PREFIX: &str = "enrtree-branch:";
 /// Prefix used for ENR entries in the ENR tree.
 const ENR_PREFIX: &str = "enr:";
+/// Number of bytes of the keccak256 hash of an entry that make up its subdomain.
+const HASH_ABBREV_SIZE: usize = 16;
+/// Maximum number of children of a branch entry, so that the entry stays below 370 bytes like in
+/// the reference implementation.
+const MAX_BRANCH_CHILDREN: usize = 13;
+/// Maximum length of a single character string of a TXT record.
+const MAX_TXT_STRING_LEN: usize = 255;
 
 /// Represents all variants of DNS entries for Ethereum node lists.
 #[derive(Debug, Clone)]
@@ -210,8 +219,14 @@ impl BranchEntry {
             Ok(hash.to_string())
         }
 
+        let input = input.trim();
+        if input.is_empty() {
+            // an empty subtree, e.g. the link subtree of a tree without links
+            return Ok(Self { children: Vec::new() })
+        }
+
         let children =
-            input.trim().split(',').map(ensure_valid_hash).collect::<ParseEntryResult<Vec<_>>>()?;
+            input.split(',').map(ensure_valid_hash).collect::<ParseEntryResult<Vec<_>>>()?;
         Ok(Self { children })
     }
 }
@@ -341,6 +356,139 @@ impl<K: EnrKeyUnambiguous> fmt::Display for NodeEntry<K> {
     }
 }
 
+/// Returns the subdomain of the entry in the tree: the base32 encoded, abbreviated keccak256 hash
+/// of the entry's text.
+pub fn entry_hash(entry: &str) -> String {
+    BASE32_NOPAD.encode(&keccak256(entry.as_bytes())[..HASH_ABBREV_SIZE])
+}
+
+/// A tree of node records and links to other trees, in the structure it's published via DNS.
+///
+/// The tree consists of the [`TreeRootEntry`], published at the domain of the tree, and of all
+/// other entries, published at `<hash>.<domain>`, see [`entry_hash`].
+#[derive(Debug, Clone)]
+pub struct DnsTree {
+    /// The root of the tree.
+    root: TreeRootEntry,
+    /// All other entries of the tree, by their hash.
+    entries: BTreeMap<String, String>,
+}
+
+// === impl DnsTree ===
+
+impl DnsTree {
+    /// Builds the tree of the given node records and links, with an unsigned root.
+    ///
+    /// The node records are ordered by node ID and deduplicated, keeping the record with the
+    /// highest sequence number, so that the same set of nodes always results in the same tree.
+    pub fn new<K: EnrKeyUnambiguous>(
+        sequence_number: u64,
+        nodes: impl IntoIterator<Item = Enr<K>>,
+        links: impl IntoIterator<Item = LinkEntry<K>>,
+    ) -> Self {
+        let mut records = BTreeMap::new();
+        for enr in nodes {
+            let node_id = enr.node_id().raw();
+            if records.get(&node_id).is_none_or(|existing: &Enr<K>| existing.seq() < enr.seq()) {
+                records.insert(node_id, enr);
+            }
+        }
+        let mut links = links.into_iter().map(|link| link.to_string()).collect::<Vec<_>>();
+        links.sort_unstable();
+        links.dedup();
+
+        let mut tree = Self {
+            root: TreeRootEntry {
+                enr_root: String::new(),
+                link_root: String::new(),
+                sequence_number,
+                signature: Bytes::new(),
+            },
+            entries: BTreeMap::new(),
+        };
+        let enr_root = tree.build(records.into_values().map(|enr| enr.to_base64()).collect());
+        tree.root.enr_root = tree.insert(enr_root);
+        let link_root = tree.build(links);
+        tree.root.link_root = tree.insert(link_root);
+
+        tree
+    }
+
+    /// Builds the subtree of the given entries and returns its root entry.
+    ///
+    /// All entries of the subtree except the root are inserted into the tree.
+    fn build(&mut self, entries: Vec<String>) -> String {
+        if entries.len() == 1 {
+            return entries.into_iter().next().expect("exactly one entry")
+        }
+        if entries.len() <= MAX_BRANCH_CHILDREN {
+            let children = entries.into_iter().map(|entry| self.insert(entry)).collect();
+            return BranchEntry { children }.to_string()
+        }
+
+        let subtrees = entries
+            .chunks(MAX_BRANCH_CHILDREN)
+            .map(|chunk| {
+                let subtree = self.build(chunk.to_vec());
+                self.insert(subtree.clone());
+                subtree
+            })
+            .collect();
+        self.build(subtrees)
+    }
+
+    /// Inserts the entry into the tree and returns its hash.
+    fn insert(&mut self, entry: String) -> String {
+        let hash = entry_hash(&entry);
+        self.entries.insert(hash.clone(), entry);
+        hash
+    }
+
+    /// Signs the root of the tree with the given key.
+    pub fn sign<K: EnrKey>(&mut self, key: &K) -> Result<(), EnrError> {
+        self.root.sign(key)
+    }
+
+    /// Returns the root of the tree.
+    pub const fn root(&self) -> &TreeRootEntry {
+        &self.root
+    }
+
+    /// Returns the TXT records of the tree published at the given domain, as pairs of the record
+    /// name and the content of the record. The root comes first.
+    pub fn records(&self, domain: &str) -> Vec<(String, String)> {
+        std::iter::once((domain.to_string(), self.root.to_string()))
+            .chain(
+                self.entries
+                    .iter()
+                    .map(|(hash, entry)| (format!("{hash}.{domain}"), entry.clone())),
+            )
+            .collect()
+    }
+
+    /// Returns the records of the tree published at the given domain in the zone file format,
+    /// with the given TTL in seconds.
+    ///
+    /// Entries longer than 255 bytes, e.g. large node records, are split into multiple character
+    /// strings of the same TXT record.
+    pub fn to_zone_file(&self, domain: &str, ttl: u64) -> String {
+        let domain = domain.trim_end_matches('.');
+        let mut zone = format!(
+            "; EIP-1459 node list of {domain}, sequence number {}\n",
+            self.root.sequence_number
+        );
+        for (name, content) in self.records(domain) {
+            let strings = content
+                .as_bytes()
+                .chunks(MAX_TXT_STRING_LEN)
+                .map(|chunk| format!("\"{}\"", String::from_utf8_lossy(chunk)))
+                .collect::<Vec<_>>();
+            let _ = writeln!(zone, "{name}. {ttl} IN TXT {}", strings.join(" "));
+        }
+        zone
+    }
+}
+
 /// Parses the value of the key value pair
 fn parse_value<F, V>(input: &mut &str, key: &str, err: &'static str, f: F) -> ParseEntryResult<V>
 where
@@ -424,6 +572,68 @@ mod tests {
         assert!(res.is_err());
     }
 
+    #[test]
+    fn parse_empty_branch_entry() {
+        let s = "enrtree-branch:";
+        let entry: BranchEntry = s.parse().unwrap();
+        assert!(entry.children.is_empty());
+        assert_eq!(entry.to_string(), s);
+    }
+
+    #[test]
+    fn build_tree() {
+        let mut rng = secp256k1::rand::thread_rng();
+        let nodes = (0..30u16)
+            .map(|port| {
+                Enr::builder()
+                    .ip4(std::net::Ipv4Addr::LOCALHOST)
+                    .tcp4(30303 + port)
+                    .build(&SecretKey::new(&mut rng))
+                    .unwrap()
+            })
+            .collect::<Vec<Enr<SecretKey>>>();
+
+        let key = SecretKey::new(&mut rng);
+        let mut tree = DnsTree::new(7, nodes.clone(), Vec::<LinkEntry>::new());
+        tree.sign(&key).unwrap();
+        assert_eq!(tree.root().sequence_number, 7);
+        assert!(tree.root().verify::<SecretKey>(&key.public()));
+
+        // the tree is deterministic
+        let mut reversed = DnsTree::new(7, nodes.iter().rev().cloned(), Vec::<LinkEntry>::new());
+        reversed.sign(&key).unwrap();
+        assert_eq!(reversed.records("nodes.example.org"), tree.records("nodes.example.org"));
+
+        // all nodes are reachable from the root
+        let records = tree.records("nodes.example.org").into_iter().collect::<BTreeMap<_, _>>();
+        let mut pending = vec![tree.root().enr_root.clone()];
+        let mut resolved = Vec::new();
+        while let Some(hash) = pending.pop() {
+            let entry = &records[&format!("{hash}.nodes.example.org")];
+            assert_eq!(entry_hash(entry), hash);
+            match entry.parse::<DnsEntry<SecretKey>>().unwrap() {
+                DnsEntry::Branch(branch) => {
+                    assert!(branch.children.len() <= MAX_BRANCH_CHILDREN);
+                    pending.extend(branch.children);
+                }
+                DnsEntry::Node(node) => resolved.push(node.enr),
+                entry => unreachable!("unexpected entry {entry}"),
+            }
+        }
+        resolved.sort_by_key(|enr| enr.node_id().raw());
+        let mut expected = nodes;
+        expected.sort_by_key(|enr| enr.node_id().raw());
+        assert_eq!(resolved, expected);
+
+        // the link subtree is empty
+        let link_root = &records[&format!("{}.nodes.example.org", tree.root().link_root)];
+        assert_eq!(link_root, "enrtree-branch:");
+
+        let zone = tree.to_zone_file("nodes.example.org.", 3600);
+        assert_eq!(zone.lines().count(), records.len() + 1);
+        assert!(zone.contains(&format!("nodes.example.org. 3600 IN TXT \"{}\"", tree.root())));
+    }
+
     #[test]
     fn parse_link_entry() {
         let s = "enrtree://AM5FCQLWIZX2QFPNJAP7VUERCCRNGRHWZG3YYHIUV7BVDQ5FDPRT2@nodes.example.org";
@@ -240,6 +240,7 @@ impl Discovery {
             DiscoveryUpdate::Removed(peer_id) => {
                 self.discovered_nodes.remove(&peer_id);
             }
+            DiscoveryUpdate::Enr(..) => {}
             DiscoveryUpdate::Batch(updates) => {
                 for update in updates {
                     self.on_discv4_update(update);
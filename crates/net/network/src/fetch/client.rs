@@ -11,6 +11,7 @@ use reth_network_p2p::{
     error::{PeerRequestResult, RequestError},
     headers::client::{HeadersClient, HeadersRequest},
     priority::Priority,
+    receipts::client::{ReceiptsClient, ReceiptsFut},
     BlockClient,
 };
 use reth_network_peers::PeerId;
@@ -102,6 +103,30 @@ impl<N: NetworkPrimitives> BodiesClient for FetchClient<N> {
     }
 }
 
+impl<N: NetworkPrimitives> ReceiptsClient for FetchClient<N> {
+    type Receipt = N::Receipt;
+    type Output = ReceiptsFut<N::Receipt>;
+
+    /// Sends a `GetReceipts` request to an available peer.
+    fn get_receipts_with_priority_and_range_hint(
+        &self,
+        request: Vec<B256>,
+        priority: Priority,
+        range_hint: Option<RangeInclusive<u64>>,
+    ) -> Self::Output {
+        let (response, rx) = oneshot::channel();
+        if self
+            .request_tx
+            .send(DownloadRequest::GetReceipts { request, response, priority, range_hint })
+            .is_ok()
+        {
+            Box::pin(FlattenedResponse::from(rx))
+        } else {
+            Box::pin(future::err(RequestError::ChannelClosed))
+        }
+    }
+}
+
 impl<N: NetworkPrimitives> BlockClient for FetchClient<N> {
     type Block = N::Block;
 }
@@ -5,9 +5,12 @@ mod client;
 pub use client::FetchClient;
 
 use crate::{message::BlockRequest, session::BlockRangeInfo};
+use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::B256;
 use futures::StreamExt;
-use reth_eth_wire::{EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, NetworkPrimitives};
+use reth_eth_wire::{
+    EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, GetReceipts, NetworkPrimitives,
+};
 use reth_network_api::test_utils::PeersHandle;
 use reth_network_p2p::{
     error::{EthResponseValidator, PeerRequestResult, RequestError, RequestResult},
@@ -27,9 +30,11 @@ use std::{
 };
 use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::debug;
 
 type InflightHeadersRequest<H> = Request<HeadersRequest, PeerRequestResult<Vec<H>>>;
 type InflightBodiesRequest<B> = Request<Vec<B256>, PeerRequestResult<Vec<B>>>;
+type InflightReceiptsRequest<R> = Request<Vec<B256>, PeerRequestResult<Vec<Vec<R>>>>;
 
 /// Manages data fetching operations.
 ///
@@ -37,12 +42,18 @@ type InflightBodiesRequest<B> = Request<Vec<B256>, PeerRequestResult<Vec<B>>>;
 /// peers and sends the response once ready.
 ///
 /// This type maintains a list of connected peers that are available for requests.
+///
+/// Requests for a known range of blocks are only routed to peers whose eth/69 advertised block
+/// range covers it, see [`BlockRangeInfo`]. If no connected peer covers the range, the request
+/// falls back to any available peer.
 #[derive(Debug)]
 pub struct StateFetcher<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// Currently active [`GetBlockHeaders`] requests
     inflight_headers_requests: HashMap<PeerId, InflightHeadersRequest<N::BlockHeader>>,
     /// Currently active [`GetBlockBodies`] requests
     inflight_bodies_requests: HashMap<PeerId, InflightBodiesRequest<N::BlockBody>>,
+    /// Currently active [`GetReceipts`] requests
+    inflight_receipts_requests: HashMap<PeerId, InflightReceiptsRequest<N::Receipt>>,
     /// The list of _available_ peers for requests.
     peers: HashMap<PeerId, Peer>,
     /// The handle to the peers manager
@@ -65,6 +76,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         Self {
             inflight_headers_requests: Default::default(),
             inflight_bodies_requests: Default::default(),
+            inflight_receipts_requests: Default::default(),
             peers: Default::default(),
             peers_handle,
             num_active_peers,
@@ -110,6 +122,9 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         if let Some(req) = self.inflight_bodies_requests.remove(peer) {
             let _ = req.response.send(Err(RequestError::ConnectionDropped));
         }
+        if let Some(req) = self.inflight_receipts_requests.remove(peer) {
+            let _ = req.response.send(Err(RequestError::ConnectionDropped));
+        }
     }
 
     /// Updates the block information for the peer.
@@ -133,11 +148,28 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         }
     }
 
-    /// Returns the _next_ idle peer that's ready to accept a request,
+    /// Returns `true` if any connected peer that isn't closing has the blocks of the range
+    /// available.
+    fn any_peer_covers(&self, range: &RangeInclusive<u64>) -> bool {
+        self.peers.values().any(|peer| !peer.state.is_closing() && peer.covers(range))
+    }
+
+    /// Returns `true` if a request for the range of blocks can be sent to the peer.
+    ///
+    /// This is the case if the peer has the blocks available, or if no connected peer has them and
+    /// the request falls back to any peer.
+    fn is_routable(&self, peer: &Peer, range: Option<&RangeInclusive<u64>>) -> bool {
+        range.is_none_or(|range| peer.covers(range) || !self.any_peer_covers(range))
+    }
+
+    /// Returns the _next_ idle peer that's ready to accept a request for the range of blocks,
     /// prioritizing those with the lowest timeout/latency and those that recently responded with
     /// adequate data.
-    fn next_best_peer(&self) -> Option<PeerId> {
-        let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
+    fn next_best_peer(&self, range: Option<&RangeInclusive<u64>>) -> Option<PeerId> {
+        let fallback = range.is_some_and(|range| !self.any_peer_covers(range));
+        let mut idle = self.peers.iter().filter(|(_, peer)| {
+            peer.state.is_idle() && (fallback || range.is_none_or(|range| peer.covers(range)))
+        });
 
         let mut best_peer = idle.next()?;
 
@@ -166,9 +198,21 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             return PollAction::NoRequests
         }
 
-        let Some(peer_id) = self.next_best_peer() else { return PollAction::NoPeersAvailable };
+        if !self.peers.values().any(|peer| peer.state.is_idle()) {
+            return PollAction::NoPeersAvailable
+        }
 
-        let request = self.queued_requests.pop_front().expect("not empty");
+        // Find the first request that an idle peer can serve, so that requests for blocks only
+        // available from busy peers don't hold back the others.
+        let Some((idx, peer_id)) =
+            self.queued_requests.iter().enumerate().find_map(|(idx, request)| {
+                Some((idx, self.next_best_peer(request.block_range().as_ref())?))
+            })
+        else {
+            return PollAction::NoPeersAvailable
+        };
+
+        let request = self.queued_requests.remove(idx).expect("exists");
         let request = self.prepare_block_request(peer_id, request);
 
         PollAction::Ready(FetchAction::BlockRequest { peer_id, request })
@@ -224,9 +268,10 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             peer.state = req.peer_state();
         }
 
+        let range = req.block_range();
         match req {
             DownloadRequest::GetBlockHeaders { request, response, .. } => {
-                let inflight = Request { request: request.clone(), response };
+                let inflight = Request { request: request.clone(), response, range };
                 self.inflight_headers_requests.insert(peer_id, inflight);
                 let HeadersRequest { start, limit, direction } = request;
                 BlockRequest::GetBlockHeaders(GetBlockHeaders {
@@ -237,22 +282,57 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 })
             }
             DownloadRequest::GetBlockBodies { request, response, .. } => {
-                let inflight = Request { request: request.clone(), response };
+                let inflight = Request { request: request.clone(), response, range };
                 self.inflight_bodies_requests.insert(peer_id, inflight);
                 BlockRequest::GetBlockBodies(GetBlockBodies(request))
             }
+            DownloadRequest::GetReceipts { request, response, .. } => {
+                let inflight = Request { request: request.clone(), response, range };
+                self.inflight_receipts_requests.insert(peer_id, inflight);
+                BlockRequest::GetReceipts(GetReceipts(request))
+            }
         }
     }
 
-    /// Returns a new followup request for the peer.
+    /// Returns a new followup request for the peer, the first queued request that the peer can
+    /// serve.
     ///
     /// Caution: this expects that the peer is _not_ closed.
     fn followup_request(&mut self, peer_id: PeerId) -> Option<BlockResponseOutcome> {
-        let req = self.queued_requests.pop_front()?;
+        let peer = self.peers.get(&peer_id)?;
+        let idx = self
+            .queued_requests
+            .iter()
+            .position(|req| self.is_routable(peer, req.block_range().as_ref()))?;
+        let req = self.queued_requests.remove(idx).expect("exists");
         let req = self.prepare_block_request(peer_id, req);
         Some(BlockResponseOutcome::Request(peer_id, req))
     }
 
+    /// Returns `true` if the peer sent an empty response to a request for blocks that it
+    /// advertised as available, and logs the violation.
+    fn violates_block_range(
+        &self,
+        peer_id: &PeerId,
+        range: Option<&RangeInclusive<u64>>,
+        is_empty_response: bool,
+    ) -> bool {
+        let Some(range) = range else { return false };
+        let Some(peer) = self.peers.get(peer_id) else { return false };
+
+        let violates = is_empty_response && peer.advertises(range);
+        if violates {
+            debug!(
+                target: "net",
+                ?peer_id,
+                ?range,
+                advertised=?peer.range_info.as_ref().map(|info| info.range()),
+                "Peer sent an empty response for blocks in its advertised range"
+            );
+        }
+        violates
+    }
+
     /// Called on a `GetBlockHeaders` response from a peer.
     ///
     /// This delegates the response and returns a [`BlockResponseOutcome`] to either queue in a
@@ -264,13 +344,23 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         res: RequestResult<Vec<N::BlockHeader>>,
     ) -> Option<BlockResponseOutcome> {
         let is_error = res.is_err();
-        let maybe_reputation_change = res.reputation_change_err();
+        let mut maybe_reputation_change = res.reputation_change_err();
 
         let resp = self.inflight_headers_requests.remove(&peer_id);
 
         let is_likely_bad_response =
             resp.as_ref().is_some_and(|r| res.is_likely_bad_headers_response(&r.request));
 
+        let is_empty_response = res.as_ref().is_ok_and(|headers| headers.is_empty());
+        if self.violates_block_range(
+            &peer_id,
+            resp.as_ref().and_then(|r| r.range.as_ref()),
+            is_empty_response,
+        ) {
+            maybe_reputation_change =
+                maybe_reputation_change.or(Some(ReputationChangeKind::BadMessage));
+        }
+
         if let Some(resp) = resp {
             // delegate the response
             let _ = resp.response.send(res.map(|h| (peer_id, h).into()));
@@ -300,10 +390,51 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         res: RequestResult<Vec<N::BlockBody>>,
     ) -> Option<BlockResponseOutcome> {
         let is_likely_bad_response = res.as_ref().map_or(true, |bodies| bodies.is_empty());
+        let is_empty_response = res.as_ref().is_ok_and(|bodies| bodies.is_empty());
 
-        if let Some(resp) = self.inflight_bodies_requests.remove(&peer_id) {
+        let resp = self.inflight_bodies_requests.remove(&peer_id);
+        let violates_range = self.violates_block_range(
+            &peer_id,
+            resp.as_ref().and_then(|r| r.range.as_ref()),
+            is_empty_response,
+        );
+        if let Some(resp) = resp {
             let _ = resp.response.send(res.map(|b| (peer_id, b).into()));
         }
+
+        self.on_response_finished(peer_id, is_likely_bad_response, violates_range)
+    }
+
+    /// Called on a `GetReceipts` response from a peer
+    pub(crate) fn on_receipts_response(
+        &mut self,
+        peer_id: PeerId,
+        res: RequestResult<Vec<Vec<N::Receipt>>>,
+    ) -> Option<BlockResponseOutcome> {
+        let is_likely_bad_response = res.as_ref().map_or(true, |receipts| receipts.is_empty());
+        let is_empty_response = res.as_ref().is_ok_and(|receipts| receipts.is_empty());
+
+        let resp = self.inflight_receipts_requests.remove(&peer_id);
+        let violates_range = self.violates_block_range(
+            &peer_id,
+            resp.as_ref().and_then(|r| r.range.as_ref()),
+            is_empty_response,
+        );
+        if let Some(resp) = resp {
+            let _ = resp.response.send(res.map(|r| (peer_id, r).into()));
+        }
+
+        self.on_response_finished(peer_id, is_likely_bad_response, violates_range)
+    }
+
+    /// Updates the peer's state after a bodies or receipts response, and returns a followup
+    /// request or the penalty for a response that violates the peer's advertised block range.
+    fn on_response_finished(
+        &mut self,
+        peer_id: PeerId,
+        is_likely_bad_response: bool,
+        violates_range: bool,
+    ) -> Option<BlockResponseOutcome> {
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             // update the peer's response state
             peer.last_response_likely_bad = is_likely_bad_response;
@@ -312,7 +443,9 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 return self.followup_request(peer_id)
             }
         }
-        None
+
+        violates_range
+            .then_some(BlockResponseOutcome::BadResponse(peer_id, ReputationChangeKind::BadMessage))
     }
 
     /// Returns a new [`FetchClient`] that can send requests to this type.
@@ -351,7 +484,6 @@ struct Peer {
     /// lowest timeout.
     last_response_likely_bad: bool,
     /// Tracks the range info for the peer.
-    #[allow(dead_code)]
     range_info: Option<BlockRangeInfo>,
 }
 
@@ -359,6 +491,23 @@ impl Peer {
     fn timeout(&self) -> u64 {
         self.timeout.load(Ordering::Relaxed)
     }
+
+    /// Returns `true` if the peer has the blocks of the range available.
+    ///
+    /// Peers without an eth/69 block range are assumed to have all blocks. Only the earliest
+    /// available block is checked, because the latest block is only advertised periodically and
+    /// lags behind the peer's head.
+    fn covers(&self, range: &RangeInclusive<u64>) -> bool {
+        self.range_info.as_ref().is_none_or(|info| info.earliest() <= *range.start())
+    }
+
+    /// Returns `true` if the range is within the eth/69 block range advertised by the peer, in
+    /// which case the peer must be able to serve it.
+    fn advertises(&self, range: &RangeInclusive<u64>) -> bool {
+        self.range_info
+            .as_ref()
+            .is_some_and(|info| info.earliest() <= *range.start() && *range.end() <= info.latest())
+    }
 }
 
 /// Tracks the state of an individual peer
@@ -370,6 +519,8 @@ enum PeerState {
     GetBlockHeaders,
     /// Peer is handling a `GetBlockBodies` request.
     GetBlockBodies,
+    /// Peer is handling a `GetReceipts` request.
+    GetReceipts,
     /// Peer session is about to close
     Closing,
 }
@@ -382,6 +533,11 @@ impl PeerState {
         matches!(self, Self::Idle)
     }
 
+    /// Returns true if the peer session is about to close.
+    const fn is_closing(&self) -> bool {
+        matches!(self, Self::Closing)
+    }
+
     /// Resets the state on a received response.
     ///
     /// If the state was already marked as `Closing` do nothing.
@@ -404,6 +560,8 @@ struct Request<Req, Resp> {
     // TODO: this can be attached to the response in error case
     request: Req,
     response: oneshot::Sender<Resp>,
+    /// The range of the requested blocks, if known.
+    range: Option<RangeInclusive<u64>>,
 }
 
 /// Requests that can be sent to the Syncer from a [`FetchClient`]
@@ -420,7 +578,13 @@ pub(crate) enum DownloadRequest<N: NetworkPrimitives> {
         request: Vec<B256>,
         response: oneshot::Sender<PeerRequestResult<Vec<N::BlockBody>>>,
         priority: Priority,
-        #[allow(dead_code)]
+        range_hint: Option<RangeInclusive<u64>>,
+    },
+    /// Download the requested receipts and send response through channel
+    GetReceipts {
+        request: Vec<B256>,
+        response: oneshot::Sender<PeerRequestResult<Vec<Vec<N::Receipt>>>>,
+        priority: Priority,
         range_hint: Option<RangeInclusive<u64>>,
     },
 }
@@ -433,14 +597,36 @@ impl<N: NetworkPrimitives> DownloadRequest<N> {
         match self {
             Self::GetBlockHeaders { .. } => PeerState::GetBlockHeaders,
             Self::GetBlockBodies { .. } => PeerState::GetBlockBodies,
+            Self::GetReceipts { .. } => PeerState::GetReceipts,
         }
     }
 
     /// Returns the requested priority of this request
     const fn get_priority(&self) -> &Priority {
         match self {
-            Self::GetBlockHeaders { priority, .. } | Self::GetBlockBodies { priority, .. } => {
-                priority
+            Self::GetBlockHeaders { priority, .. } |
+            Self::GetBlockBodies { priority, .. } |
+            Self::GetReceipts { priority, .. } => priority,
+        }
+    }
+
+    /// Returns the range of the requested blocks, if known.
+    ///
+    /// This is derived from the start and limit of headers requests by number, and from the range
+    /// hint of bodies and receipts requests.
+    fn block_range(&self) -> Option<RangeInclusive<u64>> {
+        match self {
+            Self::GetBlockHeaders { request, .. } => {
+                let BlockHashOrNumber::Number(start) = request.start else { return None };
+                let span = request.limit.saturating_sub(1);
+                if request.direction.is_falling() {
+                    Some(start.saturating_sub(span)..=start)
+                } else {
+                    Some(start..=start.saturating_add(span))
+                }
+            }
+            Self::GetBlockBodies { range_hint, .. } | Self::GetReceipts { range_hint, .. } => {
+                range_hint.clone()
             }
         }
     }
@@ -514,17 +700,17 @@ mod tests {
         fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(1)), None);
         fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(1)), None);
 
-        let first_peer = fetcher.next_best_peer().unwrap();
+        let first_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         // Pending disconnect for first_peer
         fetcher.on_pending_disconnect(&first_peer);
         // first_peer now isn't idle, so we should get other peer
-        let second_peer = fetcher.next_best_peer().unwrap();
+        let second_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         assert_ne!(first_peer, second_peer);
         // without idle peers, returns None
         fetcher.on_pending_disconnect(&second_peer);
-        assert_eq!(fetcher.next_best_peer(), None);
+        assert_eq!(fetcher.next_best_peer(None), None);
     }
 
     #[tokio::test]
@@ -544,13 +730,13 @@ mod tests {
         fetcher.new_active_peer(peer3, B256::random(), 3, Arc::new(AtomicU64::new(50)), None);
 
         // Must always get peer1 (lowest timeout)
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
         // peer2's timeout changes below peer1's
         peer2_timeout.store(10, Ordering::Relaxed);
         // Then we get peer 2 always (now lowest)
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
     }
 
     #[tokio::test]
@@ -600,6 +786,7 @@ mod tests {
                     direction: Default::default(),
                 },
                 response: tx,
+                range: None,
             };
             let header = Header { number: 0, ..Default::default() };
             (req, header)
@@ -639,4 +826,134 @@ mod tests {
 
         assert!(fetcher.peers[&peer_id].state.is_idle());
     }
+
+    #[tokio::test]
+    async fn test_route_by_block_range() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let expired = B512::random();
+        let full = B512::random();
+        let legacy = B512::random();
+
+        // the peer with expired history has the lowest timeout
+        fetcher.new_active_peer(
+            expired,
+            B256::random(),
+            200,
+            Arc::new(AtomicU64::new(10)),
+            Some(BlockRangeInfo::new(100, 200, B256::random())),
+        );
+        fetcher.new_active_peer(
+            full,
+            B256::random(),
+            200,
+            Arc::new(AtomicU64::new(20)),
+            Some(BlockRangeInfo::new(0, 200, B256::random())),
+        );
+        fetcher.new_active_peer(legacy, B256::random(), 200, Arc::new(AtomicU64::new(30)), None);
+
+        assert_eq!(fetcher.next_best_peer(None), Some(expired));
+        assert_eq!(fetcher.next_best_peer(Some(&(150..=160))), Some(expired));
+        assert_eq!(fetcher.next_best_peer(Some(&(10..=20))), Some(full));
+
+        // peers without an advertised range are assumed to have all blocks
+        fetcher.on_pending_disconnect(&full);
+        assert_eq!(fetcher.next_best_peer(Some(&(10..=20))), Some(legacy));
+
+        // falls back to any peer if no peer has the blocks
+        fetcher.on_pending_disconnect(&legacy);
+        assert_eq!(fetcher.next_best_peer(Some(&(10..=20))), Some(expired));
+    }
+
+    #[tokio::test]
+    async fn test_poll_routable_request() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let expired = B512::random();
+        let full = B512::random();
+
+        fetcher.new_active_peer(
+            expired,
+            B256::random(),
+            200,
+            Arc::new(AtomicU64::new(10)),
+            Some(BlockRangeInfo::new(100, 200, B256::random())),
+        );
+        fetcher.new_active_peer(
+            full,
+            B256::random(),
+            200,
+            Arc::new(AtomicU64::new(20)),
+            Some(BlockRangeInfo::new(0, 200, B256::random())),
+        );
+        fetcher.peers.get_mut(&full).unwrap().state = PeerState::GetBlockBodies;
+
+        let old_hash = B256::random();
+        let new_hash = B256::random();
+        for (hash, range) in [(old_hash, 10..=10), (new_hash, 150..=150)] {
+            let (tx, _rx) = oneshot::channel();
+            fetcher.queued_requests.push_back(DownloadRequest::GetBlockBodies {
+                request: vec![hash],
+                response: tx,
+                priority: Priority::default(),
+                range_hint: Some(range),
+            });
+        }
+
+        // the request for old blocks waits for the busy peer, and doesn't hold back the other one
+        let PollAction::Ready(FetchAction::BlockRequest { peer_id, request }) =
+            fetcher.poll_action()
+        else {
+            unreachable!()
+        };
+        assert_eq!(peer_id, expired);
+        assert_eq!(request, BlockRequest::GetBlockBodies(GetBlockBodies(vec![new_hash])));
+        assert!(matches!(fetcher.poll_action(), PollAction::NoPeersAvailable));
+
+        // the old blocks are requested as followup from the peer that has them
+        let outcome = fetcher.on_block_bodies_response(full, Ok(vec![Default::default()]));
+        assert_eq!(
+            outcome,
+            Some(BlockResponseOutcome::Request(
+                full,
+                BlockRequest::GetBlockBodies(GetBlockBodies(vec![old_hash]))
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_block_range_violation() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let peer_id = B512::random();
+
+        fetcher.new_active_peer(
+            peer_id,
+            B256::random(),
+            100,
+            Default::default(),
+            Some(BlockRangeInfo::new(0, 100, B256::random())),
+        );
+
+        let mut request_bodies = |range: RangeInclusive<u64>| {
+            let (tx, _rx) = oneshot::channel();
+            fetcher.inflight_bodies_requests.insert(
+                peer_id,
+                Request { request: vec![B256::random()], response: tx, range: Some(range) },
+            );
+            fetcher.on_block_bodies_response(peer_id, Ok(vec![]))
+        };
+
+        // empty response for advertised blocks is penalized
+        assert_eq!(
+            request_bodies(10..=10),
+            Some(BlockResponseOutcome::BadResponse(peer_id, ReputationChangeKind::BadMessage))
+        );
+        // blocks after the advertised latest block may not be available yet
+        assert_eq!(request_bodies(150..=150), None);
+        assert!(fetcher.peers[&peer_id].last_response_likely_bad);
+    }
 }
@@ -546,6 +546,16 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     interval
                 });
 
+                // Track the block range advertised by the peer in its status and `BlockRangeUpdate`
+                // messages, starting with ETH69
+                let range_info = (version >= EthVersion::Eth69).then(|| {
+                    BlockRangeInfo::new(
+                        status.earliest_block.unwrap_or_default(),
+                        status.latest_block.unwrap_or_default(),
+                        status.blockhash,
+                    )
+                });
+
                 let session = ActiveSession {
                     next_id: 0,
                     remote_peer_id: peer_id,
@@ -568,7 +578,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     internal_request_timeout: Arc::clone(&timeout),
                     protocol_breach_request_timeout: self.protocol_breach_request_timeout,
                     terminate_message: None,
-                    range_info: None,
+                    range_info: range_info.clone(),
                     local_range_info: self.local_range_info.clone(),
                     range_update_interval,
                 };
@@ -607,7 +617,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     messages,
                     direction,
                     timeout,
-                    range_info: None,
+                    range_info,
                 })
             }
             PendingSessionEvent::Disconnected { remote_addr, session_id, direction, error } => {
@@ -386,6 +386,12 @@ impl<N: NetworkPrimitives> NetworkState<N> {
                     let response = PeerResponse::BlockBodies { response: rx };
                     (request, response)
                 }
+                BlockRequest::GetReceipts(request) => {
+                    let (response, rx) = oneshot::channel();
+                    let request = PeerRequest::GetReceipts { request, response };
+                    let response = PeerResponse::Receipts { response: rx };
+                    (request, response)
+                }
             };
             let _ = peer.request_tx.to_session_tx.try_send(request);
             peer.pending_response = Some(response);
@@ -417,6 +423,18 @@ impl<N: NetworkPrimitives> NetworkState<N> {
             PeerResponseResult::BlockBodies(res) => {
                 self.state_fetcher.on_block_bodies_response(peer, res)
             }
+            PeerResponseResult::Receipts(res) => {
+                let res = res.map(|receipts| {
+                    receipts
+                        .into_iter()
+                        .map(|receipts| receipts.into_iter().map(|r| r.receipt).collect())
+                        .collect()
+                });
+                self.state_fetcher.on_receipts_response(peer, res)
+            }
+            PeerResponseResult::Receipts69(res) => {
+                self.state_fetcher.on_receipts_response(peer, res)
+            }
             _ => None,
         };
 
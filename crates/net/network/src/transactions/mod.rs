@@ -1427,7 +1427,7 @@ where
             trace!(target: "net::tx::propagation", new_txs_len=?new_txs.len(), "Importing new transactions");
             let import = Box::pin(async move {
                 let added = new_txs.len();
-                let res = pool.add_external_transactions(new_txs).await;
+                let res = pool.add_external_transactions_from_peer(peer_id, new_txs).await;
 
                 // update metrics
                 metric_pending_pool_imports.decrement(added as f64);
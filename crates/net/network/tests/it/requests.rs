@@ -2,8 +2,10 @@
 //! Tests for eth related requests
 
 use alloy_consensus::Header;
+use alloy_primitives::B256;
+use futures::future::join_all;
 use rand::Rng;
-use reth_eth_wire::{EthVersion, HeadersDirection};
+use reth_eth_wire::{BlockRangeUpdate, EthVersion, HeadersDirection};
 use reth_ethereum_primitives::Block;
 use reth_network::{
     test_utils::{NetworkEventStream, PeerConfig, Testnet},
@@ -13,6 +15,7 @@ use reth_network_api::{NetworkInfo, Peers};
 use reth_network_p2p::{
     bodies::client::BodiesClient,
     headers::client::{HeadersClient, HeadersRequest},
+    sync::NetworkSyncUpdater,
 };
 use reth_provider::test_utils::MockEthProvider;
 use reth_transaction_pool::test_utils::{TestPool, TransactionGenerator};
@@ -355,3 +358,75 @@ async fn test_eth69_get_receipts() {
         assert_eq!(receipts_response.0[0][1].receipt.cumulative_gas_used, 42000);
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_eth69_route_by_block_range() {
+    reth_tracing::init_test_tracing();
+    let mut rng = rand::rng();
+    let mock_provider = Arc::new(MockEthProvider::default());
+
+    let mut net: Testnet<Arc<MockEthProvider>, TestPool> = Testnet::default();
+
+    // Create peers with ETH69 protocol
+    for _ in 0..3 {
+        let peer =
+            PeerConfig::with_protocols(mock_provider.clone(), Some(EthVersion::Eth69.into()));
+        net.add_peer_with_config(peer).await.unwrap();
+    }
+
+    // install request handlers
+    net.for_each_mut(|peer| peer.install_request_handler());
+
+    let handle0 = net.peers()[0].handle();
+    let mut events0 = NetworkEventStream::new(handle0.event_listener());
+
+    // peer 1 expired the history before block 100, peer 2 has all blocks
+    let expired = net.peers()[1].handle();
+    expired.update_block_range(BlockRangeUpdate {
+        earliest: 100,
+        latest: 200,
+        latest_hash: B256::ZERO,
+    });
+    let full = net.peers()[2].handle();
+    full.update_block_range(BlockRangeUpdate { earliest: 0, latest: 200, latest_hash: B256::ZERO });
+
+    let _handle = net.spawn();
+
+    let fetch0 = handle0.fetch_client().await.unwrap();
+
+    handle0.add_peer(*expired.peer_id(), expired.local_addr());
+    handle0.add_peer(*full.peer_id(), full.local_addr());
+    events0.next_session_established().await.unwrap();
+    events0.next_session_established().await.unwrap();
+
+    for number in (10..20).chain(150..160) {
+        let header = Header { number, ..Default::default() };
+        mock_provider.add_header(rng.random(), header);
+    }
+
+    // requests for old blocks only go to the peer that has them, even if it's busy
+    let requests = (10..20u64).map(|number| {
+        fetch0.get_headers(HeadersRequest {
+            start: number.into(),
+            limit: 1,
+            direction: HeadersDirection::Rising,
+        })
+    });
+    for res in join_all(requests).await {
+        let res = res.unwrap();
+        assert_eq!(res.peer_id(), *full.peer_id());
+        assert_eq!(res.1.len(), 1);
+    }
+
+    // requests for recent blocks go to any peer
+    let requests = (150..160u64).map(|number| {
+        fetch0.get_headers(HeadersRequest {
+            start: number.into(),
+            limit: 1,
+            direction: HeadersDirection::Rising,
+        })
+    });
+    for res in join_all(requests).await {
+        assert_eq!(res.unwrap().1.len(), 1);
+    }
+}
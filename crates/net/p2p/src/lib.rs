@@ -36,6 +36,9 @@ pub mod headers;
 /// interacting with the network implementation
 pub mod error;
 
+/// Traits for implementing P2P receipt clients.
+pub mod receipts;
+
 /// Priority enum for `BlockHeader` and `BlockBody` requests
 pub mod priority;
 
@@ -51,6 +54,7 @@ pub mod test_utils;
 
 pub use bodies::client::BodiesClient;
 pub use headers::client::HeadersClient;
+pub use receipts::client::ReceiptsClient;
 use reth_primitives_traits::Block;
 
 /// Helper trait that unifies network behaviour needed for fetching entire blocks.
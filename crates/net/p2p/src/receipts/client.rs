@@ -0,0 +1,42 @@
+use std::{ops::RangeInclusive, pin::Pin};
+
+use crate::{download::DownloadClient, error::PeerRequestResult, priority::Priority};
+use alloy_consensus::TxReceipt;
+use alloy_primitives::B256;
+use futures::Future;
+
+/// The receipts future type
+pub type ReceiptsFut<R = reth_ethereum_primitives::Receipt> =
+    Pin<Box<dyn Future<Output = PeerRequestResult<Vec<Vec<R>>>> + Send + Sync>>;
+
+/// A client capable of downloading the receipts of blocks.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait ReceiptsClient: DownloadClient {
+    /// The receipt type this client fetches.
+    type Receipt: TxReceipt;
+    /// The output of the request future for querying receipts.
+    type Output: Future<Output = PeerRequestResult<Vec<Vec<Self::Receipt>>>> + Sync + Send + Unpin;
+
+    /// Fetches the receipts of the requested blocks.
+    fn get_receipts(&self, hashes: Vec<B256>) -> Self::Output {
+        self.get_receipts_with_priority(hashes, Priority::Normal)
+    }
+
+    /// Fetches the receipts of the requested blocks with priority
+    fn get_receipts_with_priority(&self, hashes: Vec<B256>, priority: Priority) -> Self::Output {
+        self.get_receipts_with_priority_and_range_hint(hashes, priority, None)
+    }
+
+    /// Fetches the receipts of the requested blocks with priority and a range hint for the
+    /// requested blocks.
+    ///
+    /// The range hint is not required, but can be used to optimize the routing of the request if
+    /// the hashes are continuous or close together and the range hint is `[earliest, latest]` for
+    /// the requested blocks.
+    fn get_receipts_with_priority_and_range_hint(
+        &self,
+        hashes: Vec<B256>,
+        priority: Priority,
+        range_hint: Option<RangeInclusive<u64>>,
+    ) -> Self::Output;
+}
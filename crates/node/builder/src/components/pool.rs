@@ -4,10 +4,14 @@ use alloy_primitives::Address;
 use reth_chain_state::CanonStateSubscriptions;
 use reth_node_api::TxTy;
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, CoinbaseTipOrdering, PoolConfig, PoolTransaction, SubPoolLimit,
-    TransactionPool, TransactionValidationTaskExecutor, TransactionValidator,
+    blobstore::DiskFileBlobStore,
+    conditional::MaybeConditionalTransaction,
+    inclusion::{InclusionPolicy, InclusionPolicyHandle},
+    CoinbaseTipOrdering, PoolConfig, PoolTransaction, SubPoolLimit, TransactionPool,
+    TransactionValidationTaskExecutor, TransactionValidator,
 };
 use std::{collections::HashSet, future::Future};
+use tracing::info;
 
 use crate::{BuilderContext, FullNodeTypes};
 
@@ -129,8 +133,9 @@ impl<'a, Node: FullNodeTypes, V> TxPoolBuilder<'a, Node, V> {
 impl<'a, Node: FullNodeTypes, V> TxPoolBuilder<'a, Node, TransactionValidationTaskExecutor<V>>
 where
     V: TransactionValidator + Clone + 'static,
-    V::Transaction:
-        PoolTransaction<Consensus = TxTy<Node::Types>> + reth_transaction_pool::EthPoolTransaction,
+    V::Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>
+        + reth_transaction_pool::EthPoolTransaction
+        + MaybeConditionalTransaction,
 {
     /// Build the transaction pool and spawn its maintenance tasks.
     /// This method creates the blob store, builds the pool, and spawns maintenance tasks.
@@ -189,6 +194,35 @@ pub fn create_blob_store_with_cache<Node: FullNodeTypes>(
     Ok(reth_transaction_pool::blobstore::DiskFileBlobStore::open(data_dir.blobstore(), config)?)
 }
 
+/// Loads the inclusion policy configured via `--txpool.inclusion-policy`, if any.
+pub fn load_inclusion_policy<Node: FullNodeTypes>(
+    ctx: &BuilderContext<Node>,
+) -> eyre::Result<Option<InclusionPolicyHandle>> {
+    let Some(path) = ctx.config().txpool.inclusion_policy_path.clone() else { return Ok(None) };
+    let handle = InclusionPolicyHandle::from_file(path)?;
+    info!(target: "reth::cli", path=?handle.path(), "Loaded transaction inclusion policy");
+    Ok(Some(handle))
+}
+
+/// Spawns a task that reloads the inclusion policy whenever its file changes.
+///
+/// `on_reload` is invoked with the initial policy and with every updated policy.
+pub fn spawn_inclusion_policy_reload_task<Node, F>(
+    ctx: &BuilderContext<Node>,
+    handle: InclusionPolicyHandle,
+    on_reload: F,
+) where
+    Node: FullNodeTypes,
+    F: FnMut(&InclusionPolicy) + Send + 'static,
+{
+    let reload_interval = ctx.config().txpool.inclusion_policy_reload_interval;
+    ctx.task_executor().spawn(reth_transaction_pool::inclusion::inclusion_policy_reload_task(
+        handle,
+        reload_interval,
+        on_reload,
+    ));
+}
+
 /// Spawn local transaction backup task if enabled.
 fn spawn_local_backup_task<Node, Pool>(ctx: &BuilderContext<Node>, pool: Pool) -> eyre::Result<()>
 where
@@ -232,7 +266,7 @@ fn spawn_pool_maintenance_task<Node, Pool>(
 where
     Node: FullNodeTypes,
     Pool: reth_transaction_pool::TransactionPoolExt + Clone + 'static,
-    Pool::Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>,
+    Pool::Transaction: PoolTransaction<Consensus = TxTy<Node::Types>> + MaybeConditionalTransaction,
 {
     let chain_events = ctx.provider().canonical_state_stream();
     let client = ctx.provider().clone();
@@ -264,7 +298,7 @@ fn spawn_maintenance_tasks<Node, Pool>(
 where
     Node: FullNodeTypes,
     Pool: reth_transaction_pool::TransactionPoolExt + Clone + 'static,
-    Pool::Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>,
+    Pool::Transaction: PoolTransaction<Consensus = TxTy<Node::Types>> + MaybeConditionalTransaction,
 {
     spawn_local_backup_task(ctx, pool.clone())?;
     spawn_pool_maintenance_task(ctx, pool, pool_config)?;
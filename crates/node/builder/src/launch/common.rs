@@ -920,8 +920,10 @@ where
             .block_number;
 
         // Skip the first stage as we've already retrieved it and comparing all other checkpoints
-        // against it.
-        for stage_id in StageId::ALL.iter().skip(1) {
+        // against it. The optional log index is only checked if it's enabled.
+        let index_logs =
+            self.toml_config().stages.index_logs.enabled.then_some(&StageId::IndexLogs);
+        for stage_id in StageId::ALL.iter().skip(1).chain(index_logs) {
             let stage_checkpoint = self
                 .blockchain_db()
                 .get_stage_checkpoint(*stage_id)?
@@ -1220,6 +1222,8 @@ mod tests {
                     bodies_distance: None,
                     receipts_log_filter: None,
                     bodies_before: None,
+                    trie_changesets_distance: None,
+                    trie_changesets_before: None,
                 },
                 ..NodeConfig::test()
             };
@@ -5,8 +5,8 @@ use futures::future;
 use reth_chain_state::ForkChoiceSubscriptions;
 use reth_chainspec::EthChainSpec;
 use reth_exex::{
-    ExExContext, ExExHandle, ExExManager, ExExManagerHandle, ExExNotificationSource, Wal,
-    DEFAULT_EXEX_MANAGER_CAPACITY,
+    BackfillCheckpoint, ExExContext, ExExHandle, ExExManager, ExExManagerHandle,
+    ExExNotificationSource, Wal, DEFAULT_EXEX_MANAGER_CAPACITY,
 };
 use reth_node_api::{FullNodeComponents, NodeTypes, PrimitivesTy};
 use reth_provider::CanonStateSubscriptions;
@@ -50,15 +50,14 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
             return Ok(None)
         }
 
+        let datadir = config_container
+            .config
+            .datadir
+            .clone()
+            .resolve_datadir(config_container.config.chain.chain());
+
         info!(target: "reth::cli", "Loading ExEx Write-Ahead Log...");
-        let exex_wal = Wal::new(
-            config_container
-                .config
-                .datadir
-                .clone()
-                .resolve_datadir(config_container.config.chain.chain())
-                .exex_wal(),
-        )?;
+        let exex_wal = Wal::new(datadir.exex_wal())?;
 
         let mut exex_handles = Vec::with_capacity(extensions.len());
         let mut exexes = Vec::with_capacity(extensions.len());
@@ -72,6 +71,11 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
                 components.evm_config().clone(),
                 exex_wal.handle(),
             );
+            // resume the backfill of an exex with a head from where it was interrupted
+            let notifications = notifications.with_backfill_checkpoint(BackfillCheckpoint::new(
+                datadir.exex_backfill(),
+                id.clone(),
+            )?);
             exex_handles.push(handle);
 
             // create the launch context for the exex
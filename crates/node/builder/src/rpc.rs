@@ -659,7 +659,17 @@ where
             engine_handle,
         } = setup_ctx;
 
-        let server_config = config.rpc.rpc_server_config().set_rpc_middleware(rpc_middleware);
+        let server_config = config
+            .rpc
+            .rpc_server_config()
+            .with_graphql(config.rpc.graphql_config().map(|config| registry.graphql_layer(config)))
+            .with_response_cache(
+                config
+                    .rpc
+                    .response_cache_max_size_bytes()
+                    .map(|size| registry.response_cache(size)),
+            )
+            .set_rpc_middleware(rpc_middleware);
         let rpc_server_handle = Self::launch_rpc_server_internal(server_config, &modules).await?;
 
         let handles =
@@ -725,7 +735,17 @@ where
             engine_handle,
         } = setup_ctx;
 
-        let server_config = config.rpc.rpc_server_config().set_rpc_middleware(rpc_middleware);
+        let server_config = config
+            .rpc
+            .rpc_server_config()
+            .with_graphql(config.rpc.graphql_config().map(|config| registry.graphql_layer(config)))
+            .with_response_cache(
+                config
+                    .rpc
+                    .response_cache_max_size_bytes()
+                    .map(|size| registry.response_cache(size)),
+            )
+            .set_rpc_middleware(rpc_middleware);
 
         let (rpc, auth) = if disable_auth {
             // Only launch the RPC server, use a noop auth handle
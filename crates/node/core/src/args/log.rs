@@ -4,10 +4,11 @@ use crate::dirs::{LogsDir, PlatformPath};
 use clap::{ArgAction, Args, ValueEnum};
 use reth_tracing::{
     tracing_subscriber::filter::Directive, FileInfo, FileWorkerGuard, LayerInfo, Layers, LogFormat,
-    RethTracer, Tracer,
+    OtlpConfig, RethTracer, Tracer,
 };
 use std::{fmt, fmt::Display};
 use tracing::{level_filters::LevelFilter, Level};
+use url::Url;
 /// Constant to convert megabytes to bytes
 const MB_TO_BYTES: u64 = 1024 * 1024;
 
@@ -66,6 +67,56 @@ pub struct LogArgs {
         default_value_t = ColorMode::Always
     )]
     pub color: ColorMode,
+
+    /// Export spans to an `OpenTelemetry` collector at the given OTLP HTTP endpoint.
+    ///
+    /// If no value is provided, defaults to `http://localhost:4318/v1/traces`.
+    ///
+    /// Example: --tracing-otlp=http://collector:4318/v1/traces
+    #[arg(
+        long = "tracing-otlp",
+        value_name = "URL",
+        global = true,
+        num_args = 0..=1,
+        default_missing_value = "http://localhost:4318/v1/traces",
+        require_equals = true,
+        help_heading = "Tracing"
+    )]
+    pub tracing_otlp: Option<Url>,
+
+    /// The service name reported with the exported spans.
+    #[arg(
+        long = "tracing-otlp.service-name",
+        value_name = "NAME",
+        global = true,
+        default_value = "reth",
+        help_heading = "Tracing"
+    )]
+    pub tracing_otlp_service_name: String,
+
+    /// The ratio of traces to export, between 0.0 and 1.0.
+    ///
+    /// Traces of RPC requests that carry a trace context follow the sampling decision of the
+    /// caller.
+    #[arg(
+        long = "tracing-otlp.sample-ratio",
+        value_name = "RATIO",
+        global = true,
+        default_value_t = 1.0,
+        help_heading = "Tracing"
+    )]
+    pub tracing_otlp_sample_ratio: f64,
+
+    /// The filter to use for the exported spans.
+    #[arg(
+        long = "tracing-otlp.filter",
+        value_name = "FILTER",
+        global = true,
+        default_value = "debug",
+        help_heading = "Tracing"
+    )]
+    pub tracing_otlp_filter: String,
+
     /// The verbosity settings for the tracer.
     #[command(flatten)]
     pub verbosity: Verbosity,
@@ -117,6 +168,13 @@ impl LogArgs {
             tracer = tracer.with_journald(self.journald_filter.clone());
         }
 
+        if let Some(endpoint) = &self.tracing_otlp {
+            let config = OtlpConfig::new(endpoint.clone())
+                .with_service_name(self.tracing_otlp_service_name.clone())
+                .with_sample_ratio(self.tracing_otlp_sample_ratio);
+            tracer = tracer.with_otlp(config, self.tracing_otlp_filter.clone());
+        }
+
         if self.log_file_max_files > 0 {
             let info = self.file_info();
             let file = self.layer_info(self.log_file_format, self.log_file_filter.clone(), false);
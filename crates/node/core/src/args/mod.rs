@@ -38,7 +38,7 @@ pub use gas_price_oracle::GasPriceOracleArgs;
 
 /// TxPoolArgs for configuring the transaction pool
 mod txpool;
-pub use txpool::TxPoolArgs;
+pub use txpool::{TxPoolArgs, TxPoolEvictionPolicy};
 
 /// DevArgs for configuring the dev testnet
 mod dev;
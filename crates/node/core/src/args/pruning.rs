@@ -103,6 +103,24 @@ pub struct PruningArgs {
     /// pruned.
     #[arg(long = "prune.bodies.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["bodies_distance", "bodies_pre_merge"])]
     pub bodies_before: Option<BlockNumber>,
+
+    // Trie Changesets
+    /// Keep the trie changesets of the last N + 1 blocks, to serve historical proofs for them.
+    /// Trie changesets are not written if neither this nor `--prune.triechangesets.before` is set.
+    #[arg(
+        long = "prune.triechangesets.distance",
+        value_name = "BLOCKS",
+        conflicts_with = "trie_changesets_before"
+    )]
+    pub trie_changesets_distance: Option<u64>,
+    /// Keep the trie changesets from the specified block number onwards, to serve historical
+    /// proofs for them. The specified block number is not pruned.
+    #[arg(
+        long = "prune.triechangesets.before",
+        value_name = "BLOCK_NUMBER",
+        conflicts_with = "trie_changesets_distance"
+    )]
+    pub trie_changesets_before: Option<BlockNumber>,
 }
 
 impl PruningArgs {
@@ -126,6 +144,7 @@ impl PruningArgs {
                     storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     // TODO: set default to pre-merge block if available
                     bodies_history: None,
+                    trie_changesets: None,
                     receipts_log_filter: Default::default(),
                 },
             }
@@ -153,6 +172,9 @@ impl PruningArgs {
         if let Some(mode) = self.storage_history_prune_mode() {
             config.segments.storage_history = Some(mode);
         }
+        if let Some(mode) = self.trie_changesets_prune_mode() {
+            config.segments.trie_changesets = Some(mode);
+        }
         if let Some(receipt_logs) =
             self.receipts_log_filter.as_ref().filter(|c| !c.is_empty()).cloned()
         {
@@ -246,6 +268,16 @@ impl PruningArgs {
             None
         }
     }
+
+    const fn trie_changesets_prune_mode(&self) -> Option<PruneMode> {
+        if let Some(distance) = self.trie_changesets_distance {
+            Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.trie_changesets_before {
+            Some(PruneMode::Before(block_number))
+        } else {
+            None
+        }
+    }
 }
 
 /// Parses `,` separated pruning info into [`ReceiptsLogPruneConfig`].
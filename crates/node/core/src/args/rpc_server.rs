@@ -15,7 +15,8 @@ use clap::{
 };
 use rand::Rng;
 use reth_cli_util::parse_ether_value;
-use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+use reth_rpc_server_types::{constants, RethRpcModule, RpcAccessConfig, RpcModuleSelection};
+use url::Url;
 
 use crate::args::{
     types::{MaxU32, ZeroAsNoneU64},
@@ -66,6 +67,37 @@ pub struct RpcServerArgs {
     #[arg(long = "http.corsdomain")]
     pub http_corsdomain: Option<String>,
 
+    /// Path to the PEM encoded TLS certificate chain of the HTTP server.
+    ///
+    /// Enables TLS for the HTTP server. The certificate is reloaded when the file changes.
+    #[arg(long = "http.tls-cert", value_name = "PATH", requires = "http_tls_key")]
+    pub http_tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM encoded private key of the HTTP server's TLS certificate.
+    #[arg(long = "http.tls-key", value_name = "PATH", requires = "http_tls_cert")]
+    pub http_tls_key: Option<PathBuf>,
+
+    /// Path to the PEM encoded CA certificates that TLS client certificates must be signed by.
+    ///
+    /// Enables mutual TLS for the HTTP server, clients without a valid certificate are
+    /// rejected.
+    #[arg(long = "http.tls-client-ca", value_name = "PATH", requires = "http_tls_cert")]
+    pub http_tls_client_ca: Option<PathBuf>,
+
+    /// Serve the EIP-1767 GraphQL API on the HTTP server, under the `/graphql` path.
+    #[arg(long)]
+    pub graphql: bool,
+
+    /// Maximum nesting depth of GraphQL queries.
+    #[arg(long = "graphql.max-depth", value_name = "DEPTH", default_value_t = constants::DEFAULT_GRAPHQL_MAX_DEPTH)]
+    pub graphql_max_depth: usize,
+
+    /// Maximum complexity of GraphQL queries.
+    ///
+    /// Every field costs one, and lists cost 100 times their items.
+    #[arg(long = "graphql.max-complexity", value_name = "COMPLEXITY", default_value_t = constants::DEFAULT_GRAPHQL_MAX_COMPLEXITY)]
+    pub graphql_max_complexity: usize,
+
     /// Enable the WS-RPC server
     #[arg(long)]
     pub ws: bool,
@@ -86,6 +118,23 @@ pub struct RpcServerArgs {
     #[arg(long = "ws.api", value_parser = RpcModuleSelectionValueParser::default())]
     pub ws_api: Option<RpcModuleSelection>,
 
+    /// Path to the PEM encoded TLS certificate chain of the WS server.
+    ///
+    /// Enables TLS for the WS server. The certificate is reloaded when the file changes.
+    #[arg(long = "ws.tls-cert", value_name = "PATH", requires = "ws_tls_key")]
+    pub ws_tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM encoded private key of the WS server's TLS certificate.
+    #[arg(long = "ws.tls-key", value_name = "PATH", requires = "ws_tls_cert")]
+    pub ws_tls_key: Option<PathBuf>,
+
+    /// Path to the PEM encoded CA certificates that TLS client certificates must be signed by.
+    ///
+    /// Enables mutual TLS for the WS server, clients without a valid certificate are
+    /// rejected.
+    #[arg(long = "ws.tls-client-ca", value_name = "PATH", requires = "ws_tls_cert")]
+    pub ws_tls_client_ca: Option<PathBuf>,
+
     /// Disable the IPC-RPC server
     #[arg(long)]
     pub ipcdisable: bool,
@@ -117,6 +166,23 @@ pub struct RpcServerArgs {
     #[arg(long = "authrpc.jwtsecret", value_name = "PATH", global = true, required = false)]
     pub auth_jwtsecret: Option<PathBuf>,
 
+    /// Path to the PEM encoded TLS certificate chain of the auth server.
+    ///
+    /// Enables TLS for the auth server. The certificate is reloaded when the file changes.
+    #[arg(long = "authrpc.tls-cert", value_name = "PATH", requires = "auth_tls_key")]
+    pub auth_tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM encoded private key of the auth server's TLS certificate.
+    #[arg(long = "authrpc.tls-key", value_name = "PATH", requires = "auth_tls_cert")]
+    pub auth_tls_key: Option<PathBuf>,
+
+    /// Path to the PEM encoded CA certificates that TLS client certificates must be signed by.
+    ///
+    /// Enables mutual TLS for the auth server, clients without a valid certificate are
+    /// rejected.
+    #[arg(long = "authrpc.tls-client-ca", value_name = "PATH", requires = "auth_tls_cert")]
+    pub auth_tls_client_ca: Option<PathBuf>,
+
     /// Enable auth engine API over IPC
     #[arg(long)]
     pub auth_ipc: bool,
@@ -140,6 +206,14 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.jwtsecret", value_name = "HEX", global = true, required = false)]
     pub rpc_jwtsecret: Option<JwtSecret>,
 
+    /// Path to a JSON file that configures access control for the HTTP and WS RPC servers.
+    ///
+    /// Callers are identified by API key, sent in a header or as the URL path, or by IP, and
+    /// belong to tenants that are restricted to a set of RPC modules and methods and to a compute
+    /// unit quota.
+    #[arg(long = "rpc.access-config", value_name = "PATH", value_parser = parse_rpc_access_config)]
+    pub rpc_access_config: Option<RpcAccessConfig>,
+
     /// Set the maximum RPC request payload size for both HTTP and WS in megabytes.
     #[arg(long = "rpc.max-request-size", alias = "rpc-max-request-size", default_value_t = RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into())]
     pub rpc_max_request_size: MaxU32,
@@ -208,6 +282,8 @@ pub struct RpcServerArgs {
     /// The maximum proof window for historical proof generation.
     /// This value allows for generating historical proofs up to
     /// configured number of blocks from current tip (up to `tip - window`).
+    /// Blocks covered by the trie changesets, which are kept with `--prune.triechangesets.*`, are
+    /// not limited by the window.
     #[arg(
         long = "rpc.eth-proof-window",
         default_value_t = constants::DEFAULT_ETH_PROOF_WINDOW,
@@ -219,6 +295,21 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
 
+    /// HTTP endpoint of an archive node that requests for pruned history are forwarded to.
+    ///
+    /// Block-scoped requests, such as `eth_getBlockByNumber`, `eth_getBalance` or
+    /// `eth_getLogs`, whose block falls into a range pruned by the configured prune modes are
+    /// served by the archive node instead.
+    #[arg(long = "rpc.archive-upstream", value_name = "URL")]
+    pub rpc_archive_upstream: Option<Url>,
+
+    /// Enable `eth_sendRawTransactionConditional` to submit transactions with a conditional on
+    /// the block number, timestamp and storage of known accounts.
+    ///
+    /// Only supported by Ethereum nodes, OP nodes use `--rollup.enable-tx-conditional` instead.
+    #[arg(long = "rpc.enable-tx-conditional")]
+    pub rpc_enable_tx_conditional: bool,
+
     /// Path to file containing disallowed addresses, json-encoded list of strings. Block
     /// validation API will reject blocks containing transactions from these addresses.
     #[arg(long = "builder.disallow", value_name = "PATH", value_parser = reth_cli_util::parsers::read_json_from_file::<HashSet<Address>>)]
@@ -336,21 +427,34 @@ impl Default for RpcServerArgs {
             http_disable_compression: false,
             http_api: None,
             http_corsdomain: None,
+            http_tls_cert: None,
+            http_tls_key: None,
+            http_tls_client_ca: None,
+            graphql: false,
+            graphql_max_depth: constants::DEFAULT_GRAPHQL_MAX_DEPTH,
+            graphql_max_complexity: constants::DEFAULT_GRAPHQL_MAX_COMPLEXITY,
             ws: false,
             ws_addr: Ipv4Addr::LOCALHOST.into(),
             ws_port: constants::DEFAULT_WS_RPC_PORT,
             ws_allowed_origins: None,
             ws_api: None,
+            ws_tls_cert: None,
+            ws_tls_key: None,
+            ws_tls_client_ca: None,
             ipcdisable: false,
             ipcpath: constants::DEFAULT_IPC_ENDPOINT.to_string(),
             ipc_socket_permissions: None,
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
+            auth_tls_cert: None,
+            auth_tls_key: None,
+            auth_tls_client_ca: None,
             auth_ipc: false,
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
             disable_auth_server: false,
             rpc_jwtsecret: None,
+            rpc_access_config: None,
             rpc_max_request_size: RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into(),
             rpc_max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into(),
             rpc_max_subscriptions_per_connection: RPC_DEFAULT_MAX_SUBS_PER_CONN.into(),
@@ -366,11 +470,20 @@ impl Default for RpcServerArgs {
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
+            rpc_archive_upstream: None,
+            rpc_enable_tx_conditional: false,
             builder_disallow: Default::default(),
         }
     }
 }
 
+/// Reads and validates the [`RpcAccessConfig`] in the given file.
+fn parse_rpc_access_config(path: &str) -> eyre::Result<RpcAccessConfig> {
+    let config: RpcAccessConfig = reth_cli_util::parsers::read_json_from_file(path)?;
+    config.validate()?;
+    Ok(config)
+}
+
 /// clap value parser for [`RpcModuleSelection`].
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
@@ -36,6 +36,13 @@ pub struct RpcStateCacheArgs {
         default_value_t = DEFAULT_CONCURRENT_DB_REQUESTS,
     )]
     pub max_concurrent_db_requests: usize,
+
+    /// Max size of the cache of immutable responses in megabytes.
+    ///
+    /// Caches the responses of requests pinned to finalized blocks, such as `eth_call`,
+    /// `eth_getLogs` and trace requests. Disabled if not set.
+    #[arg(long = "rpc-cache.max-responses-size", value_name = "MB")]
+    pub max_responses_size: Option<usize>,
 }
 
 impl RpcStateCacheArgs {
@@ -54,6 +61,7 @@ impl Default for RpcStateCacheArgs {
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_headers: DEFAULT_HEADER_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
+            max_responses_size: None,
         }
     }
 }
@@ -50,4 +50,8 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The log index stage within the pipeline.
+    ///
+    /// Manages the optional index of log addresses and topics.
+    LogIndex,
 }
@@ -3,10 +3,12 @@
 use crate::cli::config::RethTransactionPoolConfig;
 use alloy_eips::eip1559::{ETHEREUM_BLOCK_GAS_LIMIT_30M, MIN_PROTOCOL_BASE_FEE};
 use alloy_primitives::Address;
-use clap::Args;
+use clap::{Args, ValueEnum};
 use reth_cli_util::parse_duration_from_secs_or_ms;
 use reth_transaction_pool::{
     blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
+    eviction::{FairShareEvictionPolicy, DEFAULT_MAX_PEER_SIZE, DEFAULT_MAX_SENDER_SIZE},
+    inclusion::DEFAULT_INCLUSION_POLICY_RELOAD_INTERVAL,
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
@@ -15,7 +17,7 @@ use reth_transaction_pool::{
     REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
     TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
@@ -132,6 +134,45 @@ pub struct TxPoolArgs {
         conflicts_with = "transactions_backup_path"
     )]
     pub disable_transactions_backup: bool,
+
+    /// Path to an address deny-list that is enforced for transactions entering the pool and for
+    /// transactions included in built payloads.
+    ///
+    /// Each line contains an address, optionally prefixed by `sender`, `recipient` or `call` to
+    /// restrict the scope. Call targets are denied when a transaction calls or creates them while
+    /// it executes. The file is reloaded when it changes.
+    #[arg(long = "txpool.inclusion-policy", value_name = "PATH")]
+    pub inclusion_policy_path: Option<std::path::PathBuf>,
+
+    /// How often the inclusion policy file is checked for changes.
+    #[arg(long = "txpool.inclusion-policy-reload-interval", value_parser = parse_duration_from_secs_or_ms, default_value = "10", value_name = "DURATION")]
+    pub inclusion_policy_reload_interval: Duration,
+
+    /// Policy to admit and evict transactions with, in addition to evicting by fee.
+    #[arg(long = "txpool.eviction-policy", value_enum, default_value_t = TxPoolEvictionPolicy::Fee)]
+    pub eviction_policy: TxPoolEvictionPolicy,
+
+    /// Max combined size of the transactions of a sender in kilobytes, enforced by the
+    /// `fair-share` eviction policy.
+    #[arg(long = "txpool.max-sender-size", default_value_t = DEFAULT_MAX_SENDER_SIZE / 1024)]
+    pub max_sender_size: usize,
+
+    /// Max combined size of the transactions received from a network peer in kilobytes, enforced
+    /// by the `fair-share` eviction policy. Transactions submitted via RPC are only bounded by
+    /// their sender.
+    #[arg(long = "txpool.max-peer-size", default_value_t = DEFAULT_MAX_PEER_SIZE / 1024)]
+    pub max_peer_size: usize,
+}
+
+/// Admission and eviction policy of the transaction pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TxPoolEvictionPolicy {
+    /// Evict the transactions with the lowest fees
+    #[default]
+    Fee,
+    /// Bound the pool space of every sender and peer, and evict from the peers and senders
+    /// exceeding their share of a full sub-pool first
+    FairShare,
 }
 
 impl Default for TxPoolArgs {
@@ -165,6 +206,11 @@ impl Default for TxPoolArgs {
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
             transactions_backup_path: None,
             disable_transactions_backup: false,
+            inclusion_policy_path: None,
+            inclusion_policy_reload_interval: DEFAULT_INCLUSION_POLICY_RELOAD_INTERVAL,
+            eviction_policy: TxPoolEvictionPolicy::Fee,
+            max_sender_size: DEFAULT_MAX_SENDER_SIZE / 1024,
+            max_peer_size: DEFAULT_MAX_PEER_SIZE / 1024,
         }
     }
 }
@@ -207,6 +253,13 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
             max_queued_lifetime: self.max_queued_lifetime,
+            eviction_policy: match self.eviction_policy {
+                TxPoolEvictionPolicy::Fee => None,
+                TxPoolEvictionPolicy::FairShare => Some(Arc::new(FairShareEvictionPolicy::new(
+                    self.max_sender_size.saturating_mul(1024),
+                    self.max_peer_size.saturating_mul(1024),
+                ))),
+            },
         }
     }
 }
@@ -253,6 +306,42 @@ mod tests {
         assert_eq!(args.max_queued_lifetime, Duration::from_secs(3 * 60 * 60)); // Default is 3h
     }
 
+    #[test]
+    fn txpool_parse_inclusion_policy() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.inclusion-policy",
+            "policy.txt",
+            "--txpool.inclusion-policy-reload-interval",
+            "30",
+        ])
+        .args;
+        assert_eq!(args.inclusion_policy_path, Some("policy.txt".into()));
+        assert_eq!(args.inclusion_policy_reload_interval, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn txpool_parse_eviction_policy() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.eviction-policy",
+            "fair-share",
+            "--txpool.max-sender-size",
+            "512",
+            "--txpool.max-peer-size",
+            "2048",
+        ])
+        .args;
+        assert_eq!(args.eviction_policy, TxPoolEvictionPolicy::FairShare);
+        assert_eq!(args.max_sender_size, 512);
+        assert_eq!(args.max_peer_size, 2048);
+        assert!(args.pool_config().eviction_policy.is_some());
+
+        let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.eviction_policy, TxPoolEvictionPolicy::Fee);
+        assert!(args.pool_config().eviction_policy.is_none());
+    }
+
     #[test]
     fn txpool_parse_max_tx_lifetime_invalid() {
         let result =
@@ -10,6 +10,7 @@
 pub mod chain;
 /// The metrics hooks for prometheus.
 pub mod hooks;
+mod profiling;
 pub mod recorder;
 /// The metric server serving the metrics.
 pub mod server;
@@ -0,0 +1,86 @@
+//! Profiling endpoints of the metrics server, see [`handle_pprof`].
+
+use http::{header::CONTENT_TYPE, HeaderValue, Response, StatusCode};
+use jsonrpsee_server::HttpBody;
+use reth_profiling::{ProfileFormat, ProfilingError};
+use std::time::Duration;
+
+/// Path prefix of the profiling endpoints, same as Go's `net/http/pprof`.
+pub(crate) const PPROF_PATH: &str = "/debug/pprof/";
+
+/// Duration of a CPU profile if not set in the request.
+const DEFAULT_CPU_PROFILE_SECONDS: u64 = 30;
+
+/// Maximum duration of a CPU profile.
+const MAX_CPU_PROFILE_SECONDS: u64 = 600;
+
+/// Serves the profiling endpoint at the given path, relative to [`PPROF_PATH`]:
+/// - `profile?seconds=30&format=pprof`: CPU profile, in the pprof format or as a flamegraph with
+///   `format=flamegraph`.
+/// - `heap`: heap profile of the jemalloc allocator, in the pprof format.
+pub(crate) async fn handle_pprof(path: &str, query: Option<&str>) -> Response<HttpBody> {
+    let result = match path {
+        "profile" => cpu_profile(query).await,
+        "heap" => reth_profiling::heap_profile()
+            .await
+            .map(|profile| (profile, ProfileFormat::Pprof.content_type())),
+        _ => return text_response(StatusCode::NOT_FOUND, format!("unknown profile {path}")),
+    };
+
+    match result {
+        Ok((profile, content_type)) => {
+            let mut response = Response::new(HttpBody::from(profile));
+            response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+            response
+        }
+        Err(err @ (ProfilingError::UnknownFormat(_) | ProfilingError::Unsupported(_))) => {
+            text_response(StatusCode::BAD_REQUEST, err.to_string())
+        }
+        Err(err @ ProfilingError::CpuProfileInProgress) => {
+            text_response(StatusCode::CONFLICT, err.to_string())
+        }
+        Err(err) => text_response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+/// Collects a CPU profile for the duration set in the query, and encodes it in the requested
+/// format.
+async fn cpu_profile(query: Option<&str>) -> Result<(Vec<u8>, &'static str), ProfilingError> {
+    let seconds = query_param(query, "seconds")
+        .and_then(|seconds| seconds.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_CPU_PROFILE_SECONDS)
+        .min(MAX_CPU_PROFILE_SECONDS);
+    let format = query_param(query, "format")
+        .map(str::parse::<ProfileFormat>)
+        .transpose()?
+        .unwrap_or_default();
+
+    let profile = reth_profiling::cpu_profile(Duration::from_secs(seconds)).await?;
+    Ok((profile.encode(format)?, format.content_type()))
+}
+
+/// Returns the value of the query parameter with the given name.
+fn query_param<'a>(query: Option<&'a str>, name: &str) -> Option<&'a str> {
+    query?.split('&').find_map(|param| param.strip_prefix(name)?.strip_prefix('='))
+}
+
+fn text_response(status: StatusCode, body: String) -> Response<HttpBody> {
+    let mut response = Response::new(HttpBody::from(body));
+    *response.status_mut() = status;
+    response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_query_param() {
+        let query = Some("seconds=5&format=flamegraph");
+        assert_eq!(query_param(query, "seconds"), Some("5"));
+        assert_eq!(query_param(query, "format"), Some("flamegraph"));
+        assert_eq!(query_param(query, "second"), None);
+        assert_eq!(query_param(None, "seconds"), None);
+    }
+}
@@ -1,11 +1,13 @@
 use crate::{
     chain::ChainSpecInfo,
     hooks::{Hook, Hooks},
+    profiling::{handle_pprof, PPROF_PATH},
     recorder::install_prometheus_recorder,
     version::VersionInfo,
 };
 use eyre::WrapErr;
-use http::{header::CONTENT_TYPE, HeaderValue, Response};
+use http::{header::CONTENT_TYPE, HeaderValue, Request, Response};
+use jsonrpsee_server::HttpBody;
 use metrics::describe_gauge;
 use metrics_process::Collector;
 use reth_metrics::metrics::Unit;
@@ -102,14 +104,22 @@ impl MetricServer {
 
                     let handle = install_prometheus_recorder();
                     let hook = hook.clone();
-                    let service = tower::service_fn(move |_| {
-                        (hook)();
-                        let metrics = handle.handle().render();
-                        let mut response = Response::new(metrics);
-                        response
-                            .headers_mut()
-                            .insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
-                        async move { Ok::<_, Infallible>(response) }
+                    let service = tower::service_fn(move |request: Request<_>| {
+                        let hook = hook.clone();
+                        async move {
+                            let uri = request.uri();
+                            if let Some(path) = uri.path().strip_prefix(PPROF_PATH) {
+                                return Ok::<_, Infallible>(handle_pprof(path, uri.query()).await)
+                            }
+
+                            (hook)();
+                            let metrics = handle.handle().render();
+                            let mut response = Response::new(HttpBody::from(metrics));
+                            response
+                                .headers_mut()
+                                .insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+                            Ok(response)
+                        }
                     });
 
                     let mut shutdown = signal.clone().ignore_guard();
@@ -255,5 +265,16 @@ mod tests {
         let body = response.text().await.unwrap();
         assert!(body.contains("reth_process_cpu_seconds_total"));
         assert!(body.contains("reth_process_start_time_seconds"));
+
+        // CPU profile from the profiling endpoint
+        let url = format!("http://{listen_addr}/debug/pprof/profile?seconds=1&format=flamegraph");
+        let response = Client::new().get(&url).send().await.unwrap();
+        assert!(response.status().is_success());
+        assert_eq!(response.headers()[CONTENT_TYPE], "image/svg+xml");
+        assert!(response.text().await.unwrap().contains("<svg"));
+
+        let url = format!("http://{listen_addr}/debug/pprof/goroutine");
+        let response = Client::new().get(&url).send().await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
     }
 }
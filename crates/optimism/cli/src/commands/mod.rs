@@ -5,7 +5,7 @@ use import_receipts::ImportReceiptsOpCommand;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
-    config_cmd, db, dump_genesis, init_cmd,
+    config_cmd, db, dump_genesis, exex, init_cmd,
     node::{self, NoArgs},
     p2p, prune, re_execute, recover, stage,
 };
@@ -57,6 +57,9 @@ pub enum Commands<Spec: ChainSpecParser = OpChainSpecParser, Ext: clap::Args + f
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand<Spec>),
+    /// Execution extension utilities
+    #[command(name = "exex")]
+    Exex(exex::Command<Spec>),
     /// Generate Test Vectors
     #[cfg(feature = "dev")]
     #[command(name = "test-vectors")]
@@ -84,6 +87,7 @@ impl<
             Self::Config(_) => None,
             Self::Recover(cmd) => cmd.chain_spec(),
             Self::Prune(cmd) => cmd.chain_spec(),
+            Self::Exex(cmd) => cmd.chain_spec(),
             Self::ImportOp(cmd) => cmd.chain_spec(),
             Self::ImportReceiptsOp(cmd) => cmd.chain_spec(),
             #[cfg(feature = "dev")]
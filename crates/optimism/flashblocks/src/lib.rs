@@ -0,0 +1,32 @@
+//! Consumer of the flashblocks a sequencer streams while building a block.
+//!
+//! Flashblocks are incremental parts of the block the sequencer is building, see [`FlashBlock`].
+//! The [`FlashBlockService`] executes the flashblocks received from a [`WsFlashBlockStream`] on
+//! top of the canonical head, and publishes the result as the pending block.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod payload;
+pub use payload::{
+    ExecutionPayloadBaseV1, ExecutionPayloadFlashblockDeltaV1, FlashBlock, Metadata,
+};
+
+mod sequence;
+
+mod service;
+pub use service::FlashBlockService;
+
+mod ws;
+pub use ws::WsFlashBlockStream;
+
+use reth_chain_state::ExecutedBlock;
+use tokio::sync::watch;
+
+/// Receiver of the pending block built from flashblocks, if there is one.
+pub type PendingBlockRx<N> = watch::Receiver<Option<ExecutedBlock<N>>>;
@@ -0,0 +1,160 @@
+//! Types of the flashblocks feed of a sequencer.
+
+use alloy_eips::eip4895::Withdrawal;
+use alloy_primitives::{Address, Bloom, Bytes, B256, U256};
+use alloy_rpc_types_engine::PayloadId;
+use reth_optimism_evm::OpNextBlockEnvAttributes;
+use serde::{Deserialize, Serialize};
+
+/// An incremental part of the block the sequencer is building, streamed before the block is
+/// sealed.
+///
+/// The flashblocks of a block share its payload id. The first one, with index `0`, carries the
+/// [`ExecutionPayloadBaseV1`] the block is built with, and each one appends its transactions to
+/// the ones of the previous flashblocks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlashBlock {
+    /// The id of the payload the flashblock belongs to.
+    pub payload_id: PayloadId,
+    /// The position of the flashblock in the payload.
+    pub index: u64,
+    /// The fields the payload is built with, only set for the first flashblock.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<ExecutionPayloadBaseV1>,
+    /// The changes of the payload up to and including this flashblock.
+    pub diff: ExecutionPayloadFlashblockDeltaV1,
+    /// Additional data about the flashblock.
+    pub metadata: Metadata,
+}
+
+impl FlashBlock {
+    /// Returns the number of the block the flashblock belongs to.
+    pub const fn block_number(&self) -> u64 {
+        self.metadata.block_number
+    }
+}
+
+/// Additional data of a [`FlashBlock`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+    /// The number of the block the flashblock belongs to.
+    pub block_number: u64,
+}
+
+/// The fields of a payload that are known before its transactions are executed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionPayloadBaseV1 {
+    /// The root of the parent beacon block.
+    pub parent_beacon_block_root: B256,
+    /// The hash of the parent block.
+    pub parent_hash: B256,
+    /// The recipient of the fees of the block.
+    pub fee_recipient: Address,
+    /// The randomness of the block.
+    pub prev_randao: B256,
+    /// The number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// The gas limit of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_limit: u64,
+    /// The timestamp of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+    /// The extra data of the block, encoding the EIP-1559 parameters since Holocene.
+    pub extra_data: Bytes,
+    /// The base fee of the block.
+    pub base_fee_per_gas: U256,
+}
+
+impl From<ExecutionPayloadBaseV1> for OpNextBlockEnvAttributes {
+    fn from(base: ExecutionPayloadBaseV1) -> Self {
+        Self {
+            timestamp: base.timestamp,
+            suggested_fee_recipient: base.fee_recipient,
+            prev_randao: base.prev_randao,
+            gas_limit: base.gas_limit,
+            parent_beacon_block_root: Some(base.parent_beacon_block_root),
+            extra_data: base.extra_data,
+        }
+    }
+}
+
+/// The changes of a payload made by its flashblocks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionPayloadFlashblockDeltaV1 {
+    /// The state root after the flashblock.
+    pub state_root: B256,
+    /// The root of the receipts after the flashblock.
+    pub receipts_root: B256,
+    /// The bloom of the logs after the flashblock.
+    pub logs_bloom: Bloom,
+    /// The gas used by the transactions up to and including the flashblock.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// The hash of the block after the flashblock.
+    pub block_hash: B256,
+    /// The EIP-2718 encoded transactions added by the flashblock.
+    pub transactions: Vec<Bytes>,
+    /// The withdrawals added by the flashblock.
+    pub withdrawals: Vec<Withdrawal>,
+    /// The root of the withdrawals after the flashblock.
+    #[serde(default)]
+    pub withdrawals_root: B256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_flashblock() {
+        let flashblock: FlashBlock = serde_json::from_value(json!({
+            "payload_id": "0x0316ecb1aa1671b5",
+            "index": 0,
+            "base": {
+                "parent_beacon_block_root": B256::repeat_byte(0x7d),
+                "parent_hash": B256::repeat_byte(0x98),
+                "fee_recipient": "0x4200000000000000000000000000000000000011",
+                "prev_randao": B256::with_last_byte(1),
+                "block_number": "0x1ec53a1",
+                "gas_limit": "0x7270e00",
+                "timestamp": "0x68419ccd",
+                "extra_data": "0x00000000fa00000006",
+                "base_fee_per_gas": "0x1ba2"
+            },
+            "diff": {
+                "state_root": B256::ZERO,
+                "receipts_root": B256::ZERO,
+                "logs_bloom": Bloom::ZERO,
+                "gas_used": "0xb7b9",
+                "block_hash": B256::ZERO,
+                "transactions": ["0x7ef8f8a0"],
+                "withdrawals": [],
+                "withdrawals_root": B256::ZERO
+            },
+            "metadata": {
+                "block_number": 32265121,
+                "new_account_balances": {}
+            }
+        }))
+        .unwrap();
+
+        let base = flashblock.base.as_ref().unwrap();
+        assert_eq!(flashblock.index, 0);
+        assert_eq!(flashblock.block_number(), 32265121);
+        assert_eq!(base.block_number, 32265121);
+        assert_eq!(base.parent_hash, B256::repeat_byte(0x98));
+        assert_eq!(base.base_fee_per_gas, U256::from(0x1ba2));
+        assert_eq!(flashblock.diff.gas_used, 0xb7b9);
+        assert_eq!(
+            flashblock.diff.transactions,
+            vec![Bytes::from_static(&[0x7e, 0xf8, 0xf8, 0xa0])]
+        );
+
+        let attributes = OpNextBlockEnvAttributes::from(base.clone());
+        assert_eq!(attributes.timestamp, 0x68419ccd);
+        assert_eq!(attributes.parent_beacon_block_root, Some(base.parent_beacon_block_root));
+    }
+}
@@ -0,0 +1,117 @@
+//! The flashblocks of the payload the sequencer is building.
+
+use crate::{ExecutionPayloadBaseV1, ExecutionPayloadFlashblockDeltaV1, FlashBlock};
+use alloy_primitives::Bytes;
+use alloy_rpc_types_engine::PayloadId;
+
+/// The contiguous flashblocks received of the payload the sequencer is currently building.
+#[derive(Debug, Default)]
+pub(crate) struct FlashBlockSequence {
+    /// The flashblocks, ordered by index, starting with index `0`.
+    flashblocks: Vec<FlashBlock>,
+}
+
+impl FlashBlockSequence {
+    /// Appends the flashblock to the sequence, returning `false` if it doesn't continue it.
+    ///
+    /// A flashblock with index `0` starts a new sequence, any other flashblock must belong to the
+    /// payload of the sequence and directly follow its last flashblock.
+    pub(crate) fn insert(&mut self, flashblock: FlashBlock) -> bool {
+        if flashblock.index == 0 {
+            if flashblock.base.is_none() {
+                return false
+            }
+            self.flashblocks.clear();
+            self.flashblocks.push(flashblock);
+            return true
+        }
+
+        let Some(last) = self.flashblocks.last() else { return false };
+        if last.payload_id != flashblock.payload_id || last.index + 1 != flashblock.index {
+            return false
+        }
+        self.flashblocks.push(flashblock);
+        true
+    }
+
+    /// Returns the fields the payload is built with.
+    pub(crate) fn base(&self) -> Option<&ExecutionPayloadBaseV1> {
+        self.flashblocks.first()?.base.as_ref()
+    }
+
+    /// Returns the id of the payload and the index of the last flashblock.
+    pub(crate) fn position(&self) -> Option<(PayloadId, u64)> {
+        self.flashblocks.last().map(|flashblock| (flashblock.payload_id, flashblock.index))
+    }
+
+    /// Returns the changes of the payload up to and including the last flashblock.
+    pub(crate) fn diff(&self) -> Option<&ExecutionPayloadFlashblockDeltaV1> {
+        self.flashblocks.last().map(|flashblock| &flashblock.diff)
+    }
+
+    /// Returns the encoded transactions of all flashblocks, in order.
+    pub(crate) fn transactions(&self) -> impl Iterator<Item = &Bytes> {
+        self.flashblocks.iter().flat_map(|flashblock| &flashblock.diff.transactions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Metadata;
+    use alloy_primitives::{Address, Bloom, B256, U256};
+
+    fn flashblock(payload: u8, index: u64, transactions: &[u8]) -> FlashBlock {
+        let base = (index == 0).then(|| ExecutionPayloadBaseV1 {
+            parent_beacon_block_root: B256::ZERO,
+            parent_hash: B256::repeat_byte(payload),
+            fee_recipient: Address::ZERO,
+            prev_randao: B256::ZERO,
+            block_number: 1,
+            gas_limit: 30_000_000,
+            timestamp: 2,
+            extra_data: Bytes::new(),
+            base_fee_per_gas: U256::from(1),
+        });
+        FlashBlock {
+            payload_id: PayloadId::new([payload; 8]),
+            index,
+            base,
+            diff: ExecutionPayloadFlashblockDeltaV1 {
+                state_root: B256::ZERO,
+                receipts_root: B256::ZERO,
+                logs_bloom: Bloom::ZERO,
+                gas_used: 0,
+                block_hash: B256::ZERO,
+                transactions: transactions.iter().map(|tx| Bytes::from(vec![*tx])).collect(),
+                withdrawals: Vec::new(),
+                withdrawals_root: B256::ZERO,
+            },
+            metadata: Metadata { block_number: 1 },
+        }
+    }
+
+    #[test]
+    fn insert_contiguous_flashblocks() {
+        let mut sequence = FlashBlockSequence::default();
+
+        // a sequence starts with the first flashblock of a payload
+        assert!(!sequence.insert(flashblock(1, 1, &[1])));
+        assert!(sequence.insert(flashblock(1, 0, &[1])));
+        assert!(sequence.insert(flashblock(1, 1, &[2, 3])));
+        assert_eq!(sequence.position(), Some((PayloadId::new([1; 8]), 1)));
+        let transactions = sequence.transactions().map(|tx| tx.to_vec()).collect::<Vec<_>>();
+        assert_eq!(transactions, [vec![1], vec![2], vec![3]]);
+
+        // gaps, duplicates and flashblocks of other payloads are rejected
+        assert!(!sequence.insert(flashblock(1, 3, &[4])));
+        assert!(!sequence.insert(flashblock(1, 1, &[4])));
+        assert!(!sequence.insert(flashblock(2, 2, &[4])));
+        assert_eq!(sequence.position(), Some((PayloadId::new([1; 8]), 1)));
+
+        // the first flashblock of the next payload replaces the sequence
+        assert!(sequence.insert(flashblock(2, 0, &[5])));
+        assert_eq!(sequence.base().unwrap().parent_hash, B256::repeat_byte(2));
+        assert_eq!(sequence.transactions().count(), 1);
+    }
+}
@@ -0,0 +1,486 @@
+//! Service building the pending block of the flashblocks of the sequencer.
+
+use crate::{sequence::FlashBlockSequence, ExecutionPayloadBaseV1, FlashBlock, PendingBlockRx};
+use alloy_consensus::{BlockHeader, TxReceipt};
+use alloy_eips::eip2718::Decodable2718;
+use alloy_primitives::{B256, U256};
+use alloy_rpc_types_engine::PayloadId;
+use eyre::{bail, eyre};
+use futures_util::{Stream, StreamExt};
+use reth_chain_state::{CanonStateSubscriptions, ExecutedBlock};
+use reth_errors::RethError;
+use reth_evm::{
+    execute::{BlockAssembler, BlockAssemblerInput, BlockBuilder, BlockExecutor},
+    ConfigureEvm, Evm,
+};
+use reth_execution_types::{BlockExecutionResult, ExecutionOutcome};
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+use reth_optimism_primitives::OpReceipt;
+use reth_primitives_traits::{
+    NodePrimitives, Recovered, RecoveredBlock, SealedHeader, SignedTransaction,
+};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::{states::bundle_state::BundleRetention, State},
+};
+use reth_storage_api::{
+    BlockNumReader, HashedPostStateProvider, HeaderProvider, StateProviderBox, StateProviderFactory,
+};
+use std::{fmt, sync::Arc, time::Instant};
+use tokio::sync::{broadcast::error::RecvError, watch};
+use tracing::{debug, trace, warn};
+
+/// Builds the pending block of the flashblocks of a stream, on top of the canonical head.
+///
+/// The flashblocks of the payload the sequencer is building are executed once they build on the
+/// canonical head, and the resulting block is published as the pending block, see
+/// [`FlashBlockService::subscribe`]. Flashblocks that don't directly follow the previous
+/// flashblock of their payload are discarded. The pending block is cleared once it's outdated by
+/// the canonical chain.
+///
+/// The flashblocks of a payload are executed incrementally, each flashblock only executes its own
+/// transactions on top of the state of the previous flashblocks.
+#[derive(Debug)]
+pub struct FlashBlockService<N: NodePrimitives, S, EvmConfig, Provider> {
+    /// The flashblocks of the sequencer.
+    rx: S,
+    /// The flashblocks of the payload the sequencer is building.
+    sequence: FlashBlockSequence,
+    /// The position of the last flashblock included in the pending block.
+    built: Option<(PayloadId, u64)>,
+    /// The execution of the flashblocks of the pending block, continued by the next flashblocks.
+    execution: Option<PayloadExecution<N>>,
+    /// The published pending block.
+    pending_block: watch::Sender<Option<ExecutedBlock<N>>>,
+    evm_config: EvmConfig,
+    provider: Provider,
+    metrics: FlashBlockServiceMetrics,
+}
+
+impl<N, S, EvmConfig, Provider> FlashBlockService<N, S, EvmConfig, Provider>
+where
+    N: NodePrimitives<Receipt = OpReceipt>,
+    S: Stream<Item = FlashBlock> + Unpin,
+    EvmConfig: ConfigureEvm<Primitives = N, NextBlockEnvCtx: From<ExecutionPayloadBaseV1>>,
+    Provider: StateProviderFactory
+        + BlockNumReader
+        + HeaderProvider<Header = N::BlockHeader>
+        + CanonStateSubscriptions<Primitives = N>,
+{
+    /// Creates a service building the pending block of the given flashblocks.
+    pub fn new(rx: S, evm_config: EvmConfig, provider: Provider) -> Self {
+        Self {
+            rx,
+            sequence: FlashBlockSequence::default(),
+            built: None,
+            execution: None,
+            pending_block: watch::channel(None).0,
+            evm_config,
+            provider,
+            metrics: FlashBlockServiceMetrics::default(),
+        }
+    }
+
+    /// Returns a receiver of the pending block built from the flashblocks.
+    pub fn subscribe(&self) -> PendingBlockRx<N> {
+        self.pending_block.subscribe()
+    }
+
+    /// Runs the service until both the flashblocks stream and the canonical chain end.
+    pub async fn run(mut self) {
+        let mut canon_state = self.provider.subscribe_to_canonical_state();
+        let mut flashblocks_ended = false;
+
+        loop {
+            tokio::select! {
+                flashblock = self.rx.next(), if !flashblocks_ended => {
+                    let Some(flashblock) = flashblock else {
+                        debug!(target: "flashblocks", "Flashblocks stream ended");
+                        flashblocks_ended = true;
+                        continue
+                    };
+                    self.on_flashblock(flashblock);
+                }
+                notification = canon_state.recv() => {
+                    if let Err(RecvError::Closed) = notification {
+                        break
+                    }
+                    self.update_pending_block();
+                }
+            }
+        }
+    }
+
+    /// Adds the flashblock to the sequence and rebuilds the pending block.
+    fn on_flashblock(&mut self, flashblock: FlashBlock) {
+        self.metrics.received_total.increment(1);
+        let (payload_id, index) = (flashblock.payload_id, flashblock.index);
+        if !self.sequence.insert(flashblock) {
+            self.metrics.discarded_total.increment(1);
+            trace!(target: "flashblocks", %payload_id, index, "Discarding out of order flashblock");
+            return
+        }
+        self.update_pending_block();
+    }
+
+    /// Rebuilds the pending block if the sequence builds on the canonical head and has new
+    /// flashblocks, and clears the pending block if it's outdated.
+    fn update_pending_block(&mut self) {
+        let latest = match self
+            .provider
+            .best_block_number()
+            .and_then(|number| self.provider.sealed_header(number))
+        {
+            Ok(Some(latest)) => latest,
+            Ok(None) => return,
+            Err(err) => {
+                warn!(target: "flashblocks", %err, "Failed to load the canonical head");
+                return
+            }
+        };
+
+        self.pending_block.send_if_modified(|pending| {
+            let outdated = pending
+                .as_ref()
+                .is_some_and(|block| block.recovered_block().parent_hash() != latest.hash());
+            if outdated {
+                *pending = None;
+            }
+            outdated
+        });
+
+        // release the state of an outdated execution
+        if self.execution.as_ref().is_some_and(|execution| execution.parent_hash != latest.hash()) {
+            self.execution = None;
+        }
+
+        if self.sequence.base().is_none_or(|base| base.parent_hash != latest.hash()) ||
+            self.sequence.position() == self.built
+        {
+            return
+        }
+
+        let start = Instant::now();
+        match self.execute(&latest) {
+            Ok(block) => {
+                self.metrics.execution_duration_seconds.record(start.elapsed());
+                self.built = self.sequence.position();
+                debug!(
+                    target: "flashblocks",
+                    number = block.recovered_block().number(),
+                    transactions = self.sequence.transactions().count(),
+                    elapsed = ?start.elapsed(),
+                    "Built pending block from flashblocks"
+                );
+                self.pending_block.send_replace(Some(block));
+            }
+            Err(err) => {
+                self.metrics.execution_failures_total.increment(1);
+                // don't retry the flashblocks that failed
+                self.built = self.sequence.position();
+                warn!(target: "flashblocks", %err, "Failed to build pending block");
+            }
+        }
+    }
+
+    /// Executes the transactions of the sequence on top of the given parent, and validates the
+    /// built block against the last flashblock.
+    ///
+    /// Only the transactions of the flashblocks that weren't executed yet are executed, if the
+    /// previous flashblocks of the payload were executed on the same parent.
+    fn execute(&mut self, parent: &SealedHeader<N::BlockHeader>) -> eyre::Result<ExecutedBlock<N>> {
+        let (Some(base), Some(diff), Some(position)) =
+            (self.sequence.base(), self.sequence.diff(), self.sequence.position())
+        else {
+            bail!("no flashblocks to execute")
+        };
+
+        let (mut execution, is_continued) = match self.execution.take().filter(|execution| {
+            execution.parent_hash == parent.hash() &&
+                execution.position.0 == position.0 &&
+                execution.position.1 < position.1
+        }) {
+            Some(execution) => (execution, true),
+            None => {
+                let state_provider = self.provider.history_by_block_hash(parent.hash())?;
+                (PayloadExecution::new(parent.hash(), state_provider), false)
+            }
+        };
+        let prev_gas_used =
+            execution.receipts.last().map(|receipt| receipt.cumulative_gas_used()).unwrap_or(0);
+
+        let mut builder = self
+            .evm_config
+            .builder_for_next_block(&mut execution.db, parent, base.clone().into())
+            .map_err(RethError::other)?;
+        if U256::from(builder.evm().block().basefee) != base.base_fee_per_gas {
+            bail!("base fee mismatch, flashblock has {}", base.base_fee_per_gas)
+        }
+        if !is_continued {
+            builder.apply_pre_execution_changes()?;
+        }
+
+        let mut transactions = Vec::new();
+        for encoded in self.sequence.transactions().skip(execution.transactions.len()) {
+            let tx = N::SignedTx::decode_2718(&mut encoded.as_ref())?
+                .try_into_recovered()
+                .map_err(|tx| eyre!("failed to recover signer of transaction {}", tx.tx_hash()))?;
+            builder.execute_transaction(tx.clone())?;
+            transactions.push(tx);
+        }
+
+        // the executor only knows the transactions of this call, so the gas used by the previous
+        // flashblocks is added to the cumulative gas used of the new receipts
+        let (evm, result) = builder.into_executor().finish()?;
+        let (_, evm_env) = evm.finish();
+        execution.transactions.extend(transactions);
+        execution.receipts.extend(result.receipts.into_iter().map(|mut receipt| {
+            receipt.as_receipt_mut().cumulative_gas_used += prev_gas_used;
+            receipt
+        }));
+        let gas_used = prev_gas_used + result.gas_used;
+        if gas_used != diff.gas_used {
+            bail!("gas used mismatch, flashblock has {}", diff.gas_used)
+        }
+
+        // the changes stay transitions of the execution, to be continued by the next flashblocks
+        let mut bundle_state = execution.db.bundle_state.clone();
+        if let Some(transitions) = execution.db.transition_state.clone() {
+            bundle_state
+                .apply_transitions_and_create_reverts(transitions, BundleRetention::Reverts);
+        }
+        let state_provider = execution.db.database.0.as_ref();
+        let hashed_state = state_provider.hashed_post_state(&bundle_state);
+
+        // the state root isn't computed, the block is assembled with the state root of the
+        // flashblock, which is checked through the block hash
+        let output = BlockExecutionResult {
+            receipts: execution.receipts.clone(),
+            requests: result.requests,
+            gas_used,
+        };
+        let (block_transactions, senders) =
+            execution.transactions.iter().cloned().map(Recovered::into_parts).unzip();
+        let block = self.evm_config.block_assembler().assemble_block(BlockAssemblerInput::new(
+            evm_env,
+            self.evm_config.context_for_next_block(parent, base.clone().into()),
+            parent,
+            block_transactions,
+            &output,
+            &bundle_state,
+            state_provider,
+            diff.state_root,
+        ))?;
+        let block = RecoveredBlock::new_unhashed(block, senders);
+        if block.receipts_root() != diff.receipts_root {
+            bail!("receipts root mismatch, flashblock has {}", diff.receipts_root)
+        }
+        if block.hash() != diff.block_hash {
+            bail!("block hash mismatch, flashblock has {}", diff.block_hash)
+        }
+
+        let BlockExecutionResult { receipts, requests, .. } = output;
+        let execution_outcome =
+            ExecutionOutcome::new(bundle_state, vec![receipts], block.number(), vec![requests]);
+
+        execution.position = position;
+        self.execution = Some(execution);
+
+        Ok(ExecutedBlock {
+            recovered_block: Arc::new(block),
+            execution_output: Arc::new(execution_outcome),
+            hashed_state: Arc::new(hashed_state),
+        })
+    }
+}
+
+/// The execution of the flashblocks of a payload.
+struct PayloadExecution<N: NodePrimitives> {
+    /// The hash of the block the payload builds on.
+    parent_hash: B256,
+    /// The position of the last executed flashblock.
+    position: (PayloadId, u64),
+    /// The state the transactions were executed on, their changes are kept as transitions.
+    db: State<StateProviderDatabase<StateProviderBox>>,
+    /// The executed transactions.
+    transactions: Vec<Recovered<N::SignedTx>>,
+    /// The receipts of the executed transactions.
+    receipts: Vec<N::Receipt>,
+}
+
+impl<N: NodePrimitives> PayloadExecution<N> {
+    /// Creates an execution on top of the state of the given parent.
+    fn new(parent_hash: B256, state_provider: StateProviderBox) -> Self {
+        Self {
+            parent_hash,
+            position: (PayloadId::default(), 0),
+            db: State::builder()
+                .with_database(StateProviderDatabase::new(state_provider))
+                .with_bundle_update()
+                .build(),
+            transactions: Vec::new(),
+            receipts: Vec::new(),
+        }
+    }
+}
+
+impl<N: NodePrimitives> fmt::Debug for PayloadExecution<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PayloadExecution")
+            .field("parent_hash", &self.parent_hash)
+            .field("position", &self.position)
+            .field("transactions", &self.transactions.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Metrics of the [`FlashBlockService`].
+#[derive(Metrics)]
+#[metrics(scope = "optimism_flashblocks")]
+struct FlashBlockServiceMetrics {
+    /// The number of flashblocks received
+    received_total: Counter,
+    /// The number of flashblocks discarded, because they didn't follow the previous flashblock
+    discarded_total: Counter,
+    /// The number of times the pending block failed to build
+    execution_failures_total: Counter,
+    /// The duration of building the pending block
+    execution_duration_seconds: Histogram,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ExecutionPayloadFlashblockDeltaV1, Metadata};
+    use alloy_consensus::Header;
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_primitives::{Address, Bytes};
+    use op_alloy_consensus::TxDeposit;
+    use reth_optimism_chainspec::OpChainSpecBuilder;
+    use reth_optimism_evm::OpEvmConfig;
+    use reth_optimism_primitives::{OpBlock, OpPrimitives, OpTransactionSigned};
+    use reth_provider::test_utils::MockEthProvider;
+    use std::time::Duration;
+    use tokio::sync::broadcast;
+
+    /// Builds the block of the transactions from scratch, like the sequencer.
+    fn build_block(
+        evm_config: &OpEvmConfig,
+        provider: &MockEthProvider<OpPrimitives>,
+        parent: &SealedHeader,
+        base: &ExecutionPayloadBaseV1,
+        transactions: &[OpTransactionSigned],
+    ) -> RecoveredBlock<OpBlock> {
+        let state_provider = provider.history_by_block_hash(parent.hash()).unwrap();
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(&state_provider))
+            .with_bundle_update()
+            .build();
+        let mut builder =
+            evm_config.builder_for_next_block(&mut db, parent, base.clone().into()).unwrap();
+        builder.apply_pre_execution_changes().unwrap();
+        for tx in transactions {
+            builder.execute_transaction(tx.clone().try_into_recovered().unwrap()).unwrap();
+        }
+        builder.finish(&state_provider).unwrap().block
+    }
+
+    #[tokio::test]
+    async fn builds_pending_block_from_flashblocks() {
+        let chain_spec = Arc::new(OpChainSpecBuilder::base_mainnet().canyon_activated().build());
+        let evm_config = OpEvmConfig::optimism(chain_spec);
+        let (canon_state_tx, _) = broadcast::channel(1);
+        let provider =
+            MockEthProvider::<OpPrimitives>::new().with_canon_state_sender(canon_state_tx.clone());
+        let parent = SealedHeader::seal_slow(Header {
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            ..Default::default()
+        });
+        provider.add_header(parent.hash(), parent.header().clone());
+
+        let transactions = (1..=3)
+            .map(|i| {
+                TxDeposit {
+                    source_hash: B256::with_last_byte(i),
+                    from: Address::with_last_byte(i),
+                    to: Address::ZERO.into(),
+                    gas_limit: 21_000,
+                    ..Default::default()
+                }
+                .into()
+            })
+            .collect::<Vec<OpTransactionSigned>>();
+        let mut base = ExecutionPayloadBaseV1 {
+            parent_beacon_block_root: B256::ZERO,
+            parent_hash: parent.hash(),
+            fee_recipient: Address::ZERO,
+            prev_randao: B256::ZERO,
+            block_number: 1,
+            gas_limit: 30_000_000,
+            timestamp: 2,
+            extra_data: Bytes::new(),
+            base_fee_per_gas: U256::ZERO,
+        };
+
+        // each flashblock adds a transaction to the block the sequencer builds
+        let blocks = (1..=transactions.len())
+            .map(|len| build_block(&evm_config, &provider, &parent, &base, &transactions[..len]))
+            .collect::<Vec<_>>();
+        base.base_fee_per_gas = U256::from(blocks[0].base_fee_per_gas().unwrap());
+        let flashblocks = blocks
+            .iter()
+            .zip(&transactions)
+            .enumerate()
+            .map(|(index, (block, tx))| FlashBlock {
+                payload_id: PayloadId::new([1; 8]),
+                index: index as u64,
+                base: (index == 0).then(|| base.clone()),
+                diff: ExecutionPayloadFlashblockDeltaV1 {
+                    state_root: block.state_root(),
+                    receipts_root: block.receipts_root(),
+                    logs_bloom: block.logs_bloom(),
+                    gas_used: block.gas_used(),
+                    block_hash: block.hash(),
+                    transactions: vec![tx.encoded_2718().into()],
+                    withdrawals: Vec::new(),
+                    withdrawals_root: block.withdrawals_root().unwrap_or_default(),
+                },
+                metadata: Metadata { block_number: 1 },
+            })
+            .collect::<Vec<_>>();
+
+        let service = FlashBlockService::new(
+            futures_util::stream::iter(flashblocks),
+            evm_config,
+            provider.clone(),
+        );
+        let mut rx = service.subscribe();
+
+        // the last flashblocks only execute their own transaction, the result must match the
+        // block built from scratch
+        let expected = blocks.last().unwrap().hash();
+        tokio::select! {
+            _ = service.run() => panic!("service stopped"),
+            pending = tokio::time::timeout(
+                Duration::from_secs(10),
+                rx.wait_for(|pending| {
+                    pending.as_ref().is_some_and(|block| block.recovered_block().hash() == expected)
+                }),
+            ) => {
+                pending.unwrap().unwrap();
+            }
+        }
+
+        let pending = rx.borrow().clone().unwrap();
+        assert_eq!(pending.recovered_block().body().transactions.len(), transactions.len());
+        assert_eq!(pending.execution_output.receipts[0].len(), transactions.len());
+        assert_eq!(
+            pending.execution_output.receipts[0].last().unwrap().cumulative_gas_used(),
+            blocks.last().unwrap().gas_used()
+        );
+    }
+}
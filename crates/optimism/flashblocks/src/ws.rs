@@ -0,0 +1,159 @@
+//! Stream of the flashblocks of a sequencer websocket feed.
+
+use crate::FlashBlock;
+use futures_util::{stream::BoxStream, Stream, StreamExt};
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{net::TcpStream, time::sleep};
+use tokio_tungstenite::{
+    connect_async, tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream,
+};
+use tracing::{debug, trace, warn};
+use url::Url;
+
+/// Duration to wait before reconnecting to the feed.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Websocket connection to the feed.
+type WsConnection = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// A [`Stream`] of the [`FlashBlock`]s of a websocket feed.
+///
+/// The stream connects to the feed on the first poll, and reconnects whenever the connection
+/// fails or is closed by the feed, so it never ends. Messages that aren't flashblocks are
+/// skipped.
+pub struct WsFlashBlockStream {
+    url: Url,
+    inner: BoxStream<'static, FlashBlock>,
+}
+
+impl WsFlashBlockStream {
+    /// Creates a stream of the flashblocks of the feed at the given URL.
+    pub fn new(url: Url) -> Self {
+        let inner =
+            futures_util::stream::unfold((url.clone(), None), |(url, mut connection)| async move {
+                let flashblock = next_flashblock(&url, &mut connection).await;
+                Some((flashblock, (url, connection)))
+            });
+        Self { url, inner: inner.boxed() }
+    }
+
+    /// Returns the URL of the feed.
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+}
+
+/// Returns the next flashblock of the feed, (re)connecting to it when there's no connection.
+async fn next_flashblock(url: &Url, connection: &mut Option<WsConnection>) -> FlashBlock {
+    loop {
+        let Some(ws) = connection.as_mut() else {
+            match connect_async(url.as_str()).await {
+                Ok((ws, _)) => {
+                    debug!(target: "flashblocks", %url, "Connected to flashblocks feed");
+                    *connection = Some(ws);
+                }
+                Err(err) => {
+                    warn!(target: "flashblocks", %url, %err, "Failed to connect to feed");
+                    sleep(RECONNECT_INTERVAL).await;
+                }
+            }
+            continue
+        };
+
+        let message = ws.next().await;
+        let data = match message {
+            Some(Ok(Message::Text(text))) => text.as_bytes().to_vec(),
+            Some(Ok(Message::Binary(bytes))) => bytes.to_vec(),
+            // pings are answered by the connection itself
+            Some(Ok(Message::Ping(_) | Message::Pong(_) | Message::Frame(_))) => continue,
+            Some(Ok(Message::Close(_))) | None => {
+                debug!(target: "flashblocks", %url, "Flashblocks feed closed the connection");
+                *connection = None;
+                sleep(RECONNECT_INTERVAL).await;
+                continue
+            }
+            Some(Err(err)) => {
+                warn!(target: "flashblocks", %url, %err, "Lost connection to flashblocks feed");
+                *connection = None;
+                sleep(RECONNECT_INTERVAL).await;
+                continue
+            }
+        };
+
+        match serde_json::from_slice(&data) {
+            Ok(flashblock) => return flashblock,
+            Err(err) => trace!(target: "flashblocks", %err, "Skipping invalid flashblock"),
+        }
+    }
+}
+
+impl fmt::Debug for WsFlashBlockStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WsFlashBlockStream").field("url", &self.url).finish_non_exhaustive()
+    }
+}
+
+impl Stream for WsFlashBlockStream {
+    type Item = FlashBlock;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ExecutionPayloadFlashblockDeltaV1, Metadata};
+    use alloy_primitives::{Bloom, B256};
+    use alloy_rpc_types_engine::PayloadId;
+    use futures_util::SinkExt;
+    use tokio::net::TcpListener;
+
+    fn flashblock(index: u64) -> FlashBlock {
+        FlashBlock {
+            payload_id: PayloadId::new([1; 8]),
+            index,
+            base: None,
+            diff: ExecutionPayloadFlashblockDeltaV1 {
+                state_root: B256::ZERO,
+                receipts_root: B256::ZERO,
+                logs_bloom: Bloom::ZERO,
+                gas_used: 0,
+                block_hash: B256::ZERO,
+                transactions: Vec::new(),
+                withdrawals: Vec::new(),
+                withdrawals_root: B256::ZERO,
+            },
+            metadata: Metadata { block_number: 1 },
+        }
+    }
+
+    #[tokio::test]
+    async fn stream_flashblocks_of_mock_feed() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+
+        // the mock feed sends one flashblock per connection, after an invalid message
+        tokio::spawn(async move {
+            for index in 0.. {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                ws.send(Message::text("not a flashblock")).await.unwrap();
+                let flashblock = serde_json::to_vec(&flashblock(index)).unwrap();
+                ws.send(Message::binary(flashblock)).await.unwrap();
+                ws.close(None).await.unwrap();
+            }
+        });
+
+        let mut stream = WsFlashBlockStream::new(url);
+        assert_eq!(stream.next().await, Some(flashblock(0)));
+        // reconnects once the feed closes the connection
+        assert_eq!(stream.next().await, Some(flashblock(1)));
+    }
+}
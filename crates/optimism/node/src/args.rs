@@ -3,7 +3,16 @@
 //! clap [Args](clap::Args) for optimism rollup configuration
 
 use op_alloy_consensus::interop::SafetyLevel;
+use reth_optimism_rpc::{
+    sequencer::{
+        DEFAULT_SEQUENCER_HEALTH_CHECK_INTERVAL, DEFAULT_SEQUENCER_MAX_RETRIES,
+        DEFAULT_SEQUENCER_REQUEST_TIMEOUT,
+    },
+    SequencerClientConfig,
+};
 use reth_optimism_txpool::supervisor::DEFAULT_SUPERVISOR_URL;
+use std::time::Duration;
+use url::Url;
 
 /// Parameters for rollup configuration
 #[derive(Debug, Clone, PartialEq, Eq, clap::Args)]
@@ -55,6 +64,40 @@ pub struct RollupArgs {
     #[arg(long = "rollup.sequencer-headers", requires = "sequencer")]
     pub sequencer_headers: Vec<String>,
 
+    /// Endpoints to forward transactions to while the sequencer endpoint is unreachable, in order
+    /// of priority (can be both HTTP and WS)
+    #[arg(
+        long = "rollup.sequencer-fallback",
+        value_name = "SEQUENCER_URL",
+        value_delimiter = ',',
+        requires = "sequencer"
+    )]
+    pub sequencer_fallbacks: Vec<String>,
+
+    /// Interval between the health probes of the sequencer endpoints.
+    ///
+    /// Transactions are forwarded to the first healthy endpoint.
+    #[arg(
+        long = "rollup.sequencer-health-check-interval",
+        value_parser = humantime::parse_duration,
+        default_value = "5s"
+    )]
+    pub sequencer_health_check_interval: Duration,
+
+    /// Number of times forwarding a transaction is retried on all sequencer endpoints before it
+    /// fails.
+    #[arg(long = "rollup.sequencer-max-retries", default_value_t = DEFAULT_SEQUENCER_MAX_RETRIES)]
+    pub sequencer_max_retries: u32,
+
+    /// Time after which forwarding a transaction to a sequencer endpoint is considered failed,
+    /// and the transaction is forwarded to the next endpoint.
+    #[arg(
+        long = "rollup.sequencer-request-timeout",
+        value_parser = humantime::parse_duration,
+        default_value = "10s"
+    )]
+    pub sequencer_request_timeout: Duration,
+
     /// RPC endpoint for historical data.
     #[arg(
         long = "rollup.historicalrpc",
@@ -66,6 +109,13 @@ pub struct RollupArgs {
     /// Minimum suggested priority fee (tip) in wei, default `1_000_000`
     #[arg(long, default_value_t = 1_000_000)]
     pub min_suggested_priority_fee: u64,
+
+    /// Websocket URL of the flashblocks feed of the sequencer.
+    ///
+    /// If set, the flashblocks the sequencer streams while building a block are executed on top
+    /// of the latest block, and served as the pending block and state.
+    #[arg(long = "rollup.flashblocks-url", value_name = "FLASHBLOCKS_URL")]
+    pub flashblocks_url: Option<Url>,
 }
 
 impl Default for RollupArgs {
@@ -79,8 +129,25 @@ impl Default for RollupArgs {
             supervisor_http: DEFAULT_SUPERVISOR_URL.to_string(),
             supervisor_safety_level: SafetyLevel::CrossUnsafe,
             sequencer_headers: Vec::new(),
+            sequencer_fallbacks: Vec::new(),
+            sequencer_health_check_interval: DEFAULT_SEQUENCER_HEALTH_CHECK_INTERVAL,
+            sequencer_max_retries: DEFAULT_SEQUENCER_MAX_RETRIES,
+            sequencer_request_timeout: DEFAULT_SEQUENCER_REQUEST_TIMEOUT,
             historical_rpc: None,
             min_suggested_priority_fee: 1_000_000,
+            flashblocks_url: None,
+        }
+    }
+}
+
+impl RollupArgs {
+    /// Returns the failover configuration of the sequencer client.
+    pub fn sequencer_client_config(&self) -> SequencerClientConfig {
+        SequencerClientConfig {
+            fallback_endpoints: self.sequencer_fallbacks.clone(),
+            health_check_interval: self.sequencer_health_check_interval,
+            max_retries: self.sequencer_max_retries,
+            request_timeout: self.sequencer_request_timeout,
         }
     }
 }
@@ -133,6 +200,41 @@ mod tests {
         assert_eq!(args, expected_args);
     }
 
+    #[test]
+    fn test_parse_optimism_sequencer_failover_args() {
+        let expected_args = RollupArgs {
+            sequencer: Some("http://host:port".into()),
+            sequencer_fallbacks: vec!["http://fallback1:port".into(), "ws://fallback2:port".into()],
+            sequencer_health_check_interval: Duration::from_secs(10),
+            sequencer_max_retries: 5,
+            sequencer_request_timeout: Duration::from_secs(2),
+            ..Default::default()
+        };
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.sequencer",
+            "http://host:port",
+            "--rollup.sequencer-fallback",
+            "http://fallback1:port,ws://fallback2:port",
+            "--rollup.sequencer-health-check-interval",
+            "10s",
+            "--rollup.sequencer-max-retries",
+            "5",
+            "--rollup.sequencer-request-timeout",
+            "2s",
+        ])
+        .args;
+        assert_eq!(args, expected_args);
+
+        // fallbacks require a primary sequencer endpoint
+        assert!(CommandParser::<RollupArgs>::try_parse_from([
+            "reth",
+            "--rollup.sequencer-fallback",
+            "http://fallback1:port"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_parse_optimism_disable_txpool_args() {
         let expected_args = RollupArgs { disable_txpool_gossip: true, ..Default::default() };
@@ -151,6 +253,28 @@ mod tests {
         assert_eq!(args, expected_args);
     }
 
+    #[test]
+    fn test_parse_optimism_flashblocks_args() {
+        let expected_args = RollupArgs {
+            flashblocks_url: Some("wss://flashblocks.example.com".parse().unwrap()),
+            ..Default::default()
+        };
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.flashblocks-url",
+            "wss://flashblocks.example.com",
+        ])
+        .args;
+        assert_eq!(args, expected_args);
+
+        assert!(CommandParser::<RollupArgs>::try_parse_from([
+            "reth",
+            "--rollup.flashblocks-url",
+            "not a url"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_parse_optimism_many_args() {
         let expected_args = RollupArgs {
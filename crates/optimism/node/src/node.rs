@@ -47,7 +47,7 @@ use reth_optimism_rpc::{
     historical::{HistoricalRpc, HistoricalRpcClient},
     miner::{MinerApiExtServer, OpMinerExtApi},
     witness::{DebugExecutionWitnessApiServer, OpDebugWitnessApi},
-    SequencerClient,
+    SequencerClient, SequencerClientConfig,
 };
 use reth_optimism_storage::OpStorage;
 use reth_optimism_txpool::{
@@ -65,6 +65,7 @@ use reth_transaction_pool::{
 use reth_trie_db::MerklePatriciaTrie;
 use serde::de::DeserializeOwned;
 use std::{marker::PhantomData, sync::Arc};
+use url::Url;
 
 /// Marker trait for Optimism node types with standard engine, chain spec, and primitives.
 pub trait OpNodeTypes:
@@ -170,10 +171,12 @@ impl OpNode {
         OpAddOnsBuilder::default()
             .with_sequencer(self.args.sequencer.clone())
             .with_sequencer_headers(self.args.sequencer_headers.clone())
+            .with_sequencer_config(self.args.sequencer_client_config())
             .with_da_config(self.da_config.clone())
             .with_enable_tx_conditional(self.args.enable_tx_conditional)
             .with_min_suggested_priority_fee(self.args.min_suggested_priority_fee)
             .with_historical_rpc(self.args.historical_rpc.clone())
+            .with_flashblocks(self.args.flashblocks_url.clone())
     }
 
     /// Instantiates the [`ProviderFactoryBuilder`] for an opstack node.
@@ -282,6 +285,8 @@ pub struct OpAddOns<N: FullNodeComponents, EthB: EthApiBuilder<N>, EV, EB, RpcMi
     pub sequencer_url: Option<String>,
     /// Headers to use for the sequencer client requests.
     pub sequencer_headers: Vec<String>,
+    /// Failover configuration of the sequencer client.
+    pub sequencer_config: SequencerClientConfig,
     /// RPC endpoint for historical data.
     ///
     /// This can be used to forward pre-bedrock rpc requests (op-mainnet).
@@ -341,6 +346,7 @@ where
             da_config,
             sequencer_url,
             sequencer_headers,
+            sequencer_config,
             historical_rpc,
             enable_tx_conditional,
             min_suggested_priority_fee,
@@ -350,6 +356,7 @@ where
             da_config,
             sequencer_url,
             sequencer_headers,
+            sequencer_config,
             enable_tx_conditional,
             historical_rpc,
             min_suggested_priority_fee,
@@ -366,6 +373,7 @@ where
             da_config,
             sequencer_url,
             sequencer_headers,
+            sequencer_config,
             enable_tx_conditional,
             min_suggested_priority_fee,
             historical_rpc,
@@ -375,6 +383,7 @@ where
             da_config,
             sequencer_url,
             sequencer_headers,
+            sequencer_config,
             enable_tx_conditional,
             min_suggested_priority_fee,
             historical_rpc,
@@ -394,6 +403,7 @@ where
             da_config,
             sequencer_url,
             sequencer_headers,
+            sequencer_config,
             enable_tx_conditional,
             min_suggested_priority_fee,
             historical_rpc,
@@ -403,6 +413,7 @@ where
             da_config,
             sequencer_url,
             sequencer_headers,
+            sequencer_config,
             enable_tx_conditional,
             min_suggested_priority_fee,
             historical_rpc,
@@ -465,6 +476,7 @@ where
             da_config,
             sequencer_url,
             sequencer_headers,
+            sequencer_config,
             enable_tx_conditional,
             historical_rpc,
             ..
@@ -506,7 +518,10 @@ where
         let miner_ext = OpMinerExtApi::new(da_config);
 
         let sequencer_client = if let Some(url) = sequencer_url {
-            Some(SequencerClient::new_with_headers(url, sequencer_headers).await?)
+            let client =
+                SequencerClient::new_with_config(url, sequencer_headers, sequencer_config).await?;
+            ctx.node.task_executor().spawn(client.health_checks());
+            Some(client)
         } else {
             None
         };
@@ -613,6 +628,8 @@ pub struct OpAddOnsBuilder<NetworkT, RpcMiddleware = Identity> {
     sequencer_url: Option<String>,
     /// Headers to use for the sequencer client requests.
     sequencer_headers: Vec<String>,
+    /// Failover configuration of the sequencer client.
+    sequencer_config: SequencerClientConfig,
     /// RPC endpoint for historical data.
     historical_rpc: Option<String>,
     /// Data availability configuration for the OP builder.
@@ -623,6 +640,8 @@ pub struct OpAddOnsBuilder<NetworkT, RpcMiddleware = Identity> {
     _nt: PhantomData<NetworkT>,
     /// Minimum suggested priority fee (tip)
     min_suggested_priority_fee: u64,
+    /// Websocket URL of the flashblocks feed of the sequencer.
+    flashblocks_url: Option<Url>,
     /// RPC middleware to use
     rpc_middleware: RpcMiddleware,
 }
@@ -632,10 +651,12 @@ impl<NetworkT> Default for OpAddOnsBuilder<NetworkT> {
         Self {
             sequencer_url: None,
             sequencer_headers: Vec::new(),
+            sequencer_config: SequencerClientConfig::default(),
             historical_rpc: None,
             da_config: None,
             enable_tx_conditional: false,
             min_suggested_priority_fee: 1_000_000,
+            flashblocks_url: None,
             _nt: PhantomData,
             rpc_middleware: Identity::new(),
         }
@@ -655,6 +676,12 @@ impl<NetworkT, RpcMiddleware> OpAddOnsBuilder<NetworkT, RpcMiddleware> {
         self
     }
 
+    /// With the fallback endpoints, health probes and retries of the sequencer client.
+    pub fn with_sequencer_config(mut self, sequencer_config: SequencerClientConfig) -> Self {
+        self.sequencer_config = sequencer_config;
+        self
+    }
+
     /// Configure the data availability configuration for the OP builder.
     pub fn with_da_config(mut self, da_config: OpDAConfig) -> Self {
         self.da_config = Some(da_config);
@@ -679,25 +706,35 @@ impl<NetworkT, RpcMiddleware> OpAddOnsBuilder<NetworkT, RpcMiddleware> {
         self
     }
 
+    /// Configures the flashblocks feed of the sequencer to build the pending block from.
+    pub fn with_flashblocks(mut self, flashblocks_url: Option<Url>) -> Self {
+        self.flashblocks_url = flashblocks_url;
+        self
+    }
+
     /// Configure the RPC middleware to use
     pub fn with_rpc_middleware<T>(self, rpc_middleware: T) -> OpAddOnsBuilder<NetworkT, T> {
         let Self {
             sequencer_url,
             sequencer_headers,
+            sequencer_config,
             historical_rpc,
             da_config,
             enable_tx_conditional,
             min_suggested_priority_fee,
+            flashblocks_url,
             _nt,
             ..
         } = self;
         OpAddOnsBuilder {
             sequencer_url,
             sequencer_headers,
+            sequencer_config,
             historical_rpc,
             da_config,
             enable_tx_conditional,
             min_suggested_priority_fee,
+            flashblocks_url,
             _nt,
             rpc_middleware,
         }
@@ -716,10 +753,12 @@ impl<NetworkT, RpcMiddleware> OpAddOnsBuilder<NetworkT, RpcMiddleware> {
         let Self {
             sequencer_url,
             sequencer_headers,
+            sequencer_config,
             da_config,
             enable_tx_conditional,
             min_suggested_priority_fee,
             historical_rpc,
+            flashblocks_url,
             rpc_middleware,
             ..
         } = self;
@@ -729,7 +768,9 @@ impl<NetworkT, RpcMiddleware> OpAddOnsBuilder<NetworkT, RpcMiddleware> {
                 OpEthApiBuilder::default()
                     .with_sequencer(sequencer_url.clone())
                     .with_sequencer_headers(sequencer_headers.clone())
-                    .with_min_suggested_priority_fee(min_suggested_priority_fee),
+                    .with_sequencer_config(sequencer_config.clone())
+                    .with_min_suggested_priority_fee(min_suggested_priority_fee)
+                    .with_flashblocks(flashblocks_url),
                 EV::default(),
                 EB::default(),
                 rpc_middleware,
@@ -737,6 +778,7 @@ impl<NetworkT, RpcMiddleware> OpAddOnsBuilder<NetworkT, RpcMiddleware> {
             da_config: da_config.unwrap_or_default(),
             sequencer_url,
             sequencer_headers,
+            sequencer_config,
             historical_rpc,
             enable_tx_conditional,
             min_suggested_priority_fee,
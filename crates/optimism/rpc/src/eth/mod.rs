@@ -10,15 +10,24 @@ mod pending_block;
 
 use crate::{
     eth::{receipt::OpReceiptConverter, transaction::OpTxInfoMapper},
-    OpEthApiError, SequencerClient,
+    OpEthApiError, SequencerClient, SequencerClientConfig,
 };
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockId;
 use alloy_primitives::U256;
 use eyre::WrapErr;
 use op_alloy_network::Optimism;
 pub use receipt::{OpReceiptBuilder, OpReceiptFieldsBuilder};
+use reth_chain_state::{
+    ExecutedBlockWithTrieUpdates, ExecutedTrieUpdates, MemoryOverlayStateProvider,
+};
 use reth_evm::ConfigureEvm;
-use reth_node_api::{FullNodeComponents, FullNodeTypes, HeaderTy};
+use reth_node_api::{FullNodeComponents, FullNodeTypes, HeaderTy, NodePrimitives, NodeTypes};
 use reth_node_builder::rpc::{EthApiBuilder, EthApiCtx};
+use reth_optimism_flashblocks::{
+    ExecutionPayloadBaseV1, FlashBlockService, PendingBlockRx, WsFlashBlockStream,
+};
+use reth_optimism_primitives::OpReceipt;
 use reth_rpc::eth::{core::EthApiInner, DevSigner};
 use reth_rpc_eth_api::{
     helpers::{
@@ -29,12 +38,14 @@ use reth_rpc_eth_api::{
     RpcNodeCoreExt, RpcTypes, SignableTxRequest,
 };
 use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle};
-use reth_storage_api::{ProviderHeader, ProviderTx};
+use reth_storage_api::{ProviderHeader, ProviderTx, StateProviderBox, StateProviderFactory};
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
     TaskSpawner,
 };
 use std::{fmt, fmt::Formatter, marker::PhantomData, sync::Arc};
+use tracing::info;
+use url::Url;
 
 /// Adapter for [`EthApiInner`], which holds all the data required to serve core `eth_` API.
 pub type EthApiNodeBackend<N, Rpc> = EthApiInner<N, Rpc>;
@@ -66,9 +77,14 @@ impl<N: RpcNodeCore, Rpc: RpcConvert> OpEthApi<N, Rpc> {
         eth_api: EthApiNodeBackend<N, Rpc>,
         sequencer_client: Option<SequencerClient>,
         min_suggested_priority_fee: U256,
+        pending_block_rx: Option<PendingBlockRx<N::Primitives>>,
     ) -> Self {
-        let inner =
-            Arc::new(OpEthApiInner { eth_api, sequencer_client, min_suggested_priority_fee });
+        let inner = Arc::new(OpEthApiInner {
+            eth_api,
+            sequencer_client,
+            min_suggested_priority_fee,
+            pending_block_rx,
+        });
         Self { inner }
     }
 
@@ -211,6 +227,22 @@ where
     N: RpcNodeCore,
     Rpc: RpcConvert<Primitives = N::Primitives>,
 {
+    /// Returns the state at the given [`BlockId`], where the pending state is the state after the
+    /// pending block built from flashblocks, if there is one.
+    fn state_at_block_id(&self, at: BlockId) -> Result<StateProviderBox, Self::Error> {
+        if at.is_pending() {
+            if let Some(pending) = self.pending_flashblock()? {
+                let parent = pending.recovered_block().parent_hash();
+                let historical = self.provider().history_by_block_hash(parent)?;
+                let pending = ExecutedBlockWithTrieUpdates {
+                    block: pending,
+                    trie: ExecutedTrieUpdates::Missing,
+                };
+                return Ok(MemoryOverlayStateProvider::new(historical, vec![pending]).boxed())
+            }
+        }
+        Ok(self.provider().state_by_block_id(at)?)
+    }
 }
 
 impl<N, Rpc> EthState for OpEthApi<N, Rpc>
@@ -269,6 +301,8 @@ pub struct OpEthApiInner<N: RpcNodeCore, Rpc: RpcConvert> {
     ///
     /// See also <https://github.com/ethereum-optimism/op-geth/blob/d4e0fe9bb0c2075a9bff269fb975464dd8498f75/eth/gasprice/optimism-gasprice.go#L38-L38>
     min_suggested_priority_fee: U256,
+    /// Pending block built from the flashblocks of the sequencer, if configured.
+    pending_block_rx: Option<PendingBlockRx<N::Primitives>>,
 }
 
 impl<N: RpcNodeCore, Rpc: RpcConvert> fmt::Debug for OpEthApiInner<N, Rpc> {
@@ -306,8 +340,12 @@ pub struct OpEthApiBuilder<NetworkT = Optimism> {
     sequencer_url: Option<String>,
     /// Headers to use for the sequencer client requests.
     sequencer_headers: Vec<String>,
+    /// Failover configuration of the sequencer client.
+    sequencer_config: SequencerClientConfig,
     /// Minimum suggested priority fee (tip)
     min_suggested_priority_fee: u64,
+    /// Websocket URL of the flashblocks feed of the sequencer.
+    flashblocks_url: Option<Url>,
     /// Marker for network types.
     _nt: PhantomData<NetworkT>,
 }
@@ -317,7 +355,9 @@ impl<NetworkT> Default for OpEthApiBuilder<NetworkT> {
         Self {
             sequencer_url: None,
             sequencer_headers: Vec::new(),
+            sequencer_config: SequencerClientConfig::new(),
             min_suggested_priority_fee: 1_000_000,
+            flashblocks_url: None,
             _nt: PhantomData,
         }
     }
@@ -329,7 +369,9 @@ impl<NetworkT> OpEthApiBuilder<NetworkT> {
         Self {
             sequencer_url: None,
             sequencer_headers: Vec::new(),
+            sequencer_config: SequencerClientConfig::new(),
             min_suggested_priority_fee: 1_000_000,
+            flashblocks_url: None,
             _nt: PhantomData,
         }
     }
@@ -346,16 +388,34 @@ impl<NetworkT> OpEthApiBuilder<NetworkT> {
         self
     }
 
+    /// With the fallback endpoints, health probes and retries of the sequencer client.
+    pub fn with_sequencer_config(mut self, sequencer_config: SequencerClientConfig) -> Self {
+        self.sequencer_config = sequencer_config;
+        self
+    }
+
     /// With minimum suggested priority fee (tip)
     pub const fn with_min_suggested_priority_fee(mut self, min: u64) -> Self {
         self.min_suggested_priority_fee = min;
         self
     }
+
+    /// With the websocket URL of the flashblocks feed of the sequencer, to build the pending block
+    /// from.
+    pub fn with_flashblocks(mut self, flashblocks_url: Option<Url>) -> Self {
+        self.flashblocks_url = flashblocks_url;
+        self
+    }
 }
 
 impl<N, NetworkT> EthApiBuilder<N> for OpEthApiBuilder<NetworkT>
 where
-    N: FullNodeComponents<Evm: ConfigureEvm<NextBlockEnvCtx: BuildPendingEnv<HeaderTy<N::Types>>>>,
+    N: FullNodeComponents<
+        Types: NodeTypes<Primitives: NodePrimitives<Receipt = OpReceipt>>,
+        Evm: ConfigureEvm<
+            NextBlockEnvCtx: BuildPendingEnv<HeaderTy<N::Types>> + From<ExecutionPayloadBaseV1>,
+        >,
+    >,
     NetworkT: RpcTypes,
     OpRpcConvert<N, NetworkT>: RpcConvert<Network = NetworkT>,
     OpEthApi<N, OpRpcConvert<N, NetworkT>>:
@@ -364,23 +424,51 @@ where
     type EthApi = OpEthApi<N, OpRpcConvert<N, NetworkT>>;
 
     async fn build_eth_api(self, ctx: EthApiCtx<'_, N>) -> eyre::Result<Self::EthApi> {
-        let Self { sequencer_url, sequencer_headers, min_suggested_priority_fee, .. } = self;
-        let rpc_converter =
-            RpcConverter::new(OpReceiptConverter::new(ctx.components.provider().clone()))
-                .with_mapper(OpTxInfoMapper::new(ctx.components.provider().clone()));
+        let Self {
+            sequencer_url,
+            sequencer_headers,
+            sequencer_config,
+            min_suggested_priority_fee,
+            flashblocks_url,
+            ..
+        } = self;
+
+        let pending_block_rx = flashblocks_url.map(|url| {
+            info!(target: "reth::cli", %url, "Building pending block from flashblocks");
+            let service = FlashBlockService::new(
+                WsFlashBlockStream::new(url),
+                ctx.components.evm_config().clone(),
+                ctx.components.provider().clone(),
+            );
+            let rx = service.subscribe();
+            ctx.components.task_executor().spawn_blocking(service.run());
+            rx
+        });
+
+        let rpc_converter = RpcConverter::new(
+            OpReceiptConverter::new(ctx.components.provider().clone())
+                .with_pending_block(pending_block_rx.clone()),
+        )
+        .with_mapper(OpTxInfoMapper::new(ctx.components.provider().clone()));
 
         let eth_api = ctx.eth_api_builder().with_rpc_converter(rpc_converter).build_inner();
 
         let sequencer_client = if let Some(url) = sequencer_url {
-            Some(
-                SequencerClient::new_with_headers(&url, sequencer_headers)
+            let client =
+                SequencerClient::new_with_config(&url, sequencer_headers, sequencer_config)
                     .await
-                    .wrap_err_with(|| "Failed to init sequencer client with: {url}")?,
-            )
+                    .wrap_err_with(|| "Failed to init sequencer client with: {url}")?;
+            ctx.components.task_executor().spawn(client.health_checks());
+            Some(client)
         } else {
             None
         };
 
-        Ok(OpEthApi::new(eth_api, sequencer_client, U256::from(min_suggested_priority_fee)))
+        Ok(OpEthApi::new(
+            eth_api,
+            sequencer_client,
+            U256::from(min_suggested_priority_fee),
+            pending_block_rx,
+        ))
     }
 }
@@ -3,17 +3,56 @@
 use std::sync::Arc;
 
 use crate::{OpEthApi, OpEthApiError};
+use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumberOrTag;
+use reth_chain_state::ExecutedBlock;
+use reth_evm::{ConfigureEvm, SpecFor};
 use reth_primitives_traits::RecoveredBlock;
 use reth_rpc_eth_api::{
     helpers::{pending_block::PendingEnvBuilder, LoadPendingBlock},
     FromEvmError, RpcConvert, RpcNodeCore,
 };
-use reth_rpc_eth_types::{EthApiError, PendingBlock};
+use reth_rpc_eth_types::{EthApiError, PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
 use reth_storage_api::{
-    BlockReader, BlockReaderIdExt, ProviderBlock, ProviderReceipt, ReceiptProvider,
+    errors::ProviderError, BlockNumReader, BlockReader, BlockReaderIdExt, ProviderBlock,
+    ProviderReceipt, ReceiptProvider,
 };
 
+impl<N, Rpc> OpEthApi<N, Rpc>
+where
+    N: RpcNodeCore,
+    Rpc: RpcConvert<Primitives = N::Primitives>,
+{
+    /// Returns the pending block built from the flashblocks of the sequencer, if it builds on the
+    /// latest block.
+    pub fn pending_flashblock(
+        &self,
+    ) -> Result<Option<ExecutedBlock<N::Primitives>>, OpEthApiError> {
+        let Some(rx) = self.inner.pending_block_rx.as_ref() else { return Ok(None) };
+        let Some(pending) = rx.borrow().clone() else { return Ok(None) };
+        let latest = self.provider().chain_info()?.best_hash;
+        Ok((pending.recovered_block().parent_hash() == latest).then_some(pending))
+    }
+
+    /// Returns the pending block built from flashblocks, with its receipts.
+    #[expect(clippy::type_complexity)]
+    fn pending_flashblock_and_receipts(
+        &self,
+    ) -> Result<
+        Option<(
+            Arc<RecoveredBlock<ProviderBlock<N::Provider>>>,
+            Arc<Vec<ProviderReceipt<N::Provider>>>,
+        )>,
+        OpEthApiError,
+    > {
+        Ok(self.pending_flashblock()?.map(|pending| {
+            let number = pending.recovered_block().number();
+            let receipts = pending.execution_output.receipts_by_block(number).to_vec();
+            (pending.recovered_block, Arc::new(receipts))
+        }))
+    }
+}
+
 impl<N, Rpc> LoadPendingBlock for OpEthApi<N, Rpc>
 where
     N: RpcNodeCore,
@@ -30,7 +69,47 @@ where
         self.inner.eth_api.pending_env_builder()
     }
 
-    /// Returns the locally built pending block
+    /// Configures the [`PendingBlockEnv`] for the pending block built from flashblocks, or for
+    /// the pending block of the provider, falling back to the `latest` block.
+    fn pending_block_env_and_cfg(
+        &self,
+    ) -> Result<
+        PendingBlockEnv<
+            ProviderBlock<Self::Provider>,
+            ProviderReceipt<Self::Provider>,
+            SpecFor<Self::Evm>,
+        >,
+        Self::Error,
+    > {
+        let pending = match self.pending_flashblock_and_receipts()? {
+            Some(pending) => Some(pending),
+            None => self
+                .provider()
+                .pending_block_and_receipts()?
+                .map(|(block, receipts)| (Arc::new(block), Arc::new(receipts))),
+        };
+        if let Some((block, receipts)) = pending {
+            let evm_env = self.evm_config().evm_env(block.header());
+            return Ok(PendingBlockEnv::new(
+                evm_env,
+                PendingBlockEnvOrigin::ActualPending(block, receipts),
+            ));
+        }
+
+        // no pending block yet, so we use the latest block and modify the env values that we can
+        let latest = self
+            .provider()
+            .latest_header()?
+            .ok_or(EthApiError::HeaderNotFound(BlockNumberOrTag::Latest.into()))?;
+        let evm_env = self
+            .evm_config()
+            .next_evm_env(&latest, &self.next_env_attributes(&latest)?)
+            .map_err(ProviderError::other)?;
+
+        Ok(PendingBlockEnv::new(evm_env, PendingBlockEnvOrigin::DerivedFromLatest(latest)))
+    }
+
+    /// Returns the pending block built from flashblocks, or the latest block.
     async fn local_pending_block(
         &self,
     ) -> Result<
@@ -40,6 +119,10 @@ where
         )>,
         Self::Error,
     > {
+        if let Some(pending) = self.pending_flashblock_and_receipts()? {
+            return Ok(Some(pending))
+        }
+
         // See: <https://github.com/ethereum-optimism/op-geth/blob/f2e69450c6eec9c35d56af91389a1c47737206ca/miner/worker.go#L367-L375>
         let latest = self
             .provider()
@@ -1,6 +1,7 @@
 //! Loads and formats OP receipt RPC response.
 
 use crate::{eth::RpcNodeCore, OpEthApi, OpEthApiError};
+use alloy_consensus::BlockHeader;
 use alloy_eips::eip2718::Encodable2718;
 use alloy_rpc_types_eth::{Log, TransactionReceipt};
 use op_alloy_consensus::{
@@ -10,6 +11,7 @@ use op_alloy_rpc_types::{L1BlockInfo, OpTransactionReceipt, OpTransactionReceipt
 use reth_chainspec::ChainSpecProvider;
 use reth_node_api::NodePrimitives;
 use reth_optimism_evm::RethL1BlockInfo;
+use reth_optimism_flashblocks::PendingBlockRx;
 use reth_optimism_forks::OpHardforks;
 use reth_optimism_primitives::OpReceipt;
 use reth_primitives_traits::Block;
@@ -19,7 +21,7 @@ use reth_rpc_eth_api::{
     RpcConvert,
 };
 use reth_rpc_eth_types::{receipt::build_receipt, EthApiError};
-use reth_storage_api::BlockReader;
+use reth_storage_api::{BlockReader, NodePrimitivesProvider};
 use std::fmt::Debug;
 
 impl<N, Rpc> LoadReceipt for OpEthApi<N, Rpc>
@@ -31,21 +33,36 @@ where
 
 /// Converter for OP receipts.
 #[derive(Debug, Clone)]
-pub struct OpReceiptConverter<Provider> {
+pub struct OpReceiptConverter<Provider: NodePrimitivesProvider> {
     provider: Provider,
+    /// Pending block built from flashblocks, whose receipts can't be looked up by the provider.
+    pending_block: Option<PendingBlockRx<Provider::Primitives>>,
 }
 
-impl<Provider> OpReceiptConverter<Provider> {
+impl<Provider: NodePrimitivesProvider> OpReceiptConverter<Provider> {
     /// Creates a new [`OpReceiptConverter`].
     pub const fn new(provider: Provider) -> Self {
-        Self { provider }
+        Self { provider, pending_block: None }
+    }
+
+    /// Sets the pending block built from flashblocks.
+    pub fn with_pending_block(
+        mut self,
+        pending_block: Option<PendingBlockRx<Provider::Primitives>>,
+    ) -> Self {
+        self.pending_block = pending_block;
+        self
     }
 }
 
 impl<Provider, N> ReceiptConverter<N> for OpReceiptConverter<Provider>
 where
     N: NodePrimitives<SignedTx: OpTransaction, Receipt = OpReceipt>,
-    Provider: BlockReader + ChainSpecProvider<ChainSpec: OpHardforks> + Debug + 'static,
+    Provider: BlockReader
+        + NodePrimitivesProvider<Primitives = N>
+        + ChainSpecProvider<ChainSpec: OpHardforks>
+        + Debug
+        + 'static,
 {
     type RpcReceipt = OpTransactionReceipt;
     type Error = OpEthApiError;
@@ -58,12 +75,24 @@ where
             return Ok(Vec::new());
         };
 
-        let block = self
-            .provider
-            .block_by_number(block_number)?
-            .ok_or(EthApiError::HeaderNotFound(block_number.into()))?;
+        let pending_block = self.pending_block.as_ref().and_then(|rx| {
+            rx.borrow()
+                .as_ref()
+                .filter(|pending| pending.recovered_block().number() == block_number)
+                .map(|pending| pending.recovered_block.clone())
+        });
+        let l1_block_info = match pending_block {
+            Some(block) => reth_optimism_evm::extract_l1_info(block.body()),
+            None => {
+                let block = self
+                    .provider
+                    .block_by_number(block_number)?
+                    .ok_or(EthApiError::HeaderNotFound(block_number.into()))?;
+                reth_optimism_evm::extract_l1_info(block.body())
+            }
+        };
 
-        let mut l1_block_info = match reth_optimism_evm::extract_l1_info(block.body()) {
+        let mut l1_block_info = match l1_block_info {
             Ok(l1_block_info) => l1_block_info,
             Err(err) => {
                 // If it is the genesis block (i.e block number is 0), there is no L1 info, so
@@ -1,21 +1,27 @@
 //! Loads and formats OP transaction RPC response.
 
 use crate::{OpEthApi, OpEthApiError, SequencerClient};
+use alloy_consensus::{BlockHeader, TxReceipt};
 use alloy_primitives::{Bytes, B256};
 use alloy_rpc_types_eth::TransactionInfo;
 use op_alloy_consensus::{transaction::OpTransactionInfo, OpTransaction};
 use reth_optimism_primitives::DepositReceipt;
-use reth_primitives_traits::SignedTransaction;
+use reth_primitives_traits::{SignedTransaction, TransactionMeta};
 use reth_rpc_eth_api::{
-    helpers::{spec::SignersForRpc, EthTransactions, LoadTransaction},
-    try_into_op_tx_info, FromEthApiError, RpcConvert, RpcNodeCore, TxInfoMapper,
+    helpers::{spec::SignersForRpc, EthTransactions, LoadReceipt, LoadTransaction},
+    transaction::ConvertReceiptInput,
+    try_into_op_tx_info, EthApiTypes, FromEthApiError, RpcConvert, RpcNodeCore, RpcReceipt,
+    TxInfoMapper,
 };
 use reth_rpc_eth_types::utils::recover_raw_transaction;
 use reth_storage_api::{errors::ProviderError, ReceiptProvider};
 use reth_transaction_pool::{
     AddedTransactionOutcome, PoolTransaction, TransactionOrigin, TransactionPool,
 };
-use std::fmt::{Debug, Formatter};
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Formatter},
+};
 
 impl<N, Rpc> EthTransactions for OpEthApi<N, Rpc>
 where
@@ -65,6 +71,55 @@ where
 
         Ok(hash)
     }
+
+    /// Returns the transaction receipt for the given hash, including the receipts of the
+    /// transactions of the pending block built from flashblocks.
+    async fn transaction_receipt(
+        &self,
+        hash: B256,
+    ) -> Result<Option<RpcReceipt<Self::NetworkTypes>>, Self::Error>
+    where
+        Self: LoadReceipt + 'static,
+    {
+        if let Some((tx, meta, receipt)) = self.load_transaction_and_receipt(hash).await? {
+            return self.build_transaction_receipt(tx, meta, receipt).await.map(Some)
+        }
+
+        let Some(pending) = self.pending_flashblock()? else { return Ok(None) };
+        let block = pending.recovered_block();
+        let Some((index, tx)) =
+            block.transactions_recovered().enumerate().find(|(_, tx)| *tx.tx_hash() == hash)
+        else {
+            return Ok(None)
+        };
+        let receipts = pending.execution_output.receipts_by_block(block.number());
+        let Some(receipt) = receipts.get(index) else { return Ok(None) };
+
+        let previous = &receipts[..index];
+        let gas_used =
+            previous.last().map(|receipt| receipt.cumulative_gas_used()).unwrap_or_default();
+        let next_log_index = previous.iter().map(|receipt| receipt.logs().len()).sum();
+        let meta = TransactionMeta {
+            tx_hash: hash,
+            index: index as u64,
+            block_hash: block.hash(),
+            block_number: block.number(),
+            base_fee: block.base_fee_per_gas(),
+            excess_blob_gas: block.excess_blob_gas(),
+            timestamp: block.timestamp(),
+        };
+
+        Ok(self
+            .tx_resp_builder()
+            .convert_receipts(vec![ConvertReceiptInput {
+                receipt: Cow::Borrowed(receipt),
+                tx,
+                gas_used: receipt.cumulative_gas_used() - gas_used,
+                next_log_index,
+                meta,
+            }])?
+            .pop())
+    }
 }
 
 impl<N, Rpc> LoadTransaction for OpEthApi<N, Rpc>
@@ -21,4 +21,4 @@ pub use engine::OpEngineApiClient;
 pub use engine::{OpEngineApi, OpEngineApiServer, OP_ENGINE_CAPABILITIES};
 pub use error::{OpEthApiError, OpInvalidTransactionError, SequencerClientError};
 pub use eth::{OpEthApi, OpEthApiBuilder, OpReceiptBuilder};
-pub use sequencer::SequencerClient;
+pub use sequencer::{SequencerClient, SequencerClientConfig};
@@ -1,15 +1,47 @@
 //! Helpers for optimism specific RPC implementations.
 
 use crate::SequencerClientError;
-use alloy_json_rpc::{RpcRecv, RpcSend};
-use alloy_primitives::{hex, B256};
+use alloy_json_rpc::{RpcError, RpcRecv, RpcSend};
+use alloy_primitives::{hex, B256, U64};
 use alloy_rpc_client::{BuiltInConnectionString, ClientBuilder, RpcClient as Client};
 use alloy_rpc_types_eth::erc4337::TransactionConditional;
+use alloy_transport::{TransportErrorKind, TransportResult};
 use alloy_transport_http::Http;
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
 use reth_optimism_txpool::supervisor::metrics::SequencerMetrics;
-use std::{str::FromStr, sync::Arc, time::Instant};
+use std::{
+    future::Future,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 use thiserror::Error;
-use tracing::warn;
+use tokio::{
+    sync::OnceCell,
+    time::{interval, sleep, timeout, MissedTickBehavior},
+};
+use tracing::{debug, info, warn};
+
+/// Default interval between the health probes of the sequencer endpoints.
+pub const DEFAULT_SEQUENCER_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default number of times a request is retried on all sequencer endpoints before it fails.
+pub const DEFAULT_SEQUENCER_MAX_RETRIES: u32 = 3;
+
+/// Default time after which a request to a sequencer endpoint is considered failed.
+pub const DEFAULT_SEQUENCER_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time to wait before the first retry of a request, doubled for every following retry.
+const RETRY_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Time after which a health probe of an endpoint is considered failed.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// Sequencer client error
 #[derive(Error, Debug)]
@@ -39,17 +71,65 @@ pub enum Error {
     ),
 }
 
+/// Configures the failover of a [`SequencerClient`] between its endpoints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequencerClientConfig {
+    /// Endpoints to send requests to when the primary endpoint is unreachable, in order of
+    /// priority.
+    pub fallback_endpoints: Vec<String>,
+    /// Interval between the health probes of the endpoints.
+    pub health_check_interval: Duration,
+    /// Number of times a request is retried on all endpoints before it fails.
+    pub max_retries: u32,
+    /// Time after which a request to an endpoint is considered failed, and sent to the next
+    /// endpoint.
+    pub request_timeout: Duration,
+}
+
+impl SequencerClientConfig {
+    /// Creates a config without fallback endpoints, and the default health probe interval, number
+    /// of retries and request timeout.
+    pub const fn new() -> Self {
+        Self {
+            fallback_endpoints: Vec::new(),
+            health_check_interval: DEFAULT_SEQUENCER_HEALTH_CHECK_INTERVAL,
+            max_retries: DEFAULT_SEQUENCER_MAX_RETRIES,
+            request_timeout: DEFAULT_SEQUENCER_REQUEST_TIMEOUT,
+        }
+    }
+}
+
+impl Default for SequencerClientConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// A client to interact with a Sequencer
+///
+/// The client sends requests to the primary endpoint, and fails over to the fallback endpoints,
+/// in order, while the primary endpoint is unreachable. Requests are only sent to endpoints that
+/// failed a request or a health probe (see [`SequencerClient::health_checks`]) once all other
+/// endpoints failed as well. Requests that don't complete within the request timeout count as
+/// failed. If no endpoint is reachable, the request is retried with an exponential backoff, so
+/// requests survive restarts of the sequencer.
+///
+/// Error responses of the sequencer, e.g. for invalid transactions, are returned as is.
 #[derive(Debug, Clone)]
 pub struct SequencerClient {
     inner: Arc<SequencerClientInner>,
 }
 
 impl SequencerClientInner {
-    /// Creates a new instance with the given endpoint and client.
-    pub(crate) fn new(sequencer_endpoint: String, client: Client) -> Self {
+    /// Creates a new instance with the given endpoints, ordered by priority.
+    fn new(endpoints: Vec<SequencerEndpoint>, config: &SequencerClientConfig) -> Self {
         let metrics = SequencerMetrics::default();
-        Self { sequencer_endpoint, client, metrics }
+        Self {
+            endpoints,
+            health_check_interval: config.health_check_interval,
+            max_retries: config.max_retries,
+            metrics,
+        }
     }
 }
 
@@ -68,38 +148,40 @@ impl SequencerClient {
         sequencer_endpoint: impl Into<String>,
         headers: Vec<String>,
     ) -> Result<Self, Error> {
-        let sequencer_endpoint = sequencer_endpoint.into();
-        let endpoint = BuiltInConnectionString::from_str(&sequencer_endpoint)?;
-        if let BuiltInConnectionString::Http(url) = endpoint {
-            let mut builder = reqwest::Client::builder()
-                // we force use tls to prevent native issues
-                .use_rustls_tls();
-
-            if !headers.is_empty() {
-                let mut header_map = reqwest::header::HeaderMap::new();
-                for header in headers {
-                    if let Some((key, value)) = header.split_once('=') {
-                        header_map.insert(
-                            key.trim()
-                                .parse::<reqwest::header::HeaderName>()
-                                .map_err(|err| Error::InvalidHeader(err.to_string()))?,
-                            value
-                                .trim()
-                                .parse::<reqwest::header::HeaderValue>()
-                                .map_err(|err| Error::InvalidHeader(err.to_string()))?,
-                        );
-                    }
-                }
-                builder = builder.default_headers(header_map);
-            }
+        Self::new_with_config(sequencer_endpoint, headers, SequencerClientConfig::default()).await
+    }
 
-            let client = builder.build()?;
-            Self::with_http_client(url, client)
-        } else {
-            let client = ClientBuilder::default().connect_with(endpoint).await?;
-            let inner = SequencerClientInner::new(sequencer_endpoint, client);
-            Ok(Self { inner: Arc::new(inner) })
+    /// Creates a new `SequencerClient` for the given primary URL with the given headers, failing
+    /// over to the fallback endpoints of the [`SequencerClientConfig`].
+    ///
+    /// The headers are sent to all endpoints. Websocket and IPC fallback endpoints are connected
+    /// on first use, so that the client can be created while they're unreachable.
+    pub async fn new_with_config(
+        sequencer_endpoint: impl Into<String>,
+        headers: Vec<String>,
+        config: SequencerClientConfig,
+    ) -> Result<Self, Error> {
+        let urls =
+            std::iter::once(sequencer_endpoint.into()).chain(config.fallback_endpoints.clone());
+        let mut endpoints = Vec::new();
+        for (index, url) in urls.enumerate() {
+            let is_http = matches!(
+                BuiltInConnectionString::from_str(&url)?,
+                BuiltInConnectionString::Http(_)
+            );
+            let client =
+                if index == 0 || is_http { Some(connect(&url, &headers).await?) } else { None };
+            endpoints.push(SequencerEndpoint::new(
+                index,
+                url,
+                headers.clone(),
+                client,
+                config.request_timeout,
+            ));
         }
+
+        let inner = SequencerClientInner::new(endpoints, &config);
+        Ok(Self { inner: Arc::new(inner) })
     }
 
     /// Creates a new [`SequencerClient`] with http transport with the given http client.
@@ -108,26 +190,45 @@ impl SequencerClient {
         client: reqwest::Client,
     ) -> Result<Self, Error> {
         let sequencer_endpoint: String = sequencer_endpoint.into();
-        let url = sequencer_endpoint
-            .parse()
-            .map_err(|_| Error::InvalidUrl(sequencer_endpoint.clone()))?;
-
-        let http_client = Http::with_client(client, url);
-        let is_local = http_client.guess_local();
-        let client = ClientBuilder::default().transport(http_client, is_local);
+        let client = http_client(&sequencer_endpoint, client)?;
 
-        let inner = SequencerClientInner::new(sequencer_endpoint, client);
+        let config = SequencerClientConfig::default();
+        let endpoints = vec![SequencerEndpoint::new(
+            0,
+            sequencer_endpoint,
+            Vec::new(),
+            Some(client),
+            config.request_timeout,
+        )];
+        let inner = SequencerClientInner::new(endpoints, &config);
         Ok(Self { inner: Arc::new(inner) })
     }
 
-    /// Returns the network of the client
+    /// Returns the endpoint requests are currently sent to.
     pub fn endpoint(&self) -> &str {
-        &self.inner.sequencer_endpoint
+        &self.active_endpoint().url
     }
 
-    /// Returns the client
+    /// Returns the client of the endpoint requests are currently sent to.
     pub fn client(&self) -> &Client {
-        &self.inner.client
+        self.active_endpoint().client.get().expect("active endpoint is connected")
+    }
+
+    /// Returns the first healthy and connected endpoint, or the primary endpoint if none is.
+    fn active_endpoint(&self) -> &SequencerEndpoint {
+        let endpoints = &self.inner.endpoints;
+        endpoints
+            .iter()
+            .find(|endpoint| endpoint.is_healthy() && endpoint.client.initialized())
+            .unwrap_or(&endpoints[0])
+    }
+
+    /// Returns the endpoints in the order requests are sent to them: the healthy endpoints
+    /// followed by the unhealthy endpoints, each by priority.
+    fn endpoints_by_priority(&self) -> impl Iterator<Item = &SequencerEndpoint> {
+        let (healthy, unhealthy): (Vec<_>, Vec<_>) =
+            self.inner.endpoints.iter().partition(|endpoint| endpoint.is_healthy());
+        healthy.into_iter().chain(unhealthy)
     }
 
     /// Returns a reference to the [`SequencerMetrics`] for tracking client metrics.
@@ -135,23 +236,77 @@ impl SequencerClient {
         &self.inner.metrics
     }
 
+    /// Returns a future that probes the health of all endpoints at the configured interval.
+    ///
+    /// This lets requests fail back to the endpoints of higher priority once they're reachable
+    /// again. The future resolves once all clones of the client are dropped.
+    pub fn health_checks(&self) -> impl Future<Output = ()> + Send + 'static {
+        let inner = Arc::downgrade(&self.inner);
+        let period = self.inner.health_check_interval;
+        async move {
+            let mut interval = interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let Some(inner) = inner.upgrade() else { break };
+                for endpoint in &inner.endpoints {
+                    endpoint.check_health().await;
+                }
+            }
+        }
+    }
+
     /// Sends a [`alloy_rpc_client::RpcCall`] request to the sequencer endpoint.
+    ///
+    /// The request is sent to the next endpoint if an endpoint is unreachable, and retried once
+    /// all endpoints failed, up to the configured number of retries.
     pub async fn request<Params: RpcSend, Resp: RpcRecv>(
         &self,
         method: &str,
         params: Params,
     ) -> Result<Resp, SequencerClientError> {
-        let resp =
-            self.client().request::<Params, Resp>(method.to_string(), params).await.inspect_err(
-                |err| {
-                    warn!(
-                        target: "rpc::sequencer",
-                        %err,
-                        "HTTP request to sequencer failed",
-                    );
-                },
-            )?;
-        Ok(resp)
+        let mut backoff = RETRY_BACKOFF;
+        let mut retries = 0;
+        loop {
+            let mut last_err = None;
+            for endpoint in self.endpoints_by_priority() {
+                match endpoint.request(method, params.clone()).await {
+                    Ok(resp) => return Ok(resp),
+                    Err(err @ RpcError::Transport(_)) => {
+                        warn!(
+                            target: "rpc::sequencer",
+                            %err,
+                            endpoint = endpoint.index,
+                            "Sequencer endpoint unreachable, failing over to next endpoint",
+                        );
+                        last_err = Some(err);
+                    }
+                    Err(err) => {
+                        warn!(
+                            target: "rpc::sequencer",
+                            %err,
+                            "HTTP request to sequencer failed",
+                        );
+                        return Err(err.into())
+                    }
+                }
+            }
+
+            let err = last_err.expect("sequencer client has at least one endpoint");
+            if retries == self.inner.max_retries {
+                warn!(
+                    target: "rpc::sequencer",
+                    %err,
+                    retries,
+                    "All sequencer endpoints unreachable",
+                );
+                return Err(err.into())
+            }
+            retries += 1;
+            debug!(target: "rpc::sequencer", retries, ?backoff, "Retrying request to sequencer");
+            sleep(backoff).await;
+            backoff *= 2;
+        }
     }
 
     /// Forwards a transaction to the sequencer endpoint.
@@ -195,18 +350,181 @@ impl SequencerClient {
 
 #[derive(Debug)]
 struct SequencerClientInner {
-    /// The endpoint of the sequencer
-    sequencer_endpoint: String,
-    /// The client
-    client: Client,
+    /// The endpoints of the sequencer, ordered by priority, starting with the primary endpoint
+    endpoints: Vec<SequencerEndpoint>,
+    /// Interval between the health probes of the endpoints
+    health_check_interval: Duration,
+    /// Number of times a request is retried on all endpoints
+    max_retries: u32,
     // Metrics for tracking sequencer forwarding
     metrics: SequencerMetrics,
 }
 
+/// An endpoint of the sequencer.
+#[derive(Debug)]
+struct SequencerEndpoint {
+    /// The position of the endpoint, `0` being the primary endpoint
+    index: usize,
+    /// The url of the endpoint
+    url: String,
+    /// The headers sent to the endpoint
+    headers: Vec<String>,
+    /// The client, connected on first use if the endpoint wasn't connected on startup
+    client: OnceCell<Client>,
+    /// Time after which a request to the endpoint is considered failed
+    request_timeout: Duration,
+    /// Whether the last request or health probe reached the endpoint
+    healthy: AtomicBool,
+    /// Metrics of the endpoint
+    metrics: SequencerEndpointMetrics,
+}
+
+impl SequencerEndpoint {
+    fn new(
+        index: usize,
+        url: String,
+        headers: Vec<String>,
+        client: Option<Client>,
+        request_timeout: Duration,
+    ) -> Self {
+        let metrics = SequencerEndpointMetrics::new_with_labels(&[("endpoint", index.to_string())]);
+        metrics.healthy.set(1.0);
+        Self {
+            index,
+            url,
+            headers,
+            client: OnceCell::new_with(client),
+            request_timeout,
+            healthy: AtomicBool::new(true),
+            metrics,
+        }
+    }
+
+    /// Returns the client of the endpoint, and connects to the endpoint if it isn't connected.
+    async fn client(&self) -> TransportResult<&Client> {
+        self.client
+            .get_or_try_init(|| async {
+                connect(&self.url, &self.headers).await.map_err(TransportErrorKind::custom)
+            })
+            .await
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    fn set_healthy(&self, healthy: bool) {
+        let index = self.index;
+        if self.healthy.swap(healthy, Ordering::Relaxed) != healthy {
+            if healthy {
+                info!(target: "rpc::sequencer", endpoint = index, "Sequencer endpoint recovered");
+            } else {
+                warn!(target: "rpc::sequencer", endpoint = index, "Sequencer endpoint unhealthy");
+            }
+        }
+        self.metrics.healthy.set(if healthy { 1.0 } else { 0.0 });
+    }
+
+    /// Sends the request to the endpoint, and marks the endpoint as unhealthy if it's
+    /// unreachable or doesn't respond within the request timeout.
+    async fn request<Params: RpcSend, Resp: RpcRecv>(
+        &self,
+        method: &str,
+        params: Params,
+    ) -> TransportResult<Resp> {
+        let start = Instant::now();
+        let request = async {
+            self.client().await?.request::<Params, Resp>(method.to_string(), params).await
+        };
+        let resp = timeout(self.request_timeout, request).await.unwrap_or_else(|_| {
+            Err(TransportErrorKind::custom_str("request to sequencer endpoint timed out"))
+        });
+        self.metrics.requests_total.increment(1);
+        self.metrics.request_duration_seconds.record(start.elapsed().as_secs_f64());
+
+        let reachable = !matches!(resp, Err(RpcError::Transport(_)));
+        if !reachable {
+            self.metrics.failed_requests_total.increment(1);
+        }
+        self.set_healthy(reachable);
+        resp
+    }
+
+    /// Probes the endpoint with an `eth_chainId` request.
+    ///
+    /// Any response, including an error response, means the endpoint is reachable.
+    async fn check_health(&self) {
+        let probe = async { self.client().await?.request_noparams::<U64>("eth_chainId").await };
+        let reachable = match timeout(HEALTH_CHECK_TIMEOUT, probe).await {
+            Ok(resp) => !matches!(resp, Err(RpcError::Transport(_))),
+            Err(_) => false,
+        };
+        self.set_healthy(reachable);
+    }
+}
+
+/// Connects to the sequencer endpoint at the given URL, with the given headers.
+async fn connect(sequencer_endpoint: &str, headers: &[String]) -> Result<Client, Error> {
+    let endpoint = BuiltInConnectionString::from_str(sequencer_endpoint)?;
+    if let BuiltInConnectionString::Http(url) = endpoint {
+        let mut builder = reqwest::Client::builder()
+            // we force use tls to prevent native issues
+            .use_rustls_tls();
+
+        if !headers.is_empty() {
+            let mut header_map = reqwest::header::HeaderMap::new();
+            for header in headers {
+                if let Some((key, value)) = header.split_once('=') {
+                    header_map.insert(
+                        key.trim()
+                            .parse::<reqwest::header::HeaderName>()
+                            .map_err(|err| Error::InvalidHeader(err.to_string()))?,
+                        value
+                            .trim()
+                            .parse::<reqwest::header::HeaderValue>()
+                            .map_err(|err| Error::InvalidHeader(err.to_string()))?,
+                    );
+                }
+            }
+            builder = builder.default_headers(header_map);
+        }
+
+        let client = builder.build()?;
+        http_client(url.as_str(), client)
+    } else {
+        Ok(ClientBuilder::default().connect_with(endpoint).await?)
+    }
+}
+
+/// Creates a [`Client`] with http transport for the given URL with the given http client.
+fn http_client(sequencer_endpoint: &str, client: reqwest::Client) -> Result<Client, Error> {
+    let url = sequencer_endpoint
+        .parse()
+        .map_err(|_| Error::InvalidUrl(sequencer_endpoint.to_string()))?;
+
+    let http_client = Http::with_client(client, url);
+    let is_local = http_client.guess_local();
+    Ok(ClientBuilder::default().transport(http_client, is_local))
+}
+
+/// Metrics of an endpoint of the [`SequencerClient`], labeled by the position of the endpoint.
+#[derive(Metrics)]
+#[metrics(scope = "optimism_rpc.sequencer")]
+struct SequencerEndpointMetrics {
+    /// Whether the endpoint is reachable
+    healthy: Gauge,
+    /// The number of requests sent to the endpoint
+    requests_total: Counter,
+    /// The number of requests that failed because the endpoint was unreachable
+    failed_requests_total: Counter,
+    /// The duration of requests to the endpoint
+    request_duration_seconds: Histogram,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_primitives::U64;
+    use jsonrpsee::{core::RpcResult, server::ServerBuilder, RpcModule};
 
     #[tokio::test]
     async fn test_http_body_str() {
@@ -280,4 +598,129 @@ mod tests {
             r#"{"method":"eth_sendRawTransactionConditional","params":["0x61626364",{"knownAccounts":{}}],"id":1,"jsonrpc":"2.0"}"#
         );
     }
+
+    #[tokio::test]
+    async fn test_fail_over_to_reachable_endpoint() {
+        let mut module = RpcModule::new(());
+        module
+            .register_method("eth_sendRawTransaction", |_, _, _| -> RpcResult<B256> {
+                Ok(B256::with_last_byte(1))
+            })
+            .unwrap();
+        module
+            .register_method("eth_chainId", |_, _, _| -> RpcResult<U64> { Ok(U64::from(10)) })
+            .unwrap();
+        let server = ServerBuilder::default().build("127.0.0.1:0").await.unwrap();
+        let fallback = format!("http://{}", server.local_addr().unwrap());
+        let _handle = server.start(module);
+
+        // nothing listens on the primary endpoint
+        let primary = "http://127.0.0.1:1";
+        let config = SequencerClientConfig {
+            fallback_endpoints: vec![fallback.clone()],
+            max_retries: 0,
+            ..Default::default()
+        };
+        let client = SequencerClient::new_with_config(primary, Vec::new(), config).await.unwrap();
+        assert_eq!(client.endpoint(), primary);
+
+        let tx_hash = client.forward_raw_transaction(&[1]).await.unwrap();
+        assert_eq!(tx_hash, B256::with_last_byte(1));
+        assert_eq!(client.endpoint(), fallback);
+
+        // the primary endpoint stays unhealthy until a probe reaches it
+        for endpoint in &client.inner.endpoints {
+            endpoint.check_health().await;
+        }
+        assert!(!client.inner.endpoints[0].is_healthy());
+        assert!(client.inner.endpoints[1].is_healthy());
+        assert_eq!(client.endpoint(), fallback);
+    }
+
+    #[tokio::test]
+    async fn test_fail_over_on_request_timeout() {
+        // the primary endpoint never responds to transactions
+        let mut module = RpcModule::new(());
+        module
+            .register_async_method("eth_sendRawTransaction", |_, _, _| async {
+                sleep(Duration::from_secs(60)).await;
+                RpcResult::<B256>::Ok(B256::ZERO)
+            })
+            .unwrap();
+        let server = ServerBuilder::default().build("127.0.0.1:0").await.unwrap();
+        let primary = format!("http://{}", server.local_addr().unwrap());
+        let _primary_handle = server.start(module);
+
+        let mut module = RpcModule::new(());
+        module
+            .register_method("eth_sendRawTransaction", |_, _, _| -> RpcResult<B256> {
+                Ok(B256::with_last_byte(1))
+            })
+            .unwrap();
+        let server = ServerBuilder::default().build("127.0.0.1:0").await.unwrap();
+        let fallback = format!("http://{}", server.local_addr().unwrap());
+        let _fallback_handle = server.start(module);
+
+        let config = SequencerClientConfig {
+            fallback_endpoints: vec![fallback.clone()],
+            max_retries: 0,
+            request_timeout: Duration::from_millis(100),
+            ..Default::default()
+        };
+        let client = SequencerClient::new_with_config(primary, Vec::new(), config).await.unwrap();
+
+        let tx_hash = timeout(Duration::from_secs(5), client.forward_raw_transaction(&[1]))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tx_hash, B256::with_last_byte(1));
+        assert!(!client.inner.endpoints[0].is_healthy());
+        assert_eq!(client.endpoint(), fallback);
+    }
+
+    #[tokio::test]
+    async fn test_connect_to_ws_fallback_on_first_use() {
+        let mut module = RpcModule::new(());
+        module
+            .register_method("eth_sendRawTransaction", |_, _, _| -> RpcResult<B256> {
+                Ok(B256::with_last_byte(1))
+            })
+            .unwrap();
+        let server = ServerBuilder::default().build("127.0.0.1:0").await.unwrap();
+        let primary = format!("http://{}", server.local_addr().unwrap());
+        let handle = server.start(module);
+
+        // nothing listens on the fallback endpoint, which doesn't prevent creating the client
+        let config = SequencerClientConfig {
+            fallback_endpoints: vec!["ws://127.0.0.1:1".to_string()],
+            max_retries: 0,
+            ..Default::default()
+        };
+        let client = SequencerClient::new_with_config(&primary, Vec::new(), config).await.unwrap();
+        assert!(client.inner.endpoints[1].client.get().is_none());
+
+        let tx_hash = client.forward_raw_transaction(&[1]).await.unwrap();
+        assert_eq!(tx_hash, B256::with_last_byte(1));
+        assert!(client.inner.endpoints[1].client.get().is_none());
+
+        // the fallback endpoint is connected once the primary endpoint is unreachable, and fails
+        handle.stop().unwrap();
+        handle.stopped().await;
+        let err = client.forward_raw_transaction(&[1]).await.unwrap_err();
+        assert!(matches!(err, SequencerClientError::HttpError(RpcError::Transport(_))));
+        assert!(!client.inner.endpoints[1].is_healthy());
+        assert_eq!(client.endpoint(), primary);
+    }
+
+    #[tokio::test]
+    async fn test_fail_without_reachable_endpoint() {
+        let config = SequencerClientConfig { max_retries: 1, ..Default::default() };
+        let client = SequencerClient::new_with_config("http://127.0.0.1:1", Vec::new(), config)
+            .await
+            .unwrap();
+
+        let err = client.forward_raw_transaction(&[1]).await.unwrap_err();
+        assert!(matches!(err, SequencerClientError::HttpError(RpcError::Transport(_))));
+        assert_eq!(client.endpoint(), "http://127.0.0.1:1");
+    }
 }
@@ -46,9 +46,6 @@ pub struct OpPooledTransaction<
     /// The pooled transaction type.
     _pd: core::marker::PhantomData<Pooled>,
 
-    /// Optional conditional attached to this transaction.
-    conditional: Option<Box<TransactionConditional>>,
-
     /// Optional interop deadline attached to this transaction.
     interop: Arc<AtomicU64>,
 
@@ -62,7 +59,6 @@ impl<Cons: SignedTransaction, Pooled> OpPooledTransaction<Cons, Pooled> {
         Self {
             inner: EthPooledTransaction::new(transaction, encoded_length),
             estimated_tx_compressed_size: Default::default(),
-            conditional: None,
             interop: Arc::new(AtomicU64::new(NO_INTEROP_TX)),
             _pd: core::marker::PhantomData,
             encoded_2718: Default::default(),
@@ -86,18 +82,18 @@ impl<Cons: SignedTransaction, Pooled> OpPooledTransaction<Cons, Pooled> {
 
     /// Conditional setter.
     pub fn with_conditional(mut self, conditional: TransactionConditional) -> Self {
-        self.conditional = Some(Box::new(conditional));
+        self.inner.set_conditional(conditional);
         self
     }
 }
 
 impl<Cons, Pooled> MaybeConditionalTransaction for OpPooledTransaction<Cons, Pooled> {
     fn set_conditional(&mut self, conditional: TransactionConditional) {
-        self.conditional = Some(Box::new(conditional))
+        self.inner.set_conditional(conditional)
     }
 
     fn conditional(&self) -> Option<&TransactionConditional> {
-        self.conditional.as_deref()
+        self.inner.conditional()
     }
 }
 
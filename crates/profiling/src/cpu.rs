@@ -0,0 +1,234 @@
+use crate::ProfilingError;
+use parking_lot::Mutex;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+/// Sampling frequency of the CPU profiler, in Hz.
+///
+/// Slightly below 100Hz, so that the samples don't line up with work scheduled every 10ms.
+pub const CPU_PROFILE_FREQUENCY: i32 = 99;
+
+/// Libraries that the profiler doesn't unwind through, because unwinding them from the signal
+/// handler can deadlock.
+#[cfg(unix)]
+const BLOCKLIST: &[&str] = &["libc", "libgcc", "pthread", "vdso"];
+
+#[cfg(unix)]
+type ProfilerGuard = pprof::ProfilerGuard<'static>;
+
+#[cfg(not(unix))]
+type ProfilerGuard = std::convert::Infallible;
+
+/// The CPU profile started with [`start_cpu_profile`], and the file it's written to.
+static ACTIVE_PROFILE: Mutex<Option<(ProfilerGuard, PathBuf)>> = Mutex::new(None);
+
+/// Output format of a [`CpuProfile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProfileFormat {
+    /// Protobuf encoded profile, readable with `go tool pprof`.
+    #[default]
+    Pprof,
+    /// SVG flamegraph.
+    Flamegraph,
+}
+
+impl ProfileFormat {
+    /// Returns [`ProfileFormat::Flamegraph`] for files with the `svg` extension, and
+    /// [`ProfileFormat::Pprof`] otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("svg")) {
+            Self::Flamegraph
+        } else {
+            Self::Pprof
+        }
+    }
+
+    /// Returns the content type of a profile encoded in this format.
+    pub const fn content_type(&self) -> &'static str {
+        match self {
+            Self::Pprof => "application/octet-stream",
+            Self::Flamegraph => "image/svg+xml",
+        }
+    }
+}
+
+impl fmt::Display for ProfileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pprof => write!(f, "pprof"),
+            Self::Flamegraph => write!(f, "flamegraph"),
+        }
+    }
+}
+
+impl FromStr for ProfileFormat {
+    type Err = ProfilingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pprof" | "proto" => Ok(Self::Pprof),
+            "flamegraph" | "svg" => Ok(Self::Flamegraph),
+            _ => Err(ProfilingError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+/// A collected CPU profile.
+pub struct CpuProfile {
+    #[cfg(unix)]
+    report: pprof::Report,
+    #[cfg(not(unix))]
+    report: std::convert::Infallible,
+}
+
+impl CpuProfile {
+    /// Builds the profile of the samples collected so far by the profiler.
+    #[cfg(unix)]
+    fn new(guard: &ProfilerGuard) -> Result<Self, ProfilingError> {
+        let report = guard.report().build().map_err(profiler_error)?;
+        Ok(Self { report })
+    }
+
+    #[cfg(not(unix))]
+    fn new(guard: &ProfilerGuard) -> Result<Self, ProfilingError> {
+        match *guard {}
+    }
+
+    /// Encodes the profile in the given format.
+    #[cfg(unix)]
+    pub fn encode(&self, format: ProfileFormat) -> Result<Vec<u8>, ProfilingError> {
+        use pprof::protos::Message;
+
+        match format {
+            ProfileFormat::Pprof => {
+                Ok(self.report.pprof().map_err(profiler_error)?.encode_to_vec())
+            }
+            ProfileFormat::Flamegraph => {
+                let mut flamegraph = Vec::new();
+                self.report.flamegraph(&mut flamegraph).map_err(profiler_error)?;
+                Ok(flamegraph)
+            }
+        }
+    }
+
+    /// Encodes the profile in the given format.
+    #[cfg(not(unix))]
+    pub fn encode(&self, _format: ProfileFormat) -> Result<Vec<u8>, ProfilingError> {
+        match self.report {}
+    }
+
+    /// Writes the profile to the given file, as a flamegraph if the file has the `svg` extension
+    /// and in the pprof format otherwise.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), ProfilingError> {
+        let path = path.as_ref();
+        std::fs::write(path, self.encode(ProfileFormat::from_path(path))?)?;
+        Ok(())
+    }
+}
+
+impl fmt::Debug for CpuProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CpuProfile").finish_non_exhaustive()
+    }
+}
+
+/// Starts the sampling profiler.
+#[cfg(unix)]
+fn start_profiler() -> Result<ProfilerGuard, ProfilingError> {
+    pprof::ProfilerGuardBuilder::default()
+        .frequency(CPU_PROFILE_FREQUENCY)
+        .blocklist(BLOCKLIST)
+        .build()
+        .map_err(|err| match err {
+            pprof::Error::Running => ProfilingError::CpuProfileInProgress,
+            err => profiler_error(err),
+        })
+}
+
+#[cfg(not(unix))]
+fn start_profiler() -> Result<ProfilerGuard, ProfilingError> {
+    Err(ProfilingError::Unsupported("CPU profiling is only supported on unix"))
+}
+
+#[cfg(unix)]
+fn profiler_error(err: pprof::Error) -> ProfilingError {
+    ProfilingError::Profiler(err.to_string())
+}
+
+/// Starts a CPU profile, which is written to the given file by [`stop_cpu_profile`].
+///
+/// Only one CPU profile can be in progress at a time.
+pub fn start_cpu_profile(path: impl Into<PathBuf>) -> Result<(), ProfilingError> {
+    let mut active = ACTIVE_PROFILE.lock();
+    if active.is_some() {
+        return Err(ProfilingError::CpuProfileInProgress)
+    }
+
+    let path = path.into();
+    *active = Some((start_profiler()?, path.clone()));
+    tracing::info!(target: "profiling", ?path, "Started CPU profile");
+    Ok(())
+}
+
+/// Stops the CPU profile started with [`start_cpu_profile`] and writes it to its file, see
+/// [`CpuProfile::write`].
+///
+/// Returns the path of the written file.
+pub fn stop_cpu_profile() -> Result<PathBuf, ProfilingError> {
+    let (guard, path) =
+        ACTIVE_PROFILE.lock().take().ok_or(ProfilingError::CpuProfileNotInProgress)?;
+    let profile = CpuProfile::new(&guard)?;
+    drop(guard);
+
+    profile.write(&path)?;
+    tracing::info!(target: "profiling", ?path, "Wrote CPU profile");
+    Ok(path)
+}
+
+/// Profiles the CPU for the given duration.
+///
+/// Fails if another CPU profile is in progress.
+pub async fn cpu_profile(duration: Duration) -> Result<CpuProfile, ProfilingError> {
+    let guard = start_profiler()?;
+    tokio::time::sleep(duration).await;
+    CpuProfile::new(&guard)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn cpu_profiles() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("cpu.svg");
+
+        // Only one profile can run at a time
+        start_cpu_profile(&path).unwrap();
+        assert!(matches!(start_cpu_profile(&path), Err(ProfilingError::CpuProfileInProgress)));
+        assert!(matches!(
+            cpu_profile(Duration::from_millis(10)).await,
+            Err(ProfilingError::CpuProfileInProgress)
+        ));
+
+        assert_eq!(stop_cpu_profile().unwrap(), path);
+        assert!(std::fs::read_to_string(&path).unwrap().contains("<svg"));
+        assert!(matches!(stop_cpu_profile(), Err(ProfilingError::CpuProfileNotInProgress)));
+
+        let profile = cpu_profile(Duration::from_millis(10)).await.unwrap();
+        assert!(!profile.encode(ProfileFormat::Pprof).unwrap().is_empty());
+    }
+
+    #[test]
+    fn format_from_path() {
+        assert_eq!(ProfileFormat::from_path(Path::new("cpu.svg")), ProfileFormat::Flamegraph);
+        assert_eq!(ProfileFormat::from_path(Path::new("cpu.pprof")), ProfileFormat::Pprof);
+        assert_eq!(ProfileFormat::from_path(Path::new("cpu")), ProfileFormat::Pprof);
+        assert_eq!("flamegraph".parse::<ProfileFormat>().unwrap(), ProfileFormat::Flamegraph);
+        assert!("perf".parse::<ProfileFormat>().is_err());
+    }
+}
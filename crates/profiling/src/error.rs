@@ -0,0 +1,22 @@
+/// Errors of the CPU and heap profilers.
+#[derive(Debug, thiserror::Error)]
+pub enum ProfilingError {
+    /// A CPU profile is already in progress, only one can run at a time.
+    #[error("CPU profiling already in progress")]
+    CpuProfileInProgress,
+    /// No CPU profile was started.
+    #[error("CPU profiling not in progress")]
+    CpuProfileNotInProgress,
+    /// Unknown profile format.
+    #[error("unknown profile format {0}, expected pprof or flamegraph")]
+    UnknownFormat(String),
+    /// The profiler is not supported on this platform or by this build.
+    #[error("{0}")]
+    Unsupported(&'static str),
+    /// The profiler failed to collect or encode the profile.
+    #[error("profiler error: {0}")]
+    Profiler(String),
+    /// Failed to write the profile.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
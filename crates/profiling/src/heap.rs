@@ -0,0 +1,34 @@
+use crate::ProfilingError;
+use std::path::Path;
+
+/// Returns a heap profile of the jemalloc allocator, in the pprof format.
+///
+/// Profiling must have been enabled when jemalloc was initialized, see the `jemalloc-prof`
+/// feature of `reth-cli-util`.
+#[cfg(all(feature = "jemalloc-prof", unix))]
+pub async fn heap_profile() -> Result<Vec<u8>, ProfilingError> {
+    let prof_ctl = jemalloc_pprof::PROF_CTL
+        .as_ref()
+        .ok_or(ProfilingError::Unsupported("jemalloc profiling is not enabled"))?;
+    let mut prof_ctl = prof_ctl.lock().await;
+    if !prof_ctl.activated() {
+        return Err(ProfilingError::Unsupported("jemalloc profiling is not activated"))
+    }
+
+    prof_ctl.dump_pprof().map_err(|err| ProfilingError::Profiler(err.to_string()))
+}
+
+/// Returns a heap profile of the jemalloc allocator, in the pprof format.
+///
+/// Always fails, because heap profiles require the `jemalloc-prof` feature.
+#[cfg(not(all(feature = "jemalloc-prof", unix)))]
+pub async fn heap_profile() -> Result<Vec<u8>, ProfilingError> {
+    Err(ProfilingError::Unsupported("heap profiling requires the jemalloc-prof feature"))
+}
+
+/// Writes a heap profile to the given file, see [`heap_profile`].
+pub async fn write_heap_profile(path: impl AsRef<Path>) -> Result<(), ProfilingError> {
+    let profile = heap_profile().await?;
+    std::fs::write(path, profile)?;
+    Ok(())
+}
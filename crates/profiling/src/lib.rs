@@ -0,0 +1,30 @@
+//! On-demand CPU and heap profiling of the running node.
+//!
+//! CPU profiles are collected by a sampling profiler and can be encoded either in the pprof format,
+//! readable with `go tool pprof`, or as flamegraphs. Heap profiles are dumps of the jemalloc
+//! allocator profile in the pprof format, and require the `jemalloc-prof` feature.
+//!
+//! ## Feature Flags
+//!
+//! - `jemalloc-prof`: Enables heap profiles. The node must be built with the jemalloc allocator and
+//!   profiling enabled, see the `jemalloc-prof` feature of `reth-cli-util`.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod cpu;
+pub use cpu::{
+    cpu_profile, start_cpu_profile, stop_cpu_profile, CpuProfile, ProfileFormat,
+    CPU_PROFILE_FREQUENCY,
+};
+
+mod error;
+pub use error::ProfilingError;
+
+mod heap;
+pub use heap::{heap_profile, write_heap_profile};
@@ -5,8 +5,15 @@
 //! - [`crate::segments::static_file::Receipts`] is responsible for pruning receipts on an archive
 //!   node after static file producer has finished
 
-use crate::{db_ext::DbTxPruneExt, segments::PruneInput, PrunerError};
-use reth_db_api::{table::Value, tables, transaction::DbTxMut};
+use crate::{
+    db_ext::DbTxPruneExt,
+    segments::{user::prune_history_indices, PruneInput},
+    PrunerError,
+};
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{map::HashSet, Address, B256};
+use itertools::Itertools;
+use reth_db_api::{models::ShardedKey, table::Value, tables, transaction::DbTxMut};
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
     errors::provider::ProviderResult, BlockReader, DBProvider, NodePrimitivesProvider,
@@ -15,9 +22,14 @@ use reth_provider::{
 use reth_prune_types::{PruneCheckpoint, PruneSegment, SegmentOutput, SegmentOutputCheckpoint};
 use tracing::trace;
 
+/// Prunes receipts according to the input.
+///
+/// If `prune_log_index` is set, the log index entries pointing to the pruned blocks are pruned as
+/// well, because their receipts are gone for good.
 pub(crate) fn prune<Provider>(
     provider: &Provider,
     input: PruneInput,
+    prune_log_index: bool,
 ) -> Result<SegmentOutput, PrunerError>
 where
     Provider: DBProvider<Tx: DbTxMut>
@@ -36,6 +48,12 @@ where
 
     let mut limiter = input.limiter;
 
+    // The log index is optional, so there is nothing to prune if it is empty.
+    let prune_log_index =
+        prune_log_index && provider.tx_ref().entries::<tables::LogAddressHistory>()? > 0;
+    let mut log_addresses = HashSet::<Address>::default();
+    let mut log_topics = HashSet::<B256>::default();
+
     let mut last_pruned_transaction = tx_range_end;
     let (pruned, done) = provider.tx_ref().prune_table_with_range::<tables::Receipts<
         <Provider::Primitives as NodePrimitives>::Receipt,
@@ -43,7 +61,15 @@ where
         tx_range,
         &mut limiter,
         |_| false,
-        |row| last_pruned_transaction = row.0,
+        |(tx_num, receipt)| {
+            last_pruned_transaction = tx_num;
+            if prune_log_index {
+                for log in receipt.logs() {
+                    log_addresses.insert(log.address);
+                    log_topics.extend(log.topics());
+                }
+            }
+        },
     )?;
     trace!(target: "pruner", %pruned, %done, "Pruned receipts");
 
@@ -54,6 +80,28 @@ where
         // so we could finish pruning its receipts on the next run.
         .checked_sub(if done { 0 } else { 1 });
 
+    if let Some(last_pruned_block) = last_pruned_block.filter(|_| prune_log_index) {
+        let outcomes = prune_history_indices::<Provider, tables::LogAddressHistory, _>(
+            provider,
+            log_addresses
+                .into_iter()
+                .sorted_unstable()
+                .map(|address| ShardedKey::new(address, last_pruned_block)),
+            |a, b| a.key == b.key,
+        )?;
+        trace!(target: "pruner", ?outcomes, %done, "Pruned log address index");
+
+        let outcomes = prune_history_indices::<Provider, tables::LogTopicHistory, _>(
+            provider,
+            log_topics
+                .into_iter()
+                .sorted_unstable()
+                .map(|topic| ShardedKey::new(topic, last_pruned_block)),
+            |a, b| a.key == b.key,
+        )?;
+        trace!(target: "pruner", ?outcomes, %done, "Pruned log topic index");
+    }
+
     let progress = limiter.progress(done);
 
     Ok(SegmentOutput {
@@ -167,7 +215,7 @@ mod tests {
                 .sub(1);
 
             let provider = db.factory.database_provider_rw().unwrap();
-            let result = super::prune(&provider, input).unwrap();
+            let result = super::prune(&provider, input, true).unwrap();
             limiter.increment_deleted_entries_count_by(result.pruned);
 
             assert_matches!(
@@ -1,6 +1,6 @@
 use crate::segments::{
     AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory, TransactionLookup,
-    UserReceipts,
+    TrieChangeSets, UserReceipts,
 };
 use alloy_eips::eip2718::Encodable2718;
 use reth_db_api::{table::Value, transaction::DbTxMut};
@@ -66,6 +66,7 @@ where
             account_history,
             storage_history,
             bodies_history: _,
+            trie_changesets,
             receipts_log_filter,
         } = prune_modes;
 
@@ -91,6 +92,8 @@ where
             .segment_opt(transaction_lookup.map(TransactionLookup::new))
             // Sender recovery
             .segment_opt(sender_recovery.map(SenderRecovery::new))
+            // Trie changesets
+            .segment_opt(trie_changesets.map(TrieChangeSets::new))
     }
 }
 
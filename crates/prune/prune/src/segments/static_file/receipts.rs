@@ -45,7 +45,8 @@ where
     }
 
     fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
-        crate::segments::receipts::prune(provider, input)
+        // Receipts are still available in static files, so the log index is kept.
+        crate::segments::receipts::prune(provider, input, false)
     }
 
     fn save_checkpoint(
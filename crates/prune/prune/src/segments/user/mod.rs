@@ -5,10 +5,13 @@ mod receipts_by_logs;
 mod sender_recovery;
 mod storage_history;
 mod transaction_lookup;
+mod trie_changesets;
 
 pub use account_history::AccountHistory;
+pub(crate) use history::prune_history_indices;
 pub use receipts::Receipts;
 pub use receipts_by_logs::ReceiptsByLogs;
 pub use sender_recovery::SenderRecovery;
 pub use storage_history::StorageHistory;
 pub use transaction_lookup::TransactionLookup;
+pub use trie_changesets::TrieChangeSets;
@@ -44,7 +44,7 @@ where
 
     #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
     fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
-        crate::segments::receipts::prune(provider, input)
+        crate::segments::receipts::prune(provider, input, true)
     }
 
     fn save_checkpoint(
@@ -0,0 +1,181 @@
+use crate::{
+    db_ext::DbTxPruneExt,
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use reth_db_api::{models::BlockNumberHashedAddress, tables, transaction::DbTxMut};
+use reth_provider::DBProvider;
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use tracing::{instrument, trace};
+
+#[derive(Debug)]
+pub struct TrieChangeSets {
+    mode: PruneMode,
+}
+
+impl TrieChangeSets {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for TrieChangeSets
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::TrieChangeSets
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No trie changesets to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        let mut limiter = input.limiter;
+
+        // Snapshots are pruned together with the trie changesets, because the trie is only
+        // reverted from the first snapshot after the block it's reverted to.
+        let tx = provider.tx_ref();
+        let (pruned_account_changesets, account_changesets_done) = tx
+            .prune_table_with_range::<tables::AccountsTrieChangeSets>(
+            range.clone(),
+            &mut limiter,
+            |_| false,
+            |_| {},
+        )?;
+        let (pruned_storage_changesets, storage_changesets_done) = tx
+            .prune_table_with_range::<tables::StoragesTrieChangeSets>(
+            BlockNumberHashedAddress::range(range.clone()),
+            &mut limiter,
+            |_| false,
+            |_| {},
+        )?;
+        let (pruned_account_snapshots, account_snapshots_done) = tx
+            .prune_table_with_range::<tables::AccountsTrieSnapshots>(
+                range.clone(),
+                &mut limiter,
+                |_| false,
+                |_| {},
+            )?;
+        let (pruned_storage_snapshots, storage_snapshots_done) = tx
+            .prune_table_with_range::<tables::StoragesTrieSnapshots>(
+                BlockNumberHashedAddress::range(range),
+                &mut limiter,
+                |_| false,
+                |_| {},
+            )?;
+        let done = account_changesets_done &&
+            storage_changesets_done &&
+            account_snapshots_done &&
+            storage_snapshots_done;
+        let pruned = pruned_account_changesets +
+            pruned_storage_changesets +
+            pruned_account_snapshots +
+            pruned_storage_snapshots;
+        trace!(target: "pruner", %pruned, %done, "Pruned trie changesets");
+
+        let progress = limiter.progress(done);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                // The tables are pruned one after another, so the range is only pruned once all of
+                // them are done with it, and the next run starts over from the previous checkpoint
+                // otherwise.
+                block_number: if done {
+                    Some(range_end)
+                } else {
+                    input.previous_checkpoint.and_then(|checkpoint| checkpoint.block_number)
+                },
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{PruneInput, PruneLimiter, Segment, SegmentOutput, TrieChangeSets};
+    use alloy_primitives::B256;
+    use assert_matches::assert_matches;
+    use reth_db_api::{
+        cursor::DbCursorRW, models::BlockNumberHashedAddress, tables, transaction::DbTxMut,
+    };
+    use reth_provider::{DBProvider, DatabaseProviderFactory};
+    use reth_prune_types::{PruneMode, PruneProgress};
+    use reth_stages::test_utils::TestStageDB;
+    use reth_trie_common::{Nibbles, StoredNibblesSubKey, TrieChangeSetsEntry};
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+
+        let entry = TrieChangeSetsEntry {
+            nibbles: StoredNibblesSubKey(Nibbles::from_nibbles_unchecked([0x1])),
+            node: None,
+        };
+        let provider = db.factory.database_provider_rw().unwrap();
+        {
+            let tx = provider.tx_ref();
+            let mut account_changesets =
+                tx.cursor_dup_write::<tables::AccountsTrieChangeSets>().unwrap();
+            let mut storage_changesets =
+                tx.cursor_dup_write::<tables::StoragesTrieChangeSets>().unwrap();
+            for block_number in 1..=10 {
+                account_changesets.upsert(block_number, &entry).unwrap();
+                storage_changesets
+                    .upsert(BlockNumberHashedAddress((block_number, B256::ZERO)), &entry)
+                    .unwrap();
+            }
+            let mut account_snapshots =
+                tx.cursor_dup_write::<tables::AccountsTrieSnapshots>().unwrap();
+            let mut storage_snapshots =
+                tx.cursor_dup_write::<tables::StoragesTrieSnapshots>().unwrap();
+            for block_number in [5, 10] {
+                account_snapshots.upsert(block_number, &TrieChangeSetsEntry::marker()).unwrap();
+                storage_snapshots
+                    .upsert(BlockNumberHashedAddress((block_number, B256::ZERO)), &entry)
+                    .unwrap();
+            }
+        }
+        provider.commit().unwrap();
+
+        let segment = TrieChangeSets::new(PruneMode::Before(6));
+        let input =
+            PruneInput { previous_checkpoint: None, to_block: 5, limiter: PruneLimiter::default() };
+        let provider = db.factory.database_provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        provider.commit().unwrap();
+
+        assert_matches!(
+            result,
+            SegmentOutput { progress: PruneProgress::Finished, pruned: 12, checkpoint: Some(checkpoint) }
+                if checkpoint.block_number == Some(5)
+        );
+        assert_eq!(db.table::<tables::AccountsTrieChangeSets>().unwrap().len(), 5);
+        assert_eq!(db.table::<tables::StoragesTrieChangeSets>().unwrap().len(), 5);
+        assert_eq!(
+            db.table::<tables::AccountsTrieSnapshots>().unwrap(),
+            vec![(10, TrieChangeSetsEntry::marker())]
+        );
+        assert_eq!(db.table::<tables::StoragesTrieSnapshots>().unwrap().len(), 1);
+    }
+}
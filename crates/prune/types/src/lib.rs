@@ -15,6 +15,7 @@ mod checkpoint;
 mod event;
 mod mode;
 mod pruner;
+mod reconfigure;
 mod segment;
 mod target;
 
@@ -29,6 +30,7 @@ pub use pruner::{
     PruneInterruptReason, PruneProgress, PrunedSegmentInfo, PrunerOutput, SegmentOutput,
     SegmentOutputCheckpoint,
 };
+pub use reconfigure::{PruneReconfiguration, SegmentReconfiguration};
 pub use segment::{PrunePurpose, PruneSegment, PruneSegmentError};
 pub use target::{PruneModes, UnwindTargetPrunedError, MINIMUM_PRUNING_DISTANCE};
 
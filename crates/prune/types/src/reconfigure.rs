@@ -0,0 +1,207 @@
+use crate::{
+    PruneCheckpoint, PruneMode, PruneModes, PrunePurpose, PruneSegment, PruneSegmentError,
+};
+use alloc::vec::Vec;
+use alloy_primitives::BlockNumber;
+use core::ops::RangeInclusive;
+
+/// Action required to move a prune segment from its stored [`PruneCheckpoint`] to a new
+/// [`PruneMode`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SegmentReconfiguration {
+    /// The segment is already pruned according to the new mode.
+    Unchanged,
+    /// The new mode prunes more data, which can be pruned right away.
+    Prune {
+        /// Block up to which the segment will be pruned, inclusive.
+        to_block: BlockNumber,
+    },
+    /// The new mode keeps data that has already been pruned, and it can be rebuilt from the
+    /// transactions in static files, or from the changesets for history segments.
+    Rebuild {
+        /// Blocks to rebuild the segment for.
+        blocks: RangeInclusive<BlockNumber>,
+        /// Block up to which the segment stays pruned, if any.
+        pruned_to: Option<BlockNumber>,
+    },
+    /// The new mode keeps data that has already been pruned, and it can't be restored without a
+    /// resync.
+    Unrecoverable {
+        /// Blocks that were pruned, but need to be kept by the new mode.
+        blocks: RangeInclusive<BlockNumber>,
+    },
+}
+
+/// Delta between the stored [`PruneCheckpoint`]s and a new [`PruneModes`] configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PruneReconfiguration {
+    /// Reconfiguration for every segment that is either pruned or has a new prune mode.
+    pub segments: Vec<(PruneSegment, Option<PruneMode>, SegmentReconfiguration)>,
+}
+
+impl PruneReconfiguration {
+    /// Computes the reconfiguration of the segments configured by [`PruneModes`], given the
+    /// stored checkpoints and the current `tip`.
+    ///
+    /// Receipts pruned by the `receipts_log_filter` are not covered, since the previous filter is
+    /// not stored in the checkpoint.
+    pub fn new(
+        checkpoints: &[(PruneSegment, PruneCheckpoint)],
+        modes: &PruneModes,
+        tip: BlockNumber,
+    ) -> Result<Self, PruneSegmentError> {
+        let mut segments = Vec::new();
+        for (segment, mode) in [
+            (PruneSegment::SenderRecovery, modes.sender_recovery),
+            (PruneSegment::TransactionLookup, modes.transaction_lookup),
+            (PruneSegment::Receipts, modes.receipts),
+            (PruneSegment::AccountHistory, modes.account_history),
+            (PruneSegment::StorageHistory, modes.storage_history),
+        ] {
+            let pruned_to = checkpoints
+                .iter()
+                .find(|(checkpoint_segment, _)| *checkpoint_segment == segment)
+                .and_then(|(_, checkpoint)| checkpoint.block_number);
+            if pruned_to.is_none() && mode.is_none() {
+                continue
+            }
+
+            let target = mode
+                .map(|mode| mode.prune_target_block(tip, segment, PrunePurpose::User))
+                .transpose()?
+                .flatten()
+                .map(|(block, _)| block);
+
+            let reconfiguration = match (pruned_to, target) {
+                (Some(pruned_to), Some(target)) if target <= pruned_to => {
+                    if target == pruned_to {
+                        SegmentReconfiguration::Unchanged
+                    } else {
+                        Self::restore(segment, target + 1..=pruned_to, Some(target))
+                    }
+                }
+                (Some(pruned_to), None) => Self::restore(segment, 0..=pruned_to, None),
+                (_, Some(target)) => SegmentReconfiguration::Prune { to_block: target },
+                (None, None) => SegmentReconfiguration::Unchanged,
+            };
+            segments.push((segment, mode, reconfiguration));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Returns the action to restore the pruned blocks of the segment.
+    const fn restore(
+        segment: PruneSegment,
+        blocks: RangeInclusive<BlockNumber>,
+        pruned_to: Option<BlockNumber>,
+    ) -> SegmentReconfiguration {
+        match segment {
+            PruneSegment::SenderRecovery |
+            PruneSegment::TransactionLookup |
+            PruneSegment::AccountHistory |
+            PruneSegment::StorageHistory => SegmentReconfiguration::Rebuild { blocks, pruned_to },
+            _ => SegmentReconfiguration::Unrecoverable { blocks },
+        }
+    }
+
+    /// Returns the segments that can't be reconfigured without a resync.
+    pub fn unrecoverable(
+        &self,
+    ) -> impl Iterator<Item = (PruneSegment, &RangeInclusive<BlockNumber>)> {
+        self.segments.iter().filter_map(|(segment, _, reconfiguration)| match reconfiguration {
+            SegmentReconfiguration::Unrecoverable { blocks } => Some((*segment, blocks)),
+            _ => None,
+        })
+    }
+
+    /// Returns `true` if nothing needs to be done.
+    pub fn is_unchanged(&self) -> bool {
+        self.segments
+            .iter()
+            .all(|(_, _, reconfiguration)| *reconfiguration == SegmentReconfiguration::Unchanged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MINIMUM_PRUNING_DISTANCE;
+
+    fn checkpoint(block_number: BlockNumber, prune_mode: PruneMode) -> PruneCheckpoint {
+        PruneCheckpoint { block_number: Some(block_number), tx_number: None, prune_mode }
+    }
+
+    #[test]
+    fn reconfiguration() {
+        let tip = 100_000;
+        let distance = MINIMUM_PRUNING_DISTANCE + 1;
+        let checkpoints = [
+            (PruneSegment::TransactionLookup, checkpoint(tip, PruneMode::Full)),
+            (PruneSegment::Receipts, checkpoint(tip - distance, PruneMode::Distance(distance))),
+            (
+                PruneSegment::AccountHistory,
+                checkpoint(tip - distance, PruneMode::Distance(distance)),
+            ),
+            (
+                PruneSegment::StorageHistory,
+                checkpoint(tip - distance, PruneMode::Distance(distance)),
+            ),
+        ];
+        let modes = PruneModes {
+            // Added
+            sender_recovery: Some(PruneMode::Full),
+            // Removed, the whole segment can be rebuilt
+            transaction_lookup: None,
+            // Loosened, can't be rebuilt
+            receipts: Some(PruneMode::Distance(distance * 2)),
+            // Loosened, the remaining blocks can be rebuilt
+            account_history: Some(PruneMode::Distance(distance * 2)),
+            // Unchanged
+            storage_history: Some(PruneMode::Distance(distance)),
+            ..Default::default()
+        };
+
+        let reconfiguration = PruneReconfiguration::new(&checkpoints, &modes, tip).unwrap();
+        assert_eq!(
+            reconfiguration.segments,
+            vec![
+                (
+                    PruneSegment::SenderRecovery,
+                    modes.sender_recovery,
+                    SegmentReconfiguration::Prune { to_block: tip }
+                ),
+                (
+                    PruneSegment::TransactionLookup,
+                    None,
+                    SegmentReconfiguration::Rebuild { blocks: 0..=tip, pruned_to: None }
+                ),
+                (
+                    PruneSegment::Receipts,
+                    modes.receipts,
+                    SegmentReconfiguration::Unrecoverable {
+                        blocks: tip - distance * 2 + 1..=tip - distance
+                    }
+                ),
+                (
+                    PruneSegment::AccountHistory,
+                    modes.account_history,
+                    SegmentReconfiguration::Rebuild {
+                        blocks: tip - distance * 2 + 1..=tip - distance,
+                        pruned_to: Some(tip - distance * 2)
+                    }
+                ),
+                (
+                    PruneSegment::StorageHistory,
+                    modes.storage_history,
+                    SegmentReconfiguration::Unchanged
+                ),
+            ]
+        );
+        assert_eq!(
+            reconfiguration.unrecoverable().map(|(segment, _)| segment).collect::<Vec<_>>(),
+            vec![PruneSegment::Receipts]
+        );
+        assert!(!reconfiguration.is_unchanged());
+    }
+}
@@ -84,6 +84,12 @@ pub struct PruneModes {
         )
     )]
     pub bodies_history: Option<PruneMode>,
+    /// Trie changesets configuration.
+    ///
+    /// Unlike the other segments, trie changesets are only written if this is set, so historical
+    /// proofs can be served from them only for the blocks that are kept by the mode.
+    #[cfg_attr(any(test, feature = "serde"), serde(skip_serializing_if = "Option::is_none"))]
+    pub trie_changesets: Option<PruneMode>,
     /// Receipts pruning configuration by retaining only those receipts that contain logs emitted
     /// by the specified addresses, discarding others. This setting is overridden by `receipts`.
     ///
@@ -107,6 +113,7 @@ impl PruneModes {
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
             bodies_history: Some(PruneMode::Full),
+            trie_changesets: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
         }
     }
@@ -116,6 +123,12 @@ impl PruneModes {
         self.receipts.is_some() || !self.receipts_log_filter.is_empty()
     }
 
+    /// Returns whether trie changesets are written, i.e. [`Self::trie_changesets`] keeps any
+    /// blocks.
+    pub fn has_trie_changesets(&self) -> bool {
+        self.trie_changesets.is_some_and(|mode| !mode.is_full())
+    }
+
     /// Returns true if all prune modes are set to [`None`].
     pub fn is_empty(&self) -> bool {
         self == &Self::none()
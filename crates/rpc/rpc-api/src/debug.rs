@@ -207,6 +207,9 @@ pub trait DebugApi<TxReq: RpcObject> {
     ) -> RpcResult<Option<Bytes>>;
 
     /// Turns on CPU profiling for the given duration and writes profile data to disk.
+    ///
+    /// The profile is written as a flamegraph if the file has the `svg` extension, and in the
+    /// pprof format otherwise.
     #[method(name = "cpuProfile")]
     async fn debug_cpu_profile(&self, file: String, seconds: u64) -> RpcResult<()>;
 
@@ -353,7 +356,8 @@ pub trait DebugApi<TxReq: RpcObject> {
         opts: Option<GethDebugTracingCallOptions>,
     ) -> RpcResult<()>;
 
-    /// Turns on CPU profiling indefinitely, writing to the given file.
+    /// Turns on CPU profiling indefinitely, writing to the given file when stopped with
+    /// `debug_stopCPUProfile`. The format is chosen as for `debug_cpuProfile`.
     #[method(name = "startCPUProfile")]
     async fn debug_start_cpu_profile(&self, file: String) -> RpcResult<()>;
 
@@ -406,7 +410,10 @@ pub trait DebugApi<TxReq: RpcObject> {
     #[method(name = "verbosity")]
     async fn debug_verbosity(&self, level: usize) -> RpcResult<()>;
 
-    /// Sets the logging verbosity pattern.
+    /// Sets the logging verbosity pattern, applied to all log outputs on top of their filters.
+    ///
+    /// The pattern is a comma-separated list of `target=level` directives, e.g.
+    /// `net::session=trace`. Levels can also be given as numbers, same as for `debug_verbosity`.
     #[method(name = "vmodule")]
     async fn debug_vmodule(&self, pattern: String) -> RpcResult<()>;
 
@@ -414,7 +421,9 @@ pub trait DebugApi<TxReq: RpcObject> {
     #[method(name = "writeBlockProfile")]
     async fn debug_write_block_profile(&self, file: String) -> RpcResult<()>;
 
-    /// Writes an allocation profile to the given file.
+    /// Writes an allocation profile to the given file, in the pprof format.
+    ///
+    /// Requires the node to be built with the `jemalloc-prof` feature.
     #[method(name = "writeMemProfile")]
     async fn debug_write_mem_profile(&self, file: String) -> RpcResult<()>;
 
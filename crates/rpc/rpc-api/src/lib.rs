@@ -44,7 +44,7 @@ pub mod servers {
         miner::MinerApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
-        reth::RethApiServer,
+        reth::{RethAdminApiServer, RethApiServer},
         rpc::RpcApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
@@ -75,7 +75,7 @@ pub mod clients {
         miner::MinerApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
-        reth::RethApiClient,
+        reth::{RethAdminApiClient, RethApiClient},
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
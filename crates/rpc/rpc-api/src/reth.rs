@@ -25,3 +25,17 @@ pub trait RethApi {
     )]
     async fn reth_subscribe_chain_notifications(&self) -> jsonrpsee::core::SubscriptionResult;
 }
+
+/// Reth API namespace for administrative methods.
+///
+/// These methods are only served together with the `admin` namespace.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethAdminApi {
+    /// Replaces the filter directives of a log output at runtime, e.g. `info,net::session=trace`.
+    ///
+    /// The sink is one of `stdout`, `file`, `journald` or `otlp`. If not set, the filters of all
+    /// sinks are replaced.
+    #[method(name = "setLogFilter")]
+    async fn reth_set_log_filter(&self, filter: String, sink: Option<String>) -> RpcResult<()>;
+}
@@ -0,0 +1,488 @@
+//! [`jsonrpsee`] layers for access control of the HTTP and WS servers, see [`RpcAccessConfig`].
+
+use crate::tls::RemoteAddr;
+use http::{uri::PathAndQuery, Request, Uri};
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObject, ErrorObjectOwned, Id, Request as RpcRequest},
+    MethodResponse,
+};
+use parking_lot::Mutex;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_rpc_graphql::{GraphQLRequestGuard, GRAPHQL_PATH};
+use reth_rpc_server_types::{
+    access::ANONYMOUS_TENANT, RpcAccessConfig, RpcAccessPolicy, RpcTenantConfig,
+};
+use std::{
+    collections::HashMap,
+    future::Future,
+    net::IpAddr,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Instant,
+};
+use tower::{Layer, Service};
+
+/// Error code of calls that the caller is not allowed to make, the EIP-1474 "method not
+/// supported" code.
+pub const ACCESS_DENIED_ERROR_CODE: i32 = -32004;
+
+/// Error code of calls that exceed the quota of the caller, the EIP-1474 "limit exceeded" code.
+pub const QUOTA_EXCEEDED_ERROR_CODE: i32 = -32005;
+
+/// The method GraphQL requests are checked and charged as.
+///
+/// It has no module, so tenants restricted to modules must list it in their allowed methods.
+pub const GRAPHQL_METHOD: &str = "graphql";
+
+/// Access control for the HTTP and WS servers.
+///
+/// Consists of two layers: the HTTP layer returned by [`RpcAccessControl::http_layer`] identifies
+/// the caller of a request, and this type is the RPC layer that rejects the calls the caller is
+/// not allowed to make and enforces its compute unit quota.
+#[derive(Debug, Clone)]
+pub struct RpcAccessControl {
+    inner: Arc<RpcAccessControlInner>,
+}
+
+impl RpcAccessControl {
+    /// Creates the access control of the given config.
+    pub fn new(config: RpcAccessConfig) -> Self {
+        let mut api_keys = HashMap::new();
+        let mut ips = HashMap::new();
+        for (name, tenant) in &config.tenants {
+            let RpcTenantConfig { api_keys: tenant_keys, ips: tenant_ips, policy } = tenant;
+            let tenant = Arc::new(Tenant::new(name, policy.clone()));
+            api_keys.extend(tenant_keys.iter().map(|key| (key.clone(), tenant.clone())));
+            ips.extend(tenant_ips.iter().map(|ip| (*ip, tenant.clone())));
+        }
+        let anonymous =
+            config.anonymous.clone().map(|policy| Arc::new(Tenant::new(ANONYMOUS_TENANT, policy)));
+
+        Self {
+            inner: Arc::new(RpcAccessControlInner {
+                api_keys,
+                ips,
+                anonymous,
+                config,
+                metrics: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the HTTP layer that identifies the caller of a request.
+    ///
+    /// This must be the outermost HTTP layer, so that an API key in the URL path doesn't end up
+    /// in the logs of other layers.
+    pub fn http_layer(&self) -> RpcCallerLayer {
+        RpcCallerLayer { access: self.clone() }
+    }
+
+    /// Returns the guard of the GraphQL endpoint, which checks and charges GraphQL requests as
+    /// calls of the [`GRAPHQL_METHOD`] method.
+    pub fn graphql_guard(&self) -> GraphQLRequestGuard {
+        let access = self.clone();
+        Arc::new(move |req| {
+            access
+                .check(req.extensions().get(), [GRAPHQL_METHOD])
+                .map_err(|err| err.message().to_string())
+        })
+    }
+
+    /// Identifies the caller of the request, and strips the API key from the URL path.
+    fn identify<B>(&self, req: &mut Request<B>) -> RpcCaller {
+        let config = &self.inner.config;
+        // the API key may be followed by the path of the GraphQL endpoint
+        let (path_key, path) = match req.uri().path().strip_suffix(GRAPHQL_PATH) {
+            Some(path_key) => (path_key, GRAPHQL_PATH),
+            None => (req.uri().path(), "/"),
+        };
+        let path_key = path_key.trim_matches('/');
+        let api_key = if let Some(header) = req.headers().get(config.api_key_header.as_str()) {
+            header.to_str().ok().map(str::to_string)
+        } else if !path_key.is_empty() {
+            let api_key = path_key.to_string();
+            let path_and_query = match req.uri().query() {
+                Some(query) => format!("{path}?{query}"),
+                None => path.to_string(),
+            };
+            let mut parts = req.uri().clone().into_parts();
+            parts.path_and_query = PathAndQuery::try_from(path_and_query).ok();
+            if let Ok(uri) = Uri::from_parts(parts) {
+                *req.uri_mut() = uri;
+            }
+            Some(api_key)
+        } else {
+            None
+        };
+
+        // An unknown API key is rejected, instead of falling back to the IP of the caller
+        if let Some(api_key) = api_key {
+            return RpcCaller(self.inner.api_keys.get(&api_key).cloned())
+        }
+
+        let ip = config
+            .ip_header
+            .as_ref()
+            .and_then(|header| req.headers().get(header.as_str()))
+            .and_then(|header| header.to_str().ok())
+            .and_then(|ips| ips.rsplit(',').next())
+            .and_then(|ip| ip.trim().parse::<IpAddr>().ok())
+            .or_else(|| req.extensions().get::<RemoteAddr>().map(|addr| addr.0.ip()));
+        RpcCaller(
+            ip.and_then(|ip| self.inner.ips.get(&ip)).or(self.inner.anonymous.as_ref()).cloned(),
+        )
+    }
+
+    /// Checks whether the caller may call the given methods, and charges their compute units.
+    fn check<'a>(
+        &self,
+        caller: Option<&RpcCaller>,
+        methods: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), ErrorObjectOwned> {
+        let Some(tenant) = caller.and_then(|caller| caller.0.as_ref()) else {
+            self.inner.metrics.unknown_callers_total.increment(1);
+            return Err(ErrorObject::owned(ACCESS_DENIED_ERROR_CODE, "unknown caller", None::<()>))
+        };
+
+        let mut calls = 0;
+        let mut compute_units = 0u64;
+        for method in methods {
+            if !tenant.policy.is_allowed(method) {
+                tenant.metrics.denied_total.increment(1);
+                return Err(ErrorObject::owned(
+                    ACCESS_DENIED_ERROR_CODE,
+                    format!("method {method} is not allowed"),
+                    None::<()>,
+                ))
+            }
+            calls += 1;
+            compute_units = compute_units.saturating_add(self.inner.config.method_weight(method));
+        }
+
+        if let Some(bucket) = &tenant.bucket {
+            if !bucket.lock().try_acquire(compute_units, Instant::now()) {
+                tenant.metrics.quota_exceeded_total.increment(1);
+                return Err(ErrorObject::owned(
+                    QUOTA_EXCEEDED_ERROR_CODE,
+                    "compute unit quota exceeded",
+                    None::<()>,
+                ))
+            }
+        }
+
+        tenant.metrics.calls_total.increment(calls);
+        tenant.metrics.compute_units_total.increment(compute_units);
+        Ok(())
+    }
+}
+
+impl<S> Layer<S> for RpcAccessControl {
+    type Service = RpcAccessService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcAccessService { inner, access: self.clone() }
+    }
+}
+
+#[derive(Debug)]
+struct RpcAccessControlInner {
+    /// Tenants by API key
+    api_keys: HashMap<String, Arc<Tenant>>,
+    /// Tenants by IP
+    ips: HashMap<IpAddr, Arc<Tenant>>,
+    /// The tenant of unidentified callers
+    anonymous: Option<Arc<Tenant>>,
+    config: RpcAccessConfig,
+    metrics: RpcAccessMetrics,
+}
+
+/// A tenant with its remaining quota.
+#[derive(Debug)]
+struct Tenant {
+    policy: RpcAccessPolicy,
+    bucket: Option<Mutex<TokenBucket>>,
+    metrics: RpcTenantMetrics,
+}
+
+impl Tenant {
+    fn new(name: &str, policy: RpcAccessPolicy) -> Self {
+        let bucket = policy.compute_units_per_second.map(|refill_per_second| {
+            Mutex::new(TokenBucket::new(
+                policy.capacity().unwrap_or(refill_per_second),
+                refill_per_second,
+            ))
+        });
+        let metrics = RpcTenantMetrics::new_with_labels(&[("tenant", name.to_string())]);
+        Self { policy, bucket, metrics }
+    }
+}
+
+/// A token bucket of compute units.
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: u64,
+    refill_per_second: u64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a full bucket.
+    fn new(capacity: u64, refill_per_second: u64) -> Self {
+        Self { capacity, refill_per_second, tokens: capacity as f64, refilled_at: Instant::now() }
+    }
+
+    /// Takes the given number of tokens if the bucket holds enough of them.
+    fn try_acquire(&mut self, tokens: u64, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.refill_per_second as f64).min(self.capacity as f64);
+        self.refilled_at = now;
+
+        if self.tokens < tokens as f64 {
+            return false
+        }
+        self.tokens -= tokens as f64;
+        true
+    }
+}
+
+/// The caller of a request, if it could be identified.
+///
+/// Added to the extensions of requests by [`RpcCallerService`].
+#[derive(Debug, Clone)]
+struct RpcCaller(Option<Arc<Tenant>>);
+
+/// HTTP layer that identifies the caller of a request, see [`RpcAccessControl::http_layer`].
+#[derive(Debug, Clone)]
+pub struct RpcCallerLayer {
+    access: RpcAccessControl,
+}
+
+impl<S> Layer<S> for RpcCallerLayer {
+    type Service = RpcCallerService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcCallerService { inner, access: self.access.clone() }
+    }
+}
+
+/// Service that identifies the caller of the HTTP requests.
+///
+/// Created by [`RpcCallerLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcCallerService<S> {
+    inner: S,
+    access: RpcAccessControl,
+}
+
+impl<S, B> Service<Request<B>> for RpcCallerService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        let caller = self.access.identify(&mut req);
+        req.extensions_mut().insert(caller);
+        self.inner.call(req)
+    }
+}
+
+/// A [`RpcServiceT`] middleware that enforces the access control of the caller.
+///
+/// Created by [`RpcAccessControl`].
+#[derive(Debug, Clone)]
+pub struct RpcAccessService<S> {
+    inner: S,
+    access: RpcAccessControl,
+}
+
+impl<S> RpcServiceT for RpcAccessService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse, BatchResponse = MethodResponse>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(
+        &self,
+        req: RpcRequest<'a>,
+    ) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let checked = self.access.check(req.extensions().get(), [req.method_name()]);
+        let inner = self.inner.clone();
+        async move {
+            match checked {
+                Ok(()) => inner.call(req).await,
+                Err(err) => MethodResponse::error(req.id(), err),
+            }
+        }
+    }
+
+    /// Rejects the whole batch if any of its calls is rejected.
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        let calls = req
+            .iter()
+            .filter_map(|entry| match entry {
+                Ok(BatchEntry::Call(call)) => Some(call),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let caller = calls.first().and_then(|call| call.extensions().get());
+        let checked = self.access.check(caller, calls.iter().map(|call| call.method_name()));
+        let inner = self.inner.clone();
+        async move {
+            match checked {
+                Ok(()) => inner.batch(req).await,
+                Err(err) => MethodResponse::error(Id::Null, err),
+            }
+        }
+    }
+
+    /// Notifications are passed through, because the server doesn't execute them.
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Metrics of the callers that couldn't be identified.
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.access")]
+struct RpcAccessMetrics {
+    /// The number of calls rejected because the caller is unknown
+    unknown_callers_total: Counter,
+}
+
+/// Usage metrics of a tenant.
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.access")]
+struct RpcTenantMetrics {
+    /// The number of calls
+    calls_total: Counter,
+    /// The number of compute units spent
+    compute_units_total: Counter,
+    /// The number of calls rejected because the method is not allowed
+    denied_total: Counter,
+    /// The number of calls rejected because the quota is exceeded
+    quota_exceeded_total: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{net::SocketAddr, time::Duration};
+
+    fn access_control() -> RpcAccessControl {
+        let config = serde_json::from_value(serde_json::json!({
+            "ip_header": "x-forwarded-for",
+            "tenants": {
+                "indexer": {
+                    "api_keys": ["secret"],
+                    "ips": ["10.0.0.1"],
+                    "modules": ["eth"],
+                    "compute_units_per_second": 10,
+                },
+            },
+            "method_weights": { "eth_getLogs": 6 },
+        }))
+        .unwrap();
+        RpcAccessControl::new(config)
+    }
+
+    fn request(uri: &str, headers: &[(&str, &str)]) -> Request<()> {
+        let mut req = Request::builder().uri(uri);
+        for (name, value) in headers {
+            req = req.header(*name, *value);
+        }
+        req.body(()).unwrap()
+    }
+
+    #[test]
+    fn identify_callers() {
+        let access = access_control();
+        let is_known = |mut req: Request<()>| access.identify(&mut req).0.is_some();
+
+        assert!(is_known(request("/", &[("x-api-key", "secret")])));
+        assert!(!is_known(request("/", &[("x-api-key", "wrong")])));
+        assert!(is_known(request("/", &[("x-forwarded-for", "1.2.3.4, 10.0.0.1")])));
+        assert!(!is_known(request("/", &[("x-forwarded-for", "10.0.0.1, 1.2.3.4")])));
+        assert!(!is_known(request("/", &[])));
+        // an unknown API key doesn't fall back to the IP
+        assert!(!is_known(request("/wrong", &[("x-forwarded-for", "10.0.0.1")])));
+
+        // without the IP header, the address of the connection is used
+        let connected_from = |ip: [u8; 4]| {
+            let mut req = request("/", &[]);
+            req.extensions_mut().insert(RemoteAddr(SocketAddr::from((ip, 30303))));
+            req
+        };
+        assert!(is_known(connected_from([10, 0, 0, 1])));
+        assert!(!is_known(connected_from([1, 2, 3, 4])));
+        let mut req = connected_from([10, 0, 0, 1]);
+        req.headers_mut().insert("x-forwarded-for", "1.2.3.4".parse().unwrap());
+        assert!(!is_known(req));
+
+        // the API key is stripped from the path
+        let mut req = request("http://localhost:8545/secret", &[]);
+        assert!(access.identify(&mut req).0.is_some());
+        assert_eq!(req.uri().path(), "/");
+
+        // and from the path of the GraphQL endpoint
+        let mut req = request("/secret/graphql?query=%7B%7D", &[]);
+        assert!(access.identify(&mut req).0.is_some());
+        assert_eq!(req.uri().path(), "/graphql");
+        assert_eq!(req.uri().query(), Some("query=%7B%7D"));
+        let mut req = request("/graphql", &[("x-forwarded-for", "10.0.0.1")]);
+        assert!(access.identify(&mut req).0.is_some());
+        assert_eq!(req.uri().path(), "/graphql");
+    }
+
+    #[test]
+    fn check_calls() {
+        let access = access_control();
+        let caller = access.identify(&mut request("/secret", &[]));
+
+        let err = access.check(Some(&caller), ["debug_traceTransaction"]).unwrap_err();
+        assert_eq!(err.code(), ACCESS_DENIED_ERROR_CODE);
+        let err = access.check(Some(&caller), [GRAPHQL_METHOD]).unwrap_err();
+        assert_eq!(err.code(), ACCESS_DENIED_ERROR_CODE);
+        let err = access.check(Some(&RpcCaller(None)), ["eth_blockNumber"]).unwrap_err();
+        assert_eq!(err.code(), ACCESS_DENIED_ERROR_CODE);
+        assert!(access.check(None, ["eth_blockNumber"]).is_err());
+
+        // 10 compute units: one eth_getLogs and four cheap calls
+        assert!(access.check(Some(&caller), ["eth_getLogs"]).is_ok());
+        assert!(access.check(Some(&caller), ["eth_blockNumber"; 4]).is_ok());
+        let err = access.check(Some(&caller), ["eth_blockNumber"]).unwrap_err();
+        assert_eq!(err.code(), QUOTA_EXCEEDED_ERROR_CODE);
+    }
+
+    #[test]
+    fn token_bucket() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(10, 5);
+        bucket.refilled_at = start;
+
+        assert!(bucket.try_acquire(10, start));
+        assert!(!bucket.try_acquire(1, start));
+        assert!(bucket.try_acquire(5, start + Duration::from_secs(1)));
+        assert!(!bucket.try_acquire(11, start + Duration::from_secs(10)));
+        assert!(bucket.try_acquire(10, start + Duration::from_secs(10)));
+    }
+}
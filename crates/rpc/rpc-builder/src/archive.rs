@@ -0,0 +1,440 @@
+//! [`jsonrpsee`] layer that forwards requests for pruned history to an archive node, see
+//! [`ArchiveRpc`].
+
+use alloy_eips::BlockId;
+use alloy_primitives::BlockNumber;
+use alloy_rpc_client::RpcClient;
+use alloy_rpc_types_eth::{Filter, FilterBlockOption};
+use jsonrpsee::{
+    core::middleware::{Batch, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{error::INTERNAL_ERROR_CODE, ErrorObject, Params, Request, ResponsePayload},
+    MethodResponse,
+};
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_prune_types::{PruneMode, PruneModes};
+use reth_storage_api::BlockIdReader;
+use serde::Deserialize;
+use std::{borrow::Cow, future::Future, ops::RangeInclusive, sync::Arc};
+use tracing::{debug, warn};
+use url::Url;
+
+/// History a block-scoped request reads, and which may be pruned locally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistorySegment {
+    /// Block bodies, see [`PruneModes::bodies_history`].
+    Bodies,
+    /// Receipts, see [`PruneModes::receipts`].
+    Receipts,
+    /// Historical state, see [`PruneModes::account_history`] and
+    /// [`PruneModes::storage_history`].
+    State,
+}
+
+impl HistorySegment {
+    /// Returns the prune modes of the segment.
+    fn prune_modes(self, modes: &PruneModes) -> impl Iterator<Item = &PruneMode> {
+        let segments = match self {
+            Self::Bodies => [modes.bodies_history.as_ref(), None],
+            Self::Receipts => [modes.receipts.as_ref(), None],
+            Self::State => [modes.account_history.as_ref(), modes.storage_history.as_ref()],
+        };
+        segments.into_iter().flatten()
+    }
+}
+
+/// Returns the position of the block parameter of the given method, and the history the method
+/// reads at that block.
+pub fn block_param(method: &str) -> Option<(usize, &'static [HistorySegment])> {
+    use HistorySegment::*;
+
+    let param = match method {
+        "eth_getBlockByNumber" |
+        "eth_getBlockByHash" |
+        "eth_getBlockTransactionCountByNumber" |
+        "eth_getBlockTransactionCountByHash" |
+        "eth_getTransactionByBlockNumberAndIndex" |
+        "eth_getTransactionByBlockHashAndIndex" |
+        "eth_getRawTransactionByBlockNumberAndIndex" |
+        "eth_getRawTransactionByBlockHashAndIndex" |
+        "eth_getUncleCountByBlockNumber" |
+        "eth_getUncleCountByBlockHash" |
+        "eth_getUncleByBlockNumberAndIndex" |
+        "eth_getUncleByBlockHashAndIndex" => (0, &[Bodies][..]),
+        "eth_getBlockReceipts" => (0, &[Bodies, Receipts][..]),
+        "debug_traceBlockByNumber" |
+        "debug_traceBlockByHash" |
+        "trace_block" |
+        "trace_replayBlockTransactions" => (0, &[Bodies, State][..]),
+        "eth_getBalance" |
+        "eth_getCode" |
+        "eth_getTransactionCount" |
+        "eth_call" |
+        "eth_estimateGas" |
+        "eth_createAccessList" |
+        "debug_traceCall" |
+        "trace_callMany" => (1, &[State][..]),
+        "eth_getStorageAt" | "eth_getProof" | "trace_call" => (2, &[State][..]),
+        _ => return None,
+    };
+    Some(param)
+}
+
+/// Returns the first block of which all of the given history is available locally, or `None` if
+/// none of it is pruned.
+///
+/// The tip itself is always considered available, since the pruner only runs behind it.
+pub fn first_available_block(
+    modes: &PruneModes,
+    segments: &[HistorySegment],
+    tip: BlockNumber,
+) -> Option<BlockNumber> {
+    segments
+        .iter()
+        .flat_map(|segment| segment.prune_modes(modes))
+        .map(|mode| match *mode {
+            PruneMode::Full => tip,
+            PruneMode::Distance(distance) => tip.saturating_sub(distance),
+            PruneMode::Before(block) => block,
+        })
+        .max()
+}
+
+/// Splits the block range of a `eth_getLogs` request at the first available block, into the part
+/// that must be served by the archive upstream and the part that can be served locally.
+pub fn split_block_range(
+    range: RangeInclusive<BlockNumber>,
+    first_available: BlockNumber,
+) -> (Option<RangeInclusive<BlockNumber>>, Option<RangeInclusive<BlockNumber>>) {
+    let (from, to) = range.into_inner();
+    if to < first_available {
+        (Some(from..=to), None)
+    } else if from >= first_available {
+        (None, Some(from..=to))
+    } else {
+        (Some(from..=first_available - 1), Some(first_available..=to))
+    }
+}
+
+/// A layer that forwards block-scoped requests for history that is pruned by the configured
+/// [`PruneModes`] to an archive RPC upstream.
+///
+/// Requests whose block can't be resolved locally, e.g. unknown block hashes, are served locally.
+/// If the upstream fails, the request is served locally as well, which then fails like it would
+/// without the layer.
+///
+/// `eth_getLogs` ranges that straddle the prune boundary are split: the pruned part is fetched
+/// from the upstream, and the rest is served locally.
+#[derive(Debug, Clone)]
+pub struct ArchiveRpc<P> {
+    inner: Arc<ArchiveRpcInner<P>>,
+}
+
+impl<P> ArchiveRpc<P> {
+    /// Creates a layer that forwards requests for history pruned by `prune_modes` to the HTTP
+    /// endpoint `upstream`.
+    pub fn new(provider: P, upstream: Url, prune_modes: PruneModes) -> Self {
+        let client = RpcClient::new_http(upstream.clone());
+        let metrics = ArchiveRpcMetrics::default();
+        Self {
+            inner: Arc::new(ArchiveRpcInner { provider, client, upstream, prune_modes, metrics }),
+        }
+    }
+
+    /// Returns the archive upstream.
+    pub fn upstream(&self) -> &Url {
+        &self.inner.upstream
+    }
+}
+
+impl<S, P> tower::Layer<S> for ArchiveRpc<P> {
+    type Service = ArchiveRpcService<S, P>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ArchiveRpcService { inner, archive: self.inner.clone() }
+    }
+}
+
+/// A service that forwards requests for pruned history to an archive upstream, created by
+/// [`ArchiveRpc`].
+#[derive(Debug, Clone)]
+pub struct ArchiveRpcService<S, P> {
+    /// The inner service that handles requests for available history.
+    inner: S,
+    /// The context required to forward requests.
+    archive: Arc<ArchiveRpcInner<P>>,
+}
+
+impl<S, P> RpcServiceT for ArchiveRpcService<S, P>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+    P: BlockIdReader + Send + Sync + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let inner = self.inner.clone();
+        let archive = self.archive.clone();
+
+        Box::pin(async move {
+            if req.method_name() == "eth_getLogs" {
+                return archive.get_logs(inner, req).await
+            }
+
+            if let Some((position, segments)) = block_param(req.method_name()) {
+                if archive.is_pruned(&req.params(), position, segments) {
+                    if let Some(result) = archive.forward(req.method_name(), &req.params()).await {
+                        let payload = ResponsePayload::success(result).into();
+                        return MethodResponse::response(req.id, payload, usize::MAX)
+                    }
+                }
+            }
+
+            inner.call(req).await
+        })
+    }
+
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        self.inner.batch(req)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+#[derive(Debug)]
+struct ArchiveRpcInner<P> {
+    /// Provider used to resolve the blocks of requests
+    provider: P,
+    /// Client of the archive upstream
+    client: RpcClient,
+    /// The archive upstream
+    upstream: Url,
+    /// The prune modes of the node
+    prune_modes: PruneModes,
+    /// Forwarding metrics
+    metrics: ArchiveRpcMetrics,
+}
+
+impl<P: BlockIdReader> ArchiveRpcInner<P> {
+    /// Returns the first block of which the given history is available locally, or `None` if none
+    /// of it is pruned.
+    fn first_available_block(&self, segments: &[HistorySegment]) -> Option<BlockNumber> {
+        let tip = self.provider.best_block_number().ok()?;
+        first_available_block(&self.prune_modes, segments, tip).filter(|block| *block > 0)
+    }
+
+    /// Returns `true` if the block parameter at `position` is a block of which the given history
+    /// is pruned.
+    fn is_pruned(&self, params: &Params<'_>, position: usize, segments: &[HistorySegment]) -> bool {
+        let Some(first_available) = self.first_available_block(segments) else { return false };
+        let Some(block_id) = parse_block_id_from_params(params, position) else { return false };
+        match self.provider.block_number_for_id(block_id) {
+            Ok(Some(block)) => block < first_available,
+            _ => {
+                debug!(target: "rpc::archive", ?block_id, "block unknown; not forwarding");
+                false
+            }
+        }
+    }
+
+    /// Serves a `eth_getLogs` request, forwarding the pruned part of its block range.
+    async fn get_logs<S>(&self, inner: S, mut req: Request<'_>) -> MethodResponse
+    where
+        S: RpcServiceT<MethodResponse = MethodResponse>,
+    {
+        let segments = &[HistorySegment::Bodies, HistorySegment::Receipts];
+        let Some((filter, range, first_available)) =
+            req.params().one::<Filter>().ok().and_then(|filter| {
+                let range = filter_block_range(&self.provider, &filter)?;
+                let first_available = self.first_available_block(segments)?;
+                Some((filter, range, first_available))
+            })
+        else {
+            return inner.call(req).await
+        };
+
+        let (pruned, available) = match split_block_range(range, first_available) {
+            (Some(pruned), Some(available)) => (pruned, available),
+            (Some(_), None) => {
+                if let Some(result) = self.forward(req.method_name(), &req.params()).await {
+                    let payload = ResponsePayload::success(result).into();
+                    return MethodResponse::response(req.id, payload, usize::MAX)
+                }
+                return inner.call(req).await
+            }
+            (None, _) => return inner.call(req).await,
+        };
+
+        debug!(target: "rpc::archive", ?pruned, ?available, "splitting eth_getLogs request");
+        self.metrics.split_logs_total.increment(1);
+
+        let id = req.id.clone().into_owned();
+        let upstream_filter = filter.clone().from_block(*pruned.start()).to_block(*pruned.end());
+        let params = serde_json::json!([upstream_filter]);
+        let Some(serde_json::Value::Array(mut logs)) =
+            self.request(req.method_name(), params).await
+        else {
+            return MethodResponse::error(
+                id,
+                ErrorObject::owned(
+                    INTERNAL_ERROR_CODE,
+                    "failed to fetch pruned logs from the archive upstream",
+                    None::<()>,
+                ),
+            )
+        };
+
+        let local_filter = filter.from_block(*available.start()).to_block(*available.end());
+        let Ok(params) = serde_json::value::to_raw_value(&(local_filter,)) else {
+            return inner.call(req).await
+        };
+        req.params = Some(Cow::Owned(params));
+        let response = inner.call(req).await;
+
+        // local errors, e.g. exceeded limits, are returned as is
+        let Ok(local) = serde_json::from_str::<LogsResponse>(response.as_json().get()) else {
+            return response
+        };
+        logs.extend(local.result);
+        MethodResponse::response(id, ResponsePayload::success(logs).into(), usize::MAX)
+    }
+
+    /// Forwards a request to the archive upstream, returning `None` if it fails.
+    async fn forward(&self, method: &str, params: &Params<'_>) -> Option<serde_json::Value> {
+        let params = serde_json::from_str::<serde_json::Value>(params.as_str().unwrap_or("[]"))
+            .inspect_err(|err| debug!(target: "rpc::archive", %err, "invalid params"))
+            .ok()?;
+        debug!(target: "rpc::archive", %method, "forwarding request for pruned history");
+        self.request(method, params).await
+    }
+
+    /// Sends a request to the archive upstream, returning `None` if it fails.
+    async fn request(&self, method: &str, params: serde_json::Value) -> Option<serde_json::Value> {
+        self.metrics.forwarded_total.increment(1);
+        self.client
+            .request::<_, serde_json::Value>(method.to_string(), params)
+            .await
+            .inspect_err(|err| {
+                self.metrics.upstream_errors_total.increment(1);
+                warn!(target: "rpc::archive", %err, %method, "request to archive upstream failed");
+            })
+            .ok()
+    }
+}
+
+/// The result of a successful `eth_getLogs` response.
+#[derive(Deserialize)]
+struct LogsResponse {
+    result: Vec<serde_json::Value>,
+}
+
+/// Metrics of the requests forwarded to the archive upstream.
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.archive")]
+struct ArchiveRpcMetrics {
+    /// The number of requests sent to the archive upstream
+    forwarded_total: Counter,
+    /// The number of requests the archive upstream failed to serve
+    upstream_errors_total: Counter,
+    /// The number of `eth_getLogs` requests split at the prune boundary
+    split_logs_total: Counter,
+}
+
+/// Resolves the block range of a log filter.
+pub(crate) fn filter_block_range<P: BlockIdReader + ?Sized>(
+    provider: &P,
+    filter: &Filter,
+) -> Option<RangeInclusive<BlockNumber>> {
+    match filter.block_option {
+        FilterBlockOption::AtBlockHash(hash) => {
+            let block = provider.block_number_for_id(hash.into()).ok()??;
+            Some(block..=block)
+        }
+        FilterBlockOption::Range { from_block, to_block } => {
+            let best = provider.best_block_number().ok()?;
+            let resolve = |block| match block {
+                Some(block) => provider.convert_block_number(block).ok().flatten(),
+                None => Some(best),
+            };
+            Some(resolve(from_block)?..=resolve(to_block)?)
+        }
+    }
+}
+
+/// Parses a [`BlockId`] from the given parameters at the specified position.
+fn parse_block_id_from_params(params: &Params<'_>, position: usize) -> Option<BlockId> {
+    let values: Vec<serde_json::Value> = params.parse().ok()?;
+    let val = values.into_iter().nth(position)?;
+    serde_json::from_value::<BlockId>(val).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::RethRpcMiddleware;
+    use alloy_eips::BlockNumberOrTag;
+    use jsonrpsee::core::middleware::layer::Either;
+    use reth_storage_api::noop::NoopProvider;
+    use tower::layer::util::Identity;
+
+    #[test]
+    fn check_archive_rpc() {
+        fn assert_archive_rpc<T: RethRpcMiddleware>() {}
+        assert_archive_rpc::<ArchiveRpc<NoopProvider>>();
+        assert_archive_rpc::<Either<ArchiveRpc<NoopProvider>, Identity>>();
+    }
+
+    #[test]
+    fn first_available_block_of_segments() {
+        let modes = PruneModes {
+            bodies_history: Some(PruneMode::Before(15_537_394)),
+            receipts: Some(PruneMode::Distance(10_064)),
+            account_history: Some(PruneMode::Distance(10_064)),
+            storage_history: Some(PruneMode::Full),
+            ..Default::default()
+        };
+        let tip = 20_000_000;
+
+        let bodies = first_available_block(&modes, &[HistorySegment::Bodies], tip);
+        assert_eq!(bodies, Some(15_537_394));
+        let receipts =
+            first_available_block(&modes, &[HistorySegment::Bodies, HistorySegment::Receipts], tip);
+        assert_eq!(receipts, Some(tip - 10_064));
+        assert_eq!(first_available_block(&modes, &[HistorySegment::State], tip), Some(tip));
+
+        // distances beyond the tip don't prune anything
+        assert_eq!(first_available_block(&modes, &[HistorySegment::Receipts], 100), Some(0));
+        assert_eq!(first_available_block(&PruneModes::none(), &[HistorySegment::State], tip), None);
+    }
+
+    #[test]
+    fn split_log_ranges() {
+        assert_eq!(split_block_range(10..=20, 30), (Some(10..=20), None));
+        assert_eq!(split_block_range(10..=20, 10), (None, Some(10..=20)));
+        assert_eq!(split_block_range(10..=20, 15), (Some(10..=14), Some(15..=20)));
+        assert_eq!(split_block_range(10..=20, 20), (Some(10..=19), Some(20..=20)));
+    }
+
+    #[test]
+    fn block_params_of_methods() {
+        assert_eq!(block_param("eth_getBlockByNumber"), Some((0, &[HistorySegment::Bodies][..])));
+        assert_eq!(block_param("eth_getBalance"), Some((1, &[HistorySegment::State][..])));
+        assert_eq!(block_param("eth_getProof"), Some((2, &[HistorySegment::State][..])));
+        assert_eq!(block_param("eth_getLogs"), None);
+        assert_eq!(block_param("eth_sendRawTransaction"), None);
+
+        let params = Params::new(Some(r#"["0x0000000000000000000000000000000000000000", "0x64"]"#));
+        assert_eq!(
+            parse_block_id_from_params(&params, 1),
+            Some(BlockId::Number(BlockNumberOrTag::Number(100)))
+        );
+        assert_eq!(parse_block_id_from_params(&params, 2), None);
+    }
+}
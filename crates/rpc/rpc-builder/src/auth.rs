@@ -1,6 +1,7 @@
 use crate::{
     error::{RpcError, ServerKind},
-    middleware::RethRpcMiddleware,
+    middleware::{RethRpcMiddleware, RpcCallTracingLayer},
+    tls::{start_rpc_server, RpcServer, RpcTlsConfig},
 };
 use http::header::AUTHORIZATION;
 use jsonrpsee::{
@@ -14,6 +15,7 @@ use reth_rpc_api::servers::*;
 use reth_rpc_eth_types::EthSubscriptionIdProvider;
 use reth_rpc_layer::{
     secret_to_bearer_header, AuthClientLayer, AuthLayer, JwtAuthValidator, JwtSecret,
+    RequestTracingLayer,
 };
 use reth_rpc_server_types::constants;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -32,6 +34,8 @@ pub struct AuthServerConfig<RpcMiddleware = Identity> {
     pub(crate) secret: JwtSecret,
     /// Configs for JSON-RPC Http.
     pub(crate) server_config: ServerConfigBuilder,
+    /// TLS config of the server.
+    pub(crate) tls: Option<RpcTlsConfig>,
     /// Configs for IPC server
     pub(crate) ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     /// IPC endpoint
@@ -56,11 +60,14 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
 
     /// Configures the rpc middleware.
     pub fn with_rpc_middleware<T>(self, rpc_middleware: T) -> AuthServerConfig<T> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint, .. } = self;
+        let Self {
+            socket_addr, secret, server_config, tls, ipc_server_config, ipc_endpoint, ..
+        } = self;
         AuthServerConfig {
             socket_addr,
             secret,
             server_config,
+            tls,
             ipc_server_config,
             ipc_endpoint,
             rpc_middleware,
@@ -76,31 +83,35 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
             socket_addr,
             secret,
             server_config,
+            tls,
             ipc_server_config,
             ipc_endpoint,
             rpc_middleware,
         } = self;
 
         // Create auth middleware.
-        let middleware =
-            tower::ServiceBuilder::new().layer(AuthLayer::new(JwtAuthValidator::new(secret)));
+        let middleware = tower::ServiceBuilder::new()
+            .layer(RequestTracingLayer::new())
+            .layer(AuthLayer::new(JwtAuthValidator::new(secret)));
 
-        let rpc_middleware = RpcServiceBuilder::default().layer(rpc_middleware);
+        let rpc_middleware =
+            RpcServiceBuilder::default().layer(RpcCallTracingLayer).layer(rpc_middleware);
 
         // By default, both http and ws are enabled.
-        let server = ServerBuilder::new()
+        let builder = ServerBuilder::new()
             .set_config(server_config.build())
             .set_http_middleware(middleware)
-            .set_rpc_middleware(rpc_middleware)
-            .build(socket_addr)
-            .await
-            .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+            .set_rpc_middleware(rpc_middleware);
+        let server =
+            RpcServer::build(builder, socket_addr, tls, false, ServerKind::Auth(socket_addr))
+                .await?;
 
         let local_addr = server
             .local_addr()
             .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+        let tls = server.is_tls();
 
-        let handle = server.start(module.inner.clone());
+        let handle = start_rpc_server!(server, module.inner.clone());
 
         let ipc_handle = if let Some(ipc_server_config) = ipc_server_config {
             let ipc_endpoint_str = ipc_endpoint
@@ -113,7 +124,14 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
             None
         };
 
-        Ok(AuthServerHandle { handle: Some(handle), local_addr, secret, ipc_endpoint, ipc_handle })
+        Ok(AuthServerHandle {
+            handle: Some(handle),
+            local_addr,
+            tls,
+            secret,
+            ipc_endpoint,
+            ipc_handle,
+        })
     }
 }
 
@@ -123,6 +141,7 @@ pub struct AuthServerConfigBuilder<RpcMiddleware = Identity> {
     socket_addr: Option<SocketAddr>,
     secret: JwtSecret,
     server_config: Option<ServerConfigBuilder>,
+    tls: Option<RpcTlsConfig>,
     ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     ipc_endpoint: Option<String>,
     rpc_middleware: RpcMiddleware,
@@ -137,6 +156,7 @@ impl AuthServerConfigBuilder {
             socket_addr: None,
             secret,
             server_config: None,
+            tls: None,
             ipc_server_config: None,
             ipc_endpoint: None,
             rpc_middleware: Identity::new(),
@@ -147,11 +167,14 @@ impl AuthServerConfigBuilder {
 impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
     /// Configures the rpc middleware.
     pub fn with_rpc_middleware<T>(self, rpc_middleware: T) -> AuthServerConfigBuilder<T> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint, .. } = self;
+        let Self {
+            socket_addr, secret, server_config, tls, ipc_server_config, ipc_endpoint, ..
+        } = self;
         AuthServerConfigBuilder {
             socket_addr,
             secret,
             server_config,
+            tls,
             ipc_server_config,
             ipc_endpoint,
             rpc_middleware,
@@ -185,6 +208,12 @@ impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
         self
     }
 
+    /// Configures TLS for the server.
+    pub fn with_tls(mut self, tls: Option<RpcTlsConfig>) -> Self {
+        self.tls = tls;
+        self
+    }
+
     /// Set the ipc endpoint for the server.
     pub fn ipc_endpoint(mut self, ipc_endpoint: String) -> Self {
         self.ipc_endpoint = Some(ipc_endpoint);
@@ -224,6 +253,7 @@ impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
                     .max_request_body_size(128 * 1024 * 1024)
                     .set_id_provider(EthSubscriptionIdProvider::default())
             }),
+            tls: self.tls,
             ipc_server_config: self.ipc_server_config.map(|ipc_server_config| {
                 ipc_server_config
                     .max_response_body_size(750 * 1024 * 1024)
@@ -305,6 +335,8 @@ impl AuthRpcModule {
 #[must_use = "Server stops if dropped"]
 pub struct AuthServerHandle {
     local_addr: SocketAddr,
+    /// Whether the server serves TLS connections
+    tls: bool,
     handle: Option<jsonrpsee::server::ServerHandle>,
     secret: JwtSecret,
     ipc_endpoint: Option<String>,
@@ -323,6 +355,7 @@ impl AuthServerHandle {
                 IpAddr::V4(Ipv4Addr::LOCALHOST),
                 constants::DEFAULT_AUTH_PORT,
             ),
+            tls: false,
             handle: None,
             secret: JwtSecret::random(),
             ipc_endpoint: None,
@@ -343,12 +376,14 @@ impl AuthServerHandle {
 
     /// Returns the url to the http server
     pub fn http_url(&self) -> String {
-        format!("http://{}", self.local_addr)
+        let scheme = if self.tls { "https" } else { "http" };
+        format!("{scheme}://{}", self.local_addr)
     }
 
     /// Returns the url to the ws server
     pub fn ws_url(&self) -> String {
-        format!("ws://{}", self.local_addr)
+        let scheme = if self.tls { "wss" } else { "ws" };
+        format!("{scheme}://{}", self.local_addr)
     }
 
     /// Returns a http client connected to the server.
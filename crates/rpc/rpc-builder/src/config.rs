@@ -2,15 +2,19 @@ use jsonrpsee::server::ServerConfigBuilder;
 use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_path};
 use reth_rpc::ValidationApiConfig;
 use reth_rpc_eth_types::{EthConfig, EthStateCacheConfig, GasPriceOracleConfig};
+use reth_rpc_graphql::GraphQLConfig;
 use reth_rpc_layer::{JwtError, JwtSecret};
 use reth_rpc_server_types::RpcModuleSelection;
-use std::{net::SocketAddr, path::PathBuf};
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
 use tower::layer::util::Identity;
 use tracing::{debug, warn};
 
 use crate::{
-    auth::AuthServerConfig, error::RpcError, IpcServerBuilder, RpcModuleConfig, RpcServerConfig,
-    TransportRpcModuleConfig,
+    access::RpcAccessControl, auth::AuthServerConfig, error::RpcError, tls::RpcTlsConfig,
+    IpcServerBuilder, RpcModuleConfig, RpcServerConfig, TransportRpcModuleConfig,
 };
 
 /// A trait that provides a configured RPC server.
@@ -57,6 +61,12 @@ pub trait RethRpcServerConfig {
     /// Creates the [`RpcServerConfig`] from cli args.
     fn rpc_server_config(&self) -> RpcServerConfig;
 
+    /// Returns the limits of the GraphQL API, if it is enabled.
+    fn graphql_config(&self) -> Option<GraphQLConfig>;
+
+    /// Returns the max size of the response cache in bytes, if it is enabled.
+    fn response_cache_max_size_bytes(&self) -> Option<usize>;
+
     /// Creates the [`AuthServerConfig`] from cli args.
     fn auth_server_config(&self, jwt_secret: JwtSecret) -> Result<AuthServerConfig, RpcError>;
 
@@ -178,7 +188,9 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let mut config = RpcServerConfig::default()
+            .with_jwt_secret(self.rpc_secret_key())
+            .with_access_control(self.rpc_access_config.clone().map(RpcAccessControl::new));
 
         if self.http_api.is_some() && !self.http {
             warn!(
@@ -192,6 +204,11 @@ impl RethRpcServerConfig for RpcServerArgs {
             config = config
                 .with_http_address(socket_address)
                 .with_http(self.http_ws_server_builder())
+                .with_http_tls(tls_config(
+                    self.http_tls_cert.as_deref(),
+                    self.http_tls_key.as_deref(),
+                    self.http_tls_client_ca.as_deref(),
+                ))
                 .with_http_cors(self.http_corsdomain.clone())
                 .with_http_disable_compression(self.http_disable_compression)
                 .with_ws_cors(self.ws_allowed_origins.clone());
@@ -199,7 +216,14 @@ impl RethRpcServerConfig for RpcServerArgs {
 
         if self.ws {
             let socket_address = SocketAddr::new(self.ws_addr, self.ws_port);
-            config = config.with_ws_address(socket_address).with_ws(self.http_ws_server_builder());
+            config = config
+                .with_ws_address(socket_address)
+                .with_ws(self.http_ws_server_builder())
+                .with_ws_tls(tls_config(
+                    self.ws_tls_cert.as_deref(),
+                    self.ws_tls_key.as_deref(),
+                    self.ws_tls_client_ca.as_deref(),
+                ));
         }
 
         if self.is_ipc_enabled() {
@@ -210,10 +234,33 @@ impl RethRpcServerConfig for RpcServerArgs {
         config
     }
 
+    fn graphql_config(&self) -> Option<GraphQLConfig> {
+        if self.graphql && !self.http {
+            warn!(
+                target: "reth::cli",
+                "The --graphql flag is set but --http is not enabled. GraphQL API will not be exposed."
+            );
+        }
+
+        (self.graphql && self.http).then(|| GraphQLConfig {
+            max_depth: self.graphql_max_depth,
+            max_complexity: self.graphql_max_complexity,
+        })
+    }
+
+    fn response_cache_max_size_bytes(&self) -> Option<usize> {
+        self.rpc_state_cache.max_responses_size.map(|size| size.saturating_mul(1024 * 1024))
+    }
+
     fn auth_server_config(&self, jwt_secret: JwtSecret) -> Result<AuthServerConfig, RpcError> {
         let address = SocketAddr::new(self.auth_addr, self.auth_port);
 
-        let mut builder = AuthServerConfig::builder(jwt_secret).socket_addr(address);
+        let mut builder =
+            AuthServerConfig::builder(jwt_secret).socket_addr(address).with_tls(tls_config(
+                self.auth_tls_cert.as_deref(),
+                self.auth_tls_key.as_deref(),
+                self.auth_tls_client_ca.as_deref(),
+            ));
         if self.auth_ipc {
             builder = builder
                 .ipc_endpoint(self.auth_ipc_path.clone())
@@ -237,15 +284,29 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 }
 
+/// Returns the TLS config of a server if both its certificate and key are configured.
+fn tls_config(
+    cert: Option<&Path>,
+    key: Option<&Path>,
+    client_ca: Option<&Path>,
+) -> Option<RpcTlsConfig> {
+    let config = RpcTlsConfig::new(cert?, key?);
+    Some(match client_ca {
+        Some(client_ca) => config.with_client_ca(client_ca),
+        None => config,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use clap::{Args, Parser};
     use reth_node_core::args::RpcServerArgs;
     use reth_rpc_eth_types::RPC_DEFAULT_GAS_CAP;
+    use reth_rpc_layer::JwtSecret;
     use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
     use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 
-    use crate::config::RethRpcServerConfig;
+    use crate::{config::RethRpcServerConfig, tls::RpcTlsConfig};
 
     /// A helper type to parse Args more easily
     #[derive(Parser)]
@@ -355,6 +416,70 @@ mod tests {
         assert_eq!(config.ipc_endpoint().unwrap(), constants::DEFAULT_IPC_ENDPOINT);
     }
 
+    #[test]
+    fn test_tls_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--http",
+            "--http.tls-cert",
+            "cert.pem",
+            "--http.tls-key",
+            "key.pem",
+            "--authrpc.tls-cert",
+            "cert.pem",
+            "--authrpc.tls-key",
+            "key.pem",
+            "--authrpc.tls-client-ca",
+            "ca.pem",
+        ])
+        .args;
+        assert_eq!(
+            args.rpc_server_config().http_tls,
+            Some(RpcTlsConfig::new("cert.pem", "key.pem"))
+        );
+        assert_eq!(
+            args.auth_server_config(JwtSecret::random()).unwrap().tls,
+            Some(RpcTlsConfig::new("cert.pem", "key.pem").with_client_ca("ca.pem"))
+        );
+
+        let args =
+            CommandParser::<RpcServerArgs>::try_parse_from(["reth", "--ws.tls-cert", "cert.pem"]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_graphql_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth", "--graphql"]).args;
+        assert_eq!(args.graphql_config(), None);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--http",
+            "--graphql",
+            "--graphql.max-depth",
+            "8",
+        ])
+        .args;
+        assert_eq!(
+            args.graphql_config(),
+            Some(GraphQLConfig { max_depth: 8, ..Default::default() })
+        );
+    }
+
+    #[test]
+    fn test_response_cache_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.response_cache_max_size_bytes(), None);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc-cache.max-responses-size",
+            "64",
+        ])
+        .args;
+        assert_eq!(args.response_cache_max_size_bytes(), Some(64 * 1024 * 1024));
+    }
+
     #[test]
     fn test_zero_filter_limits() {
         let args = CommandParser::<RpcServerArgs>::parse_from([
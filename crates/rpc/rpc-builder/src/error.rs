@@ -1,4 +1,4 @@
-use crate::{cors::CorsDomainError, RethRpcModule};
+use crate::{cors::CorsDomainError, tls::TlsError, RethRpcModule};
 use reth_ipc::server::IpcServerStartError;
 use std::{
     collections::HashSet,
@@ -70,6 +70,9 @@ pub enum RpcError {
     /// Thrown when IPC server fails to start.
     #[error(transparent)]
     IpcServerError(#[from] IpcServerStartError),
+    /// Thrown when the TLS certificate of a server can't be loaded.
+    #[error(transparent)]
+    Tls(#[from] TlsError),
     /// Custom error.
     #[error("{0}")]
     Custom(String),
@@ -127,6 +130,9 @@ pub enum WsHttpSamePortError {
     /// Ws and http server configured on same port but with different modules.
     #[error("{0}")]
     ConflictingModules(Box<ConflictingModules>),
+    /// Ws and http server configured on same port but with different TLS certificates.
+    #[error("TLS configs for HTTP and WS are different, but they are on the same port")]
+    ConflictingTls,
 }
 
 #[cfg(test)]
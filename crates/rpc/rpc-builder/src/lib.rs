@@ -36,23 +36,29 @@ use reth_evm::ConfigureEvm;
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_primitives_traits::NodePrimitives;
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, MinerApi, NetApi,
-    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, ValidationApiConfig, Web3Api,
+    AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, EthFilter, MinerApi,
+    NetApi, OtterscanApi, RPCApi, RethAdminApi, RethApi, TraceApi, TxPoolApi, ValidationApiConfig,
+    Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
     helpers::{
-        pending_block::PendingEnvBuilder, Call, EthApiSpec, EthTransactions, LoadPendingBlock,
-        TraceExt,
+        pending_block::PendingEnvBuilder, Call, EthApiSpec, EthTransactions, FullEthApi,
+        LoadPendingBlock, TraceExt,
     },
     node::RpcNodeCoreAdapter,
-    EthApiServer, EthApiTypes, FullEthApiServer, RpcBlock, RpcConvert, RpcConverter, RpcHeader,
-    RpcNodeCore, RpcReceipt, RpcTransaction, RpcTxReq,
+    EngineEthFilter, EthApiServer, EthApiTypes, FullEthApiServer, QueryLimits, RpcBlock,
+    RpcConvert, RpcConverter, RpcHeader, RpcNodeCore, RpcReceipt, RpcTransaction, RpcTxReq,
+};
+use reth_rpc_eth_types::{
+    receipt::EthReceiptConverter, EthConfig, EthFilterConfig, EthSubscriptionIdProvider,
+};
+use reth_rpc_graphql::{build_schema, EthGraphQLBackend, GraphQLConfig, GraphQLLayer};
+use reth_rpc_layer::{
+    AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret, RequestTracingLayer,
 };
-use reth_rpc_eth_types::{receipt::EthReceiptConverter, EthConfig, EthSubscriptionIdProvider};
-use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
 use reth_storage_api::{
-    AccountReader, BlockReader, ChangeSetReader, FullRpcProvider, ProviderBlock,
+    AccountReader, BlockIdReader, BlockReader, ChangeSetReader, FullRpcProvider, ProviderBlock,
     StateProviderFactory,
 };
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
@@ -62,6 +68,7 @@ use std::{
     collections::HashMap,
     fmt::Debug,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tower_http::cors::CorsLayer;
@@ -98,7 +105,7 @@ pub use eth::EthHandlers;
 
 // Rpc server metrics
 mod metrics;
-use crate::middleware::RethRpcMiddleware;
+use crate::middleware::{RethRpcMiddleware, RpcCallTracingLayer};
 pub use metrics::{MeteredRequestFuture, RpcRequestMetricsService};
 use reth_chain_state::CanonStateSubscriptions;
 use reth_rpc::eth::sim_bundle::EthSimBundle;
@@ -106,6 +113,21 @@ use reth_rpc::eth::sim_bundle::EthSimBundle;
 // Rpc rate limiter
 pub mod rate_limiter;
 
+// Rpc access control
+pub mod access;
+use access::RpcAccessControl;
+
+// Rpc TLS termination
+pub mod tls;
+use tls::{start_rpc_server, RpcServer, RpcTlsConfig};
+
+// Rpc forwarding of pruned history
+pub mod archive;
+
+// Rpc response cache
+pub mod response_cache;
+use response_cache::RpcResponseCache;
+
 /// A builder type to configure the RPC module: See [`RpcModule`]
 ///
 /// This is the main entrypoint and the easiest way to configure an RPC server.
@@ -580,6 +602,32 @@ where
         &self.eth
     }
 
+    /// Returns the [`GraphQLLayer`] serving the EIP-1767 schema with the installed [`EthApi`]
+    /// and its filter, which limits log queries like `eth_getLogs`.
+    pub fn graphql_layer(&self, config: GraphQLConfig) -> GraphQLLayer
+    where
+        EthApi: FullEthApi,
+        EthFilter<EthApi>: EngineEthFilter,
+    {
+        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, .. } =
+            self.eth_config.filter_config();
+        let backend = EthGraphQLBackend::new(
+            self.eth.api.clone(),
+            self.eth.filter.clone(),
+            QueryLimits { max_blocks_per_filter, max_logs_per_response },
+        );
+        GraphQLLayer::new(build_schema(Arc::new(backend), config))
+    }
+
+    /// Returns a [`RpcResponseCache`] of at most `max_size` bytes, that resolves the blocks of
+    /// requests with the installed provider.
+    pub fn response_cache(&self, max_size: usize) -> RpcResponseCache
+    where
+        Provider: BlockIdReader + Clone + 'static,
+    {
+        RpcResponseCache::new(self.provider.clone(), max_size)
+    }
+
     /// Returns a reference to the pool
     pub const fn pool(&self) -> &Pool {
         &self.pool
@@ -636,8 +684,9 @@ where
     where
         Network: Peers,
     {
-        let adminapi = self.admin_api();
-        self.modules.insert(RethRpcModule::Admin, adminapi.into_rpc().into());
+        let mut module = self.admin_api().into_rpc();
+        module.merge(RethAdminApi::new().into_rpc()).expect("No conflicts");
+        self.modules.insert(RethRpcModule::Admin, module.into());
         self
     }
 
@@ -921,9 +970,11 @@ where
                     .entry(namespace)
                     .or_insert_with(|| match namespace {
                         RethRpcModule::Admin => {
-                            AdminApi::new(self.network.clone(), self.provider.chain_spec())
-                                .into_rpc()
-                                .into()
+                            let mut module =
+                                AdminApi::new(self.network.clone(), self.provider.chain_spec())
+                                    .into_rpc();
+                            module.merge(RethAdminApi::new().into_rpc()).expect("No conflicts");
+                            module.into()
                         }
                         RethRpcModule::Debug => {
                             DebugApi::new(eth_api.clone(), self.blocking_pool_guard.clone())
@@ -1014,6 +1065,8 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     http_cors_domains: Option<String>,
     /// Address where to bind the http server to
     http_addr: Option<SocketAddr>,
+    /// TLS config of the http server
+    http_tls: Option<RpcTlsConfig>,
     /// Control whether http responses should be compressed
     http_disable_compression: bool,
     /// Configs for WS server
@@ -1022,12 +1075,20 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ws_cors_domains: Option<String>,
     /// Address where to bind the ws server to
     ws_addr: Option<SocketAddr>,
+    /// TLS config of the ws server
+    ws_tls: Option<RpcTlsConfig>,
     /// Configs for JSON-RPC IPC server
     ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     /// The Endpoint where to launch the ipc server
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Access control for http and ws
+    access_control: Option<RpcAccessControl>,
+    /// GraphQL endpoint of the http server
+    graphql: Option<GraphQLLayer>,
+    /// Cache of immutable responses for http and ws
+    response_cache: Option<RpcResponseCache>,
     /// Configurable RPC middleware
     rpc_middleware: RpcMiddleware,
 }
@@ -1041,13 +1102,18 @@ impl Default for RpcServerConfig<Identity> {
             http_server_config: None,
             http_cors_domains: None,
             http_addr: None,
+            http_tls: None,
             http_disable_compression: false,
             ws_server_config: None,
             ws_cors_domains: None,
             ws_addr: None,
+            ws_tls: None,
             ipc_server_config: None,
             ipc_endpoint: None,
             jwt_secret: None,
+            access_control: None,
+            graphql: None,
+            response_cache: None,
             rpc_middleware: Default::default(),
         }
     }
@@ -1105,13 +1171,18 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             http_server_config: self.http_server_config,
             http_cors_domains: self.http_cors_domains,
             http_addr: self.http_addr,
+            http_tls: self.http_tls,
             http_disable_compression: self.http_disable_compression,
             ws_server_config: self.ws_server_config,
             ws_cors_domains: self.ws_cors_domains,
             ws_addr: self.ws_addr,
+            ws_tls: self.ws_tls,
             ipc_server_config: self.ipc_server_config,
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
+            access_control: self.access_control,
+            graphql: self.graphql,
+            response_cache: self.response_cache,
             rpc_middleware,
         }
     }
@@ -1157,6 +1228,20 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures TLS for the http server.
+    ///
+    /// If the http and ws servers are on the same port, this applies to both of them.
+    pub fn with_http_tls(mut self, tls: Option<RpcTlsConfig>) -> Self {
+        self.http_tls = tls;
+        self
+    }
+
+    /// Configures TLS for the ws server.
+    pub fn with_ws_tls(mut self, tls: Option<RpcTlsConfig>) -> Self {
+        self.ws_tls = tls;
+        self
+    }
+
     /// Sets a custom [`IdProvider`] for all configured transports.
     ///
     /// By default all transports use [`EthSubscriptionIdProvider`]
@@ -1191,6 +1276,31 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures the access control of the http and ws servers.
+    ///
+    /// See [`RpcAccessControl`] for more details.
+    pub fn with_access_control(mut self, access_control: Option<RpcAccessControl>) -> Self {
+        self.access_control = access_control;
+        self
+    }
+
+    /// Configures the GraphQL endpoint of the http server, see [`GraphQLLayer`].
+    ///
+    /// If the http and ws servers are on the same port, the endpoint is served by the combined
+    /// server.
+    pub fn with_graphql(mut self, graphql: Option<GraphQLLayer>) -> Self {
+        self.graphql = graphql;
+        self
+    }
+
+    /// Configures the cache of immutable responses of the http and ws servers.
+    ///
+    /// See [`RpcResponseCache`] for more details.
+    pub fn with_response_cache(mut self, response_cache: Option<RpcResponseCache>) -> Self {
+        self.response_cache = response_cache;
+        self
+    }
+
     /// Configures a custom tokio runtime for the rpc server.
     pub fn with_tokio_runtime(mut self, tokio_runtime: tokio::runtime::Handle) -> Self {
         if let Some(http_server_config) = self.http_server_config {
@@ -1251,6 +1361,16 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         }
     }
 
+    /// Returns the [`GraphQLLayer`], which checks the access control of the caller if
+    /// configured.
+    fn maybe_graphql_layer(&self) -> Option<GraphQLLayer> {
+        let graphql = self.graphql.clone()?;
+        Some(match &self.access_control {
+            Some(access_control) => graphql.with_guard(access_control.graphql_guard()),
+            None => graphql,
+        })
+    }
+
     /// Builds and starts the configured server(s): http, ws, ipc.
     ///
     /// If both http and ws are on the same port, they are combined into one server.
@@ -1280,7 +1400,9 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
 
         if let Some(builder) = self.ipc_server_config {
             let ipc = builder
-                .set_rpc_middleware(IpcRpcServiceBuilder::new().layer(metrics))
+                .set_rpc_middleware(
+                    IpcRpcServiceBuilder::new().layer(metrics).layer(RpcCallTracingLayer),
+                )
                 .build(ipc_path);
             ipc_handle = Some(ipc.start(modules.ipc.clone().expect("ipc server error")).await?);
         }
@@ -1305,18 +1427,31 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             }
             .cloned();
 
+            let tls = match (self.ws_tls.as_ref(), self.http_tls.as_ref()) {
+                (Some(ws_tls), Some(http_tls)) if ws_tls != http_tls => {
+                    return Err(WsHttpSamePortError::ConflictingTls.into())
+                }
+                (ws_tls, http_tls) => http_tls.or(ws_tls),
+            }
+            .cloned();
+
             // we merge this into one server using the http setup
             modules.config.ensure_ws_http_identical()?;
 
             if let Some(config) = self.http_server_config {
-                let server = ServerBuilder::new()
+                let builder = ServerBuilder::new()
                     .set_http_middleware(
                         tower::ServiceBuilder::new()
+                            .option_layer(
+                                self.access_control.as_ref().map(RpcAccessControl::http_layer),
+                            )
+                            .layer(RequestTracingLayer::new())
                             .option_layer(Self::maybe_cors_layer(cors)?)
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
                             .option_layer(Self::maybe_compression_layer(
                                 self.http_disable_compression,
-                            )),
+                            ))
+                            .option_layer(self.maybe_graphql_layer()),
                     )
                     .set_rpc_middleware(
                         RpcServiceBuilder::default()
@@ -1328,25 +1463,34 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                                     .map(RpcRequestMetrics::same_port)
                                     .unwrap_or_default(),
                             )
+                            .layer(RpcCallTracingLayer)
+                            .option_layer(self.access_control.clone())
+                            .option_layer(self.response_cache.clone())
                             .layer(self.rpc_middleware.clone()),
                     )
-                    .set_config(config.build())
-                    .build(http_socket_addr)
-                    .await
-                    .map_err(|err| {
-                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                    })?;
+                    .set_config(config.build());
+                let server = RpcServer::build(
+                    builder,
+                    http_socket_addr,
+                    tls,
+                    self.access_control.is_some(),
+                    ServerKind::WsHttp(http_socket_addr),
+                )
+                .await?;
                 let addr = server.local_addr().map_err(|err| {
                     RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
                 })?;
+                let tls = server.is_tls();
                 if let Some(module) = modules.http.as_ref().or(modules.ws.as_ref()) {
-                    let handle = server.start(module.clone());
+                    let handle = start_rpc_server!(server, module.clone());
                     http_handle = Some(handle.clone());
                     ws_handle = Some(handle);
                 }
                 return Ok(RpcServerHandle {
                     http_local_addr: Some(addr),
                     ws_local_addr: Some(addr),
+                    http_tls: tls,
+                    ws_tls: tls,
                     http: http_handle,
                     ws: ws_handle,
                     ipc_endpoint: self.ipc_endpoint.clone(),
@@ -1362,21 +1506,33 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         let mut http_server = None;
 
         if let Some(config) = self.ws_server_config {
-            let server = ServerBuilder::new()
+            let builder = ServerBuilder::new()
                 .set_config(config.ws_only().build())
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
+                        .option_layer(
+                            self.access_control.as_ref().map(RpcAccessControl::http_layer),
+                        )
+                        .layer(RequestTracingLayer::new())
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::default()
                         .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
+                        .layer(RpcCallTracingLayer)
+                        .option_layer(self.access_control.clone())
+                        .option_layer(self.response_cache.clone())
                         .layer(self.rpc_middleware.clone()),
-                )
-                .build(ws_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+                );
+            let server = RpcServer::build(
+                builder,
+                ws_socket_addr,
+                self.ws_tls,
+                self.access_control.is_some(),
+                ServerKind::WS(ws_socket_addr),
+            )
+            .await?;
 
             let addr = server
                 .local_addr()
@@ -1387,24 +1543,37 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         }
 
         if let Some(config) = self.http_server_config {
-            let server = ServerBuilder::new()
+            let builder = ServerBuilder::new()
                 .set_config(config.http_only().build())
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
+                        .option_layer(
+                            self.access_control.as_ref().map(RpcAccessControl::http_layer),
+                        )
+                        .layer(RequestTracingLayer::new())
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
-                        .option_layer(Self::maybe_compression_layer(self.http_disable_compression)),
+                        .option_layer(Self::maybe_compression_layer(self.http_disable_compression))
+                        .option_layer(self.maybe_graphql_layer()),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::default()
                         .layer(
                             modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
                         )
+                        .layer(RpcCallTracingLayer)
+                        .option_layer(self.access_control.clone())
+                        .option_layer(self.response_cache.clone())
                         .layer(self.rpc_middleware.clone()),
-                )
-                .build(http_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
+                );
+            let server = RpcServer::build(
+                builder,
+                http_socket_addr,
+                self.http_tls,
+                self.access_control.is_some(),
+                ServerKind::Http(http_socket_addr),
+            )
+            .await?;
             let local_addr = server
                 .local_addr()
                 .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
@@ -1412,13 +1581,19 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             http_server = Some(server);
         }
 
-        http_handle = http_server
-            .map(|http_server| http_server.start(modules.http.clone().expect("http server error")));
-        ws_handle = ws_server
-            .map(|ws_server| ws_server.start(modules.ws.clone().expect("ws server error")));
+        let http_tls = http_server.as_ref().is_some_and(RpcServer::is_tls);
+        let ws_tls = ws_server.as_ref().is_some_and(RpcServer::is_tls);
+        http_handle = http_server.map(|http_server| {
+            start_rpc_server!(http_server, modules.http.clone().expect("http server error"))
+        });
+        ws_handle = ws_server.map(|ws_server| {
+            start_rpc_server!(ws_server, modules.ws.clone().expect("ws server error"))
+        });
         Ok(RpcServerHandle {
             http_local_addr,
             ws_local_addr,
+            http_tls,
+            ws_tls,
             http: http_handle,
             ws: ws_handle,
             ipc_endpoint: self.ipc_endpoint.clone(),
@@ -1984,6 +2159,9 @@ pub struct RpcServerHandle {
     /// The address of the http/ws server
     http_local_addr: Option<SocketAddr>,
     ws_local_addr: Option<SocketAddr>,
+    /// Whether the http/ws server serves TLS connections
+    http_tls: bool,
+    ws_tls: bool,
     http: Option<ServerHandle>,
     ws: Option<ServerHandle>,
     ipc_endpoint: Option<String>,
@@ -2044,12 +2222,14 @@ impl RpcServerHandle {
 
     /// Returns the url to the http server
     pub fn http_url(&self) -> Option<String> {
-        self.http_local_addr.map(|addr| format!("http://{addr}"))
+        let scheme = if self.http_tls { "https" } else { "http" };
+        self.http_local_addr.map(|addr| format!("{scheme}://{addr}"))
     }
 
     /// Returns the url to the ws server
     pub fn ws_url(&self) -> Option<String> {
-        self.ws_local_addr.map(|addr| format!("ws://{addr}"))
+        let scheme = if self.ws_tls { "wss" } else { "ws" };
+        self.ws_local_addr.map(|addr| format!("{scheme}://{addr}"))
     }
 
     /// Returns a http client connected to the server.
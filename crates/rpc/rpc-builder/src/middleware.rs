@@ -1,5 +1,11 @@
-use jsonrpsee::server::middleware::rpc::RpcService;
+use jsonrpsee::{
+    core::middleware::{Batch, Notification},
+    server::middleware::rpc::{RpcService, RpcServiceT},
+    types::Request,
+};
+use std::future::Future;
 use tower::Layer;
+use tracing::{debug_span, Instrument};
 
 /// A Helper alias trait for the RPC middleware supported by the server.
 pub trait RethRpcMiddleware:
@@ -35,3 +41,52 @@ impl<T> RethRpcMiddleware for T where
         + 'static
 {
 }
+
+/// A [`Layer`] that wraps each RPC method call in a `rpc_call` span.
+///
+/// Combined with the [`RequestTracingLayer`](reth_rpc_layer::RequestTracingLayer) HTTP middleware,
+/// the calls of a request become children of the trace of the caller.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct RpcCallTracingLayer;
+
+impl<S> Layer<S> for RpcCallTracingLayer {
+    type Service = RpcCallTracingService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcCallTracingService { inner }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that instruments each method call with a span.
+///
+/// Created by [`RpcCallTracingLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcCallTracingService<S> {
+    inner: S,
+}
+
+impl<S> RpcServiceT for RpcCallTracingService<S>
+where
+    S: RpcServiceT + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = S::MethodResponse> + Send + 'a {
+        let span = debug_span!(target: "rpc", "rpc_call", method = %req.method_name());
+        self.inner.call(req).instrument(span)
+    }
+
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        self.inner.batch(req)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
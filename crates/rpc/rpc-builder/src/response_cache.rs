@@ -0,0 +1,476 @@
+//! [`jsonrpsee`] layer that caches the responses of requests pinned to finalized blocks, see
+//! [`RpcResponseCache`].
+
+use crate::archive::{block_param, filter_block_range};
+use alloy_eips::BlockId;
+use alloy_primitives::{BlockNumber, B256};
+use alloy_rpc_types_eth::Filter;
+use jsonrpsee::{
+    core::middleware::{Batch, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{Request, ResponsePayload},
+    MethodResponse,
+};
+use parking_lot::Mutex;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use reth_storage_api::BlockIdReader;
+use schnellru::{LruMap, Unlimited};
+use serde::Deserialize;
+use serde_json::{value::RawValue, Value};
+use std::{fmt, future::Future, sync::Arc};
+use tower::Layer;
+
+/// A size-bounded cache of the responses of requests whose result can't change anymore, because
+/// they are pinned to a block at or below the finalized block.
+///
+/// Requests are cached by their method and their canonicalized params, in which the block is
+/// replaced by its hash and hex strings are lowercased. Admitted are the block-scoped methods,
+/// such as `eth_call`, `eth_getBlockByNumber` or `debug_traceBlockByNumber`, and `eth_getLogs`
+/// requests whose whole range is finalized. Only successful responses are cached, and the least
+/// recently used responses are evicted once the size of the cached results exceeds the maximum.
+#[derive(Clone)]
+pub struct RpcResponseCache {
+    inner: Arc<RpcResponseCacheInner>,
+}
+
+impl RpcResponseCache {
+    /// Creates a cache of at most `max_size` bytes of results, that resolves the blocks of
+    /// requests with the given provider.
+    pub fn new<P>(provider: P, max_size: usize) -> Self
+    where
+        P: BlockIdReader + 'static,
+    {
+        let inner = RpcResponseCacheInner {
+            provider: Box::new(provider),
+            entries: Mutex::new(CacheEntries { responses: LruMap::new(Unlimited), size: 0 }),
+            max_size,
+            metrics: RpcResponseCacheMetrics::default(),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Returns the number of cached responses.
+    pub fn len(&self) -> usize {
+        self.inner.entries.lock().responses.len()
+    }
+
+    /// Returns `true` if no responses are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Debug for RpcResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcResponseCache")
+            .field("max_size", &self.inner.max_size)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for RpcResponseCache {
+    type Service = RpcResponseCacheService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcResponseCacheService { inner, cache: self.clone() }
+    }
+}
+
+struct RpcResponseCacheInner {
+    /// Provider used to resolve the blocks of requests
+    provider: Box<dyn BlockIdReader>,
+    /// The cached responses
+    entries: Mutex<CacheEntries>,
+    /// The maximum size of the cached results, in bytes
+    max_size: usize,
+    metrics: RpcResponseCacheMetrics,
+}
+
+/// The cached results by cache key, with their total size.
+struct CacheEntries {
+    responses: LruMap<String, Arc<RawValue>, Unlimited>,
+    size: usize,
+}
+
+impl RpcResponseCacheInner {
+    /// Returns the cache key of a request, if its result is immutable.
+    fn cache_key(&self, method: &str, params: Option<&str>) -> Option<String> {
+        let mut params = match params {
+            Some(params) => serde_json::from_str::<Vec<Value>>(params).ok()?,
+            None => Vec::new(),
+        };
+        let finalized = self.provider.finalized_block_number().ok()??;
+
+        if method == "eth_getLogs" {
+            let filter = serde_json::from_value::<Filter>(params.first()?.clone()).ok()?;
+            let range = filter_block_range(&*self.provider, &filter)?;
+            if *range.end() > finalized {
+                return None
+            }
+            // pin the filter to the canonical blocks of its range
+            let (from, to) = range.into_inner();
+            let from = self.provider.block_hash(from).ok()??;
+            let to = self.provider.block_hash(to).ok()??;
+            let mut filter = serde_json::to_value(filter).ok()?;
+            let filter_object = filter.as_object_mut()?;
+            filter_object.remove("blockHash");
+            filter_object.insert("fromBlock".to_string(), Value::String(from.to_string()));
+            filter_object.insert("toBlock".to_string(), Value::String(to.to_string()));
+            params[0] = filter;
+        } else {
+            let (position, _) = block_param(method)?;
+            let block_id = serde_json::from_value::<BlockId>(params.get(position)?.clone()).ok()?;
+            let hash = self.finalized_block_hash(block_id, finalized)?;
+            params[position] = Value::String(hash.to_string());
+        }
+
+        params.iter_mut().for_each(canonicalize);
+        Some(format!("{method}:{}", serde_json::to_string(&params).ok()?))
+    }
+
+    /// Returns the hash of the block, if it is canonical and at or below the finalized block.
+    fn finalized_block_hash(&self, block_id: BlockId, finalized: BlockNumber) -> Option<B256> {
+        let number = self.provider.block_number_for_id(block_id).ok()??;
+        if number > finalized {
+            return None
+        }
+        let hash = self.provider.block_hash(number).ok()??;
+        // hashes of non-canonical blocks aren't admitted
+        if let BlockId::Hash(requested) = block_id {
+            if requested.block_hash != hash {
+                return None
+            }
+        }
+        Some(hash)
+    }
+
+    /// Returns the cached result of the given key.
+    fn get(&self, key: &str) -> Option<Arc<RawValue>> {
+        self.entries.lock().responses.get(key).cloned()
+    }
+
+    /// Caches the result of a successful response.
+    fn insert(&self, key: String, response: &MethodResponse) {
+        if !response.is_success() {
+            return
+        }
+        let Ok(CachedResult { result }) =
+            serde_json::from_str::<CachedResult<'_>>(response.as_json().get())
+        else {
+            return
+        };
+        let size = key.len() + result.get().len();
+        if size > self.max_size {
+            return
+        }
+
+        let mut entries = self.entries.lock();
+        if entries.responses.peek(key.as_str()).is_some() {
+            return
+        }
+        entries.responses.insert(key, Arc::from(result.to_owned()));
+        entries.size += size;
+        while entries.size > self.max_size {
+            let Some((key, result)) = entries.responses.pop_oldest() else { break };
+            entries.size -= key.len() + result.get().len();
+            self.metrics.evictions_total.increment(1);
+        }
+        self.metrics.entries.set(entries.responses.len() as f64);
+        self.metrics.size_bytes.set(entries.size as f64);
+    }
+}
+
+/// Canonicalizes a JSON value, by sorting the keys of objects and lowercasing hex strings.
+fn canonicalize(value: &mut Value) {
+    match value {
+        Value::String(string) if string.starts_with("0x") || string.starts_with("0X") => {
+            *string = string.to_lowercase();
+        }
+        Value::Array(values) => values.iter_mut().for_each(canonicalize),
+        Value::Object(object) => {
+            let mut entries = std::mem::take(object).into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (key, mut value) in entries {
+                canonicalize(&mut value);
+                object.insert(key, value);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The result of a successful response.
+#[derive(Deserialize)]
+struct CachedResult<'a> {
+    #[serde(borrow)]
+    result: &'a RawValue,
+}
+
+/// A service that serves immutable results from the cache, created by [`RpcResponseCache`].
+#[derive(Debug, Clone)]
+pub struct RpcResponseCacheService<S> {
+    inner: S,
+    cache: RpcResponseCache,
+}
+
+impl<S> RpcServiceT for RpcResponseCacheService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let inner = self.inner.clone();
+        let cache = self.cache.inner.clone();
+
+        Box::pin(async move {
+            let Some(key) =
+                cache.cache_key(req.method_name(), req.params.as_deref().map(RawValue::get))
+            else {
+                return inner.call(req).await
+            };
+
+            if let Some(result) = cache.get(&key) {
+                cache.metrics.hits_total.increment(1);
+                let payload = ResponsePayload::success(&*result).into();
+                return MethodResponse::response(req.id, payload, usize::MAX)
+            }
+
+            cache.metrics.misses_total.increment(1);
+            let response = inner.call(req).await;
+            cache.insert(key, &response);
+            response
+        })
+    }
+
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        self.inner.batch(req)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Metrics of the response cache.
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.response_cache")]
+struct RpcResponseCacheMetrics {
+    /// The number of cacheable requests served from the cache
+    hits_total: Counter,
+    /// The number of cacheable requests that weren't cached
+    misses_total: Counter,
+    /// The number of responses evicted from the cache
+    evictions_total: Counter,
+    /// The number of cached responses
+    entries: Gauge,
+    /// The size of the cached results, in bytes
+    size_bytes: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_eips::BlockNumHash;
+    use jsonrpsee::types::Id;
+    use reth_ethereum_primitives::EthPrimitives;
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_storage_api::noop::NoopProvider;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn response(result: Value) -> MethodResponse {
+        MethodResponse::response(Id::Number(1), ResponsePayload::success(result).into(), usize::MAX)
+    }
+
+    /// Returns a provider of the canonical blocks `0..=10` with the finalized block 5, and the
+    /// hashes of the blocks.
+    fn finalized_provider() -> (MockEthProvider<EthPrimitives>, Vec<B256>) {
+        let provider = MockEthProvider::<EthPrimitives>::new();
+        let mut hashes = Vec::<B256>::new();
+        for number in 0..=10 {
+            let parent_hash = hashes.last().copied().unwrap_or_default();
+            let header = Header { number, parent_hash, ..Default::default() };
+            let hash = header.hash_slow();
+            provider.add_header(hash, header);
+            hashes.push(hash);
+        }
+        provider.set_finalized_block(BlockNumHash::new(5, hashes[5]));
+        (provider, hashes)
+    }
+
+    fn request(method: &str, params: &str) -> Request<'static> {
+        let params = RawValue::from_string(params.to_string()).unwrap();
+        Request::owned(method.to_string(), Some(params), Id::Number(1))
+    }
+
+    /// A service that counts the calls it serves.
+    #[derive(Clone, Default)]
+    struct CountingService {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl RpcServiceT for CountingService {
+        type MethodResponse = MethodResponse;
+        type NotificationResponse = MethodResponse;
+        type BatchResponse = MethodResponse;
+
+        fn call<'a>(
+            &self,
+            req: Request<'a>,
+        ) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+            let calls = self.calls.fetch_add(1, Ordering::Relaxed);
+            std::future::ready(MethodResponse::response(
+                req.id,
+                ResponsePayload::success(json!(calls)).into(),
+                usize::MAX,
+            ))
+        }
+
+        fn batch<'a>(
+            &self,
+            _req: Batch<'a>,
+        ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+            async { unimplemented!("batches aren't cached") }
+        }
+
+        fn notification<'a>(
+            &self,
+            _n: Notification<'a>,
+        ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+            async { unimplemented!("notifications aren't cached") }
+        }
+    }
+
+    #[test]
+    fn canonicalize_params() {
+        let mut params = json!([{ "to": "0xABCD", "data": "0xFF", "from": "0x0a" }, "Latest"]);
+        canonicalize(&mut params);
+        assert_eq!(
+            serde_json::to_string(&params).unwrap(),
+            r#"[{"data":"0xff","from":"0x0a","to":"0xabcd"},"Latest"]"#
+        );
+    }
+
+    #[test]
+    fn only_admit_finalized_blocks() {
+        // without a finalized block nothing is admitted
+        let cache = RpcResponseCache::new(NoopProvider::default(), 1024);
+        assert_eq!(cache.inner.cache_key("eth_getBlockByNumber", Some(r#"["0x1", false]"#)), None);
+        assert_eq!(cache.inner.cache_key("eth_getLogs", Some(r#"[{"fromBlock":"0x1"}]"#)), None);
+    }
+
+    #[test]
+    fn cache_key_of_finalized_block() {
+        let (provider, hashes) = finalized_provider();
+        let cache = RpcResponseCache::new(provider, 1024);
+
+        // the block is replaced by its hash, and hex strings are lowercased
+        assert_eq!(
+            cache.inner.cache_key("eth_getBlockByNumber", Some(r#"["0x3", false]"#)),
+            Some(format!(r#"eth_getBlockByNumber:["{}",false]"#, hashes[3]))
+        );
+        assert_eq!(
+            cache.inner.cache_key("eth_getBalance", Some(r#"["0xABCD", "finalized"]"#)),
+            Some(format!(r#"eth_getBalance:["0xabcd","{}"]"#, hashes[5]))
+        );
+        assert_eq!(
+            cache
+                .inner
+                .cache_key("eth_getBlockByHash", Some(&format!(r#"["{}", true]"#, hashes[5]))),
+            Some(format!(r#"eth_getBlockByHash:["{}",true]"#, hashes[5]))
+        );
+    }
+
+    #[test]
+    fn reject_blocks_above_finalized() {
+        let (provider, hashes) = finalized_provider();
+        let cache = RpcResponseCache::new(provider, 1024);
+
+        assert_eq!(cache.inner.cache_key("eth_getBlockByNumber", Some(r#"["0x6", false]"#)), None);
+        assert_eq!(cache.inner.cache_key("eth_getBalance", Some(r#"["0xabcd", "latest"]"#)), None);
+        assert_eq!(
+            cache
+                .inner
+                .cache_key("eth_getBlockByHash", Some(&format!(r#"["{}", true]"#, hashes[6]))),
+            None
+        );
+        // unknown hashes aren't admitted
+        assert_eq!(
+            cache
+                .inner
+                .cache_key("eth_getBlockByHash", Some(&format!(r#"["{}", true]"#, B256::ZERO))),
+            None
+        );
+    }
+
+    #[test]
+    fn logs_range_straddling_finalized() {
+        let (provider, hashes) = finalized_provider();
+        let cache = RpcResponseCache::new(provider, 1024);
+
+        let straddling = r#"[{"fromBlock":"0x3","toBlock":"0x7"}]"#;
+        assert_eq!(cache.inner.cache_key("eth_getLogs", Some(straddling)), None);
+        // the range ends at the latest block by default
+        assert_eq!(cache.inner.cache_key("eth_getLogs", Some(r#"[{"fromBlock":"0x3"}]"#)), None);
+
+        // the finalized range is pinned to the hashes of its blocks
+        let finalized = r#"[{"fromBlock":"0x3","toBlock":"finalized"}]"#;
+        let key = cache.inner.cache_key("eth_getLogs", Some(finalized)).unwrap();
+        assert!(key.starts_with("eth_getLogs:"));
+        assert!(key.contains(&format!(r#""fromBlock":"{}""#, hashes[3])));
+        assert!(key.contains(&format!(r#""toBlock":"{}""#, hashes[5])));
+    }
+
+    #[tokio::test]
+    async fn serve_finalized_responses_from_cache() {
+        let (provider, _) = finalized_provider();
+        let inner = CountingService::default();
+        let service = RpcResponseCache::new(provider, 1024).layer(inner.clone());
+
+        let first = service.call(request("eth_getBlockByNumber", r#"["0x3", false]"#)).await;
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 1);
+        assert_eq!(service.cache.len(), 1);
+
+        // served from the cache, with the same result
+        let second = service.call(request("eth_getBlockByNumber", r#"["0x03", false]"#)).await;
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 1);
+        assert_eq!(first.as_json().get(), second.as_json().get());
+
+        // blocks above the finalized block are always forwarded
+        service.call(request("eth_getBlockByNumber", r#"["0x6", false]"#)).await;
+        service.call(request("eth_getBlockByNumber", r#"["0x6", false]"#)).await;
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 3);
+        assert_eq!(service.cache.len(), 1);
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let cache = RpcResponseCache::new(NoopProvider::default(), 50);
+        cache.inner.insert("a".to_string(), &response(json!("0x0123456789")));
+        cache.inner.insert("b".to_string(), &response(json!("0x0123456789")));
+        cache.inner.insert("c".to_string(), &response(json!("0x0123456789")));
+        assert_eq!(cache.len(), 3);
+        assert!(cache.inner.get("a").is_some());
+
+        // evicts `b`, the least recently used response
+        cache.inner.insert("d".to_string(), &response(json!("0x0123456789")));
+        assert_eq!(cache.len(), 3);
+        assert!(cache.inner.get("b").is_none());
+        assert_eq!(cache.inner.get("a").unwrap().get(), r#""0x0123456789""#);
+
+        // results larger than the cache aren't cached
+        cache.inner.insert("e".to_string(), &response(json!("0".repeat(64))));
+        assert!(cache.inner.get("e").is_none());
+    }
+}
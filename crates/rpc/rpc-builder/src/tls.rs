@@ -0,0 +1,520 @@
+//! TLS termination for the HTTP, WS and auth servers.
+
+use crate::error::{RpcError, ServerKind};
+use bytes::Bytes;
+use http::{Request, Response};
+use http_body::Body;
+use hyper::body::Incoming;
+use jsonrpsee::{
+    core::BoxError,
+    server::{
+        serve_with_graceful_shutdown, stop_channel, Server, ServerBuilder, ServerHandle,
+        StopHandle, TowerService, TowerServiceBuilder,
+    },
+    Methods,
+};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::RwLock;
+use rustls::{
+    crypto::CryptoProvider,
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+    server::{VerifierBuilderError, WebPkiClientVerifier},
+    RootCertStore, ServerConfig,
+};
+use std::{
+    collections::HashSet,
+    io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+use tower::Service;
+use tracing::{debug, info, warn};
+
+/// How long a client may take to complete the TLS handshake.
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The TLS certificate of a server, and the CA that client certificates must be signed by if
+/// clients are authenticated with mutual TLS.
+///
+/// The files are PEM encoded, and reloaded by the server when they change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcTlsConfig {
+    /// The certificate chain of the server.
+    cert_path: PathBuf,
+    /// The private key of the server.
+    key_path: PathBuf,
+    /// The CA certificates that client certificates must be signed by.
+    client_ca_path: Option<PathBuf>,
+}
+
+impl RpcTlsConfig {
+    /// Creates a new config with the given certificate chain and private key.
+    pub fn new(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Self { cert_path: cert_path.into(), key_path: key_path.into(), client_ca_path: None }
+    }
+
+    /// Requires clients to present a certificate signed by one of the CA certificates in the given
+    /// file.
+    pub fn with_client_ca(mut self, client_ca_path: impl Into<PathBuf>) -> Self {
+        self.client_ca_path = Some(client_ca_path.into());
+        self
+    }
+
+    /// Returns the paths of all files of the config.
+    fn paths(&self) -> impl Iterator<Item = &Path> {
+        [self.cert_path.as_path(), self.key_path.as_path()]
+            .into_iter()
+            .chain(self.client_ca_path.as_deref())
+    }
+
+    /// Reads the files of the config and creates the [`ServerConfig`].
+    pub fn server_config(&self) -> Result<ServerConfig, TlsError> {
+        let certs = read_certs(&self.cert_path)?;
+        let key = PrivateKeyDer::from_pem_file(&self.key_path)
+            .map_err(|err| TlsError::Pem { path: self.key_path.clone(), err })?;
+
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()?;
+        let builder = if let Some(client_ca_path) = &self.client_ca_path {
+            builder.with_client_cert_verifier(client_verifier(client_ca_path, provider)?)
+        } else {
+            builder.with_no_client_auth()
+        };
+
+        let mut config = builder.with_single_cert(certs, key)?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(config)
+    }
+}
+
+/// Reads all certificates of a PEM file.
+fn read_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, TlsError> {
+    CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|err| TlsError::Pem { path: path.to_path_buf(), err })
+}
+
+/// Creates a verifier that accepts client certificates signed by the CA certificates in the
+/// given file.
+fn client_verifier(
+    path: &Path,
+    provider: Arc<CryptoProvider>,
+) -> Result<Arc<dyn rustls::server::danger::ClientCertVerifier>, TlsError> {
+    let mut roots = RootCertStore::empty();
+    for cert in read_certs(path)? {
+        roots.add(cert)?;
+    }
+    Ok(WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider).build()?)
+}
+
+/// Errors when loading a [`RpcTlsConfig`].
+#[derive(Debug, thiserror::Error)]
+pub enum TlsError {
+    /// Failed to read a PEM file.
+    #[error("failed to read {path:?}: {err}")]
+    Pem {
+        /// The path of the file.
+        path: PathBuf,
+        /// The error.
+        err: rustls::pki_types::pem::Error,
+    },
+    /// Invalid certificate or key.
+    #[error(transparent)]
+    Rustls(#[from] rustls::Error),
+    /// Invalid client CA certificates.
+    #[error(transparent)]
+    ClientVerifier(#[from] VerifierBuilderError),
+}
+
+/// A [`TlsAcceptor`] whose [`ServerConfig`] is reloaded when the files of its [`RpcTlsConfig`]
+/// change.
+#[derive(Debug, Clone)]
+struct ReloadableTlsAcceptor {
+    config: RpcTlsConfig,
+    server_config: Arc<RwLock<Arc<ServerConfig>>>,
+}
+
+impl ReloadableTlsAcceptor {
+    fn new(config: RpcTlsConfig) -> Result<Self, TlsError> {
+        let server_config = Arc::new(RwLock::new(Arc::new(config.server_config()?)));
+        Ok(Self { config, server_config })
+    }
+
+    /// Returns an acceptor with the current [`ServerConfig`].
+    fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(self.server_config.read().clone())
+    }
+
+    /// Reloads the [`ServerConfig`], and keeps the current one if the files are invalid.
+    fn reload(&self) {
+        match self.config.server_config() {
+            Ok(server_config) => {
+                *self.server_config.write() = Arc::new(server_config);
+                info!(target: "rpc", cert = ?self.config.cert_path, "Reloaded TLS certificate");
+            }
+            Err(err) => warn!(target: "rpc", %err, "Failed to reload TLS certificate"),
+        }
+    }
+
+    /// Watches the directories of the files, and reloads the [`ServerConfig`] on changes.
+    ///
+    /// Directories are watched instead of the files, so that files that are replaced, e.g. by
+    /// renaming or by updating a symlink, are reloaded as well. The files are watched until the
+    /// returned watcher is dropped.
+    fn watch(&self) -> Option<RecommendedWatcher> {
+        let acceptor = self.clone();
+        let mut watcher = RecommendedWatcher::new(
+            move |res: notify::Result<notify::Event>| {
+                if res.is_ok_and(|event| {
+                    matches!(
+                        event.kind,
+                        notify::EventKind::Modify(_) |
+                            notify::EventKind::Create(_) |
+                            notify::EventKind::Remove(_)
+                    )
+                }) {
+                    acceptor.reload();
+                }
+            },
+            notify::Config::default(),
+        )
+        .inspect_err(|err| warn!(target: "rpc", %err, "Failed to watch TLS certificate"))
+        .ok()?;
+
+        let dirs = self
+            .config
+            .paths()
+            .filter_map(|path| path.parent())
+            .map(|dir| if dir.as_os_str().is_empty() { Path::new(".") } else { dir })
+            .collect::<HashSet<_>>();
+        for dir in dirs {
+            if let Err(err) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                warn!(target: "rpc", %err, ?dir, "Failed to watch TLS certificate");
+            }
+        }
+        Some(watcher)
+    }
+}
+
+/// The address of the peer of a connection.
+///
+/// Added to the extensions of the requests of the connections accepted by an [`RpcListener`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RemoteAddr(pub(crate) SocketAddr);
+
+/// A server that is bound to its address, and serves plain or TLS connections once started.
+pub(crate) enum RpcServer<HttpMiddleware, RpcMiddleware> {
+    /// Serves plain connections.
+    Plain(Server<HttpMiddleware, RpcMiddleware>),
+    /// Serves the connections it accepts itself, with TLS if configured.
+    Listener(RpcListener, TowerServiceBuilder<RpcMiddleware, HttpMiddleware>),
+}
+
+impl<HttpMiddleware, RpcMiddleware> RpcServer<HttpMiddleware, RpcMiddleware> {
+    /// Binds the server to the address, with TLS if configured.
+    ///
+    /// If `remote_addr` is true, the [`RemoteAddr`] of the connection is added to the extensions
+    /// of its requests.
+    pub(crate) async fn build(
+        builder: ServerBuilder<HttpMiddleware, RpcMiddleware>,
+        addr: SocketAddr,
+        tls: Option<RpcTlsConfig>,
+        remote_addr: bool,
+        kind: ServerKind,
+    ) -> Result<Self, RpcError> {
+        Ok(if tls.is_some() || remote_addr {
+            Self::Listener(RpcListener::bind(addr, tls, kind).await?, builder.to_service_builder())
+        } else {
+            Self::Plain(builder.build(addr).await.map_err(|err| RpcError::server_error(err, kind))?)
+        })
+    }
+
+    /// Returns the local address of the server.
+    pub(crate) fn local_addr(&self) -> io::Result<SocketAddr> {
+        match self {
+            Self::Plain(server) => server.local_addr(),
+            Self::Listener(server, _) => server.listener.local_addr(),
+        }
+    }
+
+    /// Returns true if the server serves TLS connections.
+    pub(crate) const fn is_tls(&self) -> bool {
+        matches!(self, Self::Listener(RpcListener { acceptor: Some(_), .. }, _))
+    }
+}
+
+/// A TCP listener that accepts plain or TLS connections, and adds their [`RemoteAddr`] to the
+/// extensions of their requests.
+pub(crate) struct RpcListener {
+    listener: TcpListener,
+    acceptor: Option<ReloadableTlsAcceptor>,
+}
+
+impl RpcListener {
+    /// Loads the certificate, if any, and binds the listener to the address.
+    async fn bind(
+        addr: SocketAddr,
+        tls: Option<RpcTlsConfig>,
+        kind: ServerKind,
+    ) -> Result<Self, RpcError> {
+        let acceptor = tls.map(ReloadableTlsAcceptor::new).transpose()?;
+        let listener =
+            TcpListener::bind(addr).await.map_err(|err| RpcError::server_error(err, kind))?;
+        Ok(Self { listener, acceptor })
+    }
+
+    /// Starts serving the methods with the services of the builder.
+    pub(crate) fn start<HttpMiddleware, RpcMiddleware, B>(
+        self,
+        builder: TowerServiceBuilder<RpcMiddleware, HttpMiddleware>,
+        methods: impl Into<Methods>,
+    ) -> ServerHandle
+    where
+        TowerServiceBuilder<RpcMiddleware, HttpMiddleware>: Clone + Send + 'static,
+        TowerService<RpcMiddleware, HttpMiddleware>:
+            Service<Request<Incoming>, Response = Response<B>> + Clone + Send + 'static,
+        <TowerService<RpcMiddleware, HttpMiddleware> as Service<Request<Incoming>>>::Future: Send,
+        <TowerService<RpcMiddleware, HttpMiddleware> as Service<Request<Incoming>>>::Error:
+            Into<BoxError>,
+        B: Body<Data = Bytes> + Send + 'static,
+        B::Error: Into<BoxError>,
+    {
+        let methods = methods.into();
+        self.serve(move |stop_handle| builder.clone().build(methods.clone(), stop_handle))
+    }
+
+    /// Accepts connections, and serves each of them with a new service, until the server is
+    /// stopped.
+    fn serve<S, B, F>(self, make_service: F) -> ServerHandle
+    where
+        F: Fn(StopHandle) -> S + Send + 'static,
+        S: Service<Request<Incoming>, Response = Response<B>> + Clone + Send + 'static,
+        S::Future: Send,
+        S::Error: Into<BoxError>,
+        B: Body<Data = Bytes> + Send + 'static,
+        B::Error: Into<BoxError>,
+    {
+        let (stop_handle, server_handle) = stop_channel();
+        let Self { listener, acceptor } = self;
+
+        tokio::spawn(async move {
+            // keep watching the certificate for as long as the server runs
+            let _watcher = acceptor.as_ref().and_then(ReloadableTlsAcceptor::watch);
+            let stopped = stop_handle.clone().shutdown();
+            tokio::pin!(stopped);
+
+            loop {
+                let (stream, remote_addr) = tokio::select! {
+                    res = listener.accept() => match res {
+                        Ok(conn) => conn,
+                        Err(err) => {
+                            debug!(target: "rpc", %err, "Failed to accept connection");
+                            continue
+                        }
+                    },
+                    _ = &mut stopped => break,
+                };
+                if let Err(err) = stream.set_nodelay(true) {
+                    debug!(target: "rpc", %err, "Failed to set TCP_NODELAY");
+                }
+
+                let tls = acceptor.as_ref().map(ReloadableTlsAcceptor::acceptor);
+                let service = tower::util::MapRequest::new(
+                    make_service(stop_handle.clone()),
+                    move |mut req: Request<Incoming>| {
+                        req.extensions_mut().insert(RemoteAddr(remote_addr));
+                        req
+                    },
+                );
+                let stopped = stop_handle.clone().shutdown();
+                tokio::spawn(async move {
+                    let Some(tls) = tls else {
+                        if let Err(err) =
+                            serve_with_graceful_shutdown(stream, service, stopped).await
+                        {
+                            debug!(target: "rpc", %err, "Failed to serve connection");
+                        }
+                        return
+                    };
+                    let stream =
+                        match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, tls.accept(stream)).await
+                        {
+                            Ok(Ok(stream)) => stream,
+                            Ok(Err(err)) => {
+                                debug!(target: "rpc", %err, "TLS handshake failed");
+                                return
+                            }
+                            Err(_) => {
+                                debug!(target: "rpc", "TLS handshake timed out");
+                                return
+                            }
+                        };
+                    if let Err(err) = serve_with_graceful_shutdown(stream, service, stopped).await {
+                        debug!(target: "rpc", %err, "Failed to serve TLS connection");
+                    }
+                });
+            }
+        });
+
+        server_handle
+    }
+}
+
+/// Starts a [`RpcServer`] with the given methods.
+///
+/// This is a macro rather than a method, so that the bounds of the plain and the TLS server are
+/// checked where the middleware of the server is configured.
+macro_rules! start_rpc_server {
+    ($server:expr, $methods:expr) => {
+        match $server {
+            $crate::tls::RpcServer::Plain(server) => server.start($methods),
+            $crate::tls::RpcServer::Listener(server, builder) => server.start(builder, $methods),
+        }
+    };
+}
+pub(crate) use start_rpc_server;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::RpcModule;
+    use rustls::{pki_types::ServerName, ClientConfig};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::TlsConnector;
+
+    /// Self-signed certificates of a test CA, a server for `localhost` and a client.
+    const TESTDATA: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/tls");
+
+    fn testdata(file: &str) -> PathBuf {
+        Path::new(TESTDATA).join(file)
+    }
+
+    fn tls_config() -> RpcTlsConfig {
+        RpcTlsConfig::new(testdata("server.pem"), testdata("server.key"))
+    }
+
+    fn connector(client_cert: bool) -> TlsConnector {
+        let mut roots = RootCertStore::empty();
+        for cert in read_certs(&testdata("ca.pem")).unwrap() {
+            roots.add(cert).unwrap();
+        }
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots);
+        let config = if client_cert {
+            let key = PrivateKeyDer::from_pem_file(testdata("client.key")).unwrap();
+            builder
+                .with_client_auth_cert(read_certs(&testdata("client.pem")).unwrap(), key)
+                .unwrap()
+        } else {
+            builder.with_no_client_auth()
+        };
+        TlsConnector::from(Arc::new(config))
+    }
+
+    async fn start_server(tls: RpcTlsConfig) -> (SocketAddr, ServerHandle) {
+        let mut module = RpcModule::new(());
+        module.register_method("web3_clientVersion", |_, _, _| "reth").unwrap();
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], 0));
+        let server =
+            RpcServer::build(ServerBuilder::new(), addr, Some(tls), false, ServerKind::Http(addr))
+                .await
+                .unwrap();
+        let addr = server.local_addr().unwrap();
+        (addr, start_rpc_server!(server, module))
+    }
+
+    /// Sends a `web3_clientVersion` request over TLS and returns the response.
+    async fn request(addr: SocketAddr, connector: TlsConnector) -> io::Result<String> {
+        let stream = tokio::net::TcpStream::connect(addr).await?;
+        let mut stream =
+            connector.connect(ServerName::try_from("localhost").unwrap(), stream).await?;
+
+        let body = r#"{"jsonrpc":"2.0","method":"web3_clientVersion","params":[],"id":1}"#;
+        let request = format!(
+            "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).await?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        Ok(response)
+    }
+
+    #[test]
+    fn load_server_config() {
+        let config = tls_config().server_config().unwrap();
+        assert_eq!(config.alpn_protocols, vec![b"h2".to_vec(), b"http/1.1".to_vec()]);
+
+        assert!(tls_config().with_client_ca(testdata("ca.pem")).server_config().is_ok());
+
+        let missing = RpcTlsConfig::new(testdata("missing.pem"), testdata("server.key"));
+        assert!(matches!(missing.server_config(), Err(TlsError::Pem { .. })));
+    }
+
+    #[test]
+    fn reload_keeps_valid_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let cert = dir.path().join("cert.pem");
+        let key = dir.path().join("key.pem");
+        std::fs::copy(testdata("server.pem"), &cert).unwrap();
+        std::fs::copy(testdata("server.key"), &key).unwrap();
+
+        let acceptor = ReloadableTlsAcceptor::new(RpcTlsConfig::new(&cert, &key)).unwrap();
+        let initial = acceptor.server_config.read().clone();
+
+        // an invalid certificate doesn't replace the current config
+        std::fs::write(&cert, "invalid").unwrap();
+        acceptor.reload();
+        assert!(Arc::ptr_eq(&initial, &acceptor.server_config.read()));
+
+        // a valid certificate does
+        std::fs::copy(testdata("client.pem"), &cert).unwrap();
+        std::fs::copy(testdata("client.key"), &key).unwrap();
+        acceptor.reload();
+        assert!(!Arc::ptr_eq(&initial, &acceptor.server_config.read()));
+    }
+
+    #[tokio::test]
+    async fn serve_tls() {
+        let (addr, handle) = start_server(tls_config()).await;
+
+        let response = request(addr, connector(false)).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains(r#""result":"reth""#), "{response}");
+
+        // plain connections fail the handshake
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream.write_all(b"POST / HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+        let mut response = Vec::new();
+        let _ = stream.read_to_end(&mut response).await;
+        assert!(!response.starts_with(b"HTTP/1.1"));
+
+        handle.stop().unwrap();
+        handle.stopped().await;
+    }
+
+    #[tokio::test]
+    async fn serve_mutual_tls() {
+        let (addr, handle) = start_server(tls_config().with_client_ca(testdata("ca.pem"))).await;
+
+        let response = request(addr, connector(true)).await.unwrap();
+        assert!(response.contains(r#""result":"reth""#), "{response}");
+
+        // clients without a certificate are rejected
+        assert!(request(addr, connector(false)).await.is_err());
+
+        handle.stop().unwrap();
+        handle.stopped().await;
+    }
+}
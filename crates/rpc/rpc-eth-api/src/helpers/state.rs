@@ -105,12 +105,19 @@ pub trait EthState: LoadState + SpawnBlocking {
                 .map_err(Self::Error::from_eth_err)?
                 .ok_or(EthApiError::HeaderNotFound(block_id))?;
             let max_window = self.max_proof_window();
-            if chain_info.best_number.saturating_sub(block_number) > max_window {
-                return Err(EthApiError::ExceedsMaxProofWindow.into())
-            }
+            let exceeds_max_window =
+                chain_info.best_number.saturating_sub(block_number) > max_window;
 
             self.spawn_blocking_io(move |this| {
                 let state = this.state_at_block_id(block_id)?;
+
+                // Proofs outside of the window can only be served from persisted trie changesets.
+                if exceeds_max_window &&
+                    !state.has_trie_changesets().map_err(Self::Error::from_eth_err)?
+                {
+                    return Err(EthApiError::ExceedsMaxProofWindow.into())
+                }
+
                 let storage_keys = keys.iter().map(|key| key.as_b256()).collect::<Vec<_>>();
                 let proof = state
                     .proof(Default::default(), address, &storage_keys)
@@ -15,9 +15,12 @@ use reth_rpc_convert::{CallFeesError, EthTxEnvError, TransactionConversionError}
 use reth_rpc_server_types::result::{
     block_id_to_str, internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code,
 };
-use reth_transaction_pool::error::{
-    Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
-    PoolError, PoolErrorKind, PoolTransactionError,
+use reth_transaction_pool::{
+    error::{
+        Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
+        PoolError, PoolErrorKind, PoolTransactionError,
+    },
+    inclusion::InclusionPolicyViolation,
 };
 use revm::context_interface::result::{
     EVMError, ExecutionResult, HaltReason, InvalidHeader, InvalidTransaction, OutOfGasError,
@@ -862,6 +865,9 @@ pub enum RpcPoolError {
     /// constraint (blob vs normal tx)
     #[error("address already reserved")]
     AddressAlreadyReserved,
+    /// Thrown if the transaction touches an address that is denied by the node's inclusion policy
+    #[error(transparent)]
+    InclusionPolicy(InclusionPolicyViolation),
     /// Other unspecified error
     #[error(transparent)]
     Other(Box<dyn core::error::Error + Send + Sync>),
@@ -871,7 +877,7 @@ impl From<RpcPoolError> for jsonrpsee_types::error::ErrorObject<'static> {
     fn from(error: RpcPoolError) -> Self {
         match error {
             RpcPoolError::Invalid(err) => err.into(),
-            RpcPoolError::TxPoolOverflow => {
+            RpcPoolError::TxPoolOverflow | RpcPoolError::InclusionPolicy(_) => {
                 rpc_error_with_code(EthRpcErrorCode::TransactionRejected.code(), error.to_string())
             }
             RpcPoolError::AlreadyKnown |
@@ -900,9 +906,9 @@ impl From<PoolError> for RpcPoolError {
         match err.kind {
             PoolErrorKind::ReplacementUnderpriced => Self::ReplaceUnderpriced,
             PoolErrorKind::FeeCapBelowMinimumProtocolFeeCap(_) => Self::Underpriced,
-            PoolErrorKind::SpammerExceededCapacity(_) | PoolErrorKind::DiscardedOnInsert => {
-                Self::TxPoolOverflow
-            }
+            PoolErrorKind::SpammerExceededCapacity(_) |
+            PoolErrorKind::PeerExceededCapacity(_) |
+            PoolErrorKind::DiscardedOnInsert => Self::TxPoolOverflow,
             PoolErrorKind::InvalidTransaction(err) => err.into(),
             PoolErrorKind::Other(err) => Self::Other(err),
             PoolErrorKind::AlreadyImported => Self::AlreadyKnown,
@@ -942,6 +948,7 @@ impl From<InvalidPoolTransactionError> for RpcPoolError {
                     minimum_priority_fee,
                 })
             }
+            InvalidPoolTransactionError::InclusionPolicy(err) => Self::InclusionPolicy(err),
         }
     }
 }
@@ -0,0 +1,453 @@
+//! Data access of the GraphQL schema.
+
+use alloy_consensus::{BlockHeader, Header, Transaction, TxEnvelope, TxReceipt};
+use alloy_eips::{
+    eip2718::{Decodable2718, Encodable2718},
+    eip2930::AccessList,
+    eip4895::Withdrawal,
+    BlockId,
+};
+use alloy_primitives::{Address, Bytes, Log, Signature, B256, U256};
+use alloy_rpc_types_eth::{Filter, Log as RpcLog, SyncStatus, TransactionRequest};
+use async_graphql::{Error, Result};
+use futures::future::BoxFuture;
+use reth_primitives_traits::{Block, BlockBody, RecoveredBlock, SignedTransaction};
+use reth_rpc_eth_api::{
+    helpers::{
+        Call, EthApiSpec, EthCall, EthFees, EthState, EthTransactions, FullEthApi, LoadBlock,
+    },
+    EngineEthFilter, QueryLimits, RpcConvert, RpcNodeCore, RpcNodeCoreExt, RpcTxReq,
+};
+use reth_rpc_eth_types::TransactionSource;
+use reth_storage_api::BlockNumReader;
+use std::{fmt::Display, sync::Arc};
+
+/// The data source of the GraphQL schema.
+///
+/// The schema only deals with the types of this module, so that it doesn't depend on the
+/// primitives and the RPC types of the node. See [`EthGraphQLBackend`] for the implementation
+/// based on the `eth_` API.
+pub trait GraphQLBackend: Send + Sync + 'static {
+    /// Returns the block with the given id.
+    fn block(&self, id: BlockId) -> BoxFuture<'_, Result<Option<Arc<BlockData>>>>;
+
+    /// Returns the receipts of the block with the given hash.
+    fn receipts(&self, block_hash: B256) -> BoxFuture<'_, Result<Option<Arc<Vec<ReceiptData>>>>>;
+
+    /// Returns where the transaction with the given hash is.
+    fn transaction(&self, hash: B256) -> BoxFuture<'_, Result<Option<TransactionLocation>>>;
+
+    /// Returns the balance of an account.
+    fn balance(&self, address: Address, block: BlockId) -> BoxFuture<'_, Result<U256>>;
+
+    /// Returns the nonce of an account.
+    fn transaction_count(&self, address: Address, block: BlockId) -> BoxFuture<'_, Result<u64>>;
+
+    /// Returns the code of an account.
+    fn code(&self, address: Address, block: BlockId) -> BoxFuture<'_, Result<Bytes>>;
+
+    /// Returns a storage slot of an account.
+    fn storage(&self, address: Address, slot: B256, block: BlockId) -> BoxFuture<'_, Result<B256>>;
+
+    /// Returns the logs matching the filter.
+    fn logs(&self, filter: Filter) -> BoxFuture<'_, Result<Vec<RpcLog>>>;
+
+    /// Executes a call on top of the state of the given block.
+    fn call(
+        &self,
+        request: TransactionRequest,
+        block: BlockId,
+    ) -> BoxFuture<'_, Result<CallOutcome>>;
+
+    /// Estimates the gas of a call on top of the state of the given block.
+    fn estimate_gas(
+        &self,
+        request: TransactionRequest,
+        block: BlockId,
+    ) -> BoxFuture<'_, Result<u64>>;
+
+    /// Returns the suggested gas price.
+    fn gas_price(&self) -> BoxFuture<'_, Result<U256>>;
+
+    /// Returns the suggested priority fee.
+    fn max_priority_fee_per_gas(&self) -> BoxFuture<'_, Result<U256>>;
+
+    /// Submits a raw transaction to the pool and returns its hash.
+    fn send_raw_transaction(&self, tx: Bytes) -> BoxFuture<'_, Result<B256>>;
+
+    /// Returns the number of the latest block.
+    fn best_block_number(&self) -> Result<u64>;
+
+    /// Returns the chain id.
+    fn chain_id(&self) -> u64;
+
+    /// Returns the sync status of the node.
+    fn syncing(&self) -> Result<SyncStatus>;
+}
+
+/// A block, or an ommer of a block.
+#[derive(Debug)]
+pub struct BlockData {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The header of the block.
+    pub header: Header,
+    /// The transactions of the block, `None` for ommers.
+    pub transactions: Option<Vec<Arc<TransactionData>>>,
+    /// The ommers of the block, `None` for ommers.
+    pub ommers: Option<Vec<Header>>,
+    /// The withdrawals of the block, if any.
+    pub withdrawals: Option<Vec<Withdrawal>>,
+}
+
+impl BlockData {
+    /// Creates the data of a block.
+    pub fn new<B: Block>(block: &RecoveredBlock<B>) -> Self {
+        let base_fee = block.header().base_fee_per_gas();
+        let body = block.body();
+        Self {
+            hash: block.hash(),
+            header: header(block.header()),
+            transactions: Some(
+                block
+                    .transactions_with_sender()
+                    .map(|(from, tx)| Arc::new(TransactionData::new(tx, *from, base_fee)))
+                    .collect(),
+            ),
+            ommers: Some(body.ommers().unwrap_or_default().iter().map(header).collect()),
+            withdrawals: body.withdrawals().map(|withdrawals| withdrawals.to_vec()),
+        }
+    }
+
+    /// Creates the data of an ommer.
+    pub fn ommer(header: Header) -> Self {
+        Self {
+            hash: header.hash_slow(),
+            header,
+            transactions: None,
+            ommers: None,
+            withdrawals: None,
+        }
+    }
+}
+
+/// Converts a header into an alloy [`Header`].
+fn header<H: BlockHeader>(header: &H) -> Header {
+    Header {
+        parent_hash: header.parent_hash(),
+        ommers_hash: header.ommers_hash(),
+        beneficiary: header.beneficiary(),
+        state_root: header.state_root(),
+        transactions_root: header.transactions_root(),
+        receipts_root: header.receipts_root(),
+        logs_bloom: header.logs_bloom(),
+        difficulty: header.difficulty(),
+        number: header.number(),
+        gas_limit: header.gas_limit(),
+        gas_used: header.gas_used(),
+        timestamp: header.timestamp(),
+        extra_data: header.extra_data().clone(),
+        mix_hash: header.mix_hash().unwrap_or_default(),
+        nonce: header.nonce().unwrap_or_default(),
+        base_fee_per_gas: header.base_fee_per_gas(),
+        withdrawals_root: header.withdrawals_root(),
+        blob_gas_used: header.blob_gas_used(),
+        excess_blob_gas: header.excess_blob_gas(),
+        parent_beacon_block_root: header.parent_beacon_block_root(),
+        requests_hash: header.requests_hash(),
+    }
+}
+
+/// A transaction.
+#[derive(Debug)]
+pub struct TransactionData {
+    /// The hash of the transaction.
+    pub hash: B256,
+    /// The sender of the transaction.
+    pub from: Address,
+    /// The nonce of the transaction.
+    pub nonce: u64,
+    /// The recipient of the transaction, `None` for contract creations.
+    pub to: Option<Address>,
+    /// The value of the transaction.
+    pub value: U256,
+    /// The gas limit of the transaction.
+    pub gas: u64,
+    /// The gas price of legacy transactions.
+    pub gas_price: Option<u128>,
+    /// The maximum fee per gas, the gas price of legacy transactions.
+    pub max_fee_per_gas: u128,
+    /// The maximum priority fee per gas of dynamic fee transactions.
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// The maximum fee per blob gas of blob transactions.
+    pub max_fee_per_blob_gas: Option<u128>,
+    /// The blob hashes of blob transactions.
+    pub blob_versioned_hashes: Option<Vec<B256>>,
+    /// The price paid per gas, given the base fee of the block of the transaction.
+    pub effective_gas_price: u128,
+    /// The tip paid per gas, given the base fee of the block of the transaction.
+    pub effective_tip: Option<u128>,
+    /// The input data of the transaction.
+    pub input: Bytes,
+    /// The EIP-2718 type of the transaction.
+    pub ty: u8,
+    /// The chain id of the transaction.
+    pub chain_id: Option<u64>,
+    /// The access list of the transaction.
+    pub access_list: Option<AccessList>,
+    /// The signature of the transaction, `None` for transactions that aren't signed.
+    pub signature: Option<Signature>,
+    /// The EIP-2718 encoding of the transaction.
+    pub raw: Bytes,
+}
+
+impl TransactionData {
+    /// Creates the data of a transaction, in a block with the given base fee.
+    pub fn new<T: SignedTransaction>(tx: &T, from: Address, base_fee: Option<u64>) -> Self {
+        let raw = Bytes::from(tx.encoded_2718());
+        // signatures aren't part of the transaction traits, so they are only available for the
+        // transaction types of Ethereum
+        let signature = TxEnvelope::decode_2718(&mut raw.as_ref()).ok().map(|tx| *tx.signature());
+        Self {
+            hash: *tx.tx_hash(),
+            from,
+            nonce: tx.nonce(),
+            to: tx.to(),
+            value: tx.value(),
+            gas: tx.gas_limit(),
+            gas_price: tx.gas_price(),
+            max_fee_per_gas: tx.max_fee_per_gas(),
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas(),
+            max_fee_per_blob_gas: tx.max_fee_per_blob_gas(),
+            blob_versioned_hashes: tx.blob_versioned_hashes().map(|hashes| hashes.to_vec()),
+            effective_gas_price: tx.effective_gas_price(base_fee),
+            effective_tip: base_fee.and_then(|base_fee| tx.effective_tip_per_gas(base_fee)),
+            input: tx.input().clone(),
+            ty: tx.ty(),
+            chain_id: tx.chain_id(),
+            access_list: tx.access_list().cloned(),
+            signature,
+            raw,
+        }
+    }
+}
+
+/// The receipt of a transaction.
+#[derive(Debug)]
+pub struct ReceiptData {
+    /// Whether the transaction succeeded.
+    pub status: bool,
+    /// The gas used by the transaction.
+    pub gas_used: u64,
+    /// The gas used by the block up to and including the transaction.
+    pub cumulative_gas_used: u64,
+    /// The index of the first log of the transaction in the block.
+    pub first_log_index: u64,
+    /// The logs of the transaction.
+    pub logs: Vec<Log>,
+}
+
+/// Where a transaction is.
+#[derive(Debug)]
+pub enum TransactionLocation {
+    /// The transaction is in the pool.
+    Pool(TransactionData),
+    /// The transaction is in a block.
+    Block {
+        /// The hash of the block.
+        block_hash: B256,
+        /// The index of the transaction in the block.
+        index: usize,
+    },
+}
+
+/// The outcome of a call.
+#[derive(Debug)]
+pub struct CallOutcome {
+    /// The output of the call, or the revert data.
+    pub data: Bytes,
+    /// The gas used by the call.
+    pub gas_used: u64,
+    /// Whether the call succeeded.
+    pub success: bool,
+}
+
+/// A [`GraphQLBackend`] that serves the schema with the `eth_` API and an [`EngineEthFilter`].
+#[derive(Debug, Clone)]
+pub struct EthGraphQLBackend<Eth, Filter> {
+    eth_api: Eth,
+    filter: Filter,
+    query_limits: QueryLimits,
+}
+
+impl<Eth, Filter> EthGraphQLBackend<Eth, Filter> {
+    /// Creates a new backend. The logs queries are limited by the given [`QueryLimits`].
+    pub const fn new(eth_api: Eth, filter: Filter, query_limits: QueryLimits) -> Self {
+        Self { eth_api, filter, query_limits }
+    }
+}
+
+impl<Eth, Filter> GraphQLBackend for EthGraphQLBackend<Eth, Filter>
+where
+    Eth: FullEthApi,
+    Filter: EngineEthFilter,
+{
+    fn block(&self, id: BlockId) -> BoxFuture<'_, Result<Option<Arc<BlockData>>>> {
+        Box::pin(async move {
+            let block = self.eth_api.recovered_block(id).await.map_err(error)?;
+            Ok(block.map(|block| Arc::new(BlockData::new(&block))))
+        })
+    }
+
+    fn receipts(&self, block_hash: B256) -> BoxFuture<'_, Result<Option<Arc<Vec<ReceiptData>>>>> {
+        Box::pin(async move {
+            let Some(receipts) =
+                self.eth_api.cache().get_receipts(block_hash).await.map_err(error)?
+            else {
+                return Ok(None)
+            };
+
+            let mut cumulative_gas_used = 0;
+            let mut first_log_index = 0;
+            let receipts = receipts
+                .iter()
+                .map(|receipt| {
+                    let data = ReceiptData {
+                        status: receipt.status(),
+                        gas_used: receipt.cumulative_gas_used() - cumulative_gas_used,
+                        cumulative_gas_used: receipt.cumulative_gas_used(),
+                        first_log_index,
+                        logs: receipt.logs().to_vec(),
+                    };
+                    cumulative_gas_used = receipt.cumulative_gas_used();
+                    first_log_index += receipt.logs().len() as u64;
+                    data
+                })
+                .collect();
+            Ok(Some(Arc::new(receipts)))
+        })
+    }
+
+    fn transaction(&self, hash: B256) -> BoxFuture<'_, Result<Option<TransactionLocation>>> {
+        Box::pin(async move {
+            let tx =
+                EthTransactions::transaction_by_hash(&self.eth_api, hash).await.map_err(error)?;
+            Ok(tx.map(|tx| match tx {
+                TransactionSource::Pool(tx) => {
+                    TransactionLocation::Pool(TransactionData::new(tx.inner(), tx.signer(), None))
+                }
+                TransactionSource::Block { index, block_hash, .. } => {
+                    TransactionLocation::Block { block_hash, index: index as usize }
+                }
+            }))
+        })
+    }
+
+    fn balance(&self, address: Address, block: BlockId) -> BoxFuture<'_, Result<U256>> {
+        Box::pin(async move {
+            EthState::balance(&self.eth_api, address, Some(block)).await.map_err(error)
+        })
+    }
+
+    fn transaction_count(&self, address: Address, block: BlockId) -> BoxFuture<'_, Result<u64>> {
+        Box::pin(async move {
+            let count = EthState::transaction_count(&self.eth_api, address, Some(block))
+                .await
+                .map_err(error)?;
+            Ok(count.saturating_to())
+        })
+    }
+
+    fn code(&self, address: Address, block: BlockId) -> BoxFuture<'_, Result<Bytes>> {
+        Box::pin(async move {
+            EthState::get_code(&self.eth_api, address, Some(block)).await.map_err(error)
+        })
+    }
+
+    fn storage(&self, address: Address, slot: B256, block: BlockId) -> BoxFuture<'_, Result<B256>> {
+        Box::pin(async move {
+            EthState::storage_at(&self.eth_api, address, slot.into(), Some(block))
+                .await
+                .map_err(error)
+        })
+    }
+
+    fn logs(&self, filter: Filter) -> BoxFuture<'_, Result<Vec<RpcLog>>> {
+        Box::pin(async move {
+            self.filter
+                .logs(filter, self.query_limits)
+                .await
+                .map_err(|err| Error::new(err.message()))
+        })
+    }
+
+    fn call(
+        &self,
+        request: TransactionRequest,
+        block: BlockId,
+    ) -> BoxFuture<'_, Result<CallOutcome>> {
+        Box::pin(async move {
+            let request = call_request::<Eth>(request)?;
+            let res = self
+                .eth_api
+                .transact_call_at(request, block, Default::default())
+                .await
+                .map_err(error)?;
+            Ok(CallOutcome {
+                gas_used: res.result.gas_used(),
+                success: res.result.is_success(),
+                data: res.result.into_output().unwrap_or_default(),
+            })
+        })
+    }
+
+    fn estimate_gas(
+        &self,
+        request: TransactionRequest,
+        block: BlockId,
+    ) -> BoxFuture<'_, Result<u64>> {
+        Box::pin(async move {
+            let request = call_request::<Eth>(request)?;
+            let gas = EthCall::estimate_gas_at(&self.eth_api, request, block, None)
+                .await
+                .map_err(error)?;
+            Ok(gas.saturating_to())
+        })
+    }
+
+    fn gas_price(&self) -> BoxFuture<'_, Result<U256>> {
+        Box::pin(async move { EthFees::gas_price(&self.eth_api).await.map_err(error) })
+    }
+
+    fn max_priority_fee_per_gas(&self) -> BoxFuture<'_, Result<U256>> {
+        Box::pin(async move { self.eth_api.suggested_priority_fee().await.map_err(error) })
+    }
+
+    fn send_raw_transaction(&self, tx: Bytes) -> BoxFuture<'_, Result<B256>> {
+        Box::pin(async move { self.eth_api.send_raw_transaction(tx).await.map_err(error) })
+    }
+
+    fn best_block_number(&self) -> Result<u64> {
+        self.eth_api.provider().best_block_number().map_err(error)
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.eth_api.chain_id().to()
+    }
+
+    fn syncing(&self) -> Result<SyncStatus> {
+        self.eth_api.sync_status().map_err(error)
+    }
+}
+
+/// Converts the call request into the request type of the network.
+fn call_request<Eth: Call>(
+    request: TransactionRequest,
+) -> Result<RpcTxReq<<Eth::RpcConvert as RpcConvert>::Network>> {
+    serde_json::to_value(request).and_then(serde_json::from_value).map_err(error)
+}
+
+/// Converts an error of the `eth_` API into a GraphQL error.
+fn error(err: impl Display) -> Error {
+    Error::new(err.to_string())
+}
@@ -0,0 +1,194 @@
+//! HTTP layer that serves the GraphQL schema.
+
+use crate::GraphQLSchema;
+use async_graphql::{Request, Response, ServerError, Variables};
+use futures::future::{BoxFuture, Either};
+use http::{header, Method, StatusCode};
+use http_body_util::{BodyExt, Limited};
+use jsonrpsee::server::{HttpBody, HttpRequest, HttpResponse};
+use std::{
+    fmt,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+use tracing::trace;
+
+/// The path the GraphQL endpoint is served under.
+pub const GRAPHQL_PATH: &str = "/graphql";
+
+/// The default maximum size of the body of GraphQL requests, 10 MiB.
+pub const DEFAULT_MAX_REQUEST_SIZE: usize = 10 * 1024 * 1024;
+
+/// Decides whether a GraphQL request may be executed, returning the reason otherwise.
+pub type GraphQLRequestGuard = Arc<dyn Fn(&HttpRequest) -> Result<(), String> + Send + Sync>;
+
+/// HTTP layer that serves the GraphQL schema under [`GRAPHQL_PATH`], and forwards all other
+/// requests to the inner service.
+///
+/// Queries are accepted as JSON `POST` requests, and as `GET` requests with the `query`,
+/// `operationName` and `variables` URL parameters.
+#[derive(Clone)]
+pub struct GraphQLLayer {
+    schema: GraphQLSchema,
+    guard: Option<GraphQLRequestGuard>,
+    max_request_size: usize,
+}
+
+impl GraphQLLayer {
+    /// Creates a layer serving the given schema.
+    pub fn new(schema: GraphQLSchema) -> Self {
+        Self { schema, guard: None, max_request_size: DEFAULT_MAX_REQUEST_SIZE }
+    }
+
+    /// Sets the guard that is checked before executing a request.
+    pub fn with_guard(mut self, guard: GraphQLRequestGuard) -> Self {
+        self.guard = Some(guard);
+        self
+    }
+
+    /// Sets the maximum size of the body of requests.
+    pub const fn with_max_request_size(mut self, max_request_size: usize) -> Self {
+        self.max_request_size = max_request_size;
+        self
+    }
+}
+
+impl fmt::Debug for GraphQLLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphQLLayer")
+            .field("guard", &self.guard.is_some())
+            .field("max_request_size", &self.max_request_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for GraphQLLayer {
+    type Service = GraphQLService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GraphQLService { inner, layer: self.clone() }
+    }
+}
+
+/// Service that serves the GraphQL schema, created by [`GraphQLLayer`].
+#[derive(Debug, Clone)]
+pub struct GraphQLService<S> {
+    inner: S,
+    layer: GraphQLLayer,
+}
+
+impl<S> Service<HttpRequest> for GraphQLService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse>,
+    S::Error: Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = Either<S::Future, BoxFuture<'static, Result<HttpResponse, S::Error>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: HttpRequest) -> Self::Future {
+        if req.uri().path() != GRAPHQL_PATH {
+            return Either::Left(self.inner.call(req))
+        }
+        let layer = self.layer.clone();
+        Either::Right(Box::pin(async move { Ok(layer.handle(req).await) }))
+    }
+}
+
+impl GraphQLLayer {
+    /// Executes a GraphQL request.
+    async fn handle(self, req: HttpRequest) -> HttpResponse {
+        if let Some(Err(err)) = self.guard.as_ref().map(|guard| guard(&req)) {
+            return error_response(StatusCode::FORBIDDEN, err)
+        }
+
+        let request = match *req.method() {
+            Method::GET => parse_query_string(req.uri().query().unwrap_or_default()),
+            Method::POST => {
+                match Limited::new(req.into_body(), self.max_request_size).collect().await {
+                    Ok(body) => serde_json::from_slice::<Request>(&body.to_bytes())
+                        .map_err(|err| err.to_string()),
+                    Err(err) => {
+                        return error_response(StatusCode::PAYLOAD_TOO_LARGE, err.to_string())
+                    }
+                }
+            }
+            _ => return error_response(StatusCode::METHOD_NOT_ALLOWED, "method not allowed"),
+        };
+        let request = match request {
+            Ok(request) => request,
+            Err(err) => return error_response(StatusCode::BAD_REQUEST, err),
+        };
+
+        trace!(target: "rpc::graphql", operation = ?request.operation_name, "Executing request");
+        let response = self.schema.execute(request).await;
+        json_response(StatusCode::OK, &response)
+    }
+}
+
+/// Parses the URL parameters of a `GET` request.
+fn parse_query_string(query: &str) -> Result<Request, String> {
+    let mut request = None;
+    let mut operation_name = None;
+    let mut variables = None;
+    for (name, value) in url::form_urlencoded::parse(query.as_bytes()) {
+        match name.as_ref() {
+            "query" => request = Some(value.into_owned()),
+            "operationName" => operation_name = Some(value.into_owned()),
+            "variables" => {
+                let value = serde_json::from_str(&value).map_err(|err| err.to_string())?;
+                variables = Some(Variables::from_json(value));
+            }
+            _ => {}
+        }
+    }
+
+    let mut request = Request::new(request.ok_or("missing query parameter")?);
+    if let Some(operation_name) = operation_name {
+        request = request.operation_name(operation_name);
+    }
+    if let Some(variables) = variables {
+        request = request.variables(variables);
+    }
+    Ok(request)
+}
+
+/// Returns a GraphQL response with the given error.
+fn error_response(status: StatusCode, message: impl Into<String>) -> HttpResponse {
+    json_response(status, &Response::from_errors(vec![ServerError::new(message, None)]))
+}
+
+/// Returns a JSON response with the given body.
+fn json_response(status: StatusCode, response: &Response) -> HttpResponse {
+    let body = serde_json::to_string(response).expect("GraphQL responses are serializable");
+    http::Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(HttpBody::from(body))
+        .expect("response is valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_get_requests() {
+        let request = parse_query_string(
+            "query=query%20Q(%24n%3A%20Long)%20%7B%20block(number%3A%20%24n)%20%7B%20hash%20%7D\
+             %20%7D&operationName=Q&variables=%7B%22n%22%3A1%7D",
+        )
+        .unwrap();
+        assert_eq!(request.query, "query Q($n: Long) { block(number: $n) { hash } }");
+        assert_eq!(request.operation_name.as_deref(), Some("Q"));
+        assert_eq!(request.variables, Variables::from_json(serde_json::json!({ "n": 1 })));
+
+        assert!(parse_query_string("operationName=Q").is_err());
+        assert!(parse_query_string("query=%7B%7D&variables=nope").is_err());
+    }
+}
@@ -0,0 +1,26 @@
+//! EIP-1767 GraphQL server.
+//!
+//! The schema is served by the HTTP layer [`GraphQLLayer`] under [`GRAPHQL_PATH`], and reads the
+//! chain through a [`GraphQLBackend`], which [`EthGraphQLBackend`] implements with the `eth_` API
+//! helper traits. Queries are limited in depth and complexity, see [`GraphQLConfig`].
+//!
+//! See <https://eips.ethereum.org/EIPS/eip-1767>.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+pub mod backend;
+mod layer;
+pub mod scalars;
+pub mod schema;
+
+pub use backend::{EthGraphQLBackend, GraphQLBackend};
+pub use layer::{
+    GraphQLLayer, GraphQLRequestGuard, GraphQLService, DEFAULT_MAX_REQUEST_SIZE, GRAPHQL_PATH,
+};
+pub use schema::{build_schema, GraphQLConfig, GraphQLSchema};
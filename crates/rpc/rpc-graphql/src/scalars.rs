@@ -0,0 +1,141 @@
+//! The scalars of the EIP-1767 schema.
+
+use alloy_primitives::{hex, Address as AlloyAddress, Bytes as AlloyBytes, B256, U256};
+use async_graphql::{InputType, InputValueError, InputValueResult, Scalar, ScalarType, Value};
+use std::{fmt::Display, str::FromStr};
+
+/// A 32 byte binary string, represented as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bytes32(pub B256);
+
+#[Scalar(name = "Bytes32")]
+impl ScalarType for Bytes32 {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_str(value, Self)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(hex::encode_prefixed(self.0))
+    }
+}
+
+/// A 20 byte Ethereum address, represented as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Address(pub AlloyAddress);
+
+#[Scalar(name = "Address")]
+impl ScalarType for Address {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_str(value, Self)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(hex::encode_prefixed(self.0))
+    }
+}
+
+/// An arbitrary length binary string, represented as 0x-prefixed hexadecimal.
+///
+/// An empty byte string is represented as `0x`. Byte strings must have an even number of
+/// hexadecimal nybbles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bytes(pub AlloyBytes);
+
+#[Scalar(name = "Bytes")]
+impl ScalarType for Bytes {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_str(value, Self)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(hex::encode_prefixed(&self.0))
+    }
+}
+
+/// A large integer. Input is accepted as either a JSON number or as a string, either decimal or
+/// 0x-prefixed hexadecimal. Output values are 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BigInt(pub U256);
+
+#[Scalar(name = "BigInt")]
+impl ScalarType for BigInt {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::Number(number) => number
+                .as_u64()
+                .map(|number| Self(U256::from(number)))
+                .ok_or_else(|| InputValueError::custom("expected an unsigned integer")),
+            Value::String(_) => parse_str(value, Self),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:#x}", self.0))
+    }
+}
+
+/// A 64 bit unsigned integer. Input is accepted as either a JSON number or as a string, either
+/// decimal or 0x-prefixed hexadecimal. Output values are 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Long(pub u64);
+
+#[Scalar(name = "Long")]
+impl ScalarType for Long {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::Number(number) => number
+                .as_u64()
+                .map(Self)
+                .ok_or_else(|| InputValueError::custom("expected an unsigned integer")),
+            Value::String(s) => {
+                let number = match s.strip_prefix("0x") {
+                    Some(hex) => u64::from_str_radix(hex, 16),
+                    None => s.parse(),
+                };
+                number.map(Self).map_err(InputValueError::custom)
+            }
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:#x}", self.0))
+    }
+}
+
+/// Parses a string scalar with the [`FromStr`] implementation of its inner type.
+fn parse_str<S, T>(value: Value, f: impl FnOnce(T) -> S) -> InputValueResult<S>
+where
+    S: InputType,
+    T: FromStr<Err: Display>,
+{
+    let Value::String(s) = &value else { return Err(InputValueError::expected_type(value)) };
+    s.parse().map(f).map_err(InputValueError::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scalars() {
+        assert_eq!(Long::parse(Value::from(16)).unwrap(), Long(16));
+        assert_eq!(Long::parse(Value::from("16")).unwrap(), Long(16));
+        assert_eq!(Long::parse(Value::from("0x10")).unwrap(), Long(16));
+        assert_eq!(Long(16).to_value(), Value::from("0x10"));
+        assert!(Long::parse(Value::from(-1)).is_err());
+
+        assert_eq!(BigInt::parse(Value::from("0x10")).unwrap(), BigInt(U256::from(16)));
+        assert_eq!(BigInt::parse(Value::from("16")).unwrap(), BigInt(U256::from(16)));
+        assert_eq!(BigInt(U256::ZERO).to_value(), Value::from("0x0"));
+
+        assert_eq!(Bytes::parse(Value::from("0x")).unwrap(), Bytes(AlloyBytes::new()));
+        assert!(Bytes::parse(Value::from("0x1")).is_err());
+        assert!(Bytes32::parse(Value::from("0x01")).is_err());
+        assert_eq!(
+            Address(AlloyAddress::ZERO).to_value(),
+            Value::from(format!("0x{}", "0".repeat(40)))
+        );
+    }
+}
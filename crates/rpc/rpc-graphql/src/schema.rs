@@ -0,0 +1,1062 @@
+//! The EIP-1767 schema.
+//!
+//! See <https://eips.ethereum.org/EIPS/eip-1767>.
+
+use crate::{
+    backend::{BlockData, CallOutcome, ReceiptData, TransactionData, TransactionLocation},
+    scalars::{Address, BigInt, Bytes, Bytes32, Long},
+    GraphQLBackend,
+};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Bytes as AlloyBytes, TxKind, B256, U256};
+use alloy_rpc_types_eth::{
+    Filter, Log as RpcLog, SyncStatus, TransactionInput, TransactionRequest,
+};
+use async_graphql::{
+    Context, EmptySubscription, Error, InputObject, Object, Result, Schema, SimpleObject,
+};
+use reth_rpc_server_types::constants::{DEFAULT_GRAPHQL_MAX_COMPLEXITY, DEFAULT_GRAPHQL_MAX_DEPTH};
+use std::sync::Arc;
+
+/// The EIP-1767 schema.
+pub type GraphQLSchema = Schema<Query, Mutation, EmptySubscription>;
+
+/// The complexity a list field is weighted with, relative to its items.
+pub const LIST_COMPLEXITY: usize = 100;
+
+/// The maximum number of blocks returned by `blocks` queries without an upper bound.
+pub const MAX_OPEN_BLOCK_RANGE: u64 = 100;
+
+/// The limits of the queries accepted by the schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphQLConfig {
+    /// The maximum nesting depth of a query.
+    pub max_depth: usize,
+    /// The maximum complexity of a query.
+    ///
+    /// Every field costs one, and list fields cost [`LIST_COMPLEXITY`] times their items, or the
+    /// number of blocks for `blocks` queries.
+    pub max_complexity: usize,
+}
+
+impl Default for GraphQLConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_GRAPHQL_MAX_DEPTH,
+            max_complexity: DEFAULT_GRAPHQL_MAX_COMPLEXITY,
+        }
+    }
+}
+
+/// Builds the schema served by the given backend.
+pub fn build_schema(backend: Arc<dyn GraphQLBackend>, config: GraphQLConfig) -> GraphQLSchema {
+    Schema::build(Query, Mutation, EmptySubscription)
+        .data(backend)
+        .limit_depth(config.max_depth)
+        .limit_complexity(config.max_complexity)
+        .finish()
+}
+
+/// Returns the backend of the schema.
+fn backend<'a>(ctx: &Context<'a>) -> &'a dyn GraphQLBackend {
+    ctx.data_unchecked::<Arc<dyn GraphQLBackend>>().as_ref()
+}
+
+/// Returns the complexity of a `blocks` query.
+fn block_range_complexity(from: &Long, to: &Option<Long>, child_complexity: usize) -> usize {
+    let len = to.map_or(MAX_OPEN_BLOCK_RANGE, |to| to.0.saturating_sub(from.0).saturating_add(1));
+    usize::try_from(len).unwrap_or(usize::MAX).saturating_mul(child_complexity)
+}
+
+/// Looks up a transaction by hash.
+async fn transaction_by_hash(
+    backend: &dyn GraphQLBackend,
+    hash: B256,
+) -> Result<Option<Transaction>> {
+    match backend.transaction(hash).await? {
+        None => Ok(None),
+        Some(TransactionLocation::Pool(data)) => {
+            Ok(Some(Transaction { data: Arc::new(data), block: None, index: None }))
+        }
+        Some(TransactionLocation::Block { block_hash, index }) => {
+            let block = backend.block(BlockId::hash(block_hash)).await?;
+            Ok(block.map(Block).and_then(|block| block.transaction(index)))
+        }
+    }
+}
+
+/// Converts an input block number into the block to resolve accounts at, defaulting to the given
+/// block.
+fn block_or(block: Option<Long>, default: BlockId) -> BlockId {
+    block.map_or(default, |block| BlockId::number(block.0))
+}
+
+/// The root queries.
+#[derive(Debug)]
+pub struct Query;
+
+#[Object]
+impl Query {
+    /// Fetches an Ethereum block by number or by hash. If neither is supplied, the most recent
+    /// known block is returned.
+    async fn block(
+        &self,
+        ctx: &Context<'_>,
+        number: Option<Long>,
+        hash: Option<Bytes32>,
+    ) -> Result<Option<Block>> {
+        let id = match (number, hash) {
+            (Some(_), Some(_)) => return Err(Error::new("only one of number or hash must be set")),
+            (Some(number), None) => BlockId::number(number.0),
+            (None, Some(hash)) => BlockId::hash(hash.0),
+            (None, None) => BlockId::latest(),
+        };
+        Ok(backend(ctx).block(id).await?.map(Block))
+    }
+
+    /// Returns all available blocks in the inclusive range `from` to `to`. If `to` is not
+    /// supplied, it defaults to the most recent known block, returning at most
+    /// [`MAX_OPEN_BLOCK_RANGE`] blocks.
+    #[graphql(complexity = "block_range_complexity(&from, &to, child_complexity)")]
+    async fn blocks(&self, ctx: &Context<'_>, from: Long, to: Option<Long>) -> Result<Vec<Block>> {
+        let backend = backend(ctx);
+        let to = match to {
+            Some(to) => to.0,
+            None => {
+                backend.best_block_number()?.min(from.0.saturating_add(MAX_OPEN_BLOCK_RANGE - 1))
+            }
+        };
+        if from.0 > to {
+            return Err(Error::new("from must be lower than or equal to to"))
+        }
+
+        let mut blocks = Vec::new();
+        for number in from.0..=to {
+            let Some(block) = backend.block(BlockId::number(number)).await? else { break };
+            blocks.push(Block(block));
+        }
+        Ok(blocks)
+    }
+
+    /// Returns the current pending state.
+    async fn pending(&self) -> Pending {
+        Pending
+    }
+
+    /// Returns a transaction specified by its hash.
+    async fn transaction(&self, ctx: &Context<'_>, hash: Bytes32) -> Result<Option<Transaction>> {
+        transaction_by_hash(backend(ctx), hash.0).await
+    }
+
+    /// Returns log entries matching the provided filter.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn logs(&self, ctx: &Context<'_>, filter: FilterCriteria) -> Result<Vec<Log>> {
+        let block = |number: Option<Long>| {
+            number.map_or(BlockNumberOrTag::Latest, |number| BlockNumberOrTag::Number(number.0))
+        };
+        let query =
+            Filter::new().from_block(block(filter.from_block)).to_block(block(filter.to_block));
+        let query = topics(filter.addresses, filter.topics, query);
+        let logs = backend(ctx).logs(query).await?;
+        Ok(logs.into_iter().map(Log::from_rpc).collect())
+    }
+
+    /// Returns the node's estimate of a gas price sufficient to ensure a transaction is mined in
+    /// a timely fashion.
+    async fn gas_price(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        Ok(BigInt(backend(ctx).gas_price().await?))
+    }
+
+    /// Returns the node's estimate of a gas tip sufficient to ensure a transaction is mined in a
+    /// timely fashion.
+    async fn max_priority_fee_per_gas(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        Ok(BigInt(backend(ctx).max_priority_fee_per_gas().await?))
+    }
+
+    /// Returns the current synchronisation state, or null if the node is not syncing.
+    async fn syncing(&self, ctx: &Context<'_>) -> Result<Option<SyncState>> {
+        Ok(match backend(ctx).syncing()? {
+            SyncStatus::Info(info) => Some(SyncState {
+                starting_block: Long(info.starting_block.saturating_to()),
+                current_block: Long(info.current_block.saturating_to()),
+                highest_block: Long(info.highest_block.saturating_to()),
+            }),
+            SyncStatus::None => None,
+        })
+    }
+
+    /// Returns the chain id used for transaction signing.
+    #[graphql(name = "chainID")]
+    async fn chain_id(&self, ctx: &Context<'_>) -> BigInt {
+        BigInt(U256::from(backend(ctx).chain_id()))
+    }
+}
+
+/// The root mutations.
+#[derive(Debug)]
+pub struct Mutation;
+
+#[Object]
+impl Mutation {
+    /// Sends an RLP-encoded transaction to the network and returns its hash.
+    async fn send_raw_transaction(&self, ctx: &Context<'_>, data: Bytes) -> Result<Bytes32> {
+        Ok(Bytes32(backend(ctx).send_raw_transaction(data.0).await?))
+    }
+}
+
+/// Adds the address and topic criteria to a log filter.
+fn topics(
+    addresses: Option<Vec<Address>>,
+    topics: Option<Vec<Vec<Bytes32>>>,
+    mut filter: Filter,
+) -> Filter {
+    if let Some(addresses) = addresses {
+        filter = filter.address(addresses.into_iter().map(|address| address.0).collect::<Vec<_>>());
+    }
+    for (i, topic) in topics.unwrap_or_default().into_iter().take(4).enumerate() {
+        filter.topics[i] = topic.into_iter().map(|topic| topic.0).collect::<Vec<_>>().into();
+    }
+    filter
+}
+
+/// An Ethereum block.
+#[derive(Debug, Clone)]
+pub struct Block(Arc<BlockData>);
+
+impl Block {
+    /// Returns the block to resolve accounts at.
+    fn id(&self) -> BlockId {
+        BlockId::hash(self.0.hash)
+    }
+
+    /// Returns the transaction at the given index.
+    fn transaction(&self, index: usize) -> Option<Transaction> {
+        let data = self.0.transactions.as_ref()?.get(index)?.clone();
+        Some(Transaction { data, block: Some(self.clone()), index: Some(index) })
+    }
+}
+
+#[Object]
+impl Block {
+    /// The block number.
+    async fn number(&self) -> Long {
+        Long(self.0.header.number)
+    }
+
+    /// The block hash.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.0.hash)
+    }
+
+    /// The parent block, null for the genesis block.
+    async fn parent(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+        if self.0.header.number == 0 {
+            return Ok(None)
+        }
+        Ok(backend(ctx).block(BlockId::hash(self.0.header.parent_hash)).await?.map(Block))
+    }
+
+    /// The block nonce, an 8 byte sequence determined by the miner.
+    async fn nonce(&self) -> Bytes {
+        Bytes(AlloyBytes::copy_from_slice(self.0.header.nonce.as_slice()))
+    }
+
+    /// The hash of the root of the trie of transactions in this block.
+    async fn transactions_root(&self) -> Bytes32 {
+        Bytes32(self.0.header.transactions_root)
+    }
+
+    /// The number of transactions in this block, null for ommers.
+    async fn transaction_count(&self) -> Option<Long> {
+        self.0.transactions.as_ref().map(|txs| Long(txs.len() as u64))
+    }
+
+    /// The hash of the root of the final state trie.
+    async fn state_root(&self) -> Bytes32 {
+        Bytes32(self.0.header.state_root)
+    }
+
+    /// The hash of the trie of transaction receipts in this block.
+    async fn receipts_root(&self) -> Bytes32 {
+        Bytes32(self.0.header.receipts_root)
+    }
+
+    /// The account that mined this block, at the given block number or at this block.
+    async fn miner(&self, block: Option<Long>) -> Account {
+        Account::new(self.0.header.beneficiary, block_or(block, self.id()))
+    }
+
+    /// Arbitrary data field supplied by the miner.
+    async fn extra_data(&self) -> Bytes {
+        Bytes(self.0.header.extra_data.clone())
+    }
+
+    /// The maximum amount of gas that was available to transactions in this block.
+    async fn gas_limit(&self) -> Long {
+        Long(self.0.header.gas_limit)
+    }
+
+    /// The amount of gas that was used executing transactions in this block.
+    async fn gas_used(&self) -> Long {
+        Long(self.0.header.gas_used)
+    }
+
+    /// The price per unit of gas burned, since London.
+    async fn base_fee_per_gas(&self) -> Option<BigInt> {
+        self.0.header.base_fee_per_gas.map(|fee| BigInt(U256::from(fee)))
+    }
+
+    /// The unix timestamp at which this block was mined.
+    async fn timestamp(&self) -> Long {
+        Long(self.0.header.timestamp)
+    }
+
+    /// The bloom filter for the logs of this block.
+    async fn logs_bloom(&self) -> Bytes {
+        Bytes(AlloyBytes::copy_from_slice(self.0.header.logs_bloom.as_slice()))
+    }
+
+    /// The hash that was used as an input to the PoW process.
+    async fn mix_hash(&self) -> Bytes32 {
+        Bytes32(self.0.header.mix_hash)
+    }
+
+    /// The difficulty of the block.
+    async fn difficulty(&self) -> BigInt {
+        BigInt(self.0.header.difficulty)
+    }
+
+    /// The number of ommers of this block, null for ommers.
+    async fn ommer_count(&self) -> Option<Long> {
+        self.0.ommers.as_ref().map(|ommers| Long(ommers.len() as u64))
+    }
+
+    /// The ommers of this block, null for ommers.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn ommers(&self) -> Option<Vec<Block>> {
+        let ommers = self.0.ommers.as_ref()?;
+        Some(ommers.iter().map(|ommer| Block(Arc::new(BlockData::ommer(ommer.clone())))).collect())
+    }
+
+    /// The ommer at the given index, null for ommers or if the index is out of bounds.
+    async fn ommer_at(&self, index: Long) -> Option<Block> {
+        let ommer = self.0.ommers.as_ref()?.get(usize::try_from(index.0).ok()?)?;
+        Some(Block(Arc::new(BlockData::ommer(ommer.clone()))))
+    }
+
+    /// The hash of the ommers of this block.
+    async fn ommer_hash(&self) -> Bytes32 {
+        Bytes32(self.0.header.ommers_hash)
+    }
+
+    /// The transactions of this block, null for ommers.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn transactions(&self) -> Option<Vec<Transaction>> {
+        let len = self.0.transactions.as_ref()?.len();
+        (0..len).map(|index| self.transaction(index)).collect()
+    }
+
+    /// The transaction at the given index, null for ommers or if the index is out of bounds.
+    async fn transaction_at(&self, index: Long) -> Option<Transaction> {
+        self.transaction(usize::try_from(index.0).ok()?)
+    }
+
+    /// The logs of this block matching the filter.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn logs(&self, ctx: &Context<'_>, filter: BlockFilterCriteria) -> Result<Vec<Log>> {
+        let query =
+            topics(filter.addresses, filter.topics, Filter::new().at_block_hash(self.0.hash));
+        let logs = backend(ctx).logs(query).await?;
+        Ok(logs
+            .into_iter()
+            .map(|log| {
+                let tx = log
+                    .transaction_index
+                    .and_then(|index| self.transaction(usize::try_from(index).ok()?));
+                Log { transaction: tx, ..Log::from_rpc(log) }
+            })
+            .collect())
+    }
+
+    /// An account at this block.
+    async fn account(&self, address: Address) -> Account {
+        Account::new(address.0, self.id())
+    }
+
+    /// Executes a call on top of the state of this block.
+    async fn call(&self, ctx: &Context<'_>, data: CallData) -> Result<CallResult> {
+        Ok(backend(ctx).call(data.into(), self.id()).await?.into())
+    }
+
+    /// Estimates the gas of a call on top of the state of this block.
+    async fn estimate_gas(&self, ctx: &Context<'_>, data: CallData) -> Result<Long> {
+        Ok(Long(backend(ctx).estimate_gas(data.into(), self.id()).await?))
+    }
+
+    /// The RLP encoding of the header of this block.
+    async fn raw_header(&self) -> Bytes {
+        Bytes(alloy_rlp::encode(&self.0.header).into())
+    }
+
+    /// The root of the withdrawals trie, since Shanghai.
+    async fn withdrawals_root(&self) -> Option<Bytes32> {
+        self.0.header.withdrawals_root.map(Bytes32)
+    }
+
+    /// The withdrawals of this block, since Shanghai.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn withdrawals(&self) -> Option<Vec<Withdrawal>> {
+        let withdrawals = self.0.withdrawals.as_ref()?;
+        Some(
+            withdrawals
+                .iter()
+                .map(|withdrawal| Withdrawal {
+                    index: Long(withdrawal.index),
+                    validator: Long(withdrawal.validator_index),
+                    address: Address(withdrawal.address),
+                    amount: Long(withdrawal.amount),
+                })
+                .collect(),
+        )
+    }
+
+    /// The total amount of blob gas consumed by the transactions of this block, since Cancun.
+    async fn blob_gas_used(&self) -> Option<Long> {
+        self.0.header.blob_gas_used.map(Long)
+    }
+
+    /// The running total of blob gas consumed in excess of the target, since Cancun.
+    async fn excess_blob_gas(&self) -> Option<Long> {
+        self.0.header.excess_blob_gas.map(Long)
+    }
+}
+
+/// An Ethereum transaction.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    data: Arc<TransactionData>,
+    /// The block of the transaction, `None` for pending transactions.
+    block: Option<Block>,
+    /// The index of the transaction in its block.
+    index: Option<usize>,
+}
+
+impl Transaction {
+    /// Returns the block to resolve accounts at.
+    fn id(&self) -> BlockId {
+        self.block.as_ref().map_or_else(BlockId::latest, Block::id)
+    }
+
+    /// Applies the given function to the receipt of the transaction, if it is mined.
+    async fn receipt<T>(
+        &self,
+        ctx: &Context<'_>,
+        f: impl FnOnce(&ReceiptData) -> T,
+    ) -> Result<Option<T>> {
+        let (Some(block), Some(index)) = (&self.block, self.index) else { return Ok(None) };
+        let receipts = backend(ctx).receipts(block.0.hash).await?;
+        Ok(receipts.and_then(|receipts| receipts.get(index).map(f)))
+    }
+}
+
+#[Object]
+impl Transaction {
+    /// The hash of the transaction.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.data.hash)
+    }
+
+    /// The nonce of the account that sent the transaction.
+    async fn nonce(&self) -> Long {
+        Long(self.data.nonce)
+    }
+
+    /// The index of the transaction in its block, null for pending transactions.
+    async fn index(&self) -> Option<Long> {
+        self.index.map(|index| Long(index as u64))
+    }
+
+    /// The account that sent the transaction, at the given block number or at the block of the
+    /// transaction.
+    async fn from(&self, block: Option<Long>) -> Account {
+        Account::new(self.data.from, block_or(block, self.id()))
+    }
+
+    /// The account the transaction was sent to, null for contract creations.
+    async fn to(&self, block: Option<Long>) -> Option<Account> {
+        self.data.to.map(|to| Account::new(to, block_or(block, self.id())))
+    }
+
+    /// The value, in wei, sent along with the transaction.
+    async fn value(&self) -> BigInt {
+        BigInt(self.data.value)
+    }
+
+    /// The price offered to miners for gas, in wei per unit.
+    async fn gas_price(&self) -> BigInt {
+        BigInt(U256::from(self.data.effective_gas_price))
+    }
+
+    /// The maximum fee per gas offered, since London.
+    async fn max_fee_per_gas(&self) -> Option<BigInt> {
+        self.data.max_priority_fee_per_gas.map(|_| BigInt(U256::from(self.data.max_fee_per_gas)))
+    }
+
+    /// The maximum priority fee per gas offered, since London.
+    async fn max_priority_fee_per_gas(&self) -> Option<BigInt> {
+        self.data.max_priority_fee_per_gas.map(|fee| BigInt(U256::from(fee)))
+    }
+
+    /// The maximum fee per blob gas offered, since Cancun.
+    async fn max_fee_per_blob_gas(&self) -> Option<BigInt> {
+        self.data.max_fee_per_blob_gas.map(|fee| BigInt(U256::from(fee)))
+    }
+
+    /// The tip paid per gas to the miner, null for pending transactions.
+    async fn effective_tip(&self) -> Option<BigInt> {
+        self.data.effective_tip.map(|tip| BigInt(U256::from(tip)))
+    }
+
+    /// The maximum amount of gas that was available to the transaction.
+    async fn gas(&self) -> Long {
+        Long(self.data.gas)
+    }
+
+    /// The data supplied to the target of the transaction.
+    async fn input_data(&self) -> Bytes {
+        Bytes(self.data.input.clone())
+    }
+
+    /// The block the transaction was mined in, null for pending transactions.
+    async fn block(&self) -> Option<Block> {
+        self.block.clone()
+    }
+
+    /// The status of the transaction, 1 for success and 0 for failure. Null for pending
+    /// transactions.
+    async fn status(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        self.receipt(ctx, |receipt| Long(receipt.status as u64)).await
+    }
+
+    /// The amount of gas used by the transaction, null for pending transactions.
+    async fn gas_used(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        self.receipt(ctx, |receipt| Long(receipt.gas_used)).await
+    }
+
+    /// The total gas used in the block up to and including the transaction, null for pending
+    /// transactions.
+    async fn cumulative_gas_used(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        self.receipt(ctx, |receipt| Long(receipt.cumulative_gas_used)).await
+    }
+
+    /// The price paid for gas, null for pending transactions.
+    async fn effective_gas_price(&self) -> Option<BigInt> {
+        self.block.as_ref().map(|_| BigInt(U256::from(self.data.effective_gas_price)))
+    }
+
+    /// The account created by the transaction, null for pending transactions and calls.
+    async fn created_contract(&self, block: Option<Long>) -> Option<Account> {
+        if self.data.to.is_some() || self.block.is_none() {
+            return None
+        }
+        Some(Account::new(self.data.from.create(self.data.nonce), block_or(block, self.id())))
+    }
+
+    /// The logs emitted by the transaction, null for pending transactions.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn logs(&self, ctx: &Context<'_>) -> Result<Option<Vec<Log>>> {
+        let block = self.id();
+        self.receipt(ctx, |receipt| {
+            receipt
+                .logs
+                .iter()
+                .enumerate()
+                .map(|(i, log)| Log {
+                    inner: log.clone(),
+                    index: receipt.first_log_index + i as u64,
+                    block,
+                    transaction: Some(self.clone()),
+                    transaction_hash: self.data.hash,
+                })
+                .collect()
+        })
+        .await
+    }
+
+    /// The R field of the signature.
+    async fn r(&self) -> BigInt {
+        BigInt(self.data.signature.map(|sig| sig.r()).unwrap_or_default())
+    }
+
+    /// The S field of the signature.
+    async fn s(&self) -> BigInt {
+        BigInt(self.data.signature.map(|sig| sig.s()).unwrap_or_default())
+    }
+
+    /// The V field of the signature, including the chain id for EIP-155 legacy transactions.
+    async fn v(&self) -> BigInt {
+        let Some(signature) = self.data.signature else { return BigInt(U256::ZERO) };
+        let parity = signature.v() as u64;
+        let v = match (self.data.ty, self.data.chain_id) {
+            (0, Some(chain_id)) => parity + 35 + chain_id * 2,
+            (0, None) => parity + 27,
+            _ => parity,
+        };
+        BigInt(U256::from(v))
+    }
+
+    /// The parity of the signature, null for legacy transactions.
+    async fn y_parity(&self) -> Option<Long> {
+        let signature = self.data.signature.filter(|_| self.data.ty != 0)?;
+        Some(Long(signature.v() as u64))
+    }
+
+    /// The EIP-2718 type of the transaction.
+    #[graphql(name = "type")]
+    async fn ty(&self) -> Long {
+        Long(self.data.ty as u64)
+    }
+
+    /// The access list of the transaction, since Berlin.
+    async fn access_list(&self) -> Option<Vec<AccessTuple>> {
+        let access_list = self.data.access_list.as_ref()?;
+        Some(
+            access_list
+                .iter()
+                .map(|item| AccessTuple {
+                    address: Address(item.address),
+                    storage_keys: item.storage_keys.iter().copied().map(Bytes32).collect(),
+                })
+                .collect(),
+        )
+    }
+
+    /// The canonical encoding of the transaction.
+    async fn raw(&self) -> Bytes {
+        Bytes(self.data.raw.clone())
+    }
+
+    /// The blob hashes of the transaction, since Cancun.
+    async fn blob_versioned_hashes(&self) -> Option<Vec<Bytes32>> {
+        let hashes = self.data.blob_versioned_hashes.as_ref()?;
+        Some(hashes.iter().copied().map(Bytes32).collect())
+    }
+}
+
+/// An Ethereum account at a particular block.
+#[derive(Debug, Clone, Copy)]
+pub struct Account {
+    address: alloy_primitives::Address,
+    block: BlockId,
+}
+
+impl Account {
+    const fn new(address: alloy_primitives::Address, block: BlockId) -> Self {
+        Self { address, block }
+    }
+}
+
+#[Object]
+impl Account {
+    /// The address of the account.
+    async fn address(&self) -> Address {
+        Address(self.address)
+    }
+
+    /// The balance of the account, in wei.
+    async fn balance(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        Ok(BigInt(backend(ctx).balance(self.address, self.block).await?))
+    }
+
+    /// The number of transactions sent from the account.
+    async fn transaction_count(&self, ctx: &Context<'_>) -> Result<Long> {
+        Ok(Long(backend(ctx).transaction_count(self.address, self.block).await?))
+    }
+
+    /// The code of the contract of the account.
+    async fn code(&self, ctx: &Context<'_>) -> Result<Bytes> {
+        Ok(Bytes(backend(ctx).code(self.address, self.block).await?))
+    }
+
+    /// The value of a storage slot of the account.
+    async fn storage(&self, ctx: &Context<'_>, slot: Bytes32) -> Result<Bytes32> {
+        Ok(Bytes32(backend(ctx).storage(self.address, slot.0, self.block).await?))
+    }
+}
+
+/// A log entry emitted by a transaction.
+#[derive(Debug, Clone)]
+pub struct Log {
+    inner: alloy_primitives::Log,
+    index: u64,
+    /// The block to resolve the account of the log at.
+    block: BlockId,
+    /// The transaction of the log, if known.
+    transaction: Option<Transaction>,
+    transaction_hash: B256,
+}
+
+impl Log {
+    fn from_rpc(log: RpcLog) -> Self {
+        Self {
+            index: log.log_index.unwrap_or_default(),
+            block: log.block_hash.map_or_else(BlockId::latest, BlockId::hash),
+            transaction: None,
+            transaction_hash: log.transaction_hash.unwrap_or_default(),
+            inner: log.inner,
+        }
+    }
+}
+
+#[Object]
+impl Log {
+    /// The index of the log in its block.
+    async fn index(&self) -> Long {
+        Long(self.index)
+    }
+
+    /// The account that emitted the log.
+    async fn account(&self, block: Option<Long>) -> Account {
+        Account::new(self.inner.address, block_or(block, self.block))
+    }
+
+    /// The topics of the log.
+    async fn topics(&self) -> Vec<Bytes32> {
+        self.inner.topics().iter().copied().map(Bytes32).collect()
+    }
+
+    /// The data of the log.
+    async fn data(&self) -> Bytes {
+        Bytes(self.inner.data.data.clone())
+    }
+
+    /// The transaction that emitted the log.
+    async fn transaction(&self, ctx: &Context<'_>) -> Result<Transaction> {
+        if let Some(tx) = &self.transaction {
+            return Ok(tx.clone())
+        }
+        transaction_by_hash(backend(ctx), self.transaction_hash)
+            .await?
+            .ok_or_else(|| Error::new("transaction not found"))
+    }
+}
+
+/// The pending state.
+#[derive(Debug)]
+pub struct Pending;
+
+#[Object]
+impl Pending {
+    /// The number of transactions in the pending block.
+    async fn transaction_count(&self, ctx: &Context<'_>) -> Result<Long> {
+        let block = backend(ctx).block(BlockId::pending()).await?;
+        let count = block.and_then(|block| block.transactions.as_ref().map(Vec::len));
+        Ok(Long(count.unwrap_or_default() as u64))
+    }
+
+    /// The transactions of the pending block.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn transactions(&self, ctx: &Context<'_>) -> Result<Option<Vec<Transaction>>> {
+        let block = backend(ctx).block(BlockId::pending()).await?;
+        Ok(block.map(Block).and_then(|block| {
+            let len = block.0.transactions.as_ref()?.len();
+            (0..len).map(|index| block.transaction(index)).collect()
+        }))
+    }
+
+    /// An account in the pending state.
+    async fn account(&self, address: Address) -> Account {
+        Account::new(address.0, BlockId::pending())
+    }
+
+    /// Executes a call on top of the pending state.
+    async fn call(&self, ctx: &Context<'_>, data: CallData) -> Result<CallResult> {
+        Ok(backend(ctx).call(data.into(), BlockId::pending()).await?.into())
+    }
+
+    /// Estimates the gas of a call on top of the pending state.
+    async fn estimate_gas(&self, ctx: &Context<'_>, data: CallData) -> Result<Long> {
+        Ok(Long(backend(ctx).estimate_gas(data.into(), BlockId::pending()).await?))
+    }
+}
+
+/// The result of a call.
+#[derive(Debug, SimpleObject)]
+pub struct CallResult {
+    /// The return data of the call.
+    data: Bytes,
+    /// The amount of gas used by the call.
+    gas_used: Long,
+    /// The status of the call, 1 for success and 0 for failure.
+    status: Long,
+}
+
+impl From<CallOutcome> for CallResult {
+    fn from(outcome: CallOutcome) -> Self {
+        Self {
+            data: Bytes(outcome.data),
+            gas_used: Long(outcome.gas_used),
+            status: Long(outcome.success as u64),
+        }
+    }
+}
+
+/// The synchronisation state of the node.
+#[derive(Debug, SimpleObject)]
+pub struct SyncState {
+    /// The block number the sync started at.
+    starting_block: Long,
+    /// The current block number.
+    current_block: Long,
+    /// The latest known block number.
+    highest_block: Long,
+}
+
+/// A validator withdrawal.
+#[derive(Debug, SimpleObject)]
+pub struct Withdrawal {
+    /// The index of the withdrawal.
+    index: Long,
+    /// The index of the validator that generated the withdrawal.
+    validator: Long,
+    /// The recipient of the withdrawal.
+    address: Address,
+    /// The amount of the withdrawal, in gwei.
+    amount: Long,
+}
+
+/// An entry of an access list.
+#[derive(Debug, SimpleObject)]
+pub struct AccessTuple {
+    /// The accessed address.
+    address: Address,
+    /// The accessed storage keys.
+    storage_keys: Vec<Bytes32>,
+}
+
+/// The arguments of a call.
+#[derive(Debug, InputObject)]
+pub struct CallData {
+    /// The sender of the call, the zero address if unset.
+    from: Option<Address>,
+    /// The recipient of the call, null for contract creations.
+    to: Option<Address>,
+    /// The gas limit of the call.
+    gas: Option<Long>,
+    /// The gas price of the call.
+    gas_price: Option<BigInt>,
+    /// The maximum fee per gas of the call.
+    max_fee_per_gas: Option<BigInt>,
+    /// The maximum priority fee per gas of the call.
+    max_priority_fee_per_gas: Option<BigInt>,
+    /// The value sent along with the call.
+    value: Option<BigInt>,
+    /// The input data of the call.
+    data: Option<Bytes>,
+}
+
+impl From<CallData> for TransactionRequest {
+    fn from(data: CallData) -> Self {
+        Self {
+            from: data.from.map(|from| from.0),
+            to: Some(data.to.map_or(TxKind::Create, |to| TxKind::Call(to.0))),
+            gas: data.gas.map(|gas| gas.0),
+            gas_price: data.gas_price.map(|price| price.0.saturating_to()),
+            max_fee_per_gas: data.max_fee_per_gas.map(|fee| fee.0.saturating_to()),
+            max_priority_fee_per_gas: data
+                .max_priority_fee_per_gas
+                .map(|fee| fee.0.saturating_to()),
+            value: data.value.map(|value| value.0),
+            input: TransactionInput::maybe_input(data.data.map(|data| data.0)),
+            ..Default::default()
+        }
+    }
+}
+
+/// The criteria of the logs of a block.
+#[derive(Debug, InputObject)]
+pub struct BlockFilterCriteria {
+    /// The addresses the logs must be emitted by, any address if unset.
+    addresses: Option<Vec<Address>>,
+    /// The topics the logs must match, by position. Each position matches any of the given
+    /// topics, or any topic if empty.
+    topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+/// The criteria of logs.
+#[derive(Debug, InputObject)]
+pub struct FilterCriteria {
+    /// The first block to search, the latest block if unset.
+    from_block: Option<Long>,
+    /// The last block to search, the latest block if unset.
+    to_block: Option<Long>,
+    /// The addresses the logs must be emitted by, any address if unset.
+    addresses: Option<Vec<Address>>,
+    /// The topics the logs must match, by position. Each position matches any of the given
+    /// topics, or any topic if empty.
+    topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{Address as AlloyAddress, Bytes as AlloyBytes};
+    use futures::future::BoxFuture;
+
+    /// A backend with a chain of two empty blocks.
+    struct MockBackend;
+
+    fn chain() -> Vec<Arc<BlockData>> {
+        let mut parent_hash = B256::ZERO;
+        (0..2)
+            .map(|number| {
+                let header = Header { number, parent_hash, ..Default::default() };
+                parent_hash = header.hash_slow();
+                Arc::new(BlockData {
+                    hash: parent_hash,
+                    header,
+                    transactions: Some(Vec::new()),
+                    ommers: Some(Vec::new()),
+                    withdrawals: None,
+                })
+            })
+            .collect()
+    }
+
+    fn unsupported<T: Send>() -> BoxFuture<'static, Result<T>> {
+        Box::pin(async { Err(Error::new("unsupported")) })
+    }
+
+    impl GraphQLBackend for MockBackend {
+        fn block(&self, id: BlockId) -> BoxFuture<'_, Result<Option<Arc<BlockData>>>> {
+            let chain = chain();
+            let block = match id {
+                BlockId::Number(BlockNumberOrTag::Number(number)) => chain.get(number as usize),
+                BlockId::Number(BlockNumberOrTag::Latest) => chain.last(),
+                BlockId::Hash(hash) => chain.iter().find(|block| block.hash == hash.block_hash),
+                _ => return unsupported(),
+            };
+            let block = block.cloned();
+            Box::pin(async move { Ok(block) })
+        }
+
+        fn receipts(&self, _: B256) -> BoxFuture<'_, Result<Option<Arc<Vec<ReceiptData>>>>> {
+            unsupported()
+        }
+
+        fn transaction(&self, _: B256) -> BoxFuture<'_, Result<Option<TransactionLocation>>> {
+            Box::pin(async { Ok(None) })
+        }
+
+        fn balance(&self, _: AlloyAddress, _: BlockId) -> BoxFuture<'_, Result<U256>> {
+            Box::pin(async { Ok(U256::from(1000)) })
+        }
+
+        fn transaction_count(&self, _: AlloyAddress, _: BlockId) -> BoxFuture<'_, Result<u64>> {
+            unsupported()
+        }
+
+        fn code(&self, _: AlloyAddress, _: BlockId) -> BoxFuture<'_, Result<AlloyBytes>> {
+            unsupported()
+        }
+
+        fn storage(&self, _: AlloyAddress, _: B256, _: BlockId) -> BoxFuture<'_, Result<B256>> {
+            unsupported()
+        }
+
+        fn logs(&self, _: Filter) -> BoxFuture<'_, Result<Vec<RpcLog>>> {
+            unsupported()
+        }
+
+        fn call(&self, _: TransactionRequest, _: BlockId) -> BoxFuture<'_, Result<CallOutcome>> {
+            unsupported()
+        }
+
+        fn estimate_gas(&self, _: TransactionRequest, _: BlockId) -> BoxFuture<'_, Result<u64>> {
+            unsupported()
+        }
+
+        fn gas_price(&self) -> BoxFuture<'_, Result<U256>> {
+            unsupported()
+        }
+
+        fn max_priority_fee_per_gas(&self) -> BoxFuture<'_, Result<U256>> {
+            unsupported()
+        }
+
+        fn send_raw_transaction(&self, _: AlloyBytes) -> BoxFuture<'_, Result<B256>> {
+            unsupported()
+        }
+
+        fn best_block_number(&self) -> Result<u64> {
+            Ok(1)
+        }
+
+        fn chain_id(&self) -> u64 {
+            1
+        }
+
+        fn syncing(&self) -> Result<SyncStatus> {
+            Ok(SyncStatus::None)
+        }
+    }
+
+    async fn execute(config: GraphQLConfig, query: &str) -> async_graphql::Response {
+        build_schema(Arc::new(MockBackend), config).execute(query).await
+    }
+
+    #[tokio::test]
+    async fn execute_queries() {
+        let config = GraphQLConfig::default();
+        let response = execute(
+            config,
+            r#"{
+                block {
+                    number
+                    parent { number }
+                    account(address: "0x0000000000000000000000000000000000000001") { balance }
+                }
+                chainID
+                syncing
+            }"#,
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({
+                "block": {
+                    "number": "0x1",
+                    "parent": { "number": "0x0" },
+                    "account": { "balance": "0x3e8" },
+                },
+                "chainID": "0x1",
+                "syncing": null,
+            })
+        );
+
+        // open ranges end at the latest block
+        let response = execute(config, "{ blocks(from: 0) { number } }").await;
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({ "blocks": [{ "number": "0x0" }, { "number": "0x1" }] })
+        );
+    }
+
+    #[tokio::test]
+    async fn enforce_limits() {
+        let config = GraphQLConfig { max_depth: 3, max_complexity: 1_000 };
+
+        let response = execute(config, "{ block { parent { parent { number } } } }").await;
+        assert!(response.errors[0].message.contains("depth"), "{:?}", response.errors);
+
+        // the transactions of every block cost 100
+        let response =
+            execute(config, "{ blocks(from: 0, to: 19) { transactions { hash } } }").await;
+        assert!(response.errors[0].message.contains("complex"), "{:?}", response.errors);
+        let response =
+            execute(config, "{ blocks(from: 0, to: 1) { transactions { hash } } }").await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+    }
+}
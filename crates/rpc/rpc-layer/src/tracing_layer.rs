@@ -0,0 +1,62 @@
+use http::Request;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::{debug_span, instrument::Instrumented, Instrument};
+
+/// This layer wraps every HTTP request in a `rpc_request` span.
+///
+/// If the request carries a W3C trace context, e.g. the `traceparent` header, the span continues
+/// the trace of the caller, so that the request can be followed end to end when spans are exported
+/// to an `OpenTelemetry` collector.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct RequestTracingLayer;
+
+impl RequestTracingLayer {
+    /// Creates a new [`RequestTracingLayer`].
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for RequestTracingLayer {
+    type Service = RequestTracingService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestTracingService { inner }
+    }
+}
+
+/// Service that instruments the HTTP requests.
+///
+/// Created by [`RequestTracingLayer`].
+#[derive(Debug, Clone)]
+pub struct RequestTracingService<S> {
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for RequestTracingService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Instrumented<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let span = debug_span!(
+            target: "rpc",
+            "rpc_request",
+            http.method = %req.method(),
+            http.path = %req.uri().path()
+        );
+        reth_tracing_otlp::set_parent_from_headers(&span, req.headers());
+
+        let fut = span.in_scope(|| self.inner.call(req));
+        fut.instrument(span)
+    }
+}
@@ -0,0 +1,383 @@
+//! Access control configuration for the HTTP and WS RPC servers.
+
+use crate::RethRpcModule;
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    net::IpAddr,
+};
+
+/// The default header that carries the API key of a caller.
+pub const DEFAULT_API_KEY_HEADER: &str = "x-api-key";
+
+/// The name of the tenant that unidentified callers belong to, see
+/// [`RpcAccessConfig::anonymous`].
+pub const ANONYMOUS_TENANT: &str = "anonymous";
+
+/// Configures who can call the HTTP and WS RPC servers, which methods they can call and how much.
+///
+/// Callers are identified by an API key, sent in the [`Self::api_key_header`] header or as the
+/// URL path, e.g. `http://localhost:8545/<api_key>`. Callers without an API key are identified by
+/// their IP, as reported by a reverse proxy in the [`Self::ip_header`] header or otherwise the
+/// address of their connection, and otherwise fall back to the [`Self::anonymous`] policy.
+///
+/// Every identified caller belongs to a tenant, which restricts the methods it may call and the
+/// compute units it may spend per second. The cost of a call is its weight in
+/// [`Self::method_weights`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcAccessConfig {
+    /// The header that carries the API key, [`DEFAULT_API_KEY_HEADER`] by default.
+    pub api_key_header: String,
+    /// The header that carries the IP of the caller, e.g. `x-forwarded-for`.
+    ///
+    /// If the header contains a list of IPs, the last one is used, which is the one added by the
+    /// reverse proxy in front of the node. If this is not set, or a request doesn't have the
+    /// header, the IP of the connection is used.
+    pub ip_header: Option<String>,
+    /// The tenants, by name.
+    pub tenants: BTreeMap<String, RpcTenantConfig>,
+    /// The policy of callers that don't belong to any tenant.
+    ///
+    /// If not set, calls of unidentified callers are rejected.
+    pub anonymous: Option<RpcAccessPolicy>,
+    /// The compute units of a call, by method name, e.g. `debug_traceBlockByNumber`, or by module
+    /// name, e.g. `debug`, which applies to all methods of the module.
+    pub method_weights: HashMap<String, u64>,
+    /// The compute units of the methods that are not in [`Self::method_weights`].
+    pub default_method_weight: u64,
+}
+
+impl RpcAccessConfig {
+    /// Returns the compute units of a call to the given method.
+    pub fn method_weight(&self, method: &str) -> u64 {
+        self.method_weights
+            .get(method)
+            .or_else(|| {
+                method.split_once('_').and_then(|(module, _)| self.method_weights.get(module))
+            })
+            .copied()
+            .unwrap_or(self.default_method_weight)
+    }
+
+    /// Checks that API keys and IPs belong to at most one tenant, and that all quotas allow at
+    /// least one call of every method.
+    pub fn validate(&self) -> Result<(), RpcAccessConfigError> {
+        let mut api_keys = HashSet::new();
+        let mut ips = HashSet::new();
+        for (name, tenant) in &self.tenants {
+            if name == ANONYMOUS_TENANT {
+                return Err(RpcAccessConfigError::ReservedTenantName)
+            }
+            if let Some(api_key) = tenant.api_keys.iter().find(|key| !api_keys.insert(*key)) {
+                return Err(RpcAccessConfigError::DuplicateApiKey {
+                    tenant: name.clone(),
+                    api_key: api_key.clone(),
+                })
+            }
+            if let Some(ip) = tenant.ips.iter().find(|ip| !ips.insert(**ip)) {
+                return Err(RpcAccessConfigError::DuplicateIp { tenant: name.clone(), ip: *ip })
+            }
+            self.validate_policy(name, &tenant.policy)?;
+        }
+        if let Some(anonymous) = &self.anonymous {
+            self.validate_policy(ANONYMOUS_TENANT, anonymous)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the quota of the policy allows at least one call of every method.
+    fn validate_policy(
+        &self,
+        tenant: &str,
+        policy: &RpcAccessPolicy,
+    ) -> Result<(), RpcAccessConfigError> {
+        if policy.compute_units_per_second == Some(0) || policy.capacity() == Some(0) {
+            return Err(RpcAccessConfigError::EmptyQuota { tenant: tenant.to_string() })
+        }
+        let Some(capacity) = policy.capacity() else { return Ok(()) };
+        let weights = self
+            .method_weights
+            .iter()
+            .filter(|(method, _)| policy.may_call(method))
+            .map(|(method, weight)| (method.as_str(), *weight))
+            .chain([("default", self.default_method_weight)]);
+        for (method, weight) in weights {
+            if weight > capacity {
+                return Err(RpcAccessConfigError::WeightExceedsQuota {
+                    tenant: tenant.to_string(),
+                    method: method.to_string(),
+                    weight,
+                    capacity,
+                })
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for RpcAccessConfig {
+    fn default() -> Self {
+        Self {
+            api_key_header: DEFAULT_API_KEY_HEADER.to_string(),
+            ip_header: None,
+            tenants: BTreeMap::new(),
+            anonymous: None,
+            method_weights: HashMap::new(),
+            default_method_weight: 1,
+        }
+    }
+}
+
+/// A tenant of the RPC servers, see [`RpcAccessConfig`].
+///
+/// The fields of the policy are deserialized next to the API keys and IPs.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(from = "TenantFields")]
+pub struct RpcTenantConfig {
+    /// The API keys of the tenant.
+    pub api_keys: Vec<String>,
+    /// The IPs of the tenant, see [`RpcAccessConfig::ip_header`].
+    pub ips: Vec<IpAddr>,
+    /// The policy of the tenant.
+    pub policy: RpcAccessPolicy,
+}
+
+/// The fields of a [`RpcTenantConfig`].
+///
+/// `#[serde(flatten)]` doesn't support `deny_unknown_fields`, so the fields of the policy are
+/// repeated here to reject misspelled fields instead of ignoring them.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TenantFields {
+    api_keys: Vec<String>,
+    ips: Vec<IpAddr>,
+    modules: Option<HashSet<RethRpcModule>>,
+    methods: HashSet<String>,
+    denied_methods: HashSet<String>,
+    compute_units_per_second: Option<u64>,
+    burst: Option<u64>,
+}
+
+impl From<TenantFields> for RpcTenantConfig {
+    fn from(fields: TenantFields) -> Self {
+        let TenantFields {
+            api_keys,
+            ips,
+            modules,
+            methods,
+            denied_methods,
+            compute_units_per_second,
+            burst,
+        } = fields;
+        Self {
+            api_keys,
+            ips,
+            policy: RpcAccessPolicy {
+                modules,
+                methods,
+                denied_methods,
+                compute_units_per_second,
+                burst,
+            },
+        }
+    }
+}
+
+/// The methods a tenant may call, and how many compute units it may spend.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcAccessPolicy {
+    /// The modules whose methods may be called, all modules if not set.
+    pub modules: Option<HashSet<RethRpcModule>>,
+    /// Methods that may be called, in addition to the methods of [`Self::modules`].
+    pub methods: HashSet<String>,
+    /// Methods that may not be called, even if their module is allowed.
+    pub denied_methods: HashSet<String>,
+    /// The compute units that are refilled per second, unlimited if not set.
+    pub compute_units_per_second: Option<u64>,
+    /// The compute units that can be spent at once, [`Self::compute_units_per_second`] by
+    /// default.
+    pub burst: Option<u64>,
+}
+
+impl RpcAccessPolicy {
+    /// Returns true if the given method may be called.
+    pub fn is_allowed(&self, method: &str) -> bool {
+        if self.denied_methods.contains(method) {
+            return false
+        }
+        if self.methods.contains(method) {
+            return true
+        }
+        let Some(modules) = &self.modules else { return true };
+        method
+            .split_once('_')
+            .and_then(|(module, _)| module.parse::<RethRpcModule>().ok())
+            .is_some_and(|module| modules.contains(&module))
+    }
+
+    /// Returns true if the given method, or any method of the given module, may be called.
+    fn may_call(&self, method_or_module: &str) -> bool {
+        if method_or_module.contains('_') {
+            return self.is_allowed(method_or_module)
+        }
+        let module_allowed = match (&self.modules, method_or_module.parse::<RethRpcModule>()) {
+            (None, _) => true,
+            (Some(modules), Ok(module)) => modules.contains(&module),
+            (Some(_), Err(_)) => false,
+        };
+        module_allowed ||
+            self.methods.iter().any(|method| {
+                method.split_once('_').is_some_and(|(module, _)| module == method_or_module) &&
+                    !self.denied_methods.contains(method)
+            })
+    }
+
+    /// Returns the compute units that can be spent at once, if the policy has a quota.
+    pub fn capacity(&self) -> Option<u64> {
+        self.burst.or(self.compute_units_per_second)
+    }
+}
+
+/// Errors of an invalid [`RpcAccessConfig`].
+#[derive(Debug, thiserror::Error)]
+pub enum RpcAccessConfigError {
+    /// An API key belongs to more than one tenant.
+    #[error("API key {api_key} of tenant {tenant} belongs to another tenant")]
+    DuplicateApiKey {
+        /// The second tenant of the API key.
+        tenant: String,
+        /// The API key.
+        api_key: String,
+    },
+    /// An IP belongs to more than one tenant.
+    #[error("IP {ip} of tenant {tenant} belongs to another tenant")]
+    DuplicateIp {
+        /// The second tenant of the IP.
+        tenant: String,
+        /// The IP.
+        ip: IpAddr,
+    },
+    /// A tenant is named after the anonymous callers.
+    #[error("tenant name {ANONYMOUS_TENANT} is reserved for unidentified callers")]
+    ReservedTenantName,
+    /// A quota doesn't allow any calls.
+    #[error("quota of tenant {tenant} allows no calls")]
+    EmptyQuota {
+        /// The tenant of the quota.
+        tenant: String,
+    },
+    /// A method costs more compute units than a quota can hold, so it can never be called.
+    #[error(
+        "method {method} costs {weight} compute units, more than the quota of tenant {tenant} \
+         allows at once ({capacity})"
+    )]
+    WeightExceedsQuota {
+        /// The tenant of the quota.
+        tenant: String,
+        /// The method or module, or `default` for the default weight.
+        method: String,
+        /// The compute units of the method.
+        weight: u64,
+        /// The compute units the quota can hold.
+        capacity: u64,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> RpcAccessConfig {
+        serde_json::from_str(
+            r#"{
+                "tenants": {
+                    "indexer": {
+                        "api_keys": ["secret"],
+                        "ips": ["10.0.0.1"],
+                        "modules": ["eth", "net"],
+                        "methods": ["debug_traceTransaction"],
+                        "denied_methods": ["eth_sendRawTransaction"],
+                        "compute_units_per_second": 100
+                    }
+                },
+                "anonymous": { "modules": ["eth"], "compute_units_per_second": 10, "burst": 20 },
+                "method_weights": { "debug": 50, "eth_call": 5 }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_access_config() {
+        let config = config();
+        assert_eq!(config.api_key_header, DEFAULT_API_KEY_HEADER);
+        assert!(config.validate().is_ok());
+
+        let indexer = &config.tenants["indexer"];
+        assert_eq!(indexer.api_keys, vec!["secret".to_string()]);
+        assert_eq!(indexer.policy.capacity(), Some(100));
+        assert_eq!(config.anonymous.as_ref().unwrap().capacity(), Some(20));
+
+        assert!(serde_json::from_str::<RpcAccessConfig>(r#"{"tenant": {}}"#).is_err());
+        // misspelled fields of tenants and policies are rejected
+        let misspelled = r#"{"tenants": {"indexer": {"api_keys": ["key"], "denied_method": []}}}"#;
+        assert!(serde_json::from_str::<RpcAccessConfig>(misspelled).is_err());
+        let misspelled = r#"{"anonymous": {"compute_units_per_sec": 10}}"#;
+        assert!(serde_json::from_str::<RpcAccessConfig>(misspelled).is_err());
+    }
+
+    #[test]
+    fn allowed_methods() {
+        let config = config();
+        let policy = &config.tenants["indexer"].policy;
+        assert!(policy.is_allowed("eth_getLogs"));
+        assert!(policy.is_allowed("net_version"));
+        assert!(policy.is_allowed("debug_traceTransaction"));
+        assert!(!policy.is_allowed("debug_traceBlockByNumber"));
+        assert!(!policy.is_allowed("eth_sendRawTransaction"));
+        assert!(!policy.is_allowed("custom"));
+
+        assert!(RpcAccessPolicy::default().is_allowed("admin_addPeer"));
+    }
+
+    #[test]
+    fn method_weights() {
+        let config = config();
+        assert_eq!(config.method_weight("eth_call"), 5);
+        assert_eq!(config.method_weight("debug_traceBlockByNumber"), 50);
+        assert_eq!(config.method_weight("eth_blockNumber"), 1);
+    }
+
+    #[test]
+    fn invalid_access_config() {
+        let mut config = config();
+        config.tenants.insert(
+            "other".to_string(),
+            RpcTenantConfig { api_keys: vec!["secret".to_string()], ..Default::default() },
+        );
+        assert!(matches!(config.validate(), Err(RpcAccessConfigError::DuplicateApiKey { .. })));
+
+        let mut config = self::config();
+        config.anonymous.as_mut().unwrap().burst = Some(0);
+        assert!(matches!(config.validate(), Err(RpcAccessConfigError::EmptyQuota { .. })));
+
+        // debug methods cost 50 compute units, more than the anonymous quota holds
+        let mut config = self::config();
+        config.anonymous.as_mut().unwrap().burst = Some(40);
+        assert!(config.validate().is_ok());
+        config.anonymous.as_mut().unwrap().methods.insert("debug_traceTransaction".to_string());
+        assert!(matches!(
+            config.validate(),
+            Err(RpcAccessConfigError::WeightExceedsQuota { weight: 50, capacity: 40, .. })
+        ));
+
+        let mut config = self::config();
+        config.default_method_weight = 30;
+        assert!(matches!(
+            config.validate(),
+            Err(RpcAccessConfigError::WeightExceedsQuota { weight: 30, capacity: 20, .. })
+        ));
+    }
+}
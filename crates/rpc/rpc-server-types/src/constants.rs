@@ -15,6 +15,12 @@ pub const DEFAULT_MAX_BLOCKS_PER_FILTER: u64 = 100_000;
 /// The default maximum of logs in a single response.
 pub const DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
 
+/// The default maximum nesting depth of GraphQL queries.
+pub const DEFAULT_GRAPHQL_MAX_DEPTH: usize = 16;
+
+/// The default maximum complexity of GraphQL queries.
+pub const DEFAULT_GRAPHQL_MAX_COMPLEXITY: usize = 20_000;
+
 /// The default maximum number of blocks for `trace_filter` requests.
 pub const DEFAULT_MAX_TRACE_FILTER_BLOCKS: u64 = 100;
 
@@ -8,6 +8,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+pub mod access;
 /// Common RPC constants.
 pub mod constants;
 pub mod result;
@@ -15,4 +16,5 @@ pub mod result;
 mod module;
 pub use module::{RethRpcModule, RpcModuleSelection};
 
+pub use access::{RpcAccessConfig, RpcAccessPolicy, RpcTenantConfig};
 pub use result::ToRpcResult;
@@ -31,20 +31,26 @@ use reth_rpc_eth_api::{
     EthApiTypes, FromEthApiError, RpcNodeCore,
 };
 use reth_rpc_eth_types::{EthApiError, StateCacheDb};
-use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_server_types::{
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    ToRpcResult,
+};
 use reth_storage_api::{
     BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderBlock, ReceiptProviderIdExt,
     StateProofProvider, StateProviderFactory, StateRootProvider, TransactionVariant,
 };
 use reth_tasks::pool::BlockingTaskGuard;
+use reth_tracing::tracing::level_filters::LevelFilter;
 use reth_trie_common::{updates::TrieUpdates, HashedPostState};
 use revm::{context_interface::Transaction, state::EvmState, DatabaseCommit};
 use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
+use crate::reth::log_filter_handle;
+
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
@@ -1111,8 +1117,12 @@ where
         Self::debug_code_by_hash(self, hash, block_id).await.map_err(Into::into)
     }
 
-    async fn debug_cpu_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_cpuProfile`
+    async fn debug_cpu_profile(&self, file: String, seconds: u64) -> RpcResult<()> {
+        reth_profiling::cpu_profile(Duration::from_secs(seconds))
+            .await
+            .and_then(|profile| profile.write(file))
+            .map_err(|err| internal_rpc_err(err.to_string()))
     }
 
     async fn debug_db_ancient(&self, _kind: String, _number: u64) -> RpcResult<()> {
@@ -1239,8 +1249,9 @@ where
         Ok(())
     }
 
-    async fn debug_start_cpu_profile(&self, _file: String) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_startCPUProfile`
+    async fn debug_start_cpu_profile(&self, file: String) -> RpcResult<()> {
+        reth_profiling::start_cpu_profile(file).map_err(|err| internal_rpc_err(err.to_string()))
     }
 
     async fn debug_start_go_trace(&self, _file: String) -> RpcResult<()> {
@@ -1255,8 +1266,11 @@ where
         Self::debug_state_root_with_updates(self, hashed_state, block_id).await.map_err(Into::into)
     }
 
+    /// Handler for `debug_stopCPUProfile`
     async fn debug_stop_cpu_profile(&self) -> RpcResult<()> {
-        Ok(())
+        reth_profiling::stop_cpu_profile()
+            .map(drop)
+            .map_err(|err| internal_rpc_err(err.to_string()))
     }
 
     async fn debug_stop_go_trace(&self) -> RpcResult<()> {
@@ -1282,20 +1296,40 @@ where
         Ok(())
     }
 
-    async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_verbosity`
+    async fn debug_verbosity(&self, level: usize) -> RpcResult<()> {
+        log_filter_handle()?
+            .set_verbosity(Some(verbosity_level(level)))
+            .map_err(|err| internal_rpc_err(err.to_string()))
     }
 
-    async fn debug_vmodule(&self, _pattern: String) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_vmodule`
+    async fn debug_vmodule(&self, pattern: String) -> RpcResult<()> {
+        let directives = pattern
+            .split(',')
+            .map(|directive| match directive.rsplit_once('=') {
+                Some((target, level)) => match level.parse::<usize>() {
+                    Ok(level) => format!("{target}={}", verbosity_level(level)),
+                    Err(_) => directive.to_string(),
+                },
+                None => directive.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        log_filter_handle()?
+            .set_vmodule(&directives)
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))
     }
 
     async fn debug_write_block_profile(&self, _file: String) -> RpcResult<()> {
         Ok(())
     }
 
-    async fn debug_write_mem_profile(&self, _file: String) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_writeMemProfile`
+    async fn debug_write_mem_profile(&self, file: String) -> RpcResult<()> {
+        reth_profiling::write_heap_profile(file)
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
     }
 
     async fn debug_write_mutex_profile(&self, _file: String) -> RpcResult<()> {
@@ -1321,3 +1355,16 @@ struct DebugApiInner<Eth> {
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
 }
+
+/// Converts a geth verbosity level to a [`LevelFilter`]: `0` disables logging, `1` to `5` map to
+/// error, warn, info, debug and trace.
+const fn verbosity_level(level: usize) -> LevelFilter {
+    match level {
+        0 => LevelFilter::OFF,
+        1 => LevelFilter::ERROR,
+        2 => LevelFilter::WARN,
+        3 => LevelFilter::INFO,
+        4 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
@@ -0,0 +1,162 @@
+//! `eth_` namespace extension for transactions with a [`TransactionConditional`].
+
+use alloy_consensus::{conditional::BlockConditionalAttributes, BlockHeader};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee_types::error::{ErrorObject, INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE};
+use reth_rpc_eth_api::L2EthApiExtServer;
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
+use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
+use reth_transaction_pool::{
+    conditional::{
+        validate_known_accounts, MaybeConditionalTransaction, TransactionConditionalError,
+    },
+    AddedTransactionOutcome, PoolTransaction, TransactionOrigin, TransactionPool,
+};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Maximum execution cost of a [`TransactionConditional`], see
+/// [`TransactionConditional::cost`].
+pub const MAX_CONDITIONAL_EXECUTION_COST: u64 = 5000;
+
+/// Maximum number of conditionals whose known accounts are validated concurrently.
+const MAX_CONCURRENT_CONDITIONAL_VALIDATIONS: usize = 3;
+
+/// `eth_sendRawTransactionConditional` for Ethereum nodes.
+///
+/// Submits transactions with a [`TransactionConditional`] to the local pool. The conditional is
+/// validated against the latest block on submission, re-checked on every canonical update by the
+/// pool maintenance task, and checked again when building a payload.
+#[derive(Debug, Clone)]
+pub struct EthExtApi<Pool, Provider> {
+    inner: Arc<EthExtApiInner<Pool, Provider>>,
+}
+
+impl<Pool, Provider> EthExtApi<Pool, Provider> {
+    /// Creates a new [`EthExtApi`].
+    pub fn new(pool: Pool, provider: Provider) -> Self {
+        let inner = Arc::new(EthExtApiInner {
+            pool,
+            provider,
+            validation_semaphore: Semaphore::new(MAX_CONCURRENT_CONDITIONAL_VALIDATIONS),
+        });
+        Self { inner }
+    }
+}
+
+impl<Pool, Provider> EthExtApi<Pool, Provider>
+where
+    Provider: BlockReaderIdExt + StateProviderFactory,
+{
+    /// Validates the conditional against the latest block and state.
+    async fn validate_conditional(
+        &self,
+        condition: &TransactionConditional,
+    ) -> Result<(), TxConditionalError> {
+        let header = self
+            .inner
+            .provider
+            .latest_header()
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::HeaderNotFound(BlockId::Number(BlockNumberOrTag::Latest)))?;
+
+        // the conditional must still be satisfiable
+        let block_attr = BlockConditionalAttributes {
+            number: header.header().number(),
+            timestamp: header.header().timestamp(),
+        };
+        if condition.has_exceeded_block_attributes(&block_attr) {
+            return Err(TxConditionalError::InvalidCondition)
+        }
+
+        if condition.known_accounts.is_empty() {
+            return Ok(())
+        }
+
+        let _permit = self
+            .inner
+            .validation_semaphore
+            .acquire()
+            .await
+            .map_err(|_| EthApiError::InternalEthError)?;
+        let state =
+            self.inner.provider.state_by_block_hash(header.hash()).map_err(EthApiError::from)?;
+        validate_known_accounts(&*state, condition)?;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<Pool, Provider> L2EthApiExtServer for EthExtApi<Pool, Provider>
+where
+    Pool: TransactionPool<Transaction: MaybeConditionalTransaction> + 'static,
+    Provider: BlockReaderIdExt + StateProviderFactory + 'static,
+{
+    async fn send_raw_transaction_conditional(
+        &self,
+        bytes: Bytes,
+        condition: TransactionConditional,
+    ) -> RpcResult<B256> {
+        if condition.cost() > MAX_CONDITIONAL_EXECUTION_COST {
+            return Err(TxConditionalError::ConditionalCostExceeded.into())
+        }
+
+        let recovered = recover_raw_transaction(&bytes)?;
+        let tx = <Pool as TransactionPool>::Transaction::from_pooled(recovered);
+
+        self.validate_conditional(&condition).await?;
+
+        let AddedTransactionOutcome { hash, .. } = self
+            .inner
+            .pool
+            .add_transaction(TransactionOrigin::Private, tx.with_conditional(condition))
+            .await
+            .map_err(EthApiError::from)?;
+
+        Ok(hash)
+    }
+}
+
+#[derive(Debug)]
+struct EthExtApiInner<Pool, Provider> {
+    /// The transaction pool of the node.
+    pool: Pool,
+    /// The provider type used to interact with the node.
+    provider: Provider,
+    /// Limits the number of concurrent validations of known accounts.
+    validation_semaphore: Semaphore,
+}
+
+/// [`EthExtApi`] specific errors.
+#[derive(Debug, thiserror::Error)]
+pub enum TxConditionalError {
+    /// Thrown if the cost of the conditional exceeds [`MAX_CONDITIONAL_EXECUTION_COST`].
+    #[error("conditional cost exceeded maximum allowed")]
+    ConditionalCostExceeded,
+    /// Thrown if the conditional can no longer be satisfied by the next block.
+    #[error("invalid conditional parameters")]
+    InvalidCondition,
+    /// Thrown if the known accounts don't match the latest state.
+    #[error(transparent)]
+    KnownAccounts(#[from] TransactionConditionalError),
+    /// Any other error.
+    #[error(transparent)]
+    Eth(#[from] EthApiError),
+}
+
+impl From<TxConditionalError> for ErrorObject<'static> {
+    fn from(err: TxConditionalError) -> Self {
+        let code = match err {
+            TxConditionalError::Eth(err) => return err.into(),
+            TxConditionalError::KnownAccounts(TransactionConditionalError::Provider(_)) => {
+                INTERNAL_ERROR_CODE
+            }
+            _ => INVALID_PARAMS_CODE,
+        };
+        ErrorObject::owned(code, err.to_string(), None::<String>)
+    }
+}
@@ -585,9 +585,39 @@ where
         // get current chain tip to determine processing mode
         let chain_tip = self.provider().best_block_number()?;
 
-        // first collect all headers that match the bloom filter for cached mode decision
+        // blocks covered by the log index are looked up in the index instead of checking the
+        // bloom of every header, which only helps if the filter restricts addresses or topics
+        let mut bloom_from_block = from_block;
+        let has_constraints =
+            !filter.address.is_empty() || filter.topics.iter().any(|topic| !topic.is_empty());
+        if let Some(indexed_to_block) = self
+            .provider()
+            .log_index_tip()?
+            .map(|tip| tip.min(to_block))
+            .filter(|tip| has_constraints && *tip >= from_block)
+        {
+            let addresses = filter.address.iter().copied().collect::<Vec<_>>();
+            let topics = filter
+                .topics
+                .iter()
+                .map(|topic| topic.iter().copied().collect())
+                .collect::<Vec<Vec<_>>>();
+            for block_number in self.provider().blocks_with_logs(
+                from_block..=indexed_to_block,
+                &addresses,
+                &topics,
+            )? {
+                let Some(header) = self.provider().sealed_header(block_number)? else { continue };
+                if filter.matches_bloom(header.logs_bloom()) {
+                    matching_headers.push(header);
+                }
+            }
+            bloom_from_block = indexed_to_block + 1;
+        }
+
+        // collect all remaining headers that match the bloom filter for cached mode decision
         for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+            BlockRangeInclusiveIter::new(bloom_from_block..=to_block, self.max_headers_range)
         {
             let headers = self.provider().headers_range(from..=to)?;
 
@@ -3,6 +3,7 @@
 pub mod builder;
 pub mod bundle;
 pub mod core;
+pub mod ext;
 pub mod filter;
 pub mod helpers;
 pub mod pubsub;
@@ -12,6 +13,7 @@ pub mod sim_bundle;
 pub use builder::EthApiBuilder;
 pub use bundle::EthBundle;
 pub use core::{EthApi, EthApiFor};
+pub use ext::EthExtApi;
 pub use filter::EthFilter;
 pub use pubsub::EthPubSub;
 
@@ -1,34 +1,60 @@
 //! `eth_` `PubSub` RPC handler implementation
 
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    sync::{Arc, Once},
+};
 
-use alloy_primitives::{TxHash, U256};
+use alloy_consensus::BlockHeader;
+use alloy_eips::{BlockNumHash, BlockNumberOrTag};
+use alloy_primitives::{BlockNumber, TxHash, B256, U256};
 use alloy_rpc_types_eth::{
     pubsub::{Params, PubSubSyncStatus, SubscriptionKind, SyncStatusMetadata},
-    Filter, Header, Log,
+    Filter, FilterBlockOption, Header, Log,
 };
 use futures::StreamExt;
 use jsonrpsee::{
     server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink, SubscriptionSink,
 };
+use parking_lot::Mutex;
 use reth_chain_state::CanonStateSubscriptions;
+use reth_execution_types::Chain;
 use reth_network_api::NetworkInfo;
-use reth_primitives_traits::NodePrimitives;
+use reth_primitives_traits::{BlockBody, HeaderTy, NodePrimitives, SealedBlock};
 use reth_rpc_eth_api::{
     pubsub::EthPubSubApiServer, EthApiTypes, RpcConvert, RpcNodeCore, RpcTransaction,
 };
-use reth_rpc_eth_types::logs_utils;
+use reth_rpc_eth_types::{
+    logs_utils::{self, ProviderOrBlock},
+    EthApiError,
+};
 use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
-use reth_storage_api::BlockNumReader;
+use reth_storage_api::{
+    errors::provider::{ProviderError, ProviderResult},
+    BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader,
+    HeaderProvider, ReceiptProvider,
+};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{NewTransactionEvent, PoolConsensusTx, TransactionPool};
 use serde::Serialize;
+use tokio::sync::oneshot;
 use tokio_stream::{
     wrappers::{BroadcastStream, ReceiverStream},
     Stream,
 };
 use tracing::error;
 
+/// The maximum number of blocks a `newHeads` or `logs` subscription can be resumed from.
+pub const MAX_SUBSCRIPTION_BACKFILL_BLOCKS: u64 = 10_000;
+
+/// The number of most recent blocks delivered to a resumed subscription that are tracked, to
+/// revert them if they are reorged out.
+const DELIVERED_BLOCKS_WINDOW: usize = 256;
+
+/// The number of most recent blocks reorged out of the canonical chain that are tracked, to emit
+/// their logs with `removed: true` to subscriptions resumed from them.
+const REORGED_BLOCKS_WINDOW: usize = 256;
+
 /// `Eth` pubsub RPC implementation.
 ///
 /// This handles `eth_subscribe` RPC calls.
@@ -50,7 +76,12 @@ impl<Eth> EthPubSub<Eth> {
 
     /// Creates a new, shareable instance.
     pub fn with_spawner(eth_api: Eth, subscription_task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = EthPubSubInner { eth_api, subscription_task_spawner };
+        let inner = EthPubSubInner {
+            eth_api,
+            subscription_task_spawner,
+            reorged_blocks: Default::default(),
+            reorged_blocks_recorder: Once::new(),
+        };
         Self { inner: Arc::new(inner) }
     }
 }
@@ -101,6 +132,10 @@ where
         kind: SubscriptionKind,
         params: Option<Params>,
     ) -> Result<(), ErrorObject<'static>> {
+        if let Some((subscription, from)) = resumed_subscription(&kind, params.as_ref()) {
+            return self.inner.pipe_resumed(accepted_sink, subscription, from).await
+        }
+
         match kind {
             SubscriptionKind::NewHeads => {
                 pipe_from_stream(accepted_sink, self.new_headers_stream()).await
@@ -223,13 +258,30 @@ where
         kind: SubscriptionKind,
         params: Option<Params>,
     ) -> jsonrpsee::core::SubscriptionResult {
+        // record reorged blocks from the first subscription on, so subscriptions can be resumed
+        // from the blocks they received
+        self.inner.record_reorged_blocks();
+
+        // reject resumed subscriptions that can't be served before accepting them
+        if let Some((subscription, from)) = resumed_subscription(&kind, params.as_ref()) {
+            if let Err(err) = self.inner.resume_start(&subscription, from) {
+                pending.reject(err).await;
+                return Ok(())
+            }
+        }
+
         let sink = pending.accept().await?;
         let pubsub = self.clone();
+        let (tx, rx) = oneshot::channel();
         self.inner.subscription_task_spawner.spawn(Box::pin(async move {
-            let _ = pubsub.handle_accepted(sink, kind, params).await;
+            let _ = tx.send(pubsub.handle_accepted(sink, kind, params).await);
         }));
 
-        Ok(())
+        // notify the subscriber if the subscription ends with an error
+        match rx.await {
+            Ok(Err(err)) => Err(err.message().to_owned().into()),
+            _ => Ok(()),
+        }
     }
 }
 
@@ -287,6 +339,207 @@ where
     }
 }
 
+/// Where a subscription is resumed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResumeFrom {
+    /// From a past block, set with the `fromBlock` param
+    Block(BlockNumberOrTag),
+    /// After the last block received before disconnecting, set with the `blockHash` param
+    ///
+    /// If that block was reorged out in the meantime, the logs of the reorged blocks are emitted
+    /// with `removed: true` first.
+    After(B256),
+}
+
+/// Returns where a subscription is resumed from, if the `fromBlock` param is set to a past block
+/// or the `blockHash` param is set.
+fn resume_from(filter: &Filter) -> Option<ResumeFrom> {
+    match filter.block_option {
+        FilterBlockOption::Range { from_block: Some(from_block), .. }
+            if !matches!(from_block, BlockNumberOrTag::Latest | BlockNumberOrTag::Pending) =>
+        {
+            Some(ResumeFrom::Block(from_block))
+        }
+        FilterBlockOption::AtBlockHash(hash) => Some(ResumeFrom::After(hash)),
+        _ => None,
+    }
+}
+
+/// Returns the subscription and where it is resumed from, if a `newHeads` or `logs` subscription
+/// is resumed with the `fromBlock` or `blockHash` param.
+fn resumed_subscription(
+    kind: &SubscriptionKind,
+    params: Option<&Params>,
+) -> Option<(ResumedSubscription, ResumeFrom)> {
+    let Some(Params::Logs(filter)) = params else { return None };
+    let from = resume_from(filter)?;
+    match kind {
+        SubscriptionKind::NewHeads => Some((ResumedSubscription::NewHeads, from)),
+        SubscriptionKind::Logs => Some((ResumedSubscription::Logs(*filter.clone()), from)),
+        _ => None,
+    }
+}
+
+/// A `newHeads` or `logs` subscription that is resumed from a past block.
+#[derive(Debug)]
+enum ResumedSubscription {
+    /// `newHeads` subscription
+    NewHeads,
+    /// `logs` subscription with the filter
+    Logs(Filter),
+}
+
+/// An item of a resumed subscription.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum ResumedItem<H> {
+    /// Header of a `newHeads` subscription
+    Header(Header<H>),
+    /// Log of a `logs` subscription
+    Log(Log),
+}
+
+impl<H> ResumedItem<H> {
+    /// Returns the item that is emitted when the block of this item is reorged out, if any.
+    fn reverted(&self) -> Option<Self> {
+        match self {
+            Self::Header(_) => None,
+            Self::Log(log) => Some(Self::Log(Log { removed: true, ..log.clone() })),
+        }
+    }
+}
+
+/// A block and the items of a resumed subscription it contains.
+type BlockItems<H> = (BlockNumHash, Vec<ResumedItem<H>>);
+
+/// The most recent canonical blocks delivered to a resumed subscription, with the items that
+/// revert them.
+///
+/// This delivers every block exactly once, and reverts delivered blocks that are reorged out.
+#[derive(Debug)]
+struct DeliveredBlocks<T> {
+    blocks: BTreeMap<BlockNumber, (B256, Vec<T>)>,
+}
+
+impl<T> Default for DeliveredBlocks<T> {
+    fn default() -> Self {
+        Self { blocks: BTreeMap::new() }
+    }
+}
+
+impl<T> DeliveredBlocks<T> {
+    /// Returns the hash of the delivered block with the given number.
+    fn hash(&self, number: BlockNumber) -> Option<B256> {
+        self.blocks.get(&number).map(|(hash, _)| *hash)
+    }
+
+    /// Returns the lowest delivered block that is not canonical anymore.
+    fn first_reorged(
+        &self,
+        canonical_hash: impl Fn(BlockNumber) -> ProviderResult<Option<B256>>,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        for (number, (hash, _)) in &self.blocks {
+            if canonical_hash(*number)? != Some(*hash) {
+                return Ok(Some(*number))
+            }
+        }
+        Ok(None)
+    }
+
+    /// Records a delivered block, with the items that revert it.
+    fn insert(&mut self, block: BlockNumHash, reverts: Vec<T>) {
+        self.blocks.insert(block.number, (block.hash, reverts));
+        while self.blocks.len() > DELIVERED_BLOCKS_WINDOW {
+            self.blocks.pop_first();
+        }
+    }
+
+    /// Removes the given block if it was delivered, and returns the items that revert it.
+    fn revert(&mut self, block: BlockNumHash) -> Vec<T> {
+        if self.hash(block.number) != Some(block.hash) {
+            return Vec::new()
+        }
+        self.blocks.remove(&block.number).map(|(_, reverts)| reverts).unwrap_or_default()
+    }
+
+    /// Removes all delivered blocks from the given number, and returns the items that revert them.
+    fn revert_from(&mut self, number: BlockNumber) -> Vec<T> {
+        self.blocks.split_off(&number).into_values().flat_map(|(_, reverts)| reverts).collect()
+    }
+}
+
+/// A block that was reorged out of the canonical chain.
+#[derive(Debug)]
+struct ReorgedBlock {
+    /// Hash of the parent block
+    parent_hash: B256,
+    /// All logs of the block, with `removed: true`
+    logs: Vec<Log>,
+}
+
+/// The most recent blocks that were reorged out of the canonical chain, by hash.
+#[derive(Debug, Default)]
+struct ReorgedBlocks {
+    blocks: HashMap<B256, ReorgedBlock>,
+    /// Hashes of the blocks in the order they were reorged out
+    order: VecDeque<B256>,
+}
+
+impl ReorgedBlocks {
+    /// Records the blocks of a chain that was reorged out.
+    fn insert_chain<N: NodePrimitives>(&mut self, chain: &Chain<N>) {
+        for block in chain.blocks_iter() {
+            let num_hash = block.num_hash();
+            let logs = logs_utils::matching_block_logs_with_tx_hashes(
+                &Filter::default(),
+                num_hash,
+                block.timestamp(),
+                block
+                    .body()
+                    .transaction_hashes_iter()
+                    .copied()
+                    .zip(chain.execution_outcome().receipts_by_block(num_hash.number)),
+                true,
+            );
+            self.insert(num_hash.hash, ReorgedBlock { parent_hash: block.parent_hash(), logs });
+        }
+    }
+
+    /// Records a reorged block.
+    fn insert(&mut self, hash: B256, block: ReorgedBlock) {
+        if self.blocks.insert(hash, block).is_none() {
+            self.order.push_back(hash);
+        }
+        while self.order.len() > REORGED_BLOCKS_WINDOW {
+            if let Some(hash) = self.order.pop_front() {
+                self.blocks.remove(&hash);
+            }
+        }
+    }
+
+    /// Returns the canonical block the given block forked from, with the logs of the reorged
+    /// blocks from the fork point to the given block, in block order.
+    ///
+    /// Returns `None` if the given block is neither canonical nor a tracked reorged block, or
+    /// forked off before the tracked reorged blocks.
+    fn fork_point(
+        &self,
+        mut hash: B256,
+        canonical_number: impl Fn(B256) -> ProviderResult<Option<BlockNumber>>,
+    ) -> ProviderResult<Option<(BlockNumber, Vec<Log>)>> {
+        let mut reorged = Vec::new();
+        loop {
+            if let Some(number) = canonical_number(hash)? {
+                let logs = reorged.iter().rev().flat_map(|block| block.logs.iter().cloned());
+                return Ok(Some((number, logs.collect())))
+            }
+            let Some(block) = self.blocks.get(&hash) else { return Ok(None) };
+            reorged.push(block);
+            hash = block.parent_hash;
+        }
+    }
+}
+
 impl<Eth> std::fmt::Debug for EthPubSub<Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("EthPubSub").finish_non_exhaustive()
@@ -294,12 +547,15 @@ impl<Eth> std::fmt::Debug for EthPubSub<Eth> {
 }
 
 /// Container type `EthPubSub`
-#[derive(Clone)]
 struct EthPubSubInner<EthApi> {
     /// The `eth` API.
     eth_api: EthApi,
     /// The type that's used to spawn subscription tasks.
     subscription_task_spawner: Box<dyn TaskSpawner>,
+    /// The most recent blocks that were reorged out of the canonical chain.
+    reorged_blocks: Arc<Mutex<ReorgedBlocks>>,
+    /// Starts the task that records reorged blocks once.
+    reorged_blocks_recorder: Once,
 }
 
 // == impl EthPubSubInner ===
@@ -368,6 +624,26 @@ where
         })
     }
 
+    /// Starts recording the blocks that are reorged out of the canonical chain, unless it's
+    /// already started.
+    ///
+    /// The task ends with the first canonical state notification after the pubsub handler is
+    /// dropped.
+    fn record_reorged_blocks(&self) {
+        self.reorged_blocks_recorder.call_once(|| {
+            let reorged_blocks = Arc::downgrade(&self.reorged_blocks);
+            let mut canon_state = self.eth_api.provider().canonical_state_stream();
+            self.subscription_task_spawner.spawn(Box::pin(async move {
+                while let Some(notification) = canon_state.next().await {
+                    let Some(reorged_blocks) = reorged_blocks.upgrade() else { break };
+                    if let Some(reverted) = notification.reverted() {
+                        reorged_blocks.lock().insert_chain(&reverted);
+                    }
+                }
+            }));
+        });
+    }
+
     /// Returns a stream that yields all logs that match the given filter.
     fn log_stream(&self, filter: Filter) -> impl Stream<Item = Log> {
         BroadcastStream::new(self.eth_api.provider().subscribe_to_canonical_state())
@@ -387,3 +663,516 @@ where
             })
     }
 }
+
+impl<Eth> EthPubSubInner<Eth>
+where
+    Eth: RpcNodeCore,
+{
+    /// Returns the first block of a resumed subscription, and the logs of the blocks that were
+    /// reorged out since the block it is resumed after, with `removed: true`.
+    ///
+    /// Fails if the first block is not canonical, too far behind the tip, or its history is
+    /// pruned, or if the block it is resumed after is unknown.
+    fn resume_start(
+        &self,
+        subscription: &ResumedSubscription,
+        from: ResumeFrom,
+    ) -> Result<(BlockNumber, Vec<Log>), ErrorObject<'static>> {
+        let provider = self.eth_api.provider();
+        let internal_err = |err: ProviderError| internal_rpc_err(err.to_string());
+        let tip = provider.best_block_number().map_err(internal_err)?;
+        let (start, removed) = match from {
+            ResumeFrom::Block(from_block) => {
+                let start = provider
+                    .convert_block_number(from_block)
+                    .map_err(internal_err)?
+                    .filter(|from| *from <= tip + 1)
+                    .ok_or_else(|| invalid_params_rpc_err("fromBlock is not a canonical block"))?;
+                (start, Vec::new())
+            }
+            ResumeFrom::After(hash) => {
+                let canonical_number = |hash| -> ProviderResult<Option<BlockNumber>> {
+                    let Some(number) = provider.block_number(hash)? else { return Ok(None) };
+                    Ok((provider.block_hash(number)? == Some(hash)).then_some(number))
+                };
+                let (fork_point, removed) = self
+                    .reorged_blocks
+                    .lock()
+                    .fork_point(hash, canonical_number)
+                    .map_err(internal_err)?
+                    .ok_or_else(|| invalid_params_rpc_err("blockHash is not a known block"))?;
+                (fork_point + 1, removed)
+            }
+        };
+        if tip.saturating_sub(start) >= MAX_SUBSCRIPTION_BACKFILL_BLOCKS {
+            return Err(invalid_params_rpc_err(format!(
+                "resumed block is more than {MAX_SUBSCRIPTION_BACKFILL_BLOCKS} blocks behind tip"
+            )))
+        }
+        // history is pruned from the oldest block, so if the first block is available, the
+        // following blocks are as well
+        if start <= tip {
+            self.database_items(subscription, start)?;
+        }
+        let removed = match subscription {
+            ResumedSubscription::NewHeads => Vec::new(),
+            ResumedSubscription::Logs(filter) => {
+                removed.into_iter().filter(|log| filter.matches(&log.inner)).collect()
+            }
+        };
+        Ok((start, removed))
+    }
+
+    /// Pipes the items of a resumed subscription to the subscription sink.
+    ///
+    /// If the subscription is resumed after a block that was reorged out in the meantime, the
+    /// logs of the reorged blocks are emitted with `removed: true` first. This then delivers the
+    /// blocks from the first block to the tip from the database, and then follows the canonical
+    /// state notifications. Blocks are delivered exactly once while the subscription is open,
+    /// and the logs of delivered blocks that are reorged out are emitted again with
+    /// `removed: true`.
+    ///
+    /// Reorgs are only detected against the block a subscription is resumed after, so resuming
+    /// with `fromBlock` doesn't emit the logs of blocks that were reorged out while
+    /// disconnected.
+    async fn pipe_resumed(
+        &self,
+        sink: SubscriptionSink,
+        mut subscription: ResumedSubscription,
+        from: ResumeFrom,
+    ) -> Result<(), ErrorObject<'static>> {
+        let provider = self.eth_api.provider();
+        // subscribe before reading the database, so no block is missed in between
+        let mut canon_state = BroadcastStream::new(provider.subscribe_to_canonical_state());
+
+        let (start, removed) = self.resume_start(&subscription, from)?;
+        if !send_items(&sink, removed).await? {
+            return Ok(())
+        }
+        let mut next = start;
+        if let ResumedSubscription::Logs(filter) = &mut subscription {
+            *filter = filter.clone().from_block(next);
+        }
+
+        let mut delivered = DeliveredBlocks::default();
+        loop {
+            // catch up with the canonical chain from the database, reverting the delivered blocks
+            // that were reorged out in the meantime
+            let reorged = delivered
+                .first_reorged(|number| provider.block_hash(number))
+                .map_err(|err| internal_rpc_err(err.to_string()))?;
+            if let Some(reorged) = reorged {
+                next = next.min(reorged);
+            }
+            let tip =
+                provider.best_block_number().map_err(|err| internal_rpc_err(err.to_string()))?;
+            while next <= tip {
+                let Some((block, items)) = self.database_items(&subscription, next)? else { break };
+                if !deliver(&sink, &mut delivered, &mut next, block, items).await? {
+                    return Ok(())
+                }
+            }
+
+            // follow the canonical state notifications
+            loop {
+                let notification = tokio::select! {
+                    _ = sink.closed() => return Ok(()),
+                    notification = canon_state.next() => notification,
+                };
+                let notification = match notification {
+                    Some(Ok(notification)) => notification,
+                    // lagged behind the notifications, catch up from the database
+                    Some(Err(_)) => break,
+                    None => return Ok(()),
+                };
+
+                if let Some(reverted) = notification.reverted() {
+                    // the committed blocks that replace the reverted blocks are delivered again
+                    next = next.min((*reverted.range().start()).max(start));
+                    let reverts = reverted
+                        .blocks_iter()
+                        .flat_map(|block| delivered.revert(block.clone_sealed_header().num_hash()))
+                        .collect::<Vec<_>>();
+                    if !send_items(&sink, reverts).await? {
+                        return Ok(())
+                    }
+                }
+
+                let committed = notification.committed();
+                if *committed.range().start() > next {
+                    // missed blocks, catch up from the database
+                    break
+                }
+                for (block, items) in self.chain_items(&subscription, &committed) {
+                    if !deliver(&sink, &mut delivered, &mut next, block, items).await? {
+                        return Ok(())
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the items of the canonical block with the given number from the database.
+    ///
+    /// Returns `None` if the block doesn't exist, and an error if its history is pruned.
+    fn database_items(
+        &self,
+        subscription: &ResumedSubscription,
+        number: BlockNumber,
+    ) -> Result<Option<BlockItems<HeaderTy<Eth::Primitives>>>, ErrorObject<'static>> {
+        let provider = self.eth_api.provider();
+        let internal_err = |err: ProviderError| internal_rpc_err(err.to_string());
+        let Some(header) = provider.sealed_header(number).map_err(internal_err)? else {
+            return Ok(None)
+        };
+        let num_hash = header.num_hash();
+        match subscription {
+            ResumedSubscription::NewHeads => {
+                let block = provider
+                    .block_by_number(number)
+                    .map_err(internal_err)?
+                    .ok_or(EthApiError::PrunedHistoryUnavailable)?;
+                let block = SealedBlock::seal_slow(block);
+                let header = Header::from_consensus(
+                    block.clone_sealed_header().into(),
+                    None,
+                    Some(U256::from(block.rlp_length())),
+                );
+                Ok(Some((num_hash, vec![ResumedItem::Header(header)])))
+            }
+            ResumedSubscription::Logs(filter) => {
+                let mut logs = Vec::new();
+                if filter.matches_bloom(header.logs_bloom()) {
+                    // the receipts of pruned blocks are missing
+                    let tx_count = provider
+                        .block_body_indices(number)
+                        .map_err(internal_err)?
+                        .map(|indices| indices.tx_count());
+                    let receipts = provider
+                        .receipts_by_block(number.into())
+                        .map_err(internal_err)?
+                        .filter(|receipts| Some(receipts.len() as u64) == tx_count)
+                        .ok_or(EthApiError::PrunedHistoryUnavailable)?;
+                    logs_utils::append_matching_block_logs(
+                        &mut logs,
+                        ProviderOrBlock::Provider(provider),
+                        filter,
+                        num_hash,
+                        &receipts,
+                        false,
+                        header.timestamp(),
+                    )
+                    .map_err(internal_err)?;
+                }
+                Ok(Some((num_hash, logs.into_iter().map(ResumedItem::Log).collect())))
+            }
+        }
+    }
+
+    /// Returns the items of the blocks of a canonical chain.
+    fn chain_items(
+        &self,
+        subscription: &ResumedSubscription,
+        chain: &Chain<Eth::Primitives>,
+    ) -> Vec<BlockItems<HeaderTy<Eth::Primitives>>> {
+        match subscription {
+            ResumedSubscription::NewHeads => chain
+                .blocks_iter()
+                .map(|block| {
+                    let header = block.clone_sealed_header();
+                    let num_hash = header.num_hash();
+                    let header = Header::from_consensus(
+                        header.into(),
+                        None,
+                        Some(U256::from(block.rlp_length())),
+                    );
+                    (num_hash, vec![ResumedItem::Header(header)])
+                })
+                .collect(),
+            ResumedSubscription::Logs(filter) => chain
+                .receipts_with_attachment()
+                .into_iter()
+                .map(|block_receipts| {
+                    let logs = logs_utils::matching_block_logs_with_tx_hashes(
+                        filter,
+                        block_receipts.block,
+                        block_receipts.timestamp,
+                        block_receipts.tx_receipts.iter().map(|(tx, receipt)| (*tx, receipt)),
+                        false,
+                    );
+                    (block_receipts.block, logs.into_iter().map(ResumedItem::Log).collect())
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Delivers the items of a canonical block to the subscription sink, unless the block was already
+/// delivered, and advances `next` past it.
+///
+/// If another block with the same number was delivered, the blocks from that number are reverted
+/// first. Returns `false` if the subscription is closed.
+async fn deliver<H: Serialize + Clone>(
+    sink: &SubscriptionSink,
+    delivered: &mut DeliveredBlocks<ResumedItem<H>>,
+    next: &mut BlockNumber,
+    block: BlockNumHash,
+    items: Vec<ResumedItem<H>>,
+) -> Result<bool, ErrorObject<'static>> {
+    let mut messages = match delivered.hash(block.number) {
+        Some(hash) if hash == block.hash => return Ok(true),
+        Some(_) => delivered.revert_from(block.number),
+        // older than the tracked blocks, so it was delivered already
+        None if block.number < *next => return Ok(true),
+        None => Vec::new(),
+    };
+
+    delivered.insert(block, items.iter().filter_map(ResumedItem::reverted).collect());
+    *next = block.number + 1;
+    messages.extend(items);
+    send_items(sink, messages).await
+}
+
+/// Sends the items to the subscription sink, returns `false` if the subscription is closed.
+async fn send_items<T: Serialize>(
+    sink: &SubscriptionSink,
+    items: Vec<T>,
+) -> Result<bool, ErrorObject<'static>> {
+    for item in items {
+        let msg = SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &item)
+            .map_err(SubscriptionSerializeError::new)?;
+        if sink.send(msg).await.is_err() {
+            return Ok(false)
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthApiBuilder;
+    use jsonrpsee::RpcModule;
+    use reth_chain_state::CanonStateNotification;
+    use reth_chainspec::ChainSpecProvider;
+    use reth_ethereum_primitives::{Block, EthPrimitives};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_execution_types::ExecutionOutcome;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives_traits::RecoveredBlock;
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_transaction_pool::test_utils::testing_pool;
+    use tokio::sync::broadcast;
+
+    fn block(number: BlockNumber, hash: u8) -> BlockNumHash {
+        BlockNumHash::new(number, B256::with_last_byte(hash))
+    }
+
+    fn test_block(number: BlockNumber, parent_hash: B256, timestamp: u64) -> RecoveredBlock<Block> {
+        let header =
+            alloy_consensus::Header { number, parent_hash, timestamp, ..Default::default() };
+        let block = Block { header, body: Default::default() };
+        RecoveredBlock::new_sealed(SealedBlock::seal_slow(block), Vec::new())
+    }
+
+    /// Returns a pubsub module over a chain of three blocks, and the sender of its canonical state
+    /// notifications.
+    fn test_pubsub() -> (
+        RpcModule<()>,
+        Vec<RecoveredBlock<Block>>,
+        broadcast::Sender<CanonStateNotification<EthPrimitives>>,
+    ) {
+        let (canon_state_tx, _) = broadcast::channel(16);
+        let provider = MockEthProvider::default().with_canon_state_sender(canon_state_tx.clone());
+        let mut blocks = vec![test_block(0, B256::ZERO, 0)];
+        for number in 1..=2 {
+            blocks.push(test_block(number, blocks[number as usize - 1].hash(), number));
+        }
+        for block in &blocks {
+            provider.add_block(block.hash(), block.clone().into_block());
+        }
+
+        let eth_api = EthApiBuilder::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            EthEvmConfig::new(provider.chain_spec()),
+        )
+        .build();
+        (EthPubSub::new(eth_api).into_rpc().remove_context(), blocks, canon_state_tx)
+    }
+
+    #[tokio::test]
+    async fn resumed_new_heads_follow_reorgs() {
+        let (module, blocks, canon_state_tx) = test_pubsub();
+        let mut sub = module
+            .subscribe_unbounded(
+                "eth_subscribe",
+                ("newHeads", serde_json::json!({"fromBlock": "0x1"})),
+            )
+            .await
+            .unwrap();
+        for block in &blocks[1..] {
+            let (header, _) = sub.next::<Header>().await.unwrap().unwrap();
+            assert_eq!(header.hash, block.hash());
+        }
+
+        // the block replacing a reorged block is delivered again
+        let reorged = test_block(2, blocks[1].hash(), 3);
+        canon_state_tx
+            .send(CanonStateNotification::Reorg {
+                old: Arc::new(Chain::from_block(
+                    blocks[2].clone(),
+                    ExecutionOutcome::default(),
+                    None,
+                )),
+                new: Arc::new(Chain::from_block(
+                    reorged.clone(),
+                    ExecutionOutcome::default(),
+                    None,
+                )),
+            })
+            .unwrap();
+        let (header, _) = sub.next::<Header>().await.unwrap().unwrap();
+        assert_eq!(header.hash, reorged.hash());
+
+        // blocks following the reorged block are delivered
+        let next = test_block(3, reorged.hash(), 4);
+        canon_state_tx
+            .send(CanonStateNotification::Commit {
+                new: Arc::new(Chain::from_block(next.clone(), ExecutionOutcome::default(), None)),
+            })
+            .unwrap();
+        let (header, _) = sub.next::<Header>().await.unwrap().unwrap();
+        assert_eq!(header.hash, next.hash());
+    }
+
+    #[tokio::test]
+    async fn reject_resuming_pruned_logs() {
+        let (module, _, _) = test_pubsub();
+        // the receipts of block 1 are missing
+        let err = module
+            .subscribe_unbounded("eth_subscribe", ("logs", serde_json::json!({"fromBlock": "0x1"})))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("pruned history unavailable"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn resume_new_heads_after_block_hash() {
+        let (module, blocks, _) = test_pubsub();
+        let mut sub = module
+            .subscribe_unbounded(
+                "eth_subscribe",
+                ("newHeads", serde_json::json!({"blockHash": blocks[1].hash()})),
+            )
+            .await
+            .unwrap();
+        let (header, _) = sub.next::<Header>().await.unwrap().unwrap();
+        assert_eq!(header.hash, blocks[2].hash());
+
+        let err = module
+            .subscribe_unbounded(
+                "eth_subscribe",
+                ("newHeads", serde_json::json!({"blockHash": B256::with_last_byte(1)})),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("blockHash is not a known block"), "{err}");
+    }
+
+    #[test]
+    fn resume_from_past_blocks() {
+        assert_eq!(resume_from(&Filter::new().from_block(10)), Some(ResumeFrom::Block(10.into())));
+        assert_eq!(
+            resume_from(&Filter::new().from_block(BlockNumberOrTag::Earliest)),
+            Some(ResumeFrom::Block(BlockNumberOrTag::Earliest))
+        );
+        assert_eq!(resume_from(&Filter::new().from_block(BlockNumberOrTag::Latest)), None);
+        assert_eq!(resume_from(&Filter::new()), None);
+        assert_eq!(
+            resume_from(&Filter::new().at_block_hash(B256::ZERO)),
+            Some(ResumeFrom::After(B256::ZERO))
+        );
+    }
+
+    #[test]
+    fn fork_point_of_reorged_blocks() {
+        let log = |block: u8| Log {
+            block_hash: Some(B256::with_last_byte(block)),
+            removed: true,
+            ..Default::default()
+        };
+        // blocks 3 and 4 were reorged out of a chain that forked from block 2
+        let mut reorged = ReorgedBlocks::default();
+        reorged.insert(
+            B256::with_last_byte(3),
+            ReorgedBlock { parent_hash: B256::with_last_byte(2), logs: vec![log(3)] },
+        );
+        reorged.insert(
+            B256::with_last_byte(4),
+            ReorgedBlock { parent_hash: B256::with_last_byte(3), logs: vec![log(4), log(4)] },
+        );
+        let canonical_number = |hash: B256| -> ProviderResult<Option<BlockNumber>> {
+            Ok((hash[31] <= 2).then_some(hash[31] as u64))
+        };
+
+        let (fork_point, removed) =
+            reorged.fork_point(B256::with_last_byte(4), canonical_number).unwrap().unwrap();
+        assert_eq!(fork_point, 2);
+        assert_eq!(removed, vec![log(3), log(4), log(4)]);
+
+        let (fork_point, removed) =
+            reorged.fork_point(B256::with_last_byte(1), canonical_number).unwrap().unwrap();
+        assert_eq!(fork_point, 1);
+        assert!(removed.is_empty());
+
+        assert!(reorged.fork_point(B256::with_last_byte(5), canonical_number).unwrap().is_none());
+    }
+
+    #[test]
+    fn track_recent_reorged_blocks() {
+        let mut reorged = ReorgedBlocks::default();
+        for hash in 0..REORGED_BLOCKS_WINDOW as u64 + 10 {
+            reorged.insert(
+                B256::from(U256::from(hash)),
+                ReorgedBlock { parent_hash: B256::ZERO, logs: Vec::new() },
+            );
+        }
+        assert_eq!(reorged.blocks.len(), REORGED_BLOCKS_WINDOW);
+        assert!(!reorged.blocks.contains_key(&B256::from(U256::from(9))));
+        assert!(reorged.blocks.contains_key(&B256::from(U256::from(10))));
+    }
+
+    #[test]
+    fn revert_delivered_blocks() {
+        let mut delivered = DeliveredBlocks::default();
+        delivered.insert(block(1, 1), vec![10]);
+        delivered.insert(block(2, 2), vec![20, 21]);
+        delivered.insert(block(3, 3), vec![30]);
+
+        // blocks that weren't delivered aren't reverted
+        assert!(delivered.revert(block(3, 4)).is_empty());
+        assert_eq!(delivered.revert(block(3, 3)), vec![30]);
+        assert_eq!(delivered.hash(3), None);
+
+        assert_eq!(delivered.revert_from(2), vec![20, 21]);
+        assert_eq!(delivered.hash(1), Some(B256::with_last_byte(1)));
+
+        let reorged = delivered.first_reorged(|_| Ok(Some(B256::with_last_byte(1)))).unwrap();
+        assert_eq!(reorged, None);
+        let reorged = delivered.first_reorged(|_| Ok(None)).unwrap();
+        assert_eq!(reorged, Some(1));
+    }
+
+    #[test]
+    fn track_recent_delivered_blocks() {
+        let mut delivered = DeliveredBlocks::default();
+        for number in 0..DELIVERED_BLOCKS_WINDOW as u64 + 10 {
+            delivered.insert(block(number, 1), Vec::<()>::new());
+        }
+        assert_eq!(delivered.blocks.len(), DELIVERED_BLOCKS_WINDOW);
+        assert_eq!(delivered.hash(9), None);
+        assert!(delivered.hash(10).is_some());
+    }
+}
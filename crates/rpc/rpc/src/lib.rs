@@ -49,11 +49,13 @@ mod web3;
 pub use admin::AdminApi;
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
-pub use eth::{helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthFilter, EthPubSub};
+pub use eth::{
+    helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthExtApi, EthFilter, EthPubSub,
+};
 pub use miner::MinerApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
-pub use reth::RethApi;
+pub use reth::{RethAdminApi, RethApi};
 pub use reth_rpc_convert::RpcTypes;
 pub use rpc::RPCApi;
 pub use trace::TraceApi;
@@ -9,11 +9,12 @@ use jsonrpsee_types::ErrorObject;
 use reth_chain_state::{CanonStateNotificationStream, CanonStateSubscriptions};
 use reth_errors::RethResult;
 use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::RethApiServer;
+use reth_rpc_api::{RethAdminApiServer, RethApiServer};
 use reth_rpc_eth_types::{EthApiError, EthResult};
-use reth_rpc_server_types::result::internal_rpc_err;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_storage_api::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
 use reth_tasks::TaskSpawner;
+use reth_tracing::{LogFilterHandle, LogSink};
 use tokio::sync::oneshot;
 
 /// `reth` API implementation.
@@ -170,3 +171,35 @@ struct RethApiInner<Provider> {
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
 }
+
+/// `reth` API methods that control the node, served only with the `admin` namespace.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct RethAdminApi;
+
+impl RethAdminApi {
+    /// Create a new instance of the [`RethAdminApi`]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl RethAdminApiServer for RethAdminApi {
+    /// Handler for `reth_setLogFilter`
+    async fn reth_set_log_filter(&self, filter: String, sink: Option<String>) -> RpcResult<()> {
+        let sink = sink
+            .map(|sink| sink.parse::<LogSink>())
+            .transpose()
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        log_filter_handle()?
+            .set_filter(sink, &filter)
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))
+    }
+}
+
+/// Returns the handle to reload the log filters of the node.
+pub(crate) fn log_filter_handle() -> RpcResult<&'static LogFilterHandle> {
+    reth_tracing::log_filter_handle()
+        .ok_or_else(|| internal_rpc_err("log filters are not reloadable"))
+}
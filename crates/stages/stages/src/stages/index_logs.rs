@@ -0,0 +1,282 @@
+use super::load_history_indices;
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{Address, BlockNumber, B256};
+use reth_config::config::{EtlConfig, IndexLogsConfig};
+use reth_db_api::{
+    models::ShardedKey,
+    table::{Decode, Key},
+    tables,
+    transaction::DbTxMut,
+    BlockNumberList,
+};
+use reth_etl::Collector;
+use reth_provider::{DBProvider, HistoryWriter, PruneCheckpointReader, ReceiptProvider};
+use reth_prune_types::PruneSegment;
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::{collections::HashMap, hash::Hash, ops::RangeInclusive};
+use tracing::info;
+
+/// Number of blocks before pushing indices from cache to [`Collector`]
+const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
+
+/// Stage is indexing the addresses and topics of the logs in the receipts generated in
+/// [`ExecutionStage`][crate::stages::ExecutionStage]. For more information on index sharding take
+/// a look at [`tables::LogAddressHistory`] and [`tables::LogTopicHistory`].
+///
+/// The log index is optional and used to speed up `eth_getLogs`. Once the stage has run, the index
+/// is also extended when blocks are persisted outside of the pipeline.
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [`IndexLogsStage`].
+    pub const fn new(config: IndexLogsConfig, etl_config: EtlConfig) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexLogsStage
+where
+    Provider: DBProvider<Tx: DbTxMut> + HistoryWriter + ReceiptProvider + PruneCheckpointReader,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync the tables might contain leftovers of a previously dropped index. We clear
+        // them since it's faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::LogAddressHistory>()?;
+            provider.tx_ref().clear::<tables::LogTopicHistory>()?;
+        }
+
+        // There is nothing to index for blocks with pruned receipts.
+        let start = provider
+            .get_prune_checkpoint(PruneSegment::Receipts)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(*range.start(), |pruned| (pruned + 1).max(*range.start()));
+
+        info!(target: "sync::stages::index_logs::exec", ?first_sync, "Collecting indices");
+        let (addresses, topics) =
+            collect_log_indices(provider, start..=*range.end(), &self.etl_config)?;
+
+        info!(target: "sync::stages::index_logs::exec", "Loading indices into database");
+        load_history_indices::<_, tables::LogAddressHistory, _>(
+            provider,
+            addresses,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+        load_history_indices::<_, tables::LogTopicHistory, _>(
+            provider,
+            topics,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<B256>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        let mut receipts = Vec::new();
+        for block_number in range {
+            let block_receipts =
+                provider.receipts_by_block(block_number.into())?.unwrap_or_default();
+            receipts.push((block_number, block_receipts));
+        }
+        provider.unwind_log_indices(receipts.iter().flat_map(|(block_number, receipts)| {
+            receipts.iter().flat_map(|receipt| receipt.logs()).map(|log| (*block_number, log))
+        }))?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+/// Collects the log address and topic indices for a range of blocks and stores them in
+/// [`Collector`]s, the same way as
+/// [`collect_history_indices`](super::utils::collect_history_indices) does for changesets.
+fn collect_log_indices<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    etl_config: &EtlConfig,
+) -> Result<
+    (Collector<ShardedKey<Address>, BlockNumberList>, Collector<ShardedKey<B256>, BlockNumberList>),
+    StageError,
+>
+where
+    Provider: ReceiptProvider,
+{
+    let mut address_collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut topic_collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut address_cache = HashMap::<Address, Vec<u64>>::default();
+    let mut topic_cache = HashMap::<B256, Vec<u64>>::default();
+
+    let mut flush_counter = 0;
+    for block_number in range {
+        // Receipts are read one block at a time, since they could be partially pruned by the
+        // contract logs filter.
+        let receipts = provider.receipts_by_block(block_number.into())?.unwrap_or_default();
+        for log in receipts.iter().flat_map(|receipt| receipt.logs()) {
+            push_index(&mut address_cache, log.address, block_number);
+            for topic in log.topics() {
+                push_index(&mut topic_cache, *topic, block_number);
+            }
+        }
+
+        // Make sure we only flush the cache every DEFAULT_CACHE_THRESHOLD blocks.
+        flush_counter += 1;
+        if flush_counter > DEFAULT_CACHE_THRESHOLD {
+            flush_cache(&mut address_cache, &mut address_collector)?;
+            flush_cache(&mut topic_cache, &mut topic_collector)?;
+            flush_counter = 0;
+        }
+    }
+    flush_cache(&mut address_cache, &mut address_collector)?;
+    flush_cache(&mut topic_cache, &mut topic_collector)?;
+
+    Ok((address_collector, topic_collector))
+}
+
+/// Records that the block contains a log with the given address or topic.
+fn push_index<K: Eq + Hash>(cache: &mut HashMap<K, Vec<u64>>, key: K, block_number: u64) {
+    let blocks = cache.entry(key).or_default();
+    if blocks.last() != Some(&block_number) {
+        blocks.push(block_number);
+    }
+}
+
+/// Moves the cached indices to the [`Collector`], keyed by the highest block number in the list.
+fn flush_cache<K: Copy>(
+    cache: &mut HashMap<K, Vec<u64>>,
+    collector: &mut Collector<ShardedKey<K>, BlockNumberList>,
+) -> Result<(), StageError>
+where
+    ShardedKey<K>: Key,
+{
+    for (key, indices) in cache.drain() {
+        let last = indices.last().expect("qed");
+        collector.insert(
+            ShardedKey::new(key, *last),
+            BlockNumberList::new_pre_sorted(indices.iter().copied()),
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use alloy_primitives::{address, b256, Log};
+    use reth_db_api::{models::StoredBlockBodyIndices, table::Table};
+    use reth_ethereum_primitives::Receipt;
+    use reth_provider::DatabaseProviderFactory;
+    use std::collections::BTreeMap;
+
+    const ADDRESS: Address = address!("0x0000000000000000000000000000000000000001");
+    const TOPIC: B256 = b256!("0x0000000000000000000000000000000000000000000000000000000000000002");
+
+    fn setup(db: &TestStageDB, blocks_with_logs: &[BlockNumber], last_block: BlockNumber) {
+        db.commit(|tx| {
+            for block in 0..=last_block {
+                tx.put::<tables::BlockBodyIndices>(
+                    block,
+                    StoredBlockBodyIndices { first_tx_num: block, tx_count: 1 },
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+        db.insert_receipts((0..=last_block).map(|block| {
+            let logs = if blocks_with_logs.contains(&block) {
+                vec![Log::new_unchecked(ADDRESS, vec![TOPIC], Default::default())]
+            } else {
+                Vec::new()
+            };
+            (block, Receipt { logs, ..Default::default() })
+        }))
+        .unwrap();
+    }
+
+    fn table<T>(db: &TestStageDB) -> BTreeMap<T::Key, Vec<u64>>
+    where
+        T: Table<Value = BlockNumberList>,
+        T::Key: Default + Ord,
+    {
+        db.table::<T>().unwrap().into_iter().map(|(k, v)| (k, v.iter().collect())).collect()
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        setup(&db, &[1, 3, 5], 5);
+
+        let mut stage = IndexLogsStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let input = ExecInput { target: Some(5), checkpoint: None };
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(5), done: true });
+        provider.commit().unwrap();
+
+        assert_eq!(
+            table::<tables::LogAddressHistory>(&db),
+            BTreeMap::from([(ShardedKey::last(ADDRESS), vec![1, 3, 5])])
+        );
+        assert_eq!(
+            table::<tables::LogTopicHistory>(&db),
+            BTreeMap::from([(ShardedKey::last(TOPIC), vec![1, 3, 5])])
+        );
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(5), unwind_to: 2, ..Default::default() };
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(2) });
+        provider.commit().unwrap();
+
+        assert_eq!(
+            table::<tables::LogAddressHistory>(&db),
+            BTreeMap::from([(ShardedKey::last(ADDRESS), vec![1])])
+        );
+        assert_eq!(
+            table::<tables::LogTopicHistory>(&db),
+            BTreeMap::from([(ShardedKey::last(TOPIC), vec![1])])
+        );
+    }
+}
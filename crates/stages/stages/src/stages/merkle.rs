@@ -226,6 +226,8 @@ where
                 self.save_execution_checkpoint(provider, None)?;
                 provider.tx_ref().clear::<tables::AccountsTrie>()?;
                 provider.tx_ref().clear::<tables::StoragesTrie>()?;
+                // The rebuilt trie can't be reverted with the existing trie changesets.
+                provider.clear_trie_changesets()?;
 
                 None
             }
@@ -288,11 +290,14 @@ where
                     "Processing chunk"
                 );
                 let (root, updates) =
-                StateRoot::incremental_root_with_updates(provider.tx_ref(), chunk_range)
+                StateRoot::incremental_root_with_updates(provider.tx_ref(), chunk_range.clone())
                     .map_err(|e| {
                         error!(target: "sync::stages::merkle", %e, ?current_block_number, ?to_block, "Incremental state root failed! {INVALID_STATE_ROOT_ERROR_MESSAGE}");
                         StageError::Fatal(Box::new(e))
                     })?;
+                // The trie is updated for the whole chunk at once, so its changes are recorded
+                // for the first block of the chunk.
+                provider.write_batched_trie_changesets(chunk_range, &updates)?;
                 provider.write_trie_updates(&updates)?;
                 final_root = Some(root);
             }
@@ -352,6 +357,7 @@ where
         if input.unwind_to == 0 {
             tx.clear::<tables::AccountsTrie>()?;
             tx.clear::<tables::StoragesTrie>()?;
+            provider.clear_trie_changesets()?;
 
             entities_checkpoint.processed = 0;
 
@@ -376,6 +382,7 @@ where
             validate_state_root(block_root, SealedHeader::seal_slow(target), input.unwind_to)?;
 
             // Validation passed, apply unwind changes to the database.
+            provider.remove_trie_changesets_above(input.unwind_to)?;
             provider.write_trie_updates(&updates)?;
 
             // TODO(alexey): update entities checkpoint
@@ -79,6 +79,9 @@ pub mod table;
 pub mod tables;
 pub use tables::*;
 
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_suite;
+
 /// Transaction database traits.
 pub mod transaction;
 
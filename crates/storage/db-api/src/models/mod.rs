@@ -222,6 +222,7 @@ impl_compression_for_compact!(
     StoredNibbles,
     StoredNibblesSubKey,
     StorageTrieEntry,
+    TrieChangeSetsEntry,
     StoredBlockBodyIndices,
     StoredBlockOmmers<H>,
     StoredBlockWithdrawals,
@@ -18,7 +18,7 @@ pub use raw::{RawDupSort, RawKey, RawTable, RawValue, TableRawRow};
 
 use crate::{
     models::{
-        accounts::BlockNumberAddress,
+        accounts::{BlockNumberAddress, BlockNumberHashedAddress},
         blocks::{HeaderHash, StoredBlockOmmers},
         storage_sharded_key::StorageShardedKey,
         AccountBeforeTx, ClientVersion, CompactU256, IntegerList, ShardedKey,
@@ -32,7 +32,9 @@ use reth_ethereum_primitives::{Receipt, TransactionSigned};
 use reth_primitives_traits::{Account, Bytecode, StorageEntry};
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::StageCheckpoint;
-use reth_trie_common::{BranchNodeCompact, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey};
+use reth_trie_common::{
+    BranchNodeCompact, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey, TrieChangeSetsEntry,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -436,6 +438,25 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores pointers to blocks that contain at least one log emitted by the address.
+    ///
+    /// Only maintained if the log index is enabled. Sharded the same way as [`AccountsHistory`],
+    /// with the last shard of every address keyed by `u64::MAX`.
+    table LogAddressHistory {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to blocks that contain at least one log with the topic, regardless of the
+    /// topic position.
+    ///
+    /// Only maintained if the log index is enabled. Sharded the same way as [`AccountsHistory`],
+    /// with the last shard of every topic keyed by `u64::MAX`.
+    table LogTopicHistory {
+        type Key = ShardedKey<B256>;
+        type Value = BlockNumberList;
+    }
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -486,6 +507,41 @@ tables! {
         type SubKey = StoredNibblesSubKey;
     }
 
+    /// Stores the value of an account trie node before a certain block changed it.
+    /// If [`TrieChangeSetsEntry::node`] is `None`, the node did not exist before the block.
+    /// Blocks whose changes were recorded together with the preceding blocks' are marked with
+    /// [`TrieChangeSetsEntry::marker`].
+    table AccountsTrieChangeSets {
+        type Key = BlockNumber;
+        type Value = TrieChangeSetsEntry;
+        type SubKey = StoredNibblesSubKey;
+    }
+
+    /// Stores the value of a storage trie node before a certain block changed it.
+    /// If [`TrieChangeSetsEntry::node`] is `None`, the node did not exist before the block.
+    table StoragesTrieChangeSets {
+        type Key = BlockNumberHashedAddress;
+        type Value = TrieChangeSetsEntry;
+        type SubKey = StoredNibblesSubKey;
+    }
+
+    /// Stores the value of an account trie node at a snapshot block, for every node changed since.
+    /// If [`TrieChangeSetsEntry::node`] is `None`, the node did not exist at the block.
+    /// Every snapshot contains a [`TrieChangeSetsEntry::marker`].
+    table AccountsTrieSnapshots {
+        type Key = BlockNumber;
+        type Value = TrieChangeSetsEntry;
+        type SubKey = StoredNibblesSubKey;
+    }
+
+    /// Stores the value of a storage trie node at a snapshot block, for every node changed since.
+    /// If [`TrieChangeSetsEntry::node`] is `None`, the node did not exist at the block.
+    table StoragesTrieSnapshots {
+        type Key = BlockNumberHashedAddress;
+        type Value = TrieChangeSetsEntry;
+        type SubKey = StoredNibblesSubKey;
+    }
+
     /// Stores the transaction sender for each canonical transaction.
     /// It is needed to speed up execution stage and allows fetching signer without doing
     /// transaction signed recovery
@@ -0,0 +1,295 @@
+//! Backend-agnostic test suite for [`Database`] implementations.
+//!
+//! Every backend should pass these tests, so that the rest of reth can rely on the same semantics
+//! of transactions and cursors regardless of the storage engine. Use
+//! [`database_tests!`](crate::database_tests) to generate a test for each check:
+//!
+//! ```ignore
+//! fn create_test_db() -> (TempDir, MyDatabase) { ... }
+//!
+//! reth_db_api::database_tests!(create_test_db);
+//! ```
+//!
+//! The database has to be created with all [`Tables`](crate::Tables).
+
+use crate::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+    database::Database,
+    tables::{CanonicalHeaders, PlainStorageState},
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use alloy_primitives::{Address, B256, U256};
+use reth_primitives_traits::StorageEntry;
+
+/// Generates a `#[test]` for each check of the [test suite](crate::test_suite).
+///
+/// Takes a function returning a guard that keeps the database alive (e.g. its temporary
+/// directory) and the database.
+#[macro_export]
+macro_rules! database_tests {
+    ($create_db:path) => {
+        $crate::database_tests!(
+            $create_db;
+            put_get_delete,
+            uncommitted_changes,
+            cursor_navigation,
+            cursor_walk,
+            cursor_delete_current,
+            cursor_write_ordering,
+            dupsort_navigation,
+            dupsort_write,
+            clear_table,
+        );
+    };
+    ($create_db:path; $($test:ident),+ $(,)?) => {
+        $(
+            #[test]
+            fn $test() {
+                let (_guard, db) = $create_db();
+                $crate::test_suite::$test(&db);
+            }
+        )+
+    };
+}
+
+const fn hash(n: u8) -> B256 {
+    B256::with_last_byte(n)
+}
+
+fn storage(key: u8, value: u64) -> StorageEntry {
+    StorageEntry { key: hash(key), value: U256::from(value) }
+}
+
+/// Inserts `CanonicalHeaders` for the given blocks, with the hash derived from the block number.
+fn insert_headers<DB: Database>(db: &DB, blocks: impl IntoIterator<Item = u8>) {
+    let tx = db.tx_mut().unwrap();
+    for block in blocks {
+        tx.put::<CanonicalHeaders>(block.into(), hash(block)).unwrap();
+    }
+    tx.commit().unwrap();
+}
+
+/// Inserts `PlainStorageState` entries for the given addresses and slots.
+fn insert_storage<DB: Database>(db: &DB, entries: &[(Address, u8, u64)]) {
+    let tx = db.tx_mut().unwrap();
+    for (address, key, value) in entries {
+        tx.put::<PlainStorageState>(*address, storage(*key, *value)).unwrap();
+    }
+    tx.commit().unwrap();
+}
+
+/// Collects the keys of the rows returned by a walker.
+fn keys<K>(walker: impl Iterator<Item = Result<(K, B256), DatabaseError>>) -> Vec<K> {
+    walker.map(|row| row.unwrap().0).collect()
+}
+
+/// Values can be written, overwritten, read and deleted.
+pub fn put_get_delete<DB: Database>(db: &DB) {
+    insert_headers(db, [1, 2]);
+
+    let tx = db.tx_mut().unwrap();
+    assert_eq!(tx.get::<CanonicalHeaders>(1).unwrap(), Some(hash(1)));
+    assert_eq!(tx.get::<CanonicalHeaders>(3).unwrap(), None);
+
+    tx.put::<CanonicalHeaders>(1, hash(10)).unwrap();
+    assert_eq!(tx.get::<CanonicalHeaders>(1).unwrap(), Some(hash(10)));
+
+    assert!(tx.delete::<CanonicalHeaders>(2, None).unwrap());
+    assert!(!tx.delete::<CanonicalHeaders>(2, None).unwrap());
+    tx.commit().unwrap();
+
+    let tx = db.tx().unwrap();
+    assert_eq!(tx.get::<CanonicalHeaders>(1).unwrap(), Some(hash(10)));
+    assert_eq!(tx.get::<CanonicalHeaders>(2).unwrap(), None);
+    assert_eq!(tx.entries::<CanonicalHeaders>().unwrap(), 1);
+}
+
+/// Changes are only visible to other transactions once committed.
+pub fn uncommitted_changes<DB: Database>(db: &DB) {
+    let reader = db.tx().unwrap();
+
+    let tx = db.tx_mut().unwrap();
+    tx.put::<CanonicalHeaders>(1, hash(1)).unwrap();
+    tx.abort();
+    assert_eq!(db.tx().unwrap().get::<CanonicalHeaders>(1).unwrap(), None);
+
+    insert_headers(db, [2]);
+    assert_eq!(db.tx().unwrap().get::<CanonicalHeaders>(2).unwrap(), Some(hash(2)));
+    // A transaction opened before the commit keeps its snapshot.
+    assert_eq!(reader.get::<CanonicalHeaders>(2).unwrap(), None);
+}
+
+/// Cursors can be positioned and moved in both directions.
+pub fn cursor_navigation<DB: Database>(db: &DB) {
+    let tx = db.tx().unwrap();
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+    assert_eq!(cursor.first().unwrap(), None);
+    assert_eq!(cursor.last().unwrap(), None);
+    drop(cursor);
+    drop(tx);
+
+    insert_headers(db, [1, 3, 5]);
+
+    let tx = db.tx().unwrap();
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+    assert_eq!(cursor.first().unwrap(), Some((1, hash(1))));
+    assert_eq!(cursor.next().unwrap(), Some((3, hash(3))));
+    assert_eq!(cursor.current().unwrap(), Some((3, hash(3))));
+    assert_eq!(cursor.next().unwrap(), Some((5, hash(5))));
+    assert_eq!(cursor.next().unwrap(), None);
+
+    assert_eq!(cursor.last().unwrap(), Some((5, hash(5))));
+    assert_eq!(cursor.prev().unwrap(), Some((3, hash(3))));
+    assert_eq!(cursor.prev().unwrap(), Some((1, hash(1))));
+    assert_eq!(cursor.prev().unwrap(), None);
+
+    assert_eq!(cursor.seek(2).unwrap(), Some((3, hash(3))));
+    assert_eq!(cursor.seek(3).unwrap(), Some((3, hash(3))));
+    assert_eq!(cursor.seek(6).unwrap(), None);
+    assert_eq!(cursor.seek_exact(2).unwrap(), None);
+    assert_eq!(cursor.seek_exact(5).unwrap(), Some((5, hash(5))));
+    assert_eq!(cursor.prev().unwrap(), Some((3, hash(3))));
+}
+
+/// Walkers iterate over the expected rows.
+pub fn cursor_walk<DB: Database>(db: &DB) {
+    insert_headers(db, 0..10);
+
+    let tx = db.tx().unwrap();
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+
+    assert_eq!(keys(cursor.walk(None).unwrap()), (0..10).collect::<Vec<_>>());
+    assert_eq!(keys(cursor.walk(Some(7)).unwrap()), vec![7, 8, 9]);
+    assert_eq!(keys(cursor.walk_range(2..5).unwrap()), vec![2, 3, 4]);
+    assert_eq!(keys(cursor.walk_range(8..=9).unwrap()), vec![8, 9]);
+    assert_eq!(keys(cursor.walk_range(..2).unwrap()), vec![0, 1]);
+    assert_eq!(keys(cursor.walk_range(20..).unwrap()), Vec::<u64>::new());
+    assert_eq!(keys(cursor.walk_back(Some(2)).unwrap()), vec![2, 1, 0]);
+    assert_eq!(keys(cursor.walk_back(None).unwrap()).len(), 10);
+}
+
+/// Deleting the current row while walking continues with the following row.
+pub fn cursor_delete_current<DB: Database>(db: &DB) {
+    insert_headers(db, 0..6);
+
+    let tx = db.tx_mut().unwrap();
+    let mut cursor = tx.cursor_write::<CanonicalHeaders>().unwrap();
+    let mut walker = cursor.walk(None).unwrap();
+    while let Some((block, _)) = walker.next().transpose().unwrap() {
+        if block % 2 == 0 {
+            walker.delete_current().unwrap();
+        }
+    }
+    drop(cursor);
+    tx.commit().unwrap();
+
+    let tx = db.tx().unwrap();
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+    assert_eq!(keys(cursor.walk(None).unwrap()), vec![1, 3, 5]);
+}
+
+/// `insert` fails for existing keys and `append` fails for keys out of order.
+pub fn cursor_write_ordering<DB: Database>(db: &DB) {
+    let tx = db.tx_mut().unwrap();
+    let mut cursor = tx.cursor_write::<CanonicalHeaders>().unwrap();
+
+    cursor.append(1, &hash(1)).unwrap();
+    cursor.append(3, &hash(3)).unwrap();
+    assert!(cursor.append(2, &hash(2)).is_err());
+    assert!(cursor.append(3, &hash(3)).is_err());
+
+    cursor.insert(2, &hash(2)).unwrap();
+    assert!(cursor.insert(2, &hash(20)).is_err());
+
+    cursor.upsert(2, &hash(20)).unwrap();
+    assert_eq!(cursor.seek_exact(2).unwrap(), Some((2, hash(20))));
+    drop(cursor);
+    tx.commit().unwrap();
+
+    assert_eq!(db.tx().unwrap().entries::<CanonicalHeaders>().unwrap(), 3);
+}
+
+/// Duplicate values of a `DUPSORT` table are sorted and can be navigated.
+pub fn dupsort_navigation<DB: Database>(db: &DB) {
+    let a = Address::with_last_byte(1);
+    let b = Address::with_last_byte(2);
+    insert_storage(db, &[(b, 1, 10), (a, 3, 30), (a, 1, 10), (a, 2, 20)]);
+
+    let tx = db.tx().unwrap();
+    assert_eq!(tx.get::<PlainStorageState>(a).unwrap(), Some(storage(1, 10)));
+    assert_eq!(tx.entries::<PlainStorageState>().unwrap(), 4);
+
+    let mut cursor = tx.cursor_dup_read::<PlainStorageState>().unwrap();
+    assert_eq!(cursor.first().unwrap(), Some((a, storage(1, 10))));
+    assert_eq!(cursor.next_dup().unwrap(), Some((a, storage(2, 20))));
+    assert_eq!(cursor.next_dup_val().unwrap(), Some(storage(3, 30)));
+    assert_eq!(cursor.next_dup().unwrap(), None);
+    assert_eq!(cursor.next().unwrap(), Some((b, storage(1, 10))));
+    assert_eq!(cursor.prev().unwrap(), Some((a, storage(3, 30))));
+
+    cursor.first().unwrap();
+    assert_eq!(cursor.next_no_dup().unwrap(), Some((b, storage(1, 10))));
+    assert_eq!(cursor.next_no_dup().unwrap(), None);
+
+    assert_eq!(cursor.seek_by_key_subkey(a, hash(2)).unwrap(), Some(storage(2, 20)));
+    assert_eq!(cursor.seek_by_key_subkey(b, hash(2)).unwrap(), None);
+    assert_eq!(cursor.seek_exact(b).unwrap(), Some((b, storage(1, 10))));
+
+    let values = |key, subkey| {
+        let mut cursor = tx.cursor_dup_read::<PlainStorageState>().unwrap();
+        cursor.walk_dup(key, subkey).unwrap().map(|row| row.unwrap().1.key).collect::<Vec<_>>()
+    };
+    assert_eq!(values(Some(a), None), vec![hash(1), hash(2), hash(3)]);
+    assert_eq!(values(Some(a), Some(hash(2))), vec![hash(2), hash(3)]);
+    assert_eq!(values(None, Some(hash(3))), vec![hash(3)]);
+    assert_eq!(values(Some(b), None), vec![hash(1)]);
+}
+
+/// Duplicate values of a `DUPSORT` table can be appended and deleted.
+pub fn dupsort_write<DB: Database>(db: &DB) {
+    let a = Address::with_last_byte(1);
+    let b = Address::with_last_byte(2);
+
+    let tx = db.tx_mut().unwrap();
+    let mut cursor = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+    cursor.append_dup(a, storage(1, 10)).unwrap();
+    cursor.append_dup(a, storage(2, 20)).unwrap();
+    assert!(cursor.append_dup(a, storage(1, 10)).is_err());
+    cursor.append_dup(b, storage(1, 10)).unwrap();
+    cursor.append_dup(b, storage(2, 20)).unwrap();
+    cursor.append_dup(b, storage(3, 30)).unwrap();
+    drop(cursor);
+
+    // Delete a single duplicate.
+    assert!(tx.delete::<PlainStorageState>(a, Some(storage(2, 20))).unwrap());
+
+    // Delete all duplicates of a key.
+    let mut cursor = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+    cursor.seek_exact(b).unwrap();
+    cursor.delete_current_duplicates().unwrap();
+    drop(cursor);
+    tx.commit().unwrap();
+
+    let tx = db.tx().unwrap();
+    let mut cursor = tx.cursor_dup_read::<PlainStorageState>().unwrap();
+    let rows = cursor.walk(None).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(rows, vec![(a, storage(1, 10))]);
+}
+
+/// Clearing a table removes all of its rows, but keeps the table usable.
+pub fn clear_table<DB: Database>(db: &DB) {
+    insert_headers(db, 0..3);
+    insert_storage(db, &[(Address::ZERO, 1, 1), (Address::ZERO, 2, 2)]);
+
+    let tx = db.tx_mut().unwrap();
+    tx.clear::<CanonicalHeaders>().unwrap();
+    tx.clear::<PlainStorageState>().unwrap();
+    tx.put::<CanonicalHeaders>(7, hash(7)).unwrap();
+    tx.commit().unwrap();
+
+    let tx = db.tx().unwrap();
+    assert_eq!(tx.entries::<CanonicalHeaders>().unwrap(), 1);
+    assert_eq!(tx.entries::<PlainStorageState>().unwrap(), 0);
+    assert_eq!(tx.get::<CanonicalHeaders>(7).unwrap(), Some(hash(7)));
+}
@@ -549,6 +549,18 @@ mod tests {
         env
     }
 
+    mod suite {
+        use super::*;
+
+        fn create_suite_db() -> (TempDir, DatabaseEnv) {
+            let dir = TempDir::new().expect(ERROR_TEMPDIR);
+            let env = create_test_db_with_path(DatabaseEnvKind::RW, dir.path());
+            (dir, env)
+        }
+
+        reth_db_api::database_tests!(create_suite_db);
+    }
+
     const ERROR_DB_CREATION: &str = "Not able to create the mdbx file.";
     const ERROR_PUT: &str = "Not able to insert value into table.";
     const ERROR_APPEND: &str = "Not able to append the value to the table.";
@@ -447,6 +447,19 @@ impl<N: ProviderNodeTypes> ReceiptProvider for BlockchainProvider<N> {
     ) -> ProviderResult<Vec<Vec<Self::Receipt>>> {
         self.consistent_provider()?.receipts_by_block_range(block_range)
     }
+
+    fn log_index_tip(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.log_index_tip()
+    }
+
+    fn blocks_with_logs(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.blocks_with_logs(range, addresses, topics)
+    }
 }
 
 impl<N: ProviderNodeTypes> ReceiptProviderIdExt for BlockchainProvider<N> {
@@ -1126,6 +1126,19 @@ impl<N: ProviderNodeTypes> ReceiptProvider for ConsistentProvider<N> {
     ) -> ProviderResult<Vec<Vec<Self::Receipt>>> {
         self.storage_provider.receipts_by_block_range(block_range)
     }
+
+    fn log_index_tip(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.storage_provider.log_index_tip()
+    }
+
+    fn blocks_with_logs(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.storage_provider.blocks_with_logs(range, addresses, topics)
+    }
 }
 
 impl<N: ProviderNodeTypes> ReceiptProviderIdExt for ConsistentProvider<N> {
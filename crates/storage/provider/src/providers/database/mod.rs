@@ -533,6 +533,19 @@ impl<N: ProviderNodeTypes> ReceiptProvider for ProviderFactory<N> {
     ) -> ProviderResult<Vec<Vec<Self::Receipt>>> {
         self.provider()?.receipts_by_block_range(block_range)
     }
+
+    fn log_index_tip(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.provider()?.log_index_tip()
+    }
+
+    fn blocks_with_logs(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.blocks_with_logs(range, addresses, topics)
+    }
 }
 
 impl<N: ProviderNodeTypes> BlockBodyIndicesProvider for ProviderFactory<N> {
@@ -26,7 +26,7 @@ use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
 use alloy_primitives::{
     keccak256,
     map::{hash_map, B256Map, HashMap, HashSet},
-    Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
+    Address, BlockHash, BlockNumber, Log, TxHash, TxNumber, B256, U256,
 };
 use itertools::Itertools;
 use rayon::slice::ParallelSliceMut;
@@ -36,9 +36,9 @@ use reth_db_api::{
     database::Database,
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StoredBlockBodyIndices,
+        BlockNumberHashedAddress, ShardedKey, StoredBlockBodyIndices,
     },
-    table::Table,
+    table::{DupSort, Table},
     tables,
     transaction::{DbTx, DbTxMut},
     BlockNumberList, DatabaseError, PlainAccountState, PlainStorageState,
@@ -62,9 +62,12 @@ use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
     prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
     updates::{StorageTrieUpdates, TrieUpdates},
-    HashedPostStateSorted, Nibbles, StateRoot, StoredNibbles,
+    HashedPostStateSorted, Nibbles, StateRoot, StoredNibbles, StoredNibblesSubKey,
+    TrieChangeSetsEntry,
+};
+use reth_trie_db::{
+    DatabaseStateRoot, DatabaseStorageTrieCursor, DatabaseTrieUpdates, TRIE_SNAPSHOT_INTERVAL,
 };
-use reth_trie_db::{DatabaseStateRoot, DatabaseStorageTrieCursor};
 use revm_database::states::{
     PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset,
 };
@@ -336,9 +339,40 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         }
         self.write_trie_updates(&trie_updates)?;
 
+        // Remove trie changesets and snapshots of the unwound blocks.
+        self.remove::<tables::AccountsTrieChangeSets>(range.clone())?;
+        self.remove::<tables::StoragesTrieChangeSets>(BlockNumberHashedAddress::range(
+            range.clone(),
+        ))?;
+        self.remove::<tables::AccountsTrieSnapshots>(range.clone())?;
+        self.remove::<tables::StoragesTrieSnapshots>(BlockNumberHashedAddress::range(range))?;
+
         Ok(())
     }
 
+    /// Unwinds the log index above the given block number, if the log index is maintained.
+    ///
+    /// Must be called before the receipts of the unwound blocks are removed.
+    pub fn unwind_log_indices_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogs)? else {
+            return Ok(())
+        };
+        if checkpoint.block_number <= block {
+            return Ok(())
+        }
+
+        let mut receipts = Vec::new();
+        for block_number in block + 1..=checkpoint.block_number {
+            let block_receipts = self.receipts_by_block(block_number.into())?.unwrap_or_default();
+            receipts.push((block_number, block_receipts));
+        }
+        self.unwind_log_indices(receipts.iter().flat_map(|(block_number, receipts)| {
+            receipts.iter().flat_map(|receipt| receipt.logs()).map(|log| (*block_number, log))
+        }))?;
+
+        self.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(block))
+    }
+
     /// Removes receipts from all transactions starting with provided number (inclusive).
     fn remove_receipts_from(
         &self,
@@ -549,6 +583,38 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Returns the blocks within the range that a log index table points to for any of the keys.
+    fn log_index_blocks<T, K>(
+        &self,
+        range: &RangeInclusive<BlockNumber>,
+        keys: &[K],
+    ) -> ProviderResult<BTreeSet<BlockNumber>>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: Copy + PartialEq,
+    {
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        let mut blocks = BTreeSet::new();
+        for key in keys {
+            // Shards are keyed by their highest block number, so the first shard that can contain
+            // the start of the range is the first one at or above it.
+            let mut shard = cursor.seek(ShardedKey::new(*key, *range.start()))?;
+            while let Some((sharded_key, list)) = shard {
+                if sharded_key.key != *key {
+                    break
+                }
+
+                blocks.extend(list.iter().filter(|block| range.contains(block)));
+
+                if sharded_key.highest_block_number >= *range.end() {
+                    break
+                }
+                shard = cursor.next()?;
+            }
+        }
+        Ok(blocks)
+    }
+
     fn transactions_by_tx_range_with_cursor<C>(
         &self,
         range: impl RangeBounds<TxNumber>,
@@ -866,6 +932,37 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         }
         Ok(())
     }
+
+    /// Unwinds the shards of a log index table for every key, starting from the given block
+    /// number.
+    fn unwind_log_index<T, K>(
+        &self,
+        keys: impl IntoIterator<Item = (K, BlockNumber)>,
+    ) -> ProviderResult<()>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: Copy + PartialEq,
+    {
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        for (key, rem_index) in keys {
+            let partial_shard = unwind_history_shards::<_, T, _>(
+                &mut cursor,
+                ShardedKey::last(key),
+                rem_index,
+                |sharded_key| sharded_key.key == key,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(key),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<TX: DbTx, N: NodeTypes> AccountReader for DatabaseProvider<TX, N> {
@@ -1627,6 +1724,34 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
 
         Ok(result)
     }
+
+    fn log_index_tip(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.get_stage_checkpoint(StageId::IndexLogs)?.map(|checkpoint| checkpoint.block_number))
+    }
+
+    fn blocks_with_logs(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let mut candidates = None::<BTreeSet<BlockNumber>>;
+        if !addresses.is_empty() {
+            candidates =
+                Some(self.log_index_blocks::<tables::LogAddressHistory, _>(&range, addresses)?);
+        }
+        for topic in topics.iter().filter(|topic| !topic.is_empty()) {
+            let blocks = self.log_index_blocks::<tables::LogTopicHistory, _>(&range, topic)?;
+            candidates = Some(match candidates {
+                Some(candidates) => candidates.intersection(&blocks).copied().collect(),
+                None => blocks,
+            });
+        }
+
+        // Without any constraint every block in the range can contain a matching log.
+        Ok(candidates
+            .map_or_else(|| range.collect(), |candidates| candidates.into_iter().collect()))
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> BlockBodyIndicesProvider
@@ -2353,6 +2478,191 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> TrieWriter for DatabaseProvider
 
         Ok(num_entries)
     }
+
+    /// Writes the trie changesets of the given block, and records the touched nodes in the trie
+    /// snapshots. Returns the number of entries written.
+    fn write_trie_changesets(
+        &self,
+        block_number: BlockNumber,
+        trie_updates: &TrieUpdates,
+    ) -> ProviderResult<usize> {
+        let tx = self.tx_ref();
+
+        if !self.prune_modes.has_trie_changesets() {
+            // Trie changesets written before they were disabled don't cover this block, so they
+            // can no longer revert the trie.
+            if TrieUpdates::lowest_trie_changesets_block(tx)?.is_some() ||
+                TrieUpdates::trie_snapshot_from(tx, 0)?.is_some()
+            {
+                self.clear_trie_changesets()?;
+            }
+            return Ok(0)
+        }
+
+        // Track the number of inserted entries.
+        let mut num_entries = 0;
+
+        // Take a snapshot at every interval, it records the nodes changed from this block on.
+        let mut account_snapshots_cursor =
+            tx.cursor_dup_write::<tables::AccountsTrieSnapshots>()?;
+        if block_number % TRIE_SNAPSHOT_INTERVAL == 0 {
+            account_snapshots_cursor.upsert(block_number, &TrieChangeSetsEntry::marker())?;
+            num_entries += 1;
+        }
+
+        if trie_updates.is_empty() {
+            return Ok(num_entries)
+        }
+
+        // Newest snapshots first. A node recorded by a snapshot is recorded by all older snapshots
+        // as well, so a node is only recorded by the snapshots newer than the first one that has
+        // it.
+        let mut snapshots = TrieUpdates::trie_snapshots(tx)?;
+        snapshots.retain(|snapshot| *snapshot <= block_number);
+        snapshots.reverse();
+
+        // Collect all touched account trie nodes. The root node is never stored, so skip it.
+        let account_paths = trie_updates
+            .account_nodes_ref()
+            .keys()
+            .chain(trie_updates.removed_nodes_ref())
+            .filter(|nibbles| !nibbles.is_empty())
+            .collect::<BTreeSet<_>>();
+
+        let mut account_trie_cursor = tx.cursor_read::<tables::AccountsTrie>()?;
+        let mut account_changesets_cursor =
+            tx.cursor_dup_write::<tables::AccountsTrieChangeSets>()?;
+        for nibbles in account_paths {
+            let node =
+                account_trie_cursor.seek_exact(StoredNibbles(*nibbles))?.map(|(_, node)| node);
+            let entry = TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(*nibbles), node };
+            num_entries += write_trie_snapshots_entry(
+                &mut account_snapshots_cursor,
+                snapshots.iter().copied(),
+                &entry,
+            )?;
+            account_changesets_cursor.upsert(block_number, &entry)?;
+            num_entries += 1;
+        }
+
+        let mut storage_tries = Vec::from_iter(trie_updates.storage_tries_ref());
+        storage_tries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        let mut storage_trie_cursor = tx.cursor_dup_read::<tables::StoragesTrie>()?;
+        let mut storage_changesets_cursor =
+            tx.cursor_dup_write::<tables::StoragesTrieChangeSets>()?;
+        let mut storage_snapshots_cursor =
+            tx.cursor_dup_write::<tables::StoragesTrieSnapshots>()?;
+        for (hashed_address, storage_trie_updates) in storage_tries {
+            let mut changed_nodes = BTreeMap::new();
+
+            // If the storage trie is wiped, all of its existing nodes are changed.
+            if storage_trie_updates.is_deleted() {
+                for entry in storage_trie_cursor.walk_dup(Some(*hashed_address), None)? {
+                    let (_, entry) = entry?;
+                    changed_nodes.insert(entry.nibbles.0, Some(entry.node));
+                }
+            }
+
+            for nibbles in storage_trie_updates
+                .storage_nodes_ref()
+                .keys()
+                .chain(storage_trie_updates.removed_nodes_ref())
+                .filter(|nibbles| !nibbles.is_empty())
+            {
+                if changed_nodes.contains_key(nibbles) {
+                    continue
+                }
+                let node = storage_trie_cursor
+                    .seek_by_key_subkey(*hashed_address, StoredNibblesSubKey(*nibbles))?
+                    .filter(|entry| entry.nibbles.0 == *nibbles)
+                    .map(|entry| entry.node);
+                changed_nodes.insert(*nibbles, node);
+            }
+
+            let key = BlockNumberHashedAddress((block_number, *hashed_address));
+            for (nibbles, node) in changed_nodes {
+                let entry = TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(nibbles), node };
+                num_entries += write_trie_snapshots_entry(
+                    &mut storage_snapshots_cursor,
+                    snapshots
+                        .iter()
+                        .map(|snapshot| BlockNumberHashedAddress((*snapshot, *hashed_address))),
+                    &entry,
+                )?;
+                storage_changesets_cursor.upsert(key, &entry)?;
+                num_entries += 1;
+            }
+        }
+
+        Ok(num_entries)
+    }
+
+    fn write_batched_trie_changesets(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        trie_updates: &TrieUpdates,
+    ) -> ProviderResult<usize> {
+        let mut num_entries = self.write_trie_changesets(*range.start(), trie_updates)?;
+        if !self.prune_modes.has_trie_changesets() {
+            return Ok(num_entries)
+        }
+
+        // The trie can't be reverted to the start of the other blocks of the range, and no
+        // snapshots can be taken at them.
+        let mut account_changesets_cursor =
+            self.tx.cursor_dup_write::<tables::AccountsTrieChangeSets>()?;
+        for block_number in range.skip(1) {
+            account_changesets_cursor.upsert(block_number, &TrieChangeSetsEntry::marker())?;
+            num_entries += 1;
+        }
+
+        Ok(num_entries)
+    }
+
+    fn remove_trie_changesets_above(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        self.remove::<tables::AccountsTrieChangeSets>(block_number + 1..)?;
+        self.remove::<tables::StoragesTrieChangeSets>(
+            BlockNumberHashedAddress((block_number + 1, B256::ZERO))..,
+        )?;
+        self.remove::<tables::AccountsTrieSnapshots>(block_number + 1..)?;
+        self.remove::<tables::StoragesTrieSnapshots>(
+            BlockNumberHashedAddress((block_number + 1, B256::ZERO))..,
+        )?;
+        Ok(())
+    }
+
+    fn clear_trie_changesets(&self) -> ProviderResult<()> {
+        self.tx.clear::<tables::AccountsTrieChangeSets>()?;
+        self.tx.clear::<tables::StoragesTrieChangeSets>()?;
+        self.tx.clear::<tables::AccountsTrieSnapshots>()?;
+        self.tx.clear::<tables::StoragesTrieSnapshots>()?;
+        Ok(())
+    }
+}
+
+/// Records the value of a trie node in the trie snapshots with the given keys, newest first,
+/// until a snapshot already has it. Returns the number of entries written.
+fn write_trie_snapshots_entry<T, C>(
+    cursor: &mut C,
+    snapshots: impl IntoIterator<Item = T::Key>,
+    entry: &TrieChangeSetsEntry,
+) -> ProviderResult<usize>
+where
+    T: DupSort<Value = TrieChangeSetsEntry, SubKey = StoredNibblesSubKey>,
+    C: DbDupCursorRO<T> + DbCursorRW<T>,
+{
+    let mut num_entries = 0;
+    for snapshot in snapshots {
+        if cursor
+            .seek_by_key_subkey(snapshot.clone(), entry.nibbles.clone())?
+            .is_some_and(|snapshot_entry| snapshot_entry.nibbles == entry.nibbles)
+        {
+            break
+        }
+        cursor.upsert(snapshot, entry)?;
+        num_entries += 1;
+    }
+    Ok(num_entries)
 }
 
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> StorageTrieWriter for DatabaseProvider<TX, N> {
@@ -2749,6 +3059,43 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
 
         Ok(())
     }
+
+    fn unwind_log_indices<'a>(
+        &self,
+        logs: impl Iterator<Item = (BlockNumber, &'a Log)>,
+    ) -> ProviderResult<usize> {
+        // Every key only needs to be unwound from the lowest block it appears in.
+        let mut addresses = BTreeMap::<Address, BlockNumber>::new();
+        let mut topics = BTreeMap::<B256, BlockNumber>::new();
+        let mut walked = 0;
+        for (block_number, log) in logs {
+            addresses
+                .entry(log.address)
+                .and_modify(|lowest| *lowest = (*lowest).min(block_number))
+                .or_insert(block_number);
+            for topic in log.topics() {
+                topics
+                    .entry(*topic)
+                    .and_modify(|lowest| *lowest = (*lowest).min(block_number))
+                    .or_insert(block_number);
+            }
+            walked += 1;
+        }
+
+        self.unwind_log_index::<tables::LogAddressHistory, _>(addresses)?;
+        self.unwind_log_index::<tables::LogTopicHistory, _>(topics)?;
+
+        Ok(walked)
+    }
+
+    fn insert_log_indices(
+        &self,
+        addresses: impl IntoIterator<Item = (Address, impl IntoIterator<Item = u64>)>,
+        topics: impl IntoIterator<Item = (B256, impl IntoIterator<Item = u64>)>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::LogAddressHistory>(addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicHistory>(topics, ShardedKey::new)
+    }
 }
 
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecutionWriter
@@ -2762,6 +3109,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
         let range = block + 1..=self.last_block_number()?;
 
         self.unwind_trie_state_range(range.clone())?;
+        self.unwind_log_indices_above(block)?;
 
         // get execution res
         let execution_state = self.take_state_above(block, remove_from)?;
@@ -2786,6 +3134,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
         let range = block + 1..=self.last_block_number()?;
 
         self.unwind_trie_state_range(range)?;
+        self.unwind_log_indices_above(block)?;
 
         // remove execution res
         self.remove_state_above(block, remove_from)?;
@@ -3086,6 +3435,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
 
         // insert hashes and intermediate merkle nodes
         self.write_hashed_state(&hashed_state)?;
+        self.write_batched_trie_changesets(first_number..=last_block_number, &trie_updates)?;
         self.write_trie_updates(&trie_updates)?;
         durations_recorder.record_relative(metrics::Action::InsertHashes);
 
@@ -3454,4 +3804,46 @@ mod tests {
 
         assert_eq!(range_result, individual_results);
     }
+
+    #[test]
+    fn test_blocks_with_logs() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let other_address = Address::with_last_byte(2);
+        let topic = B256::with_last_byte(3);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .insert_log_indices(
+                [(address, vec![1, 3, 5]), (other_address, vec![2, 3])],
+                [(topic, vec![3, 4, 5])],
+            )
+            .unwrap();
+        provider_rw.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(5)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.log_index_tip().unwrap(), Some(5));
+        assert_eq!(provider.blocks_with_logs(1..=5, &[address], &[]).unwrap(), vec![1, 3, 5]);
+        assert_eq!(provider.blocks_with_logs(2..=4, &[address], &[]).unwrap(), vec![3]);
+        assert_eq!(
+            provider.blocks_with_logs(1..=5, &[address, other_address], &[]).unwrap(),
+            vec![1, 2, 3, 5]
+        );
+        assert_eq!(
+            provider.blocks_with_logs(1..=5, &[address], &[vec![topic], vec![]]).unwrap(),
+            vec![3, 5]
+        );
+        assert_eq!(provider.blocks_with_logs(1..=2, &[], &[]).unwrap(), vec![1, 2]);
+        drop(provider);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        let log = Log::new_unchecked(address, vec![topic], Default::default());
+        assert_eq!(provider_rw.unwind_log_indices([(4, &log), (5, &log)].into_iter()).unwrap(), 2);
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.blocks_with_logs(1..=5, &[address], &[]).unwrap(), vec![1, 3]);
+        assert_eq!(provider.blocks_with_logs(1..=5, &[], &[vec![topic]]).unwrap(), vec![3]);
+    }
 }
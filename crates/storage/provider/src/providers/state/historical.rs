@@ -27,7 +27,8 @@ use reth_trie::{
 };
 use reth_trie_db::{
     DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
-    DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness, StateCommitment,
+    DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieUpdates, DatabaseTrieWitness,
+    StateCommitment, TRIE_SNAPSHOT_INTERVAL,
 };
 use std::fmt::Debug;
 
@@ -42,6 +43,10 @@ use std::fmt::Debug;
 /// - [`tables::StoragesHistory`]
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
+/// - [`tables::AccountsTrieChangeSets`]
+/// - [`tables::StoragesTrieChangeSets`]
+/// - [`tables::AccountsTrieSnapshots`]
+/// - [`tables::StoragesTrieSnapshots`]
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, Provider> {
     /// Database provider
@@ -140,6 +145,25 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
         >(self.tx(), self.block_number)?)
     }
 
+    /// Retrieve revert trie nodes for this history provider.
+    fn revert_trie_nodes(&self) -> ProviderResult<TrieUpdates> {
+        Ok(TrieUpdates::from_reverts(self.tx(), self.block_number)?)
+    }
+
+    /// Prepends the reverts of this history provider to the given trie input.
+    ///
+    /// If the trie changesets cover this block, the reverted trie nodes are prepended as well, so
+    /// the trie doesn't have to be recomputed for the changed prefixes.
+    fn prepend_reverts(&self, input: &mut TrieInput) -> ProviderResult<()> {
+        let revert_state = self.revert_state()?;
+        if self.has_trie_changesets()? {
+            input.prepend_cached(self.revert_trie_nodes()?, revert_state);
+        } else {
+            input.prepend(revert_state);
+        }
+        Ok(())
+    }
+
     /// Retrieve revert hashed storage for this history provider and target address.
     fn revert_storage(&self, address: Address) -> ProviderResult<HashedStorage> {
         if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
@@ -371,7 +395,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         address: Address,
         slots: &[B256],
     ) -> ProviderResult<AccountProof> {
-        input.prepend(self.revert_state()?);
+        self.prepend_reverts(&mut input)?;
         Proof::overlay_account_proof(self.tx(), input, address, slots).map_err(ProviderError::from)
     }
 
@@ -380,16 +404,29 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         mut input: TrieInput,
         targets: MultiProofTargets,
     ) -> ProviderResult<MultiProof> {
-        input.prepend(self.revert_state()?);
+        self.prepend_reverts(&mut input)?;
         Proof::overlay_multiproof(self.tx(), input, targets).map_err(ProviderError::from)
     }
 
     fn witness(&self, mut input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
-        input.prepend(self.revert_state()?);
+        self.prepend_reverts(&mut input)?;
         TrieWitness::overlay_witness(self.tx(), input, target)
             .map_err(ProviderError::from)
             .map(|hm| hm.into_values().collect())
     }
+
+    /// Trie changesets are used if they can revert the trie to this block, and there are at most
+    /// [`TRIE_SNAPSHOT_INTERVAL`] blocks of them to walk until the next trie snapshot or the tip.
+    fn has_trie_changesets(&self) -> ProviderResult<bool> {
+        if !TrieUpdates::can_revert_to(self.tx(), self.block_number)? {
+            return Ok(false)
+        }
+        let walk_to = match TrieUpdates::trie_snapshot_from(self.tx(), self.block_number)? {
+            Some(snapshot) => snapshot,
+            None => self.provider.best_block_number()?,
+        };
+        Ok(walk_to.saturating_sub(self.block_number) <= TRIE_SNAPSHOT_INTERVAL)
+    }
 }
 
 impl<Provider: StateCommitmentProvider> HashedPostStateProvider
@@ -12,7 +12,7 @@ use alloy_primitives::{
     TxHash, TxNumber, B256, U256,
 };
 use parking_lot::Mutex;
-use reth_chain_state::{CanonStateNotifications, CanonStateSubscriptions};
+use reth_chain_state::{CanonStateNotification, CanonStateNotifications, CanonStateSubscriptions};
 use reth_chainspec::{ChainInfo, EthChainSpec};
 use reth_db_api::{
     mock::{DatabaseMock, TxMock},
@@ -68,6 +68,10 @@ pub struct MockEthProvider<
     pub block_body_indices: Arc<Mutex<HashMap<BlockNumber, StoredBlockBodyIndices>>>,
     tx: TxMock,
     prune_modes: Arc<PruneModes>,
+    /// Sender of the canonical state notifications, see [`Self::with_canon_state_sender`].
+    canon_state_sender: Option<broadcast::Sender<CanonStateNotification<T>>>,
+    /// The finalized block, see [`Self::set_finalized_block`].
+    finalized_block: Arc<Mutex<Option<alloy_eips::BlockNumHash>>>,
 }
 
 impl<T: NodePrimitives, ChainSpec> Clone for MockEthProvider<T, ChainSpec>
@@ -85,6 +89,8 @@ where
             block_body_indices: self.block_body_indices.clone(),
             tx: self.tx.clone(),
             prune_modes: self.prune_modes.clone(),
+            canon_state_sender: self.canon_state_sender.clone(),
+            finalized_block: self.finalized_block.clone(),
         }
     }
 }
@@ -102,26 +108,27 @@ impl<T: NodePrimitives> MockEthProvider<T, reth_chainspec::ChainSpec> {
             block_body_indices: Default::default(),
             tx: Default::default(),
             prune_modes: Default::default(),
+            canon_state_sender: None,
+            finalized_block: Default::default(),
         }
     }
 }
 
-impl<ChainSpec> MockEthProvider<reth_ethereum_primitives::EthPrimitives, ChainSpec> {
-    /// Add block to local block store
-    pub fn add_block(&self, hash: B256, block: reth_ethereum_primitives::Block) {
-        self.add_header(hash, block.header.clone());
-        self.blocks.lock().insert(hash, block);
+impl<T: NodePrimitives, ChainSpec> MockEthProvider<T, ChainSpec> {
+    /// Set the sender of the canonical state notifications.
+    ///
+    /// Without a sender, canonical state subscriptions end immediately.
+    pub fn with_canon_state_sender(
+        mut self,
+        sender: broadcast::Sender<CanonStateNotification<T>>,
+    ) -> Self {
+        self.canon_state_sender = Some(sender);
+        self
     }
 
-    /// Add multiple blocks to local block store
-    pub fn extend_blocks(
-        &self,
-        iter: impl IntoIterator<Item = (B256, reth_ethereum_primitives::Block)>,
-    ) {
-        for (hash, block) in iter {
-            self.add_header(hash, block.header.clone());
-            self.add_block(hash, block)
-        }
+    /// Set the finalized block.
+    pub fn set_finalized_block(&self, block: alloy_eips::BlockNumHash) {
+        *self.finalized_block.lock() = Some(block);
     }
 
     /// Add header to local header store
@@ -147,6 +154,25 @@ impl<ChainSpec> MockEthProvider<reth_ethereum_primitives::EthPrimitives, ChainSp
             self.add_account(address, account)
         }
     }
+}
+
+impl<ChainSpec> MockEthProvider<reth_ethereum_primitives::EthPrimitives, ChainSpec> {
+    /// Add block to local block store
+    pub fn add_block(&self, hash: B256, block: reth_ethereum_primitives::Block) {
+        self.add_header(hash, block.header.clone());
+        self.blocks.lock().insert(hash, block);
+    }
+
+    /// Add multiple blocks to local block store
+    pub fn extend_blocks(
+        &self,
+        iter: impl IntoIterator<Item = (B256, reth_ethereum_primitives::Block)>,
+    ) {
+        for (hash, block) in iter {
+            self.add_header(hash, block.header.clone());
+            self.add_block(hash, block)
+        }
+    }
 
     /// Add receipts to local receipt store
     pub fn add_receipts(&self, block_number: BlockNumber, receipts: Vec<Receipt>) {
@@ -189,6 +215,8 @@ impl<ChainSpec> MockEthProvider<reth_ethereum_primitives::EthPrimitives, ChainSp
             block_body_indices: self.block_body_indices,
             tx: self.tx,
             prune_modes: self.prune_modes,
+            canon_state_sender: self.canon_state_sender,
+            finalized_block: self.finalized_block,
         }
     }
 }
@@ -300,8 +328,8 @@ impl<T: NodePrimitives, ChainSpec: EthChainSpec + 'static> DBProvider
     }
 }
 
-impl<ChainSpec: EthChainSpec + Send + Sync + 'static> HeaderProvider
-    for MockEthProvider<reth_ethereum_primitives::EthPrimitives, ChainSpec>
+impl<T: NodePrimitives, ChainSpec: EthChainSpec + Send + Sync + 'static> HeaderProvider
+    for MockEthProvider<T, ChainSpec>
 {
     type Header = Header;
 
@@ -656,7 +684,7 @@ impl<T: NodePrimitives, ChainSpec: EthChainSpec + Send + Sync + 'static> BlockId
     }
 
     fn finalized_block_num_hash(&self) -> ProviderResult<Option<alloy_eips::BlockNumHash>> {
-        Ok(None)
+        Ok(*self.finalized_block.lock())
     }
 }
 
@@ -1000,7 +1028,10 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> CanonStateSubscriptions
     for MockEthProvider<T, ChainSpec>
 {
     fn subscribe_to_canonical_state(&self) -> CanonStateNotifications<T> {
-        broadcast::channel(1).1
+        match &self.canon_state_sender {
+            Some(sender) => sender.subscribe(),
+            None => broadcast::channel(1).1,
+        }
     }
 }
 
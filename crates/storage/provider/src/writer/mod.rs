@@ -3,16 +3,20 @@ use crate::{
     BlockExecutionWriter, BlockWriter, HistoryWriter, StateWriter, StaticFileProviderFactory,
     StorageLocation, TrieWriter,
 };
-use alloy_consensus::BlockHeader;
+use alloy_consensus::{BlockHeader, TxReceipt};
+use alloy_primitives::{Address, BlockNumber, B256};
 use reth_chain_state::{ExecutedBlock, ExecutedBlockWithTrieUpdates};
 use reth_db_api::transaction::{DbTx, DbTxMut};
 use reth_errors::{ProviderError, ProviderResult};
 use reth_primitives_traits::{NodePrimitives, SignedTransaction};
+use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
-use reth_storage_api::{DBProvider, StageCheckpointWriter, TransactionsProviderExt};
+use reth_storage_api::{
+    DBProvider, StageCheckpointReader, StageCheckpointWriter, TransactionsProviderExt,
+};
 use reth_storage_errors::writer::UnifiedStorageWriterError;
 use revm_database::OriginalValuesKnown;
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 use tracing::debug;
 
 /// [`UnifiedStorageWriter`] is responsible for managing the writing to storage with both database
@@ -126,6 +130,7 @@ where
         + TrieWriter
         + StateWriter
         + HistoryWriter
+        + StageCheckpointReader
         + StageCheckpointWriter
         + BlockExecutionWriter
         + AsRef<ProviderDB>
@@ -151,6 +156,14 @@ where
 
         debug!(target: "provider::storage_writer", block_count = %blocks.len(), "Writing blocks and execution data to storage");
 
+        // The log index is optional and only extended if it has caught up with the database.
+        let index_logs = self
+            .database()
+            .get_stage_checkpoint(StageId::IndexLogs)?
+            .is_some_and(|checkpoint| checkpoint.block_number + 1 == first_number);
+        let mut log_addresses = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        let mut log_topics = BTreeMap::<B256, Vec<BlockNumber>>::new();
+
         // TODO: Do performant / batched writes for each type of object
         // instead of a loop over all blocks,
         // meaning:
@@ -166,6 +179,7 @@ where
         } in blocks
         {
             let block_hash = recovered_block.hash();
+            let block_number = recovered_block.number();
             self.database()
                 .insert_block(Arc::unwrap_or_clone(recovered_block), StorageLocation::Both)?;
 
@@ -177,17 +191,35 @@ where
                 StorageLocation::StaticFiles,
             )?;
 
+            if index_logs {
+                for log in execution_output.receipts.iter().flatten().flat_map(|r| r.logs()) {
+                    push_log_index(&mut log_addresses, log.address, block_number);
+                    for topic in log.topics() {
+                        push_log_index(&mut log_topics, *topic, block_number);
+                    }
+                }
+            }
+
             // insert hashes and intermediate merkle nodes
             self.database()
                 .write_hashed_state(&Arc::unwrap_or_clone(hashed_state).into_sorted())?;
-            self.database().write_trie_updates(
-                trie.as_ref().ok_or(ProviderError::MissingTrieUpdates(block_hash))?,
-            )?;
+            let trie = trie.as_ref().ok_or(ProviderError::MissingTrieUpdates(block_hash))?;
+            // trie changesets must be written before the updates are applied
+            self.database().write_trie_changesets(block_number, trie)?;
+            self.database().write_trie_updates(trie)?;
         }
 
         // update history indices
         self.database().update_history_indices(first_number..=last_block_number)?;
 
+        if index_logs {
+            self.database().insert_log_indices(log_addresses, log_topics)?;
+            self.database().save_stage_checkpoint(
+                StageId::IndexLogs,
+                StageCheckpoint::new(last_block_number),
+            )?;
+        }
+
         // Update pipeline progress
         self.database().update_pipeline_stages(last_block_number, false)?;
 
@@ -224,6 +256,18 @@ where
     }
 }
 
+/// Records that the block contains a log with the given address or topic.
+fn push_log_index<K: Ord>(
+    index: &mut BTreeMap<K, Vec<BlockNumber>>,
+    key: K,
+    block_number: BlockNumber,
+) {
+    let blocks = index.entry(key).or_default();
+    if blocks.last() != Some(&block_number) {
+        blocks.push(block_number);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -240,12 +284,16 @@ mod tests {
     use reth_ethereum_primitives::Receipt;
     use reth_execution_types::ExecutionOutcome;
     use reth_primitives_traits::{Account, StorageEntry};
+    use reth_prune_types::{PruneMode, PruneModes};
     use reth_storage_api::{DatabaseProviderFactory, HashedPostStateProvider};
     use reth_trie::{
         test_utils::{state_root, storage_root_prehashed},
-        HashedPostState, HashedStorage, StateRoot, StorageRoot,
+        updates::{StorageTrieUpdates, TrieUpdates},
+        BranchNodeCompact, HashedPostState, HashedStorage, Nibbles, StateRoot, StorageRoot,
+    };
+    use reth_trie_db::{
+        DatabaseStateRoot, DatabaseStorageRoot, DatabaseTrieUpdates, TRIE_SNAPSHOT_INTERVAL,
     };
-    use reth_trie_db::{DatabaseStateRoot, DatabaseStorageRoot};
     use revm_database::{
         states::{
             bundle_state::BundleRetention, changes::PlainStorageRevert, PlainStorageChangeset,
@@ -1369,4 +1417,116 @@ mod tests {
         let storage_root = StorageRoot::overlay_root(tx, address, updated_storage.clone()).unwrap();
         assert_eq!(storage_root, storage_root_prehashed(updated_storage.storage));
     }
+
+    #[test]
+    fn write_trie_changesets() {
+        let factory = create_test_provider_factory().with_prune_modes(PruneModes {
+            trie_changesets: Some(PruneMode::Distance(10_064)),
+            ..PruneModes::none()
+        });
+        let provider_rw = factory.provider_rw().unwrap();
+
+        let path = Nibbles::from_nibbles_unchecked([0x1]);
+        let node = BranchNodeCompact::new(0b11, 0, 0, vec![], None);
+        let updated_node = BranchNodeCompact::new(0b101, 0, 0, vec![], None);
+        let hashed_address = B256::with_last_byte(1);
+
+        // block 1 creates the account and storage trie nodes
+        let mut updates = TrieUpdates::default();
+        updates.account_nodes.insert(path, node.clone());
+        updates
+            .storage_tries
+            .insert(hashed_address, StorageTrieUpdates::new([(path, node.clone())]));
+        assert_eq!(provider_rw.write_trie_changesets(1, &updates).unwrap(), 2);
+        provider_rw.write_trie_updates(&updates).unwrap();
+
+        // block 2 updates the account trie node and wipes the storage trie
+        let mut updates = TrieUpdates::default();
+        updates.account_nodes.insert(path, updated_node.clone());
+        updates.storage_tries.insert(hashed_address, StorageTrieUpdates::deleted());
+        assert_eq!(provider_rw.write_trie_changesets(2, &updates).unwrap(), 2);
+        provider_rw.write_trie_updates(&updates).unwrap();
+
+        let tx = provider_rw.tx_ref();
+        assert_eq!(TrieUpdates::lowest_trie_changesets_block(tx).unwrap(), Some(1));
+
+        let reverts = TrieUpdates::from_reverts(tx, 2).unwrap();
+        assert_eq!(reverts.account_nodes.get(&path), Some(&node));
+        assert_eq!(reverts.storage_tries[&hashed_address].storage_nodes.get(&path), Some(&node));
+
+        let reverts = TrieUpdates::from_reverts(tx, 1).unwrap();
+        assert!(reverts.removed_nodes.contains(&path));
+        assert!(reverts.storage_tries[&hashed_address].removed_nodes.contains(&path));
+
+        // blocks 3 and 4 are updated at once and recorded for block 3
+        let mut updates = TrieUpdates::default();
+        updates.removed_nodes.insert(path);
+        assert_eq!(provider_rw.write_batched_trie_changesets(3..=4, &updates).unwrap(), 2);
+        provider_rw.write_trie_updates(&updates).unwrap();
+        assert!(TrieUpdates::can_revert_to(tx, 3).unwrap());
+        assert!(!TrieUpdates::can_revert_to(tx, 4).unwrap());
+        let reverts = TrieUpdates::from_reverts(tx, 3).unwrap();
+        assert_eq!(reverts.account_nodes.get(&path), Some(&updated_node));
+
+        provider_rw.remove_trie_changesets_above(2).unwrap();
+        assert!(TrieUpdates::from_reverts(tx, 3).unwrap().is_empty());
+        assert!(TrieUpdates::can_revert_to(tx, 1).unwrap());
+
+        provider_rw.clear_trie_changesets().unwrap();
+        assert_eq!(TrieUpdates::lowest_trie_changesets_block(tx).unwrap(), None);
+    }
+
+    #[test]
+    fn write_trie_snapshots() {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory
+            .clone()
+            .with_prune_modes(PruneModes {
+                trie_changesets: Some(PruneMode::Distance(10_064)),
+                ..PruneModes::none()
+            })
+            .provider_rw()
+            .unwrap();
+
+        let path = Nibbles::from_nibbles_unchecked([0x1]);
+        let other_path = Nibbles::from_nibbles_unchecked([0x2]);
+        let node = BranchNodeCompact::new(0b11, 0, 0, vec![], None);
+        let snapshot = TRIE_SNAPSHOT_INTERVAL;
+
+        // a snapshot is taken at the block, and records the node created by it
+        let mut updates = TrieUpdates::default();
+        updates.account_nodes.insert(path, node.clone());
+        assert_eq!(provider_rw.write_trie_changesets(snapshot, &updates).unwrap(), 3);
+        provider_rw.write_trie_updates(&updates).unwrap();
+
+        // the snapshot already has the node updated by the next block, and records the new one
+        let mut updates = TrieUpdates::default();
+        updates.removed_nodes.insert(path);
+        updates.account_nodes.insert(other_path, node.clone());
+        assert_eq!(provider_rw.write_trie_changesets(snapshot + 1, &updates).unwrap(), 3);
+        provider_rw.write_trie_updates(&updates).unwrap();
+
+        let tx = provider_rw.tx_ref();
+        assert_eq!(TrieUpdates::trie_snapshots(tx).unwrap(), vec![snapshot]);
+
+        let reverts = TrieUpdates::from_reverts(tx, snapshot).unwrap();
+        assert!(reverts.removed_nodes.contains(&path));
+        assert!(reverts.removed_nodes.contains(&other_path));
+
+        let reverts = TrieUpdates::from_reverts(tx, snapshot + 1).unwrap();
+        assert_eq!(reverts.account_nodes.get(&path), Some(&node));
+        assert!(reverts.removed_nodes.contains(&other_path));
+
+        provider_rw.remove_trie_changesets_above(snapshot - 1).unwrap();
+        assert!(TrieUpdates::trie_snapshots(tx).unwrap().is_empty());
+
+        // trie changesets are removed once they're disabled
+        provider_rw.write_trie_changesets(snapshot, &updates).unwrap();
+        provider_rw.commit().unwrap();
+        let provider_rw = factory.provider_rw().unwrap();
+        assert_eq!(provider_rw.write_trie_changesets(snapshot + 1, &updates).unwrap(), 0);
+        let tx = provider_rw.tx_ref();
+        assert_eq!(TrieUpdates::lowest_trie_changesets_block(tx).unwrap(), None);
+        assert!(TrieUpdates::trie_snapshots(tx).unwrap().is_empty());
+    }
 }
@@ -1,4 +1,4 @@
-use alloy_primitives::{Address, BlockNumber, B256};
+use alloy_primitives::{Address, BlockNumber, Log, B256};
 use auto_impl::auto_impl;
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_db_api::models::BlockNumberAddress;
@@ -55,4 +55,20 @@ pub trait HistoryWriter: Send + Sync {
 
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
+
+    /// Unwind and clear log address and topic indices.
+    ///
+    /// Returns number of logs walked.
+    fn unwind_log_indices<'a>(
+        &self,
+        logs: impl Iterator<Item = (BlockNumber, &'a Log)>,
+    ) -> ProviderResult<usize>;
+
+    /// Insert log address and topic indices to database. Used inside `IndexLogs` stage and when
+    /// persisting blocks while the log index is maintained.
+    fn insert_log_indices(
+        &self,
+        addresses: impl IntoIterator<Item = (Address, impl IntoIterator<Item = u64>)>,
+        topics: impl IntoIterator<Item = (B256, impl IntoIterator<Item = u64>)>,
+    ) -> ProviderResult<()>;
 }
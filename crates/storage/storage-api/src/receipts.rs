@@ -1,10 +1,10 @@
 use crate::BlockIdReader;
 use alloc::vec::Vec;
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
-use alloy_primitives::{BlockNumber, TxHash, TxNumber};
+use alloy_primitives::{Address, BlockNumber, TxHash, TxNumber, B256};
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_primitives_traits::Receipt;
-use reth_storage_errors::provider::ProviderResult;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
 /// A helper type alias to access [`ReceiptProvider::Receipt`].
 pub type ProviderReceipt<P> = <P as ReceiptProvider>::Receipt;
@@ -52,6 +52,31 @@ pub trait ReceiptProvider: Send + Sync {
         &self,
         block_range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<Vec<Vec<Self::Receipt>>>;
+
+    /// Returns the highest block covered by the persistent log index, or `None` if the log index
+    /// is not maintained.
+    ///
+    /// Blocks up to and including the returned one can be looked up with
+    /// [`ReceiptProvider::blocks_with_logs`].
+    fn log_index_tip(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    /// Returns the blocks in the given range that may contain logs matching the given addresses
+    /// and topics, in ascending order.
+    ///
+    /// Empty `addresses` match any address and every entry of `topics` is a set of alternatives,
+    /// where an empty set matches any topic. Topics are indexed regardless of their position in
+    /// the log, so the returned blocks are a superset of the matching blocks and their logs still
+    /// need to be checked against the filter.
+    fn blocks_with_logs(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+        _addresses: &[Address],
+        _topics: &[Vec<B256>],
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
 }
 
 /// Trait extension for `ReceiptProvider`, for types that implement `BlockId` conversion.
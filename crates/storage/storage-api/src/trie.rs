@@ -1,5 +1,6 @@
 use alloc::vec::Vec;
-use alloy_primitives::{map::B256Map, Address, Bytes, B256};
+use alloy_primitives::{map::B256Map, Address, BlockNumber, Bytes, B256};
+use core::ops::RangeInclusive;
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie_common::{
     updates::{StorageTrieUpdates, TrieUpdates},
@@ -87,6 +88,15 @@ pub trait StateProofProvider: Send + Sync {
 
     /// Get trie witness for provided state.
     fn witness(&self, input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>>;
+
+    /// Returns `true` if proofs for this state are generated from persisted trie changesets
+    /// instead of recomputing the reverted trie nodes.
+    ///
+    /// In that case the cost of generating a proof does not grow with the amount of trie
+    /// recomputation, so callers may serve proofs outside of the configured proof window.
+    fn has_trie_changesets(&self) -> ProviderResult<bool> {
+        Ok(false)
+    }
 }
 
 /// Trie Writer
@@ -96,6 +106,44 @@ pub trait TrieWriter: Send + Sync {
     ///
     /// Returns the number of entries modified.
     fn write_trie_updates(&self, trie_updates: &TrieUpdates) -> ProviderResult<usize>;
+
+    /// Writes the trie changesets of the given block, i.e. the values of all trie nodes touched
+    /// by the provided trie updates before the updates are applied, and records the nodes in the
+    /// trie snapshots that don't have them yet.
+    ///
+    /// Trie changesets are opt-in: if they're disabled by the prune modes, nothing is written and
+    /// the existing trie changesets are removed. Must be called before
+    /// [`Self::write_trie_updates`] is called with the same updates.
+    ///
+    /// Returns the number of entries written.
+    fn write_trie_changesets(
+        &self,
+        block_number: BlockNumber,
+        trie_updates: &TrieUpdates,
+    ) -> ProviderResult<usize>;
+
+    /// Writes the trie changesets of a range of blocks whose trie updates are applied at once,
+    /// e.g. by the pipeline.
+    ///
+    /// The values of the touched trie nodes are recorded for the first block of the range, and
+    /// the other blocks are marked as blocks the trie can't be reverted to, or snapshotted at.
+    /// Must be called before [`Self::write_trie_updates`] is called with the same updates.
+    ///
+    /// Returns the number of entries written.
+    fn write_batched_trie_changesets(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        trie_updates: &TrieUpdates,
+    ) -> ProviderResult<usize>;
+
+    /// Removes the trie changesets and snapshots of all blocks above the given block number.
+    fn remove_trie_changesets_above(&self, block_number: BlockNumber) -> ProviderResult<()>;
+
+    /// Removes all trie changesets and snapshots.
+    ///
+    /// Must be called whenever the trie is modified without recording changesets, e.g. when it's
+    /// rebuilt from scratch, because the existing changesets can no longer revert the trie.
+    fn clear_trie_changesets(&self) -> ProviderResult<()>;
 }
 
 /// Storage Trie Writer
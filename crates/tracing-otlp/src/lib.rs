@@ -4,16 +4,60 @@
 //! applications. It allows for easily capturing and exporting distributed traces to compatible
 //! backends like Jaeger, Zipkin, or any other OpenTelemetry-compatible tracing system.
 
-use opentelemetry::{trace::TracerProvider, KeyValue, Value};
-use opentelemetry_otlp::SpanExporter;
+use http::HeaderMap;
+use opentelemetry::{
+    global,
+    propagation::Extractor,
+    trace::{TraceContextExt, TracerProvider},
+    Context, KeyValue, Value,
+};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
 use opentelemetry_sdk::{
-    trace::{SdkTracer, SdkTracerProvider},
+    propagation::TraceContextPropagator,
+    trace::{Sampler, SdkTracer, SdkTracerProvider},
     Resource,
 };
 use opentelemetry_semantic_conventions::{attribute::SERVICE_VERSION, SCHEMA_URL};
 use tracing::Subscriber;
-use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
 use tracing_subscriber::registry::LookupSpan;
+use url::Url;
+
+/// Default service name reported to the OTLP endpoint.
+pub const DEFAULT_SERVICE_NAME: &str = "reth";
+
+/// Configuration of the OTLP span export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtlpConfig {
+    /// HTTP endpoint of the collector, e.g. `http://localhost:4318/v1/traces`.
+    pub endpoint: Url,
+    /// Service name reported with the spans.
+    pub service_name: String,
+    /// Ratio of the traces to sample, between `0.0` and `1.0`.
+    ///
+    /// Traces started by a remote parent, e.g. an RPC request with trace headers, follow the
+    /// sampling decision of the parent.
+    pub sample_ratio: f64,
+}
+
+impl OtlpConfig {
+    /// Creates a new configuration exporting all traces to the given endpoint.
+    pub fn new(endpoint: Url) -> Self {
+        Self { endpoint, service_name: DEFAULT_SERVICE_NAME.to_string(), sample_ratio: 1.0 }
+    }
+
+    /// Sets the service name.
+    pub fn with_service_name(mut self, service_name: impl Into<String>) -> Self {
+        self.service_name = service_name.into();
+        self
+    }
+
+    /// Sets the ratio of the traces to sample.
+    pub const fn with_sample_ratio(mut self, sample_ratio: f64) -> Self {
+        self.sample_ratio = sample_ratio;
+        self
+    }
+}
 
 /// Creates a tracing [`OpenTelemetryLayer`] that exports spans to an OTLP endpoint.
 ///
@@ -36,3 +80,171 @@ where
     let tracer = provider.tracer("reth-otlp");
     tracing_opentelemetry::layer().with_tracer(tracer)
 }
+
+/// Creates a [`SdkTracerProvider`] that exports sampled spans in batches to the configured
+/// endpoint over HTTP.
+pub fn tracer_provider(config: &OtlpConfig) -> eyre::Result<SdkTracerProvider> {
+    eyre::ensure!(
+        (0.0..=1.0).contains(&config.sample_ratio),
+        "OTLP sample ratio must be between 0.0 and 1.0, got {}",
+        config.sample_ratio
+    );
+
+    let exporter =
+        SpanExporter::builder().with_http().with_endpoint(config.endpoint.as_str()).build()?;
+
+    let resource = Resource::builder()
+        .with_service_name(config.service_name.clone())
+        .with_schema_url([KeyValue::new(SERVICE_VERSION, env!("CARGO_PKG_VERSION"))], SCHEMA_URL)
+        .build();
+
+    Ok(SdkTracerProvider::builder()
+        .with_resource(resource)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            config.sample_ratio,
+        ))))
+        .with_batch_exporter(exporter)
+        .build())
+}
+
+/// Creates a tracing [`OpenTelemetryLayer`] exporting spans according to the [`OtlpConfig`].
+///
+/// The tracer provider and the W3C trace context propagator are installed globally, so that
+/// [`set_parent_from_headers`] can continue traces of incoming requests.
+pub fn span_layer<S>(config: &OtlpConfig) -> eyre::Result<OpenTelemetryLayer<S, SdkTracer>>
+where
+    for<'span> S: Subscriber + LookupSpan<'span>,
+{
+    let provider = tracer_provider(config)?;
+    let tracer = provider.tracer("reth-otlp");
+
+    global::set_tracer_provider(provider);
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Extracts the remote trace context from the HTTP headers, e.g. `traceparent`, using the global
+/// propagator.
+///
+/// Returns an empty context if the headers don't contain a trace context, or if no propagator was
+/// installed with [`span_layer`].
+pub fn extract_context(headers: &HeaderMap) -> Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)))
+}
+
+/// Sets the parent of the span to the remote trace context of the HTTP headers, if any.
+pub fn set_parent_from_headers(span: &tracing::Span, headers: &HeaderMap) {
+    let context = extract_context(headers);
+    if context.span().span_context().is_valid() {
+        span.set_parent(context);
+    }
+}
+
+/// [`Extractor`] of the trace context from [`HeaderMap`].
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        time::Duration,
+    };
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// Starts a collector stub that records the request line of every export request.
+    fn collector_stub() -> (Url, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint =
+            format!("http://{}/v1/traces", listener.local_addr().unwrap()).parse().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { break };
+                let mut reader = BufReader::new(stream);
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                reader
+                    .into_inner()
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                    .unwrap();
+                let _ = tx.send(request_line.trim().to_string());
+            }
+        });
+
+        (endpoint, rx)
+    }
+
+    #[test]
+    fn export_to_collector() {
+        let (endpoint, requests) = collector_stub();
+        let provider = tracer_provider(&OtlpConfig::new(endpoint)).unwrap();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info_span!("exported").in_scope(|| {});
+        });
+        provider.force_flush().unwrap();
+
+        assert_eq!(
+            requests.recv_timeout(Duration::from_secs(10)).unwrap(),
+            "POST /v1/traces HTTP/1.1"
+        );
+    }
+
+    #[test]
+    fn invalid_sample_ratio() {
+        let config = OtlpConfig::new("http://localhost:4318/v1/traces".parse().unwrap())
+            .with_sample_ratio(1.5);
+        assert!(tracer_provider(&config).is_err());
+    }
+
+    #[test]
+    fn extract_trace_context() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01".parse().unwrap(),
+        );
+        let context = extract_context(&headers);
+        let span_context = context.span().span_context().clone();
+        assert!(span_context.is_remote());
+        assert_eq!(span_context.trace_id().to_string(), "0af7651916cd43dd8448eb211c80319c");
+
+        assert!(!extract_context(&HeaderMap::new()).span().span_context().is_valid());
+    }
+}
@@ -0,0 +1,255 @@
+use std::{
+    fmt,
+    str::FromStr,
+    sync::{Arc, OnceLock},
+};
+
+use parking_lot::Mutex;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::{filter::Directive, reload, EnvFilter, Registry};
+
+use crate::layers::build_env_filter;
+
+/// Handle of the log filters installed by the global [`RethTracer`](crate::RethTracer).
+static LOG_FILTER_HANDLE: OnceLock<LogFilterHandle> = OnceLock::new();
+
+/// Returns the handle to change the log filters at runtime, if the global subscriber was installed
+/// by the [`RethTracer`](crate::RethTracer).
+pub fn log_filter_handle() -> Option<&'static LogFilterHandle> {
+    LOG_FILTER_HANDLE.get()
+}
+
+/// Installs the handle returned by [`log_filter_handle`].
+pub(crate) fn set_log_filter_handle(handle: LogFilterHandle) {
+    let _ = LOG_FILTER_HANDLE.set(handle);
+}
+
+/// A sink of the log output with its own filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogSink {
+    /// Logs written to stdout.
+    Stdout,
+    /// Logs written to the log file.
+    File,
+    /// Logs written to journald.
+    Journald,
+    /// Spans exported to an OTLP endpoint.
+    Otlp,
+}
+
+impl fmt::Display for LogSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdout => write!(f, "stdout"),
+            Self::File => write!(f, "file"),
+            Self::Journald => write!(f, "journald"),
+            Self::Otlp => write!(f, "otlp"),
+        }
+    }
+}
+
+impl FromStr for LogSink {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "stdout" => Ok(Self::Stdout),
+            "file" => Ok(Self::File),
+            "journald" => Ok(Self::Journald),
+            "otlp" => Ok(Self::Otlp),
+            _ => eyre::bail!("unknown log sink {s}, expected one of stdout, file, journald, otlp"),
+        }
+    }
+}
+
+/// The reloadable filter of a [`LogSink`].
+pub(crate) struct SinkFilter {
+    pub(crate) sink: LogSink,
+    /// Directive for the default logging level, as configured on startup.
+    pub(crate) default_directive: Option<Directive>,
+    /// Filter directives of the sink, as configured on startup or with
+    /// [`LogFilterHandle::set_filter`].
+    pub(crate) filters: String,
+    pub(crate) handle: reload::Handle<EnvFilter, Registry>,
+}
+
+impl fmt::Debug for SinkFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SinkFilter")
+            .field("sink", &self.sink)
+            .field("default_directive", &self.default_directive)
+            .field("filters", &self.filters)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Default)]
+struct LogFilterState {
+    sinks: Vec<SinkFilter>,
+    /// Level overriding the level of all sinks, set with [`LogFilterHandle::set_verbosity`].
+    verbosity: Option<LevelFilter>,
+    /// Directives applied to all sinks, set with [`LogFilterHandle::set_vmodule`].
+    vmodule: String,
+}
+
+impl LogFilterState {
+    /// Rebuilds the filters of the sinks matching `sink`, or of all sinks if `None`.
+    fn reload(&self, sink: Option<LogSink>) -> eyre::Result<()> {
+        for sink_filter in self.sinks.iter().filter(|s| sink.is_none_or(|sink| s.sink == sink)) {
+            let (default_directive, filters) = match self.verbosity {
+                // The verbosity overrides the global levels of the sink, but keeps the per-target
+                // directives.
+                Some(level) => (
+                    Some(level.into()),
+                    sink_filter
+                        .filters
+                        .split(',')
+                        .filter(|directive| directive.parse::<LevelFilter>().is_err())
+                        .collect::<Vec<_>>()
+                        .join(","),
+                ),
+                None => (sink_filter.default_directive.clone(), sink_filter.filters.clone()),
+            };
+
+            let filter =
+                build_env_filter(default_directive, &[filters, self.vmodule.clone()].join(","))?;
+            sink_filter.handle.reload(filter)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Handle to change the filters of the [`LogSink`]s at runtime.
+///
+/// The filters are composed of:
+/// - the filter of each sink, configured on startup and replaceable with
+///   [`LogFilterHandle::set_filter`],
+/// - an optional level overriding the default level of all sinks, see
+///   [`LogFilterHandle::set_verbosity`],
+/// - directives applied to all sinks, see [`LogFilterHandle::set_vmodule`].
+#[derive(Debug, Clone, Default)]
+pub struct LogFilterHandle {
+    state: Arc<Mutex<LogFilterState>>,
+}
+
+impl LogFilterHandle {
+    /// Creates a new handle for the given sink filters.
+    pub(crate) fn new(sinks: Vec<SinkFilter>) -> Self {
+        Self { state: Arc::new(Mutex::new(LogFilterState { sinks, ..Default::default() })) }
+    }
+
+    /// Returns the sinks with a reloadable filter.
+    pub fn sinks(&self) -> Vec<LogSink> {
+        self.state.lock().sinks.iter().map(|sink| sink.sink).collect()
+    }
+
+    /// Sets the maximum level of all sinks, or restores the configured levels if `None`.
+    pub fn set_verbosity(&self, level: Option<LevelFilter>) -> eyre::Result<()> {
+        let mut state = self.state.lock();
+        state.verbosity = level;
+        state.reload(None)
+    }
+
+    /// Sets the directives applied to all sinks on top of their own filters, e.g.
+    /// `net::session=trace`. An empty string removes them.
+    pub fn set_vmodule(&self, directives: &str) -> eyre::Result<()> {
+        validate_directives(directives)?;
+
+        let mut state = self.state.lock();
+        state.vmodule = directives.to_string();
+        state.reload(None)
+    }
+
+    /// Replaces the filter directives of the given sink, or of all sinks if `None`.
+    pub fn set_filter(&self, sink: Option<LogSink>, directives: &str) -> eyre::Result<()> {
+        validate_directives(directives)?;
+
+        let mut state = self.state.lock();
+        if let Some(sink) = sink {
+            eyre::ensure!(
+                state.sinks.iter().any(|s| s.sink == sink),
+                "log sink {sink} is not enabled"
+            );
+        }
+        for sink_filter in state.sinks.iter_mut().filter(|s| sink.is_none_or(|sink| s.sink == sink))
+        {
+            sink_filter.filters = directives.to_string();
+        }
+        state.reload(sink)
+    }
+}
+
+/// Returns an error if any of the comma-separated directives is invalid.
+fn validate_directives(directives: &str) -> eyre::Result<()> {
+    for directive in directives.split(',').filter(|d| !d.is_empty()) {
+        directive
+            .parse::<Directive>()
+            .map_err(|err| eyre::eyre!("invalid filter directive {directive}: {err}"))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::{layer::SubscriberExt, Layer};
+
+    #[test]
+    fn reload_filters() {
+        let (stdout_filter, stdout_handle) =
+            reload::Layer::new(build_env_filter(Some(LevelFilter::INFO.into()), "").unwrap());
+        let (file_filter, file_handle) =
+            reload::Layer::new(build_env_filter(None, "debug").unwrap());
+        let handle = LogFilterHandle::new(vec![
+            SinkFilter {
+                sink: LogSink::Stdout,
+                default_directive: Some(LevelFilter::INFO.into()),
+                filters: String::new(),
+                handle: stdout_handle.clone(),
+            },
+            SinkFilter {
+                sink: LogSink::File,
+                default_directive: None,
+                filters: "debug".to_string(),
+                handle: file_handle.clone(),
+            },
+        ]);
+        let subscriber = tracing_subscriber::registry().with(vec![
+            tracing_subscriber::fmt::layer().with_filter(stdout_filter).boxed(),
+            tracing_subscriber::fmt::layer().with_filter(file_filter).boxed(),
+        ]);
+        let max_level = |handle: &reload::Handle<EnvFilter, Registry>| {
+            handle.with_current(|filter| filter.max_level_hint()).unwrap()
+        };
+
+        tracing::subscriber::with_default(subscriber, || {
+            assert_eq!(handle.sinks(), vec![LogSink::Stdout, LogSink::File]);
+            assert_eq!(max_level(&stdout_handle), Some(LevelFilter::INFO));
+            assert_eq!(max_level(&file_handle), Some(LevelFilter::DEBUG));
+
+            // Verbosity overrides the levels of all sinks
+            handle.set_verbosity(Some(LevelFilter::WARN)).unwrap();
+            assert_eq!(max_level(&stdout_handle), Some(LevelFilter::WARN));
+            assert_eq!(max_level(&file_handle), Some(LevelFilter::WARN));
+
+            // Vmodule directives apply to all sinks
+            handle.set_vmodule("net::session=trace").unwrap();
+            assert_eq!(max_level(&stdout_handle), Some(LevelFilter::TRACE));
+            assert_eq!(max_level(&file_handle), Some(LevelFilter::TRACE));
+            assert!(handle.set_vmodule("net::session=loud").is_err());
+
+            // Configured levels are restored
+            handle.set_vmodule("").unwrap();
+            handle.set_verbosity(None).unwrap();
+            assert_eq!(max_level(&stdout_handle), Some(LevelFilter::INFO));
+            assert_eq!(max_level(&file_handle), Some(LevelFilter::DEBUG));
+
+            // Filters can be replaced per sink
+            handle.set_filter(Some(LogSink::File), "trace").unwrap();
+            assert_eq!(max_level(&stdout_handle), Some(LevelFilter::INFO));
+            assert_eq!(max_level(&file_handle), Some(LevelFilter::TRACE));
+            assert!(handle.set_filter(Some(LogSink::Journald), "trace").is_err());
+        });
+    }
+}
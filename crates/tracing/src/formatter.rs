@@ -2,7 +2,7 @@ use crate::layers::BoxedLayer;
 use clap::ValueEnum;
 use std::{fmt, fmt::Display};
 use tracing_appender::non_blocking::NonBlocking;
-use tracing_subscriber::{EnvFilter, Layer, Registry};
+use tracing_subscriber::{layer::Filter, reload, EnvFilter, Layer, Registry};
 
 /// Represents the logging format.
 ///
@@ -43,18 +43,49 @@ impl LogFormat {
         color: Option<String>,
         file_writer: Option<NonBlocking>,
     ) -> BoxedLayer<Registry> {
-        let ansi = if let Some(color) = color {
-            std::env::var("RUST_LOG_STYLE").map(|val| val != "never").unwrap_or(color != "never")
-        } else {
-            false
-        };
-        let target = std::env::var("RUST_LOG_TARGET")
+        let target = Self::show_target(&filter);
+        self.apply_filter(filter, target, color, file_writer)
+    }
+
+    /// Same as [`LogFormat::apply`], but the filter can be changed at runtime with the returned
+    /// [`reload::Handle`].
+    pub(crate) fn apply_reloadable(
+        &self,
+        filter: EnvFilter,
+        color: Option<String>,
+        file_writer: Option<NonBlocking>,
+    ) -> (BoxedLayer<Registry>, reload::Handle<EnvFilter, Registry>) {
+        let target = Self::show_target(&filter);
+        let (filter, handle) = reload::Layer::new(filter);
+        (self.apply_filter(filter, target, color, file_writer), handle)
+    }
+
+    /// Returns whether the target should be shown in the logs.
+    fn show_target(filter: &EnvFilter) -> bool {
+        std::env::var("RUST_LOG_TARGET")
             // `RUST_LOG_TARGET` always overrides default behaviour
             .map(|val| val != "0")
             .unwrap_or_else(|_|
                 // If `RUST_LOG_TARGET` is not set, show target in logs only if the max enabled
                 // level is higher than INFO (DEBUG, TRACE)
-                filter.max_level_hint().is_none_or(|max_level| max_level > tracing::Level::INFO));
+                filter.max_level_hint().is_none_or(|max_level| max_level > tracing::Level::INFO))
+    }
+
+    fn apply_filter<F>(
+        &self,
+        filter: F,
+        target: bool,
+        color: Option<String>,
+        file_writer: Option<NonBlocking>,
+    ) -> BoxedLayer<Registry>
+    where
+        F: Filter<Registry> + Send + Sync + 'static,
+    {
+        let ansi = if let Some(color) = color {
+            std::env::var("RUST_LOG_STYLE").map(|val| val != "never").unwrap_or(color != "never")
+        } else {
+            false
+        };
 
         match self {
             Self::Json => {
@@ -3,11 +3,15 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use reth_tracing_otlp::OtlpConfig;
 use rolling_file::{RollingConditionBasic, RollingFileAppender};
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{filter::Directive, EnvFilter, Layer, Registry};
+use tracing_subscriber::{filter::Directive, reload, EnvFilter, Layer, Registry};
 
-use crate::formatter::LogFormat;
+use crate::{
+    filter::{LogFilterHandle, LogSink, SinkFilter},
+    formatter::LogFormat,
+};
 
 /// A worker guard returned by the file layer.
 ///
@@ -37,6 +41,8 @@ const DEFAULT_ENV_FILTER_DIRECTIVES: [&str; 5] = [
 #[derive(Default)]
 pub struct Layers {
     inner: Vec<BoxedLayer<Registry>>,
+    /// Reloadable filters of the sinks added by the tracer.
+    filters: Vec<SinkFilter>,
 }
 
 impl fmt::Debug for Layers {
@@ -59,9 +65,26 @@ impl Layers {
         self.inner.push(layer.boxed());
     }
 
-    /// Consumes the `Layers` instance, returning the inner vector of layers.
-    pub(crate) fn into_inner(self) -> Vec<BoxedLayer<Registry>> {
-        self.inner
+    /// Consumes the `Layers` instance, returning the inner vector of layers and the handle to
+    /// their reloadable filters.
+    pub(crate) fn into_inner(self) -> (Vec<BoxedLayer<Registry>>, LogFilterHandle) {
+        (self.inner, LogFilterHandle::new(self.filters))
+    }
+
+    /// Registers the reloadable filter of a sink.
+    fn add_filter(
+        &mut self,
+        sink: LogSink,
+        default_directive: Option<Directive>,
+        filters: &str,
+        handle: reload::Handle<EnvFilter, Registry>,
+    ) {
+        self.filters.push(SinkFilter {
+            sink,
+            default_directive,
+            filters: filters.to_string(),
+            handle,
+        });
     }
 
     /// Adds a journald layer to the layers collection.
@@ -72,9 +95,10 @@ impl Layers {
     /// # Returns
     /// An `eyre::Result<()>` indicating the success or failure of the operation.
     pub(crate) fn journald(&mut self, filter: &str) -> eyre::Result<()> {
-        let journald_filter = build_env_filter(None, filter)?;
+        let (journald_filter, handle) = reload::Layer::new(build_env_filter(None, filter)?);
         let layer = tracing_journald::layer()?.with_filter(journald_filter);
         self.add_layer(layer);
+        self.add_filter(LogSink::Journald, None, filter, handle);
         Ok(())
     }
 
@@ -98,9 +122,10 @@ impl Layers {
         filters: &str,
         color: Option<String>,
     ) -> eyre::Result<()> {
-        let filter = build_env_filter(Some(default_directive), filters)?;
-        let layer = format.apply(filter, color, None);
-        self.add_layer(layer);
+        let filter = build_env_filter(Some(default_directive.clone()), filters)?;
+        let (layer, handle) = format.apply_reloadable(filter, color, None);
+        self.inner.push(layer);
+        self.add_filter(LogSink::Stdout, Some(default_directive), filters, handle);
         Ok(())
     }
 
@@ -121,10 +146,27 @@ impl Layers {
     ) -> eyre::Result<FileWorkerGuard> {
         let (writer, guard) = file_info.create_log_writer();
         let file_filter = build_env_filter(None, filter)?;
-        let layer = format.apply(file_filter, None, Some(writer));
-        self.add_layer(layer);
+        let (layer, handle) = format.apply_reloadable(file_filter, None, Some(writer));
+        self.inner.push(layer);
+        self.add_filter(LogSink::File, None, filter, handle);
         Ok(guard)
     }
+
+    /// Adds a layer exporting spans to an OTLP endpoint.
+    ///
+    /// # Arguments
+    /// * `config` - The OTLP endpoint, service name and sampling configuration.
+    /// * `filter` - Additional filter directives as a string.
+    ///
+    /// # Returns
+    /// An `eyre::Result<()>` indicating the success or failure of the operation.
+    pub(crate) fn otlp(&mut self, config: &OtlpConfig, filter: &str) -> eyre::Result<()> {
+        let (otlp_filter, handle) = reload::Layer::new(build_env_filter(None, filter)?);
+        let layer = reth_tracing_otlp::span_layer(config)?.with_filter(otlp_filter);
+        self.add_layer(layer);
+        self.add_filter(LogSink::Otlp, None, filter, handle);
+        Ok(())
+    }
 }
 
 /// Holds configuration information for file logging.
@@ -184,7 +226,7 @@ impl FileInfo {
 ///
 /// # Returns
 /// An `eyre::Result<EnvFilter>` that can be used to configure a tracing subscriber.
-fn build_env_filter(
+pub(crate) fn build_env_filter(
     default_directive: Option<Directive>,
     directives: &str,
 ) -> eyre::Result<EnvFilter> {
@@ -49,10 +49,13 @@ pub use tracing_appender;
 pub use tracing_subscriber;
 
 // Re-export our types
+pub use filter::{log_filter_handle, LogFilterHandle, LogSink};
 pub use formatter::LogFormat;
 pub use layers::{FileInfo, FileWorkerGuard, Layers};
+pub use reth_tracing_otlp::OtlpConfig;
 pub use test_tracer::TestTracer;
 
+mod filter;
 mod formatter;
 mod layers;
 mod test_tracer;
@@ -70,6 +73,7 @@ pub struct RethTracer {
     stdout: LayerInfo,
     journald: Option<String>,
     file: Option<(LayerInfo, FileInfo)>,
+    otlp: Option<(OtlpConfig, String)>,
 }
 
 impl RethTracer {
@@ -78,7 +82,7 @@ impl RethTracer {
     ///  Initializes with default stdout layer configuration.
     ///  Journald and file layers are not set by default.
     pub fn new() -> Self {
-        Self { stdout: LayerInfo::default(), journald: None, file: None }
+        Self { stdout: LayerInfo::default(), journald: None, file: None, otlp: None }
     }
 
     ///  Sets a custom configuration for the stdout layer.
@@ -108,6 +112,16 @@ impl RethTracer {
         self.file = Some((config, file_info));
         self
     }
+
+    ///  Sets the OTLP span export configuration and its filter.
+    ///
+    ///  # Arguments
+    ///  * `config` - The `OtlpConfig` with the endpoint to export spans to.
+    ///  * `filter` - The `filter` to use for the exported spans.
+    pub fn with_otlp(mut self, config: OtlpConfig, filter: String) -> Self {
+        self.otlp = Some((config, filter));
+        self
+    }
 }
 
 impl Default for RethTracer {
@@ -199,7 +213,7 @@ impl Tracer for RethTracer {
     ///  Initializes the logging system based on the configured layers.
     ///
     ///  This method sets up the global tracing subscriber with the specified
-    ///  stdout, journald, and file layers.
+    ///  stdout, journald, file, and OTLP layers.
     ///
     ///  The default layer is stdout.
     ///
@@ -218,6 +232,10 @@ impl Tracer for RethTracer {
             layers.journald(&config)?;
         }
 
+        if let Some((config, filter)) = self.otlp {
+            layers.otlp(&config, &filter)?;
+        }
+
         let file_guard = if let Some((config, file_info)) = self.file {
             Some(layers.file(config.format, &config.filters, file_info)?)
         } else {
@@ -226,7 +244,10 @@ impl Tracer for RethTracer {
 
         // The error is returned if the global default subscriber is already set,
         // so it's safe to ignore it
-        let _ = tracing_subscriber::registry().with(layers.into_inner()).try_init();
+        let (layers, filter_handle) = layers.into_inner();
+        if tracing_subscriber::registry().with(layers).try_init().is_ok() {
+            filter::set_log_filter_handle(filter_handle);
+        }
         Ok(file_guard)
     }
 }
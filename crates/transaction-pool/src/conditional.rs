@@ -0,0 +1,177 @@
+//! Support for pooled transactions with a [`TransactionConditional`].
+//!
+//! A conditional restricts the blocks a transaction can be included in, by block number and
+//! timestamp bounds and by the expected storage of known accounts. Transactions with a conditional
+//! are removed from the pool by the [maintenance task](crate::maintain) once the conditional can no
+//! longer hold for the block after the canonical tip.
+
+use crate::error::PoolTransactionError;
+use alloy_consensus::conditional::BlockConditionalAttributes;
+use alloy_primitives::{Address, StorageKey, B256, U256};
+use alloy_rpc_types_eth::erc4337::{AccountStorage, TransactionConditional};
+use reth_storage_api::{errors::provider::ProviderError, StateProvider, StorageRootProvider};
+use std::any::Any;
+
+/// Helper trait that allows attaching a [`TransactionConditional`].
+pub trait MaybeConditionalTransaction {
+    /// Attach a [`TransactionConditional`].
+    fn set_conditional(&mut self, conditional: TransactionConditional);
+
+    /// Get attached [`TransactionConditional`] if any.
+    fn conditional(&self) -> Option<&TransactionConditional>;
+
+    /// Check if the conditional has exceeded the block attributes.
+    fn has_exceeded_block_attributes(&self, block_attr: &BlockConditionalAttributes) -> bool {
+        self.conditional().map(|tc| tc.has_exceeded_block_attributes(block_attr)).unwrap_or(false)
+    }
+
+    /// Check if the block attributes are within the bounds of the conditional, if any.
+    fn matches_block_attributes(&self, block_attr: &BlockConditionalAttributes) -> bool {
+        self.conditional().is_none_or(|tc| matches_block_attributes(tc, block_attr))
+    }
+
+    /// Helper that sets the conditional and returns the instance again
+    fn with_conditional(mut self, conditional: TransactionConditional) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_conditional(conditional);
+        self
+    }
+}
+
+/// Errors of a [`TransactionConditional`] that doesn't hold.
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionConditionalError {
+    /// Thrown if the block is outside the block number or timestamp bounds of the conditional.
+    #[error("block outside of the conditional's block number or timestamp bounds")]
+    BlockAttributesMismatch,
+    /// Thrown if a storage slot of a known account doesn't hold the expected value.
+    #[error("storage value mismatch for {address} at slot {slot}")]
+    StorageValueMismatch {
+        /// The known account.
+        address: Address,
+        /// The mismatching storage slot.
+        slot: U256,
+    },
+    /// Thrown if the storage root of a known account doesn't match the expected root.
+    #[error("storage root mismatch for {0}")]
+    StorageRootMismatch(Address),
+    /// Failed to read the state.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+impl PoolTransactionError for TransactionConditionalError {
+    fn is_bad_transaction(&self) -> bool {
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// State that the known accounts of a [`TransactionConditional`] are validated against.
+///
+/// This is implemented for any [`StateProvider`], and can be implemented for the state of a block
+/// that is being built.
+pub trait KnownAccountsState {
+    /// Returns the value of the storage slot of the account, zero if it's not set.
+    fn storage_value(&mut self, address: Address, slot: U256) -> Result<U256, ProviderError>;
+
+    /// Returns the storage root of the account.
+    fn storage_root(&mut self, address: Address) -> Result<B256, ProviderError>;
+}
+
+impl<S: StateProvider + ?Sized> KnownAccountsState for &S {
+    fn storage_value(&mut self, address: Address, slot: U256) -> Result<U256, ProviderError> {
+        Ok(self.storage(address, StorageKey::from(slot))?.unwrap_or_default())
+    }
+
+    fn storage_root(&mut self, address: Address) -> Result<B256, ProviderError> {
+        StorageRootProvider::storage_root(*self, address, Default::default())
+    }
+}
+
+/// Validates the [`TransactionConditional`] for inclusion in a block with the given attributes,
+/// on top of the given state.
+pub fn validate_conditional<S: KnownAccountsState>(
+    state: S,
+    conditional: &TransactionConditional,
+    block_attr: &BlockConditionalAttributes,
+) -> Result<(), TransactionConditionalError> {
+    if !matches_block_attributes(conditional, block_attr) {
+        return Err(TransactionConditionalError::BlockAttributesMismatch)
+    }
+    validate_known_accounts(state, conditional)
+}
+
+/// Validates the known accounts of the [`TransactionConditional`] against the given state.
+pub fn validate_known_accounts<S: KnownAccountsState>(
+    mut state: S,
+    conditional: &TransactionConditional,
+) -> Result<(), TransactionConditionalError> {
+    for (address, storage) in &conditional.known_accounts {
+        match storage {
+            AccountStorage::Slots(slots) => {
+                for (slot, expected_value) in slots {
+                    let current = state.storage_value(*address, *slot)?;
+                    if current != U256::from_be_bytes(**expected_value) {
+                        return Err(TransactionConditionalError::StorageValueMismatch {
+                            address: *address,
+                            slot: *slot,
+                        })
+                    }
+                }
+            }
+            AccountStorage::RootHash(expected_root) => {
+                if state.storage_root(*address)? != *expected_root {
+                    return Err(TransactionConditionalError::StorageRootMismatch(*address))
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns `true` if the block attributes are within the bounds of the conditional.
+fn matches_block_attributes(
+    conditional: &TransactionConditional,
+    block_attr: &BlockConditionalAttributes,
+) -> bool {
+    !conditional.has_exceeded_block_attributes(block_attr) &&
+        conditional.block_number_min.is_none_or(|min| block_attr.number >= min) &&
+        conditional.timestamp_min.is_none_or(|min| block_attr.timestamp >= min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TransactionGenerator;
+
+    #[test]
+    fn matches_block_attributes_within_bounds() {
+        let conditional = TransactionConditional {
+            block_number_min: Some(10),
+            block_number_max: Some(20),
+            timestamp_min: Some(100),
+            timestamp_max: Some(200),
+            ..Default::default()
+        };
+        let mut tx_gen = TransactionGenerator::new(rand::rng());
+        let tx = tx_gen.gen_eip1559_pooled().with_conditional(conditional);
+
+        let attr = |number, timestamp| BlockConditionalAttributes { number, timestamp };
+        assert!(tx.matches_block_attributes(&attr(10, 100)));
+        assert!(tx.matches_block_attributes(&attr(20, 200)));
+        assert!(!tx.matches_block_attributes(&attr(9, 150)));
+        assert!(!tx.matches_block_attributes(&attr(15, 99)));
+        assert!(!tx.matches_block_attributes(&attr(21, 150)));
+        assert!(tx.has_exceeded_block_attributes(&attr(21, 150)));
+        assert!(!tx.has_exceeded_block_attributes(&attr(9, 150)));
+
+        // transactions without a conditional always match
+        assert!(tx_gen.gen_eip1559_pooled().matches_block_attributes(&attr(0, 0)));
+    }
+}
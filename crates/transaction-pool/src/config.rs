@@ -1,4 +1,5 @@
 use crate::{
+    eviction::EvictionPolicy,
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     PoolSize, TransactionOrigin,
@@ -6,7 +7,7 @@ use crate::{
 use alloy_consensus::constants::EIP4844_TX_TYPE_ID;
 use alloy_eips::eip1559::{ETHEREUM_BLOCK_GAS_LIMIT_30M, MIN_PROTOCOL_BASE_FEE};
 use alloy_primitives::Address;
-use std::{collections::HashSet, ops::Mul, time::Duration};
+use std::{collections::HashSet, ops::Mul, sync::Arc, time::Duration};
 
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub const TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
@@ -65,6 +66,10 @@ pub struct PoolConfig {
     pub max_new_pending_txs_notifications: usize,
     /// Maximum lifetime for transactions in the pool
     pub max_queued_lifetime: Duration,
+    /// Admission and eviction policy applied on top of the fee based eviction, if any.
+    ///
+    /// See also [`EvictionPolicy`].
+    pub eviction_policy: Option<Arc<dyn EvictionPolicy>>,
 }
 
 impl PoolConfig {
@@ -96,6 +101,7 @@ impl Default for PoolConfig {
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            eviction_policy: None,
         }
     }
 }
@@ -2,8 +2,10 @@
 
 use std::any::Any;
 
+use crate::inclusion::InclusionPolicyViolation;
 use alloy_eips::eip4844::BlobTransactionValidationError;
 use alloy_primitives::{Address, TxHash, U256};
+use reth_network_peers::PeerId;
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
 
 /// Transaction pool result type.
@@ -56,6 +58,10 @@ pub enum PoolErrorKind {
     /// Thrown when the number of unique transactions of a sender exceeded the slot capacity.
     #[error("rejected due to {0} being identified as a spammer")]
     SpammerExceededCapacity(Address),
+    /// Thrown when the transactions received from a network peer exceed the capacity of the
+    /// [`EvictionPolicy`](crate::eviction::EvictionPolicy).
+    #[error("rejected due to peer {0} exceeding its pool capacity")]
+    PeerExceededCapacity(PeerId),
     /// Thrown when a new transaction is added to the pool, but then immediately discarded to
     /// respect the size limits of the pool.
     #[error("transaction discarded outright due to pool size constraints")]
@@ -126,6 +132,11 @@ impl PoolError {
                 // (pool lags behind) and old transaction still occupy a slot in the pool
                 false
             }
+            PoolErrorKind::PeerExceededCapacity(_) => {
+                // the peer relayed more than its share of the pool, which may well be valid
+                // transactions of other senders, so we should not penalize it
+                false
+            }
             PoolErrorKind::DiscardedOnInsert => {
                 // valid tx but dropped due to size constraints
                 false
@@ -273,6 +284,10 @@ pub enum InvalidPoolTransactionError {
         /// Minimum required priority fee.
         minimum_priority_fee: u128,
     },
+    /// Thrown if the transaction touches an address that is denied by the configured
+    /// [`InclusionPolicy`](crate::inclusion::InclusionPolicy).
+    #[error(transparent)]
+    InclusionPolicy(#[from] InclusionPolicyViolation),
 }
 
 // === impl InvalidPoolTransactionError ===
@@ -388,6 +403,10 @@ impl InvalidPoolTransactionError {
                 Eip7702PoolTransactionError::AuthorityReserved => false,
             },
             Self::PriorityFeeBelowMinimum { .. } => false,
+            Self::InclusionPolicy(_) => {
+                // local setting
+                false
+            }
         }
     }
 
@@ -402,6 +421,11 @@ impl InvalidPoolTransactionError {
             matches!(self, Self::Eip4844(Eip4844PoolTransactionError::Eip4844NonceGap))
     }
 
+    /// Returns `true` if the transaction was rejected by the configured inclusion policy.
+    pub const fn is_inclusion_policy_violation(&self) -> bool {
+        matches!(self, Self::InclusionPolicy(_))
+    }
+
     /// Returns the arbitrary error if it is [`InvalidPoolTransactionError::Other`]
     pub fn as_other(&self) -> Option<&dyn PoolTransactionError> {
         match self {
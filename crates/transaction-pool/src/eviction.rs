@@ -0,0 +1,523 @@
+//! Admission and eviction policies of the transaction pool.
+//!
+//! By default, the pool bounds the transactions of a sender by
+//! [`max_account_slots`](crate::PoolConfig::max_account_slots) and evicts transactions from a
+//! sub-pool that exceeds its [`SubPoolLimit`] by fee. An [`EvictionPolicy`] configured via
+//! [`PoolConfig::eviction_policy`](crate::PoolConfig::eviction_policy) additionally decides which
+//! new transactions are admitted to the pool, and which transactions are evicted first from an
+//! oversized sub-pool. Transactions that the policy doesn't select are evicted by fee as before.
+//!
+//! The [`FairShareEvictionPolicy`] bounds the pool space of each sender and of each network peer
+//! the transactions are received from, so that a single sender or peer can't churn the pool.
+//! Transactions submitted via RPC or by the node itself have no peer, so they are only bounded by
+//! their sender.
+
+use crate::{
+    identifier::{SenderId, TransactionId},
+    pool::state::SubPool,
+    SubPoolLimit, TransactionOrigin,
+};
+use reth_network_peers::PeerId;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{
+    collections::{hash_map, BTreeMap},
+    fmt,
+    hash::Hash,
+    time::Instant,
+};
+
+/// Default maximum combined size of the transactions of a sender, see
+/// [`FairShareEvictionPolicy`].
+pub const DEFAULT_MAX_SENDER_SIZE: usize = 1024 * 1024;
+
+/// Default maximum combined size of the transactions received from a peer, see
+/// [`FairShareEvictionPolicy`].
+pub const DEFAULT_MAX_PEER_SIZE: usize = 4 * 1024 * 1024;
+
+/// A transaction considered by an [`EvictionPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvictionCandidate {
+    /// The sender and nonce of the transaction.
+    pub id: TransactionId,
+    /// Where the transaction was received from.
+    pub origin: TransactionOrigin,
+    /// The peer the transaction was received from, if it was imported from the network, see
+    /// [`ValidPoolTransaction::peer`](crate::ValidPoolTransaction::peer).
+    pub peer: Option<PeerId>,
+    /// Whether the transaction is treated as local, see
+    /// [`LocalTransactionConfig::is_local`](crate::LocalTransactionConfig::is_local).
+    pub local: bool,
+    /// The size of the transaction in the pool.
+    pub size: usize,
+    /// When the transaction was added to the pool.
+    pub timestamp: Instant,
+    /// How often the sender replaced one of its pooled transactions.
+    pub sender_replacements: usize,
+}
+
+/// The transactions of a sender in the pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SenderUsage {
+    /// The number of transactions of the sender.
+    pub txs: usize,
+    /// The combined size of the transactions of the sender.
+    pub size: usize,
+    /// How often the sender replaced one of its pooled transactions.
+    ///
+    /// This is reset once the sender has no transactions left in the pool.
+    pub replacements: usize,
+}
+
+/// The transactions received from a network peer in the pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PeerUsage {
+    /// The number of transactions received from the peer.
+    pub txs: usize,
+    /// The combined size of the transactions received from the peer.
+    pub size: usize,
+}
+
+/// The reason an [`EvictionPolicy`] rejected a new transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdmissionError {
+    /// The transactions of the sender would exceed its capacity.
+    SenderExceededCapacity,
+    /// The transactions received from the peer would exceed its capacity.
+    PeerExceededCapacity(PeerId),
+}
+
+/// The non-local transactions of a sender or peer in a sub-pool.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShareTransactions {
+    /// The transactions, ordered by sender and nonce.
+    pub txs: BTreeMap<TransactionId, EvictionCandidate>,
+    /// The combined size of the transactions.
+    pub size: usize,
+}
+
+/// The transactions of a sub-pool, with its non-local transactions grouped by sender and by the
+/// network peer they were received from.
+///
+/// The pool updates this as transactions enter and leave the sub-pool, so an [`EvictionPolicy`]
+/// only needs to look at the senders and peers it evicts from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubPoolShares {
+    /// The number of transactions in the sub-pool.
+    pub len: usize,
+    /// The combined size of the transactions in the sub-pool.
+    pub size: usize,
+    /// The non-local transactions by sender.
+    pub senders: FxHashMap<SenderId, ShareTransactions>,
+    /// The non-local transactions by the peer they were received from.
+    pub peers: FxHashMap<PeerId, ShareTransactions>,
+}
+
+impl SubPoolShares {
+    /// Adds a transaction that entered the sub-pool.
+    pub fn insert(&mut self, tx: EvictionCandidate) {
+        self.len += 1;
+        self.size += tx.size;
+        if tx.local {
+            return
+        }
+        if let Some(peer) = tx.peer {
+            let share = self.peers.entry(peer).or_default();
+            share.size += tx.size;
+            share.txs.insert(tx.id, tx);
+        }
+        let share = self.senders.entry(tx.id.sender).or_default();
+        share.size += tx.size;
+        share.txs.insert(tx.id, tx);
+    }
+
+    /// Removes a transaction that left the sub-pool.
+    pub fn remove(&mut self, tx: &EvictionCandidate) {
+        self.len = self.len.saturating_sub(1);
+        self.size = self.size.saturating_sub(tx.size);
+        if let Some(peer) = tx.peer {
+            remove_from_share(&mut self.peers, peer, &tx.id);
+        }
+        remove_from_share(&mut self.senders, tx.id.sender, &tx.id);
+    }
+}
+
+/// Removes a transaction from the share of the given sender or peer, and the share once it's
+/// empty.
+fn remove_from_share<K: Eq + Hash>(
+    shares: &mut FxHashMap<K, ShareTransactions>,
+    key: K,
+    id: &TransactionId,
+) {
+    if let hash_map::Entry::Occupied(mut entry) = shares.entry(key) {
+        let share = entry.get_mut();
+        if let Some(tx) = share.txs.remove(id) {
+            share.size = share.size.saturating_sub(tx.size);
+        }
+        if share.txs.is_empty() {
+            entry.remove();
+        }
+    }
+}
+
+/// The [`SubPoolShares`] of every sub-pool.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PoolShares {
+    pending: SubPoolShares,
+    basefee: SubPoolShares,
+    blob: SubPoolShares,
+    queued: SubPoolShares,
+}
+
+impl PoolShares {
+    /// Returns the shares of the given sub-pool.
+    pub(crate) const fn get(&self, pool: SubPool) -> &SubPoolShares {
+        match pool {
+            SubPool::Pending => &self.pending,
+            SubPool::BaseFee => &self.basefee,
+            SubPool::Blob => &self.blob,
+            SubPool::Queued => &self.queued,
+        }
+    }
+
+    /// Returns the mutable shares of the given sub-pool.
+    pub(crate) const fn get_mut(&mut self, pool: SubPool) -> &mut SubPoolShares {
+        match pool {
+            SubPool::Pending => &mut self.pending,
+            SubPool::BaseFee => &mut self.basefee,
+            SubPool::Blob => &mut self.blob,
+            SubPool::Queued => &mut self.queued,
+        }
+    }
+}
+
+/// Decides which transactions enter the pool, and which are evicted first once a sub-pool exceeds
+/// its limits.
+pub trait EvictionPolicy: fmt::Debug + Send + Sync {
+    /// Returns whether the new transaction is admitted to the pool, or why it's rejected.
+    ///
+    /// `sender` is the usage of the other transactions of the sender, excluding a transaction that
+    /// is replaced by the new transaction, and `peer` the usage of the other transactions received
+    /// from the [`EvictionCandidate::peer`] of the new transaction, empty if it has none.
+    /// Transactions with the on-chain nonce of the sender are always admitted.
+    fn admit(
+        &self,
+        tx: &EvictionCandidate,
+        sender: &SenderUsage,
+        peer: &PeerUsage,
+    ) -> Result<(), AdmissionError>;
+
+    /// Returns the transactions to evict from a sub-pool that exceeds the given limit, in order.
+    ///
+    /// The descendants of an evicted transaction are evicted as well.
+    fn select_evictions(&self, shares: &SubPoolShares, limit: &SubPoolLimit) -> Vec<TransactionId>;
+}
+
+/// An [`EvictionPolicy`] that gives every sender a fair share of the pool.
+///
+/// - A new non-local transaction is rejected if the transactions of its sender would exceed
+///   [`max_sender_size`](Self::max_sender_size), or the transactions received from its network peer
+///   would exceed [`max_peer_size`](Self::max_peer_size). Transactions submitted via RPC have no
+///   peer, so they are only bounded by their sender.
+/// - When a sub-pool exceeds its limit, the size available to each peer is the size limit of the
+///   sub-pool divided by the number of peers the non-local transactions in the sub-pool were
+///   received from. Peers exceeding their share are trimmed down to it, starting with the highest
+///   sender and nonce. Peers that exceed their share the most go first, where every replacement of
+///   one of their senders counts as another copy of their transactions, and equal peers that
+///   relayed more recently go before peers whose transactions have been waiting longer.
+/// - If the sub-pool still exceeds its limit, the non-local senders are trimmed to their share the
+///   same way, starting with their highest nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FairShareEvictionPolicy {
+    /// Maximum combined size of the transactions of a non-local sender.
+    pub max_sender_size: usize,
+    /// Maximum combined size of the non-local transactions received from a network peer.
+    pub max_peer_size: usize,
+}
+
+impl FairShareEvictionPolicy {
+    /// Creates a policy with the given maximum combined size of the transactions of a sender, and
+    /// of the transactions received from a peer.
+    pub const fn new(max_sender_size: usize, max_peer_size: usize) -> Self {
+        Self { max_sender_size, max_peer_size }
+    }
+}
+
+impl Default for FairShareEvictionPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_SENDER_SIZE, DEFAULT_MAX_PEER_SIZE)
+    }
+}
+
+impl EvictionPolicy for FairShareEvictionPolicy {
+    fn admit(
+        &self,
+        tx: &EvictionCandidate,
+        sender: &SenderUsage,
+        peer: &PeerUsage,
+    ) -> Result<(), AdmissionError> {
+        if tx.local {
+            return Ok(())
+        }
+        if sender.size.saturating_add(tx.size) > self.max_sender_size {
+            return Err(AdmissionError::SenderExceededCapacity)
+        }
+        match tx.peer {
+            Some(id) if peer.size.saturating_add(tx.size) > self.max_peer_size => {
+                Err(AdmissionError::PeerExceededCapacity(id))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn select_evictions(&self, shares: &SubPoolShares, limit: &SubPoolLimit) -> Vec<TransactionId> {
+        let mut usage = SubPoolUsage { len: shares.len, size: shares.size, evicted: Vec::new() };
+
+        // peers first, which may relay the transactions of many senders within their share
+        let peers = shares.peers.values().map(|share| (share, share.size));
+        usage.trim_shares(peers, &FxHashSet::default(), limit);
+        if limit.is_exceeded(usage.len, usage.size) {
+            // the senders without the transactions evicted from the peers
+            let evicted = usage.evicted.iter().copied().collect::<FxHashSet<_>>();
+            let mut evicted_by_sender = FxHashMap::<SenderId, (usize, usize)>::default();
+            for id in &evicted {
+                let Some(tx) = shares.senders.get(&id.sender).and_then(|share| share.txs.get(id))
+                else {
+                    continue
+                };
+                let (txs, size) = evicted_by_sender.entry(id.sender).or_default();
+                *txs += 1;
+                *size += tx.size;
+            }
+            let senders = shares.senders.iter().filter_map(|(sender, share)| {
+                let (txs, size) = evicted_by_sender.get(sender).copied().unwrap_or_default();
+                (txs < share.txs.len()).then_some((share, share.size - size))
+            });
+            usage.trim_shares(senders, &evicted, limit);
+        }
+        usage.evicted
+    }
+}
+
+/// The usage of an oversized sub-pool, and the transactions evicted from it so far.
+struct SubPoolUsage {
+    len: usize,
+    size: usize,
+    evicted: Vec<TransactionId>,
+}
+
+impl SubPoolUsage {
+    /// Trims the groups of transactions that exceed their share of the limit down to it, until the
+    /// sub-pool is within the limit.
+    ///
+    /// `groups` are the transactions of every group with their combined size, excluding the given
+    /// transactions, and the share of every group is the size limit divided by the number of
+    /// groups. Only the transactions of the groups exceeding their share are visited.
+    fn trim_shares<'a>(
+        &mut self,
+        groups: impl Iterator<Item = (&'a ShareTransactions, usize)> + Clone,
+        excluded: &FxHashSet<TransactionId>,
+        limit: &SubPoolLimit,
+    ) {
+        let count = groups.clone().count();
+        if count == 0 {
+            return
+        }
+        let share = limit.max_size / count;
+
+        let mut groups = groups
+            .filter(|(_, size)| *size > share)
+            .map(|(group, size)| {
+                let txs = group.txs.values().filter(|tx| !excluded.contains(&tx.id));
+                Share::new(txs.collect(), size, share)
+            })
+            .collect::<Vec<_>>();
+        groups.sort_unstable_by(|a, b| {
+            b.weighted_excess().cmp(&a.weighted_excess()).then(b.last_seen.cmp(&a.last_seen))
+        });
+
+        for mut group in groups {
+            while limit.is_exceeded(self.len, self.size) && group.size > share {
+                let Some(tx) = group.txs.pop() else { break };
+                group.size -= tx.size;
+                self.len -= 1;
+                self.size -= tx.size;
+                self.evicted.push(tx.id);
+            }
+            if !limit.is_exceeded(self.len, self.size) {
+                break
+            }
+        }
+    }
+}
+
+/// The transactions of a sender or peer in an oversized sub-pool.
+struct Share<'a> {
+    /// The transactions, ordered by sender and nonce.
+    txs: Vec<&'a EvictionCandidate>,
+    /// The combined size of the transactions.
+    size: usize,
+    /// The size available to the sender or peer.
+    share: usize,
+    /// How often the senders of the transactions replaced one of their transactions, at most.
+    replacements: usize,
+    /// When the last transaction was submitted.
+    last_seen: Instant,
+}
+
+impl<'a> Share<'a> {
+    fn new(txs: Vec<&'a EvictionCandidate>, size: usize, share: usize) -> Self {
+        let replacements = txs.iter().map(|tx| tx.sender_replacements).max().unwrap_or_default();
+        let last_seen = txs.iter().map(|tx| tx.timestamp).max().expect("group has transactions");
+        Self { txs, size, share, replacements, last_seen }
+    }
+
+    /// The size exceeding the share, weighted by the replacements of the senders.
+    const fn weighted_excess(&self) -> usize {
+        (self.size - self.share).saturating_mul(self.replacements.saturating_add(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn candidate(sender: u64, nonce: u64, size: usize) -> EvictionCandidate {
+        EvictionCandidate {
+            id: TransactionId::new(SenderId::from(sender), nonce),
+            origin: TransactionOrigin::External,
+            peer: None,
+            local: false,
+            size,
+            timestamp: Instant::now(),
+            sender_replacements: 0,
+        }
+    }
+
+    fn shares(candidates: &[EvictionCandidate]) -> SubPoolShares {
+        let mut shares = SubPoolShares::default();
+        for tx in candidates {
+            shares.insert(*tx);
+        }
+        shares
+    }
+
+    #[test]
+    fn admit_within_sender_size() {
+        let policy = FairShareEvictionPolicy::new(100, 200);
+        let usage = SenderUsage { txs: 2, size: 80, replacements: 0 };
+        let peer = PeerUsage::default();
+
+        assert_eq!(policy.admit(&candidate(1, 2, 20), &usage, &peer), Ok(()));
+        assert_eq!(
+            policy.admit(&candidate(1, 2, 21), &usage, &peer),
+            Err(AdmissionError::SenderExceededCapacity)
+        );
+        // local transactions are exempt
+        let local = EvictionCandidate { local: true, ..candidate(1, 2, 21) };
+        assert_eq!(policy.admit(&local, &usage, &peer), Ok(()));
+    }
+
+    #[test]
+    fn admit_within_peer_size() {
+        let policy = FairShareEvictionPolicy::new(100, 200);
+        let usage = SenderUsage::default();
+        let peer = PeerUsage { txs: 3, size: 150 };
+        let from_peer =
+            |size| EvictionCandidate { peer: Some(PeerId::ZERO), ..candidate(1, 0, size) };
+
+        assert_eq!(policy.admit(&from_peer(50), &usage, &peer), Ok(()));
+        assert_eq!(
+            policy.admit(&from_peer(51), &usage, &peer),
+            Err(AdmissionError::PeerExceededCapacity(PeerId::ZERO))
+        );
+        // transactions that weren't received from a peer are only bound by their sender
+        assert_eq!(policy.admit(&candidate(1, 0, 51), &usage, &peer), Ok(()));
+    }
+
+    #[test]
+    fn evict_senders_exceeding_share() {
+        let policy = FairShareEvictionPolicy::default();
+        let limit = SubPoolLimit::new(usize::MAX, 300);
+
+        // sender 1 uses 400 bytes, sender 2 and 3 use 50 bytes each, the share is 100 bytes
+        let mut candidates = (0..4).map(|nonce| candidate(1, nonce, 100)).collect::<Vec<_>>();
+        candidates.push(candidate(2, 0, 50));
+        candidates.push(candidate(3, 0, 50));
+
+        let evicted = policy.select_evictions(&shares(&candidates), &limit);
+        let expected = [3, 2].map(|nonce| TransactionId::new(SenderId::from(1), nonce));
+        assert_eq!(evicted, expected);
+
+        // within the limit, nothing is evicted
+        assert!(policy.select_evictions(&shares(&candidates[2..]), &limit).is_empty());
+    }
+
+    #[test]
+    fn evict_peers_exceeding_share() {
+        let policy = FairShareEvictionPolicy::default();
+        let limit = SubPoolLimit::new(usize::MAX, 300);
+        let (spammer, honest) = (PeerId::with_last_byte(1), PeerId::with_last_byte(2));
+
+        // one peer relayed 300 of the 400 bytes, more than its share of 150 bytes
+        let candidates = (0..4)
+            .map(|sender| EvictionCandidate {
+                peer: Some(if sender == 0 { honest } else { spammer }),
+                ..candidate(sender, 0, 100)
+            })
+            .collect::<Vec<_>>();
+
+        let evicted = policy.select_evictions(&shares(&candidates), &limit);
+        assert_eq!(evicted, [TransactionId::new(SenderId::from(3), 0)]);
+    }
+
+    #[test]
+    fn evict_replacing_and_recent_senders_first() {
+        let policy = FairShareEvictionPolicy::default();
+        let limit = SubPoolLimit::new(usize::MAX, 500);
+        let earlier = Instant::now() - Duration::from_secs(60);
+
+        // both senders exceed their share of 250 bytes by 50 bytes
+        let mut candidates = Vec::new();
+        for nonce in 0..3 {
+            candidates.push(EvictionCandidate { timestamp: earlier, ..candidate(1, nonce, 100) });
+            candidates.push(candidate(2, nonce, 100));
+        }
+
+        // the sender that submitted more recently goes first
+        let evicted = policy.select_evictions(&shares(&candidates), &limit);
+        assert_eq!(evicted, [TransactionId::new(SenderId::from(2), 2)]);
+
+        // unless the other sender replaced its transactions
+        for tx in candidates.iter_mut().filter(|tx| tx.id.sender == SenderId::from(1)) {
+            tx.sender_replacements = 1;
+        }
+        let evicted = policy.select_evictions(&shares(&candidates), &limit);
+        assert_eq!(evicted, [TransactionId::new(SenderId::from(1), 2)]);
+    }
+
+    #[test]
+    fn track_shares_of_sub_pool() {
+        let from_peer = |sender, nonce| EvictionCandidate {
+            peer: Some(PeerId::ZERO),
+            ..candidate(sender, nonce, 100)
+        };
+        let local = EvictionCandidate { local: true, ..candidate(3, 0, 100) };
+        let mut shares = shares(&[from_peer(1, 0), from_peer(1, 1), from_peer(2, 0), local]);
+        assert_eq!((shares.len, shares.size), (4, 400));
+        assert_eq!(shares.peers[&PeerId::ZERO].size, 300);
+        assert_eq!(shares.senders[&SenderId::from(1)].size, 200);
+        // local transactions only count towards the sub-pool
+        assert!(!shares.senders.contains_key(&SenderId::from(3)));
+
+        shares.remove(&from_peer(1, 0));
+        shares.remove(&from_peer(2, 0));
+        shares.remove(&local);
+        assert_eq!((shares.len, shares.size), (1, 100));
+        assert_eq!(shares.peers[&PeerId::ZERO].size, 100);
+        assert_eq!(shares.senders.keys().copied().collect::<Vec<_>>(), [SenderId::from(1)]);
+
+        shares.remove(&from_peer(1, 1));
+        assert_eq!(shares, SubPoolShares::default());
+    }
+}
@@ -0,0 +1,409 @@
+//! Address based inclusion policy for the transaction pool and payload building.
+//!
+//! An [`InclusionPolicy`] is a deny-list of addresses that must not be touched by transactions
+//! that enter the pool or are included in built payloads. The policy is loaded from a plain text
+//! file, see [`InclusionPolicy::parse`] for the format, and can be swapped at runtime through an
+//! [`InclusionPolicyHandle`].
+
+use crate::{traits::TransactionPool, PoolTransaction};
+use alloy_primitives::Address;
+use parking_lot::RwLock;
+use reth_fs_util::FsPathError;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+use tokio::time::{self, Duration};
+use tracing::{debug, info, warn};
+
+/// Default interval at which the inclusion policy file is checked for changes.
+pub const DEFAULT_INCLUSION_POLICY_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A set of addresses that transactions are not allowed to touch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InclusionPolicy {
+    /// Transactions sent by any of these addresses are rejected.
+    senders: HashSet<Address>,
+    /// Transactions calling any of these addresses directly are rejected.
+    recipients: HashSet<Address>,
+    /// Transactions that call or create any of these accounts during execution are excluded from
+    /// built payloads.
+    ///
+    /// This covers internal `CALL`, `CALLCODE`, `DELEGATECALL` and `STATICCALL`s as well as
+    /// contracts deployed via `CREATE` or `CREATE2`.
+    call_targets: HashSet<Address>,
+}
+
+impl InclusionPolicy {
+    /// Parses a policy from its textual representation.
+    ///
+    /// Every non-empty line contains a single address, optionally prefixed by the scope it applies
+    /// to. Everything after a `#` is treated as a comment.
+    ///
+    /// ```text
+    /// # denied as sender, recipient and call target
+    /// 0x0000000000000000000000000000000000000001
+    /// sender 0x0000000000000000000000000000000000000002
+    /// recipient 0x0000000000000000000000000000000000000003
+    /// call 0x0000000000000000000000000000000000000004
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, InclusionPolicyError> {
+        let mut policy = Self::default();
+        for (idx, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue
+            }
+
+            let mut parts = line.split_whitespace();
+            let (scope, address) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(address), None, None) => (None, address),
+                (Some(scope), Some(address), None) => (Some(scope), address),
+                _ => return Err(InclusionPolicyError::Parse { line: idx + 1 }),
+            };
+            let address: Address =
+                address.parse().map_err(|_| InclusionPolicyError::Parse { line: idx + 1 })?;
+
+            match scope {
+                None => {
+                    policy.senders.insert(address);
+                    policy.recipients.insert(address);
+                    policy.call_targets.insert(address);
+                }
+                Some("sender") => {
+                    policy.senders.insert(address);
+                }
+                Some("recipient") => {
+                    policy.recipients.insert(address);
+                }
+                Some("call") => {
+                    policy.call_targets.insert(address);
+                }
+                Some(_) => return Err(InclusionPolicyError::Parse { line: idx + 1 }),
+            }
+        }
+        Ok(policy)
+    }
+
+    /// Reads and parses the policy file at the given path.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, InclusionPolicyError> {
+        Self::parse(&reth_fs_util::read_to_string(path)?)
+    }
+
+    /// Adds an address that is not allowed to send transactions.
+    pub fn deny_sender(mut self, address: Address) -> Self {
+        self.senders.insert(address);
+        self
+    }
+
+    /// Adds an address that transactions are not allowed to call directly.
+    pub fn deny_recipient(mut self, address: Address) -> Self {
+        self.recipients.insert(address);
+        self
+    }
+
+    /// Adds an account that transactions are not allowed to call or create during execution.
+    pub fn deny_call_target(mut self, address: Address) -> Self {
+        self.call_targets.insert(address);
+        self
+    }
+
+    /// Returns `true` if the policy doesn't deny anything.
+    pub fn is_empty(&self) -> bool {
+        self.senders.is_empty() && self.recipients.is_empty() && self.call_targets.is_empty()
+    }
+
+    /// Returns `true` if the policy contains call targets that can only be checked by executing
+    /// the transaction.
+    pub fn has_call_targets(&self) -> bool {
+        !self.call_targets.is_empty()
+    }
+
+    /// Checks the sender and the recipient of the given transaction against the policy.
+    pub fn check_transaction<T: PoolTransaction>(
+        &self,
+        transaction: &T,
+    ) -> Result<(), InclusionPolicyViolation> {
+        let sender = transaction.sender_ref();
+        if self.senders.contains(sender) {
+            return Err(InclusionPolicyViolation::DeniedSender(*sender))
+        }
+        if let Some(to) = transaction.to() {
+            if self.recipients.contains(&to) {
+                return Err(InclusionPolicyViolation::DeniedRecipient(to))
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the accounts called or created by an executed transaction against the policy.
+    pub fn check_call_targets<'a>(
+        &self,
+        targets: impl IntoIterator<Item = &'a Address>,
+    ) -> Result<(), InclusionPolicyViolation> {
+        if let Some(target) = targets.into_iter().find(|target| self.call_targets.contains(*target))
+        {
+            return Err(InclusionPolicyViolation::DeniedCallTarget(*target))
+        }
+        Ok(())
+    }
+}
+
+/// A shared, reloadable [`InclusionPolicy`].
+///
+/// Cloning the handle is cheap and all clones observe policy updates.
+#[derive(Debug, Clone, Default)]
+pub struct InclusionPolicyHandle {
+    /// The currently active policy.
+    policy: Arc<RwLock<Arc<InclusionPolicy>>>,
+    /// The file the policy was loaded from, if any.
+    path: Option<PathBuf>,
+}
+
+impl InclusionPolicyHandle {
+    /// Creates a new handle for a fixed policy.
+    pub fn new(policy: InclusionPolicy) -> Self {
+        Self { policy: Arc::new(RwLock::new(Arc::new(policy))), path: None }
+    }
+
+    /// Loads the policy from the given file.
+    ///
+    /// The file is re-read on [`InclusionPolicyHandle::reload`].
+    pub fn from_file(path: impl Into<PathBuf>) -> Result<Self, InclusionPolicyError> {
+        let path = path.into();
+        let policy = InclusionPolicy::from_file(&path)?;
+        Ok(Self { policy: Arc::new(RwLock::new(Arc::new(policy))), path: Some(path) })
+    }
+
+    /// Returns the file the policy was loaded from.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Returns a snapshot of the currently active policy.
+    pub fn current(&self) -> Arc<InclusionPolicy> {
+        self.policy.read().clone()
+    }
+
+    /// Replaces the active policy.
+    pub fn set(&self, policy: InclusionPolicy) {
+        *self.policy.write() = Arc::new(policy);
+    }
+
+    /// Re-reads the policy file, if the handle was loaded from a file.
+    ///
+    /// Returns `true` if the active policy changed. On error the previous policy stays active.
+    pub fn reload(&self) -> Result<bool, InclusionPolicyError> {
+        let Some(path) = &self.path else { return Ok(false) };
+        let policy = InclusionPolicy::from_file(path)?;
+        let mut current = self.policy.write();
+        if **current == policy {
+            return Ok(false)
+        }
+        *current = Arc::new(policy);
+        Ok(true)
+    }
+}
+
+impl PartialEq for InclusionPolicyHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.policy, &other.policy)
+    }
+}
+
+impl Eq for InclusionPolicyHandle {}
+
+/// Reason a transaction was rejected by the [`InclusionPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum InclusionPolicyViolation {
+    /// The sender of the transaction is denied.
+    #[error("sender {0} is denied by the inclusion policy")]
+    DeniedSender(Address),
+    /// The recipient of the transaction is denied.
+    #[error("recipient {0} is denied by the inclusion policy")]
+    DeniedRecipient(Address),
+    /// The transaction called or created a denied account during execution.
+    #[error("call target {0} is denied by the inclusion policy")]
+    DeniedCallTarget(Address),
+}
+
+impl InclusionPolicyViolation {
+    /// Returns the denied address.
+    pub const fn address(&self) -> Address {
+        match self {
+            Self::DeniedSender(address) |
+            Self::DeniedRecipient(address) |
+            Self::DeniedCallTarget(address) => *address,
+        }
+    }
+}
+
+/// Errors that can occur while loading an [`InclusionPolicy`].
+#[derive(Debug, thiserror::Error)]
+pub enum InclusionPolicyError {
+    /// Failed to read the policy file.
+    #[error(transparent)]
+    FsPath(#[from] FsPathError),
+    /// A line of the policy file could not be parsed.
+    #[error("invalid inclusion policy entry on line {line}")]
+    Parse {
+        /// The 1-based line number.
+        line: usize,
+    },
+}
+
+/// Task that periodically checks the policy file of the given handle for changes and reloads it.
+///
+/// `on_reload` is invoked with the initial policy and with every updated policy, e.g. to evict
+/// transactions that are denied by the new policy via [`evict_denied_transactions`].
+pub async fn inclusion_policy_reload_task<F>(
+    handle: InclusionPolicyHandle,
+    reload_interval: Duration,
+    mut on_reload: F,
+) where
+    F: FnMut(&InclusionPolicy),
+{
+    on_reload(&handle.current());
+
+    let Some(path) = handle.path().map(Path::to_path_buf) else {
+        // nothing to watch
+        return
+    };
+
+    let mut last_modified = modified_at(&path);
+    let mut interval = time::interval(reload_interval);
+    loop {
+        interval.tick().await;
+
+        let modified = modified_at(&path);
+        if modified == last_modified {
+            continue
+        }
+        last_modified = modified;
+
+        match handle.reload() {
+            Ok(true) => {
+                info!(target: "txpool", path=?path, "Reloaded inclusion policy");
+                on_reload(&handle.current());
+            }
+            Ok(false) => {}
+            Err(err) => {
+                warn!(target: "txpool", %err, path=?path, "Failed to reload inclusion policy, keeping previous policy");
+            }
+        }
+    }
+}
+
+/// Removes all transactions (and their descendants) from the pool that are denied by the policy.
+pub fn evict_denied_transactions<P: TransactionPool>(pool: &P, policy: &InclusionPolicy) {
+    if policy.is_empty() {
+        return
+    }
+
+    let denied = pool
+        .pooled_transactions()
+        .into_iter()
+        .filter(|tx| policy.check_transaction(&tx.transaction).is_err())
+        .map(|tx| *tx.hash())
+        .collect::<Vec<_>>();
+
+    if !denied.is_empty() {
+        debug!(target: "txpool", num_txs=%denied.len(), "Evicting transactions denied by inclusion policy");
+        pool.remove_transactions_and_descendants(denied);
+    }
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use alloy_primitives::address;
+
+    const POLICY: &str = r"
+        # denied everywhere
+        0x0000000000000000000000000000000000000001
+        sender 0x0000000000000000000000000000000000000002 # only as sender
+        recipient 0x0000000000000000000000000000000000000003
+        call 0x0000000000000000000000000000000000000004
+    ";
+
+    #[test]
+    fn parse_policy() {
+        let policy = InclusionPolicy::parse(POLICY).unwrap();
+        let expected = InclusionPolicy::default()
+            .deny_sender(address!("0x0000000000000000000000000000000000000001"))
+            .deny_recipient(address!("0x0000000000000000000000000000000000000001"))
+            .deny_call_target(address!("0x0000000000000000000000000000000000000001"))
+            .deny_sender(address!("0x0000000000000000000000000000000000000002"))
+            .deny_recipient(address!("0x0000000000000000000000000000000000000003"))
+            .deny_call_target(address!("0x0000000000000000000000000000000000000004"));
+        assert_eq!(policy, expected);
+    }
+
+    #[test]
+    fn parse_invalid_policy() {
+        let err = InclusionPolicy::parse("0x01\nblock 0x0000000000000000000000000000000000000001")
+            .unwrap_err();
+        assert!(matches!(err, InclusionPolicyError::Parse { line: 1 }));
+
+        let err =
+            InclusionPolicy::parse("block 0x0000000000000000000000000000000000000001").unwrap_err();
+        assert!(matches!(err, InclusionPolicyError::Parse { line: 1 }));
+    }
+
+    #[test]
+    fn check_transaction() {
+        let policy = InclusionPolicy::parse(POLICY).unwrap();
+
+        let tx = MockTransaction::eip1559();
+        assert!(policy.check_transaction(&tx).is_ok());
+
+        let denied = address!("0x0000000000000000000000000000000000000002");
+        let tx = MockTransaction::eip1559().with_sender(denied);
+        assert_eq!(
+            policy.check_transaction(&tx),
+            Err(InclusionPolicyViolation::DeniedSender(denied))
+        );
+
+        let denied = address!("0x0000000000000000000000000000000000000003");
+        let tx = MockTransaction::eip1559().with_to(denied);
+        assert_eq!(
+            policy.check_transaction(&tx),
+            Err(InclusionPolicyViolation::DeniedRecipient(denied))
+        );
+
+        // call targets are only checked during execution
+        let target = address!("0x0000000000000000000000000000000000000004");
+        let tx = MockTransaction::eip1559().with_to(target);
+        assert!(policy.check_transaction(&tx).is_ok());
+        assert_eq!(
+            policy.check_call_targets([&target]),
+            Err(InclusionPolicyViolation::DeniedCallTarget(target))
+        );
+    }
+
+    #[test]
+    fn reload_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.txt");
+        std::fs::write(&path, "sender 0x0000000000000000000000000000000000000002").unwrap();
+
+        let handle = InclusionPolicyHandle::from_file(&path).unwrap();
+        assert!(!handle.reload().unwrap());
+
+        std::fs::write(&path, POLICY).unwrap();
+        assert!(handle.reload().unwrap());
+        assert_eq!(*handle.current(), InclusionPolicy::parse(POLICY).unwrap());
+
+        // invalid files keep the previous policy
+        std::fs::write(&path, "not an address").unwrap();
+        assert!(handle.reload().is_err());
+        assert_eq!(*handle.current(), InclusionPolicy::parse(POLICY).unwrap());
+    }
+}
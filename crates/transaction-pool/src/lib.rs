@@ -307,7 +307,10 @@ use std::{collections::HashSet, sync::Arc};
 use tokio::sync::mpsc::Receiver;
 use tracing::{instrument, trace};
 
+pub mod conditional;
 pub mod error;
+pub mod eviction;
+pub mod inclusion;
 pub mod maintain;
 pub mod metrics;
 pub mod noop;
@@ -474,6 +477,10 @@ where
         self.pool.block_info()
     }
 
+    fn inclusion_policy(&self) -> Option<inclusion::InclusionPolicyHandle> {
+        self.pool.validator().inclusion_policy().cloned()
+    }
+
     async fn add_transaction_and_subscribe(
         &self,
         origin: TransactionOrigin,
@@ -506,6 +513,24 @@ where
         self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx))
     }
 
+    async fn add_external_transactions_from_peer(
+        &self,
+        peer: PeerId,
+        transactions: Vec<Self::Transaction>,
+    ) -> Vec<PoolResult<AddedTransactionOutcome>> {
+        if transactions.is_empty() {
+            return Vec::new()
+        }
+        let origin = TransactionOrigin::External;
+        let validated = self.validate_all(origin, transactions).await;
+
+        self.pool.add_transactions_from_peer(
+            origin,
+            Some(peer),
+            validated.into_iter().map(|(_, tx)| tx),
+        )
+    }
+
     fn transaction_event_listener(&self, tx_hash: TxHash) -> Option<TransactionEvents> {
         self.pool.add_transaction_event_listener(tx_hash)
     }
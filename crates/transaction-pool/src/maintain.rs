@@ -2,14 +2,20 @@
 
 use crate::{
     blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
+    conditional::{
+        validate_known_accounts, MaybeConditionalTransaction, TransactionConditionalError,
+    },
     error::PoolError,
     metrics::MaintainPoolMetrics,
-    traits::{CanonicalStateUpdate, EthPoolTransaction, TransactionPool, TransactionPoolExt},
+    traits::{
+        AllPoolTransactions, CanonicalStateUpdate, EthPoolTransaction, TransactionPool,
+        TransactionPoolExt,
+    },
     BlockInfo, PoolTransaction, PoolUpdateKind,
 };
-use alloy_consensus::{BlockHeader, Typed2718};
+use alloy_consensus::{conditional::BlockConditionalAttributes, BlockHeader, Typed2718};
 use alloy_eips::BlockNumberOrTag;
-use alloy_primitives::{Address, BlockHash, BlockNumber};
+use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash};
 use alloy_rlp::Encodable;
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
@@ -40,6 +46,13 @@ use tracing::{debug, error, info, trace, warn};
 /// Maximum amount of time non-executable transaction are queued.
 pub const MAX_QUEUED_TRANSACTION_LIFETIME: Duration = Duration::from_secs(3 * 60 * 60);
 
+/// Maximum number of known accounts of conditional transactions that are validated against the
+/// state of a new tip.
+///
+/// Storage roots are expensive to compute, so the remaining transactions are validated on the
+/// following blocks, and the payload builder skips them until then.
+const MAX_CONDITIONAL_KNOWN_ACCOUNTS: usize = 100;
+
 /// Additional settings for maintaining the transaction pool
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MaintainPoolConfig {
@@ -105,7 +118,9 @@ where
         + ChainSpecProvider<ChainSpec: EthChainSpec<Header = N::BlockHeader>>
         + Clone
         + 'static,
-    P: TransactionPoolExt<Transaction: PoolTransaction<Consensus = N::SignedTx>> + 'static,
+    P: TransactionPoolExt<
+            Transaction: PoolTransaction<Consensus = N::SignedTx> + MaybeConditionalTransaction,
+        > + 'static,
     St: Stream<Item = CanonStateNotification<N>> + Send + Unpin + 'static,
     Tasks: TaskSpawner + 'static,
 {
@@ -131,7 +146,9 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
         + ChainSpecProvider<ChainSpec: EthChainSpec<Header = N::BlockHeader>>
         + Clone
         + 'static,
-    P: TransactionPoolExt<Transaction: PoolTransaction<Consensus = N::SignedTx>> + 'static,
+    P: TransactionPoolExt<
+            Transaction: PoolTransaction<Consensus = N::SignedTx> + MaybeConditionalTransaction,
+        > + 'static,
     St: Stream<Item = CanonStateNotification<N>> + Send + Unpin + 'static,
     Tasks: TaskSpawner + 'static,
 {
@@ -170,6 +187,15 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     // the future that reloads accounts from state
     let mut reload_accounts_fut = Fuse::terminated();
 
+    // the tip to validate the known accounts of conditional transactions against
+    let mut known_accounts_block = None;
+
+    // the last transaction whose known accounts were validated
+    let mut last_known_accounts_tx = None;
+
+    // the future that validates known accounts of conditional transactions
+    let mut known_accounts_fut = Fuse::terminated();
+
     // eviction interval for stale non local txs
     let mut stale_eviction_interval = time::interval(config.max_tx_lifetime);
 
@@ -226,6 +252,20 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
             task_spawner.spawn_blocking(fut);
         }
 
+        // validate the known accounts of conditional transactions against the latest tip, one
+        // check at a time
+        if known_accounts_fut.is_terminated() {
+            if let Some(block_hash) = known_accounts_block.take() {
+                let (tx, rx) = oneshot::channel();
+                let (c, p) = (client.clone(), pool.clone());
+                let after = last_known_accounts_tx;
+                task_spawner.spawn_blocking(Box::pin(async move {
+                    let _ = tx.send(remove_failed_known_accounts(&c, &p, block_hash, after));
+                }));
+                known_accounts_fut = rx.fuse();
+            }
+        }
+
         // check if we have a new finalized block
         if let Some(finalized) =
             last_finalized_block.update(client.finalized_block_number().ok().flatten())
@@ -255,6 +295,12 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
             res = &mut reload_accounts_fut =>  {
                 reloaded = Some(res);
             }
+            res = &mut known_accounts_fut => {
+                if let Ok((removed, last_checked)) = res {
+                    metrics.inc_removed_conditional_transactions(removed);
+                    last_known_accounts_tx = last_checked;
+                }
+            }
             ev = events.next() =>  {
                  if ev.is_none() {
                     // the stream ended, we are done
@@ -410,6 +456,14 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 };
                 pool.on_canonical_state_change(update);
 
+                // evict conditional transactions that can no longer be included after the new tip
+                let removed = remove_exceeded_conditional_transactions(
+                    &pool,
+                    next_block_attributes(new_tip.header()),
+                );
+                metrics.inc_removed_conditional_transactions(removed);
+                known_accounts_block = Some(new_tip.hash());
+
                 // all transactions that were mined in the old chain but not in the new chain need
                 // to be re-injected
                 //
@@ -493,6 +547,14 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 };
                 pool.on_canonical_state_change(update);
 
+                // evict conditional transactions that can no longer be included after the new tip
+                let removed = remove_exceeded_conditional_transactions(
+                    &pool,
+                    next_block_attributes(tip.header()),
+                );
+                metrics.inc_removed_conditional_transactions(removed);
+                known_accounts_block = Some(tip.hash());
+
                 // keep track of mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&blocks);
             }
@@ -500,6 +562,115 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     }
 }
 
+/// Returns the earliest block attributes of the block following the given tip.
+fn next_block_attributes<H: BlockHeader>(tip: &H) -> BlockConditionalAttributes {
+    BlockConditionalAttributes { number: tip.number() + 1, timestamp: tip.timestamp() + 1 }
+}
+
+/// Removes all transactions from the pool whose [`TransactionConditional`] bounds are exceeded by
+/// the given attributes of the next block.
+///
+/// Returns the number of removed transactions.
+///
+/// [`TransactionConditional`]: alloy_rpc_types_eth::erc4337::TransactionConditional
+fn remove_exceeded_conditional_transactions<P>(
+    pool: &P,
+    block_attr: BlockConditionalAttributes,
+) -> usize
+where
+    P: TransactionPool<Transaction: MaybeConditionalTransaction>,
+{
+    // includes private transactions, which are not propagated
+    let AllPoolTransactions { pending, queued } = pool.all_transactions();
+    let to_remove = pending
+        .into_iter()
+        .chain(queued)
+        .filter(|tx| tx.transaction.has_exceeded_block_attributes(&block_attr))
+        .map(|tx| *tx.hash())
+        .collect::<Vec<_>>();
+
+    if to_remove.is_empty() {
+        return 0
+    }
+    debug!(target: "txpool", count=%to_remove.len(), "removing exceeded conditional transactions");
+    pool.remove_transactions(to_remove).len()
+}
+
+/// Removes transactions from the pool whose [`TransactionConditional`] known accounts don't match
+/// the state of the given block.
+///
+/// At most [`MAX_CONDITIONAL_KNOWN_ACCOUNTS`] known accounts are validated. Transactions are
+/// validated in hash order, starting after `after`, the last transaction validated by the previous
+/// call, so every transaction is eventually validated.
+///
+/// Returns the number of removed transactions and the last validated transaction.
+///
+/// [`TransactionConditional`]: alloy_rpc_types_eth::erc4337::TransactionConditional
+fn remove_failed_known_accounts<Client, P>(
+    client: &Client,
+    pool: &P,
+    block_hash: BlockHash,
+    after: Option<TxHash>,
+) -> (usize, Option<TxHash>)
+where
+    Client: StateProviderFactory,
+    P: TransactionPool<Transaction: MaybeConditionalTransaction>,
+{
+    // includes private transactions, which are not propagated
+    let AllPoolTransactions { pending, queued } = pool.all_transactions();
+    let mut txs = pending
+        .into_iter()
+        .chain(queued)
+        .filter(|tx| tx.transaction.conditional().is_some_and(|c| !c.known_accounts.is_empty()))
+        .collect::<Vec<_>>();
+    if txs.is_empty() {
+        return (0, None)
+    }
+
+    // continue after the last validated transaction, wrapping around
+    txs.sort_unstable_by_key(|tx| *tx.hash());
+    let start = after.map_or(0, |after| txs.partition_point(|tx| *tx.hash() <= after));
+    txs.rotate_left(start);
+
+    let state = match client.state_by_block_hash(block_hash) {
+        Ok(state) => state,
+        Err(err) => {
+            debug!(target: "txpool", %err, "failed to open state to validate known accounts");
+            return (0, after)
+        }
+    };
+
+    let mut to_remove = Vec::new();
+    let mut last_checked = after;
+    let mut checked_accounts = 0;
+    for tx in txs {
+        let Some(conditional) = tx.transaction.conditional() else { continue };
+        let accounts = conditional.known_accounts.len();
+        if checked_accounts > 0 && checked_accounts + accounts > MAX_CONDITIONAL_KNOWN_ACCOUNTS {
+            break
+        }
+        checked_accounts += accounts;
+        last_checked = Some(*tx.hash());
+
+        match validate_known_accounts(&*state, conditional) {
+            Ok(()) => {}
+            Err(TransactionConditionalError::Provider(err)) => {
+                debug!(target: "txpool", %err, hash=%tx.hash(), "failed to check known accounts");
+            }
+            Err(err) => {
+                trace!(target: "txpool", %err, hash=%tx.hash(), "known accounts changed");
+                to_remove.push(*tx.hash());
+            }
+        }
+    }
+
+    if to_remove.is_empty() {
+        return (0, last_checked)
+    }
+    debug!(target: "txpool", count=%to_remove.len(), "removing failed conditional transactions");
+    (pool.remove_transactions(to_remove).len(), last_checked)
+}
+
 struct FinalizedBlockTracker {
     last_finalized_block: Option<BlockNumber>,
 }
@@ -719,8 +890,10 @@ mod tests {
         blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
         CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionOrigin,
     };
+    use alloy_consensus::Header;
     use alloy_eips::eip2718::Decodable2718;
-    use alloy_primitives::{hex, U256};
+    use alloy_primitives::{hex, B256, U256};
+    use alloy_rpc_types_eth::erc4337::{AccountStorage, TransactionConditional};
     use reth_ethereum_primitives::{PooledTransactionVariant, TransactionSigned};
     use reth_fs_util as fs;
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
@@ -786,6 +959,90 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn removes_exceeded_conditional_transactions() {
+        let tx_bytes = hex!(
+            "02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507"
+        );
+        let tx = PooledTransactionVariant::decode_2718(&mut &tx_bytes[..]).unwrap();
+        let provider = MockEthProvider::default();
+        let conditional =
+            TransactionConditional { block_number_max: Some(10), ..Default::default() };
+        let transaction = EthPooledTransaction::from_pooled(tx.try_into_recovered().unwrap())
+            .with_conditional(conditional);
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+        let blob_store = InMemoryBlobStore::default();
+        let validator =
+            EthTransactionValidatorBuilder::new(provider.clone()).build(blob_store.clone());
+
+        let txpool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        txpool.add_transaction(TransactionOrigin::Private, transaction).await.unwrap();
+
+        // the transaction can still be included in the block after the tip
+        let tip = |number| Header { number, ..Default::default() };
+        let removed =
+            remove_exceeded_conditional_transactions(&txpool, next_block_attributes(&tip(9)));
+        assert_eq!(removed, 0);
+        assert_eq!(txpool.len(), 1);
+
+        let removed =
+            remove_exceeded_conditional_transactions(&txpool, next_block_attributes(&tip(10)));
+        assert_eq!(removed, 1);
+        assert!(txpool.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn removes_failed_known_accounts() {
+        let tx_bytes = hex!(
+            "02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507"
+        );
+        let tx = PooledTransactionVariant::decode_2718(&mut &tx_bytes[..]).unwrap();
+        let provider = MockEthProvider::default();
+        let known_account = Address::random();
+        let slot = U256::from(1);
+        let conditional = TransactionConditional {
+            known_accounts: [(
+                known_account,
+                AccountStorage::Slots([(slot, B256::from(U256::from(1)))].into_iter().collect()),
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        let transaction = EthPooledTransaction::from_pooled(tx.try_into_recovered().unwrap())
+            .with_conditional(conditional);
+        let hash = *transaction.hash();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+        let known_account_with_slot =
+            |value| ExtendedAccount::new(0, U256::ZERO).extend_storage([(B256::from(slot), value)]);
+        provider.add_account(known_account, known_account_with_slot(U256::from(1)));
+        let blob_store = InMemoryBlobStore::default();
+        let validator =
+            EthTransactionValidatorBuilder::new(provider.clone()).build(blob_store.clone());
+
+        let txpool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        txpool.add_transaction(TransactionOrigin::Private, transaction).await.unwrap();
+
+        // the known account matches the state
+        let (removed, last_checked) =
+            remove_failed_known_accounts(&provider, &txpool, B256::ZERO, None);
+        assert_eq!(removed, 0);
+        assert_eq!(last_checked, Some(hash));
+        assert_eq!(txpool.len(), 1);
+
+        // the known account changed
+        provider.add_account(known_account, known_account_with_slot(U256::from(2)));
+        let (removed, last_checked) =
+            remove_failed_known_accounts(&provider, &txpool, B256::ZERO, last_checked);
+        assert_eq!(removed, 1);
+        assert_eq!(last_checked, Some(hash));
+        assert!(txpool.is_empty());
+    }
+
     #[test]
     fn test_update_with_higher_finalized_block() {
         let mut tracker = FinalizedBlockTracker::new(Some(10));
@@ -1,6 +1,7 @@
 use crate::{
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
     identifier::{SenderId, TransactionId},
+    inclusion::InclusionPolicy,
     pool::pending::PendingTransaction,
     PoolTransaction, TransactionOrdering, ValidPoolTransaction,
 };
@@ -369,6 +370,65 @@ where
     }
 }
 
+/// Wrapper over [`crate::traits::BestTransactions`] that skips transactions denied by an
+/// [`InclusionPolicy`].
+///
+/// Skipped transactions are marked invalid with [`InvalidPoolTransactionError::InclusionPolicy`],
+/// so that their descendants are skipped as well.
+#[derive(Debug)]
+pub struct BestTransactionsWithInclusionPolicy<I> {
+    /// Inner iterator
+    inner: I,
+    /// Policy snapshot the transactions are checked against.
+    policy: Arc<InclusionPolicy>,
+}
+
+impl<I> BestTransactionsWithInclusionPolicy<I> {
+    /// Constructs a new [`BestTransactionsWithInclusionPolicy`].
+    pub const fn new(policy: Arc<InclusionPolicy>, inner: I) -> Self {
+        Self { inner, policy }
+    }
+}
+
+impl<I, T> Iterator for BestTransactionsWithInclusionPolicy<I>
+where
+    I: crate::traits::BestTransactions<Item = Arc<ValidPoolTransaction<T>>>,
+    T: PoolTransaction,
+{
+    type Item = <I as Iterator>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let best = self.inner.next()?;
+            match self.policy.check_transaction(&best.transaction) {
+                Ok(()) => return Some(best),
+                Err(err) => {
+                    debug!(target: "txpool", tx=?best.hash(), %err, "skipping transaction denied by inclusion policy");
+                    self.inner.mark_invalid(&best, err.into());
+                }
+            }
+        }
+    }
+}
+
+impl<I, T> crate::traits::BestTransactions for BestTransactionsWithInclusionPolicy<I>
+where
+    I: crate::traits::BestTransactions<Item = Arc<ValidPoolTransaction<T>>>,
+    T: PoolTransaction,
+{
+    fn mark_invalid(&mut self, tx: &Self::Item, kind: InvalidPoolTransactionError) {
+        self.inner.mark_invalid(tx, kind)
+    }
+
+    fn no_updates(&mut self) {
+        self.inner.no_updates()
+    }
+
+    fn set_skip_blobs(&mut self, skip_blobs: bool) {
+        self.inner.set_skip_blobs(skip_blobs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -809,6 +869,41 @@ mod tests {
         assert_eq!(top_of_block_tx2.sender(), prioritized_tx2.sender());
     }
 
+    #[test]
+    fn test_best_transactions_with_inclusion_policy() {
+        let mut pool = PendingPool::new(MockOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        // Add a chain of transactions from a denied sender
+        let denied_tx = MockTransaction::eip1559();
+        for nonce in 0..3 {
+            let tx = denied_tx.clone().rng_hash().with_nonce(nonce);
+            pool.add_transaction(Arc::new(f.validated(tx)), 0);
+        }
+
+        // Add a transaction to a denied recipient
+        let denied_recipient = Address::random();
+        let tx = MockTransaction::eip1559().with_to(denied_recipient);
+        pool.add_transaction(Arc::new(f.validated(tx)), 0);
+
+        // Add allowed transactions
+        for _ in 0..2 {
+            pool.add_transaction(Arc::new(f.validated(MockTransaction::eip1559())), 0);
+        }
+
+        let policy = InclusionPolicy::default()
+            .deny_sender(denied_tx.sender())
+            .deny_recipient(denied_recipient);
+        let best = BestTransactionsWithInclusionPolicy::new(Arc::new(policy), pool.best());
+
+        let txs = best.collect::<Vec<_>>();
+        assert_eq!(txs.len(), 2);
+        for tx in txs {
+            assert_ne!(tx.sender(), denied_tx.sender());
+            assert_ne!(tx.to(), Some(denied_recipient));
+        }
+    }
+
     #[test]
     fn test_best_with_fees_iter_no_blob_fee_required() {
         // Tests transactions without blob fees where base fees are checked.
@@ -93,6 +93,7 @@ use best::BestTransactions;
 use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
+use reth_network_peers::PeerId;
 
 use alloy_eips::{eip7594::BlobTransactionSidecarVariant, Typed2718};
 use reth_primitives_traits::Recovered;
@@ -101,7 +102,10 @@ use std::{collections::HashSet, fmt, sync::Arc, time::Instant};
 use tokio::sync::mpsc;
 use tracing::{debug, trace, warn};
 mod events;
-pub use best::{BestTransactionFilter, BestTransactionsWithPrioritizedSenders};
+pub use best::{
+    BestTransactionFilter, BestTransactionsWithInclusionPolicy,
+    BestTransactionsWithPrioritizedSenders,
+};
 pub use blob::{blob_tx_priority, fee_delta, BlobOrd, BlobTransactions};
 pub use events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent};
 pub use listener::{AllTransactionsEvents, TransactionEvents, TransactionListenerKind};
@@ -458,6 +462,7 @@ where
         &self,
         pool: &mut RwLockWriteGuard<'_, TxPool<T>>,
         origin: TransactionOrigin,
+        peer: Option<PeerId>,
         tx: TransactionValidationOutcome<T::Transaction>,
     ) -> PoolResult<AddedTransactionOutcome> {
         match tx {
@@ -490,6 +495,7 @@ where
                     propagate,
                     timestamp: Instant::now(),
                     origin,
+                    peer,
                     authority_ids: authorities.map(|auths| self.get_sender_ids(auths)),
                 };
 
@@ -568,13 +574,26 @@ where
         &self,
         origin: TransactionOrigin,
         transactions: impl IntoIterator<Item = TransactionValidationOutcome<T::Transaction>>,
+    ) -> Vec<PoolResult<AddedTransactionOutcome>> {
+        self.add_transactions_from_peer(origin, None, transactions)
+    }
+
+    /// Adds all transactions in the iterator to the pool, recording the peer they were received
+    /// from, if any.
+    ///
+    /// See also [`Self::add_transactions`].
+    pub fn add_transactions_from_peer(
+        &self,
+        origin: TransactionOrigin,
+        peer: Option<PeerId>,
+        transactions: impl IntoIterator<Item = TransactionValidationOutcome<T::Transaction>>,
     ) -> Vec<PoolResult<AddedTransactionOutcome>> {
         // Add the transactions and enforce the pool size limits in one write lock
         let (mut added, discarded) = {
             let mut pool = self.pool.write();
             let added = transactions
                 .into_iter()
-                .map(|tx| self.add_transaction(&mut pool, origin, tx))
+                .map(|tx| self.add_transaction(&mut pool, origin, peer, tx))
                 .collect::<Vec<_>>();
 
             // Enforce the pool size limits if at least one transaction was added successfully
@@ -6,6 +6,9 @@ use crate::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
         PoolError, PoolErrorKind,
     },
+    eviction::{
+        AdmissionError, EvictionCandidate, EvictionPolicy, PeerUsage, PoolShares, SenderUsage,
+    },
     identifier::{SenderId, TransactionId},
     metrics::{AllTransactionsMetrics, TxPoolMetrics},
     pool::{
@@ -18,8 +21,8 @@ use crate::{
         AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
     },
     traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
-    PoolConfig, PoolResult, PoolTransaction, PoolUpdateKind, PriceBumpConfig, TransactionOrdering,
-    ValidPoolTransaction, U256,
+    PoolConfig, PoolResult, PoolTransaction, PoolUpdateKind, PriceBumpConfig, SubPoolLimit,
+    TransactionOrdering, ValidPoolTransaction, U256,
 };
 use alloy_consensus::constants::{
     EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID, KECCAK_EMPTY,
@@ -31,6 +34,7 @@ use alloy_eips::{
     Typed2718,
 };
 use alloy_primitives::{Address, TxHash, B256};
+use reth_network_peers::PeerId;
 use rustc_hash::FxHashMap;
 use smallvec::SmallVec;
 use std::{
@@ -114,6 +118,9 @@ pub struct TxPool<T: TransactionOrdering> {
     blob_pool: BlobTransactions<T::Transaction>,
     /// All transactions in the pool.
     all_transactions: AllTransactions<T::Transaction>,
+    /// The transactions of every sub-pool by sender and peer, tracked if an [`EvictionPolicy`] is
+    /// configured.
+    eviction_shares: Option<PoolShares>,
     /// Transaction pool metrics
     metrics: TxPoolMetrics,
     /// The last update kind that was applied to the pool.
@@ -135,6 +142,7 @@ impl<T: TransactionOrdering> TxPool<T> {
             basefee_pool: Default::default(),
             blob_pool: Default::default(),
             all_transactions: AllTransactions::new(&config),
+            eviction_shares: config.eviction_policy.is_some().then(PoolShares::default),
             config,
             metrics: Default::default(),
             latest_update_kind: None,
@@ -233,6 +241,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                 let removed =
                     self.pending_pool.update_blob_fee(self.all_transactions.pending_fees.blob_fee);
                 for tx in removed {
+                    self.remove_share(SubPool::Pending, &tx);
                     let to = {
                         let tx =
                             self.all_transactions.txs.get_mut(tx.id()).expect("tx exists in set");
@@ -250,6 +259,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                 let removed =
                     self.blob_pool.enforce_pending_fees(&self.all_transactions.pending_fees);
                 for tx in removed {
+                    self.remove_share(SubPool::Blob, &tx);
                     let to = {
                         let tx =
                             self.all_transactions.txs.get_mut(tx.id()).expect("tx exists in set");
@@ -280,6 +290,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                 let removed =
                     self.pending_pool.update_base_fee(self.all_transactions.pending_fees.base_fee);
                 for tx in removed {
+                    self.remove_share(SubPool::Pending, &tx);
                     let to = {
                         let tx =
                             self.all_transactions.txs.get_mut(tx.id()).expect("tx exists in set");
@@ -297,6 +308,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                 let removed =
                     self.basefee_pool.enforce_basefee(self.all_transactions.pending_fees.base_fee);
                 for tx in removed {
+                    self.remove_share(SubPool::BaseFee, &tx);
                     let to = {
                         let tx =
                             self.all_transactions.txs.get_mut(tx.id()).expect("tx exists in set");
@@ -720,6 +732,12 @@ impl<T: TransactionOrdering> TxPool<T> {
                             PoolErrorKind::SpammerExceededCapacity(transaction.sender()),
                         ))
                     }
+                    InsertErr::ExceededPeerTransactionsCapacity { transaction, peer } => {
+                        Err(PoolError::new(
+                            *transaction.hash(),
+                            PoolErrorKind::PeerExceededCapacity(peer),
+                        ))
+                    }
                     InsertErr::TxGasLimitMoreThanAvailableBlockGas {
                         transaction,
                         block_gas_limit,
@@ -1003,11 +1021,26 @@ impl<T: TransactionOrdering> TxPool<T> {
             // is generic and it would not be possible to distinguish whether a transaction is
             // being removed from the `BaseFee` pool, or the `Queued` pool.
             trace!(target: "txpool", hash=%tx.transaction.hash(), ?pool, "Removed transaction from a subpool");
+            self.remove_share(pool, tx);
         }
 
         tx
     }
 
+    /// Adds a transaction that entered the given sub-pool to its shares, if they are tracked.
+    fn insert_share(&mut self, pool: SubPool, tx: &ValidPoolTransaction<T::Transaction>) {
+        if let Some(shares) = &mut self.eviction_shares {
+            shares.get_mut(pool).insert(self.all_transactions.eviction_candidate(tx));
+        }
+    }
+
+    /// Removes a transaction that left the given sub-pool from its shares, if they are tracked.
+    fn remove_share(&mut self, pool: SubPool, tx: &ValidPoolTransaction<T::Transaction>) {
+        if let Some(shares) = &mut self.eviction_shares {
+            shares.get_mut(pool).remove(&self.all_transactions.eviction_candidate(tx));
+        }
+    }
+
     /// Removes _only_ the descendants of the given transaction from the __entire__ pool.
     ///
     /// All removed transactions are added to the `removed` vec.
@@ -1043,6 +1076,7 @@ impl<T: TransactionOrdering> TxPool<T> {
         // generic and it would not be possible to distinguish whether a transaction is being
         // added to the `BaseFee` pool, or the `Queued` pool.
         trace!(target: "txpool", hash=%tx.transaction.hash(), ?pool, "Adding transaction to a subpool");
+        self.insert_share(pool, &tx);
         match pool {
             SubPool::Queued => self.queued_pool.add_transaction(tx),
             SubPool::Pending => {
@@ -1084,8 +1118,17 @@ impl<T: TransactionOrdering> TxPool<T> {
 
         // Helper macro that discards the worst transactions for the pools
         macro_rules! discard_worst {
-            ($this:ident, $removed:ident, [$($limit:ident => ($pool:ident, $metric:ident)),* $(,)*]) => {
+            ($this:ident, $removed:ident, [$($limit:ident => ($pool:ident, $subpool:expr, $metric:ident)),* $(,)*]) => {
                 $ (
+                // first evict the transactions selected by the eviction policy, if any
+                if let Some(policy) = $this.config.eviction_policy.clone() {
+                    if $this.$pool.exceeds(&$this.config.$limit) {
+                        let limit = $this.config.$limit;
+                        let evicted = $this.evict_by_policy(&*policy, $subpool, &limit, &mut $removed);
+                        $this.metrics.$metric.increment(evicted as u64);
+                    }
+                }
+
                 while $this.$pool.exceeds(&$this.config.$limit)
                     {
                         trace!(
@@ -1113,6 +1156,7 @@ impl<T: TransactionOrdering> TxPool<T> {
 
                         // 2. remove all transactions from the total set
                         for tx in removed_from_subpool {
+                            $this.remove_share($subpool, &tx);
                             $this.all_transactions.remove_transaction(tx.id());
 
                             let id = *tx.id();
@@ -1131,16 +1175,41 @@ impl<T: TransactionOrdering> TxPool<T> {
 
         discard_worst!(
             self, removed, [
-                pending_limit => (pending_pool, pending_transactions_evicted),
-                basefee_limit => (basefee_pool, basefee_transactions_evicted),
-                blob_limit    => (blob_pool, blob_transactions_evicted),
-                queued_limit  => (queued_pool, queued_transactions_evicted),
+                pending_limit => (pending_pool, SubPool::Pending, pending_transactions_evicted),
+                basefee_limit => (basefee_pool, SubPool::BaseFee, basefee_transactions_evicted),
+                blob_limit    => (blob_pool, SubPool::Blob, blob_transactions_evicted),
+                queued_limit  => (queued_pool, SubPool::Queued, queued_transactions_evicted),
             ]
         );
 
         removed
     }
 
+    /// Evicts the transactions of the given sub-pool that the [`EvictionPolicy`] selects, and their
+    /// descendants.
+    ///
+    /// Returns the number of transactions evicted from the sub-pool.
+    fn evict_by_policy(
+        &mut self,
+        policy: &dyn EvictionPolicy,
+        pool: SubPool,
+        limit: &SubPoolLimit,
+        removed: &mut Vec<Arc<ValidPoolTransaction<T::Transaction>>>,
+    ) -> usize {
+        let Some(shares) = &self.eviction_shares else { return 0 };
+
+        let mut evicted = 0;
+        for id in policy.select_evictions(shares.get(pool), limit) {
+            // the transaction may already be removed as a descendant of an evicted transaction
+            let Some(tx) = self.remove_transaction(&id) else { continue };
+            trace!(target: "txpool", hash=%tx.hash(), ?pool, "evicting transaction by policy");
+            removed.push(tx);
+            evicted += 1;
+            self.remove_descendants(&id, removed);
+        }
+        evicted
+    }
+
     /// Number of transactions in the entire pool
     pub(crate) fn len(&self) -> usize {
         self.all_transactions.len()
@@ -1167,6 +1236,21 @@ impl<T: TransactionOrdering> TxPool<T> {
             "total size must be equal to the sum of all sub-pools, basefee:{}, pending:{}, queued:{}, blob:{}",
             size.basefee, size.pending, size.queued, size.blob
         );
+        if let Some(shares) = &self.eviction_shares {
+            for (pool, len, size) in [
+                (SubPool::Pending, self.pending_pool.len(), self.pending_pool.size()),
+                (SubPool::BaseFee, self.basefee_pool.len(), self.basefee_pool.size()),
+                (SubPool::Blob, self.blob_pool.len(), self.blob_pool.size()),
+                (SubPool::Queued, self.queued_pool.len(), self.queued_pool.size()),
+            ] {
+                let shares = shares.get(pool);
+                assert_eq!(
+                    (shares.len, shares.size),
+                    (len, size),
+                    "shares of {pool:?} out of sync"
+                );
+            }
+        }
         self.all_transactions.assert_invariants();
         self.pending_pool.assert_invariants();
         self.basefee_pool.assert_invariants();
@@ -1231,6 +1315,11 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     txs: BTreeMap<TransactionId, PoolInternalTransaction<T>>,
     /// Tracks the number of transactions by sender that are currently in the pool.
     tx_counter: FxHashMap<SenderId, usize>,
+    /// Tracks the transactions in the pool by sender, and how often each sender replaced one of
+    /// them.
+    senders: FxHashMap<SenderId, SenderUsage>,
+    /// Tracks the transactions in the pool by the network peer they were received from.
+    peers: FxHashMap<PeerId, PeerUsage>,
     /// The current block number the pool keeps track of.
     last_seen_block_number: u64,
     /// The current block hash the pool keeps track of.
@@ -1243,6 +1332,8 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     local_transactions_config: LocalTransactionConfig,
     /// All accounts with a pooled authorization
     auths: FxHashMap<SenderId, HashSet<TxHash>>,
+    /// Admission policy for new transactions, if any.
+    eviction_policy: Option<Arc<dyn EvictionPolicy>>,
     /// All Transactions metrics
     metrics: AllTransactionsMetrics,
 }
@@ -1256,6 +1347,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
             local_transactions_config: config.local_transactions_config.clone(),
             minimal_protocol_basefee: config.minimal_protocol_basefee,
             block_gas_limit: config.gas_limit,
+            eviction_policy: config.eviction_policy.clone(),
             ..Default::default()
         }
     }
@@ -1304,6 +1396,88 @@ impl<T: PoolTransaction> AllTransactions<T> {
         }
     }
 
+    /// Adds the transaction to the usage of its sender and of the peer it was received from, if
+    /// any.
+    fn usage_inc(&mut self, tx: &ValidPoolTransaction<T>) {
+        let sender = self.senders.entry(tx.sender_id()).or_default();
+        sender.txs += 1;
+        sender.size += tx.size();
+
+        let Some(peer) = tx.peer else { return };
+        let usage = self.peers.entry(peer).or_default();
+        usage.txs += 1;
+        usage.size += tx.size();
+    }
+
+    /// Removes the transaction from the usage of its sender and of the peer it was received from,
+    /// if any.
+    ///
+    /// The replacements of the sender are reset once it has no transactions left.
+    fn usage_decr(&mut self, tx: &ValidPoolTransaction<T>) {
+        if let hash_map::Entry::Occupied(mut entry) = self.senders.entry(tx.sender_id()) {
+            let usage = entry.get_mut();
+            if usage.txs <= 1 {
+                entry.remove();
+            } else {
+                usage.txs -= 1;
+                usage.size = usage.size.saturating_sub(tx.size());
+            }
+        }
+
+        let Some(peer) = tx.peer else { return };
+        if let hash_map::Entry::Occupied(mut entry) = self.peers.entry(peer) {
+            let usage = entry.get_mut();
+            if usage.txs <= 1 {
+                entry.remove();
+                return
+            }
+            usage.txs -= 1;
+            usage.size = usage.size.saturating_sub(tx.size());
+        }
+    }
+
+    /// Returns the usage of the pool by the transactions received from the peer of the given
+    /// transaction, excluding the transaction it replaces.
+    fn peer_usage(&self, tx: &ValidPoolTransaction<T>) -> PeerUsage {
+        let Some(peer) = tx.peer else { return PeerUsage::default() };
+        let mut usage = self.peers.get(&peer).copied().unwrap_or_default();
+        if let Some(replaced) =
+            self.txs.get(tx.id()).filter(|replaced| replaced.transaction.peer == tx.peer)
+        {
+            usage.txs -= 1;
+            usage.size = usage.size.saturating_sub(replaced.transaction.size());
+        }
+        usage
+    }
+
+    /// Returns the transaction as considered by the [`EvictionPolicy`].
+    fn eviction_candidate(&self, tx: &ValidPoolTransaction<T>) -> EvictionCandidate {
+        EvictionCandidate {
+            id: *tx.id(),
+            origin: tx.origin,
+            peer: tx.peer,
+            local: self.local_transactions_config.is_local(tx.origin, tx.sender_ref()),
+            size: tx.size(),
+            timestamp: tx.timestamp,
+            sender_replacements: self
+                .senders
+                .get(&tx.sender_id())
+                .map(|usage| usage.replacements)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the usage of the pool by the transactions of the sender of the given transaction,
+    /// excluding the transaction it replaces.
+    fn sender_usage(&self, tx: &ValidPoolTransaction<T>) -> SenderUsage {
+        let mut usage = self.senders.get(&tx.sender_id()).copied().unwrap_or_default();
+        if let Some(replaced) = self.txs.get(tx.id()) {
+            usage.txs -= 1;
+            usage.size = usage.size.saturating_sub(replaced.transaction.size());
+        }
+        usage
+    }
+
     /// Updates the block specific info
     fn set_block_info(&mut self, block_info: BlockInfo) {
         let BlockInfo {
@@ -1582,6 +1756,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         self.remove_auths(&internal);
         // decrement the counter for the sender.
         self.tx_decr(tx.sender_id());
+        self.usage_decr(&tx);
         self.update_size_metrics();
         Some((tx, internal.subpool))
     }
@@ -1598,6 +1773,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         self.remove_auths(&internal);
         // decrement the counter for the sender.
         self.tx_decr(tx.sender_id());
+        self.usage_decr(&tx);
         Some((tx, internal.subpool))
     }
 
@@ -1642,6 +1818,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
         // decrement the counter for the sender.
         self.tx_decr(internal.transaction.sender_id());
+        self.usage_decr(&internal.transaction);
 
         let result =
             self.by_hash.remove(internal.transaction.hash()).map(|tx| (tx, internal.subpool));
@@ -1687,6 +1864,8 @@ impl<T: PoolTransaction> AllTransactions<T> {
     /// This will enforce all additional rules in the context of this pool, such as:
     ///   - Spam protection: reject new non-local transaction from a sender that exhausted its slot
     ///     capacity.
+    ///   - Eviction policy: reject new transactions that the configured [`EvictionPolicy`] doesn't
+    ///     admit.
     ///   - Gas limit: reject transactions if they exceed a block's maximum gas.
     ///   - Ensures transaction types are not conflicting for the sender: blob vs normal
     ///     transactions are mutually exclusive for the same sender.
@@ -1707,6 +1886,27 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 })
             }
         }
+        if let Some(policy) = &self.eviction_policy {
+            // If transaction's nonce matches on-chain nonce always let it through
+            if transaction.nonce() > on_chain_nonce {
+                let sender = self.sender_usage(&transaction);
+                let peer = self.peer_usage(&transaction);
+                match policy.admit(&self.eviction_candidate(&transaction), &sender, &peer) {
+                    Ok(()) => {}
+                    Err(AdmissionError::SenderExceededCapacity) => {
+                        return Err(InsertErr::ExceededSenderTransactionsCapacity {
+                            transaction: Arc::new(transaction),
+                        })
+                    }
+                    Err(AdmissionError::PeerExceededCapacity(peer)) => {
+                        return Err(InsertErr::ExceededPeerTransactionsCapacity {
+                            transaction: Arc::new(transaction),
+                            peer,
+                        })
+                    }
+                }
+            }
+        }
         if transaction.gas_limit() > self.block_gas_limit {
             return Err(InsertErr::TxGasLimitMoreThanAvailableBlockGas {
                 block_gas_limit: self.block_gas_limit,
@@ -1890,6 +2090,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 // Insert the transaction in both maps
                 self.by_hash.insert(*pool_tx.transaction.hash(), pool_tx.transaction.clone());
                 entry.insert(pool_tx);
+                self.usage_inc(&transaction);
             }
             Entry::Occupied(mut entry) => {
                 // Transaction with the same nonce already exists: replacement candidate
@@ -1910,6 +2111,10 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 self.by_hash.insert(new_hash, new_transaction);
 
                 self.remove_auths(&replaced);
+                // add the replacement first, so the sender keeps its replacements
+                self.usage_inc(&transaction);
+                self.usage_decr(&replaced.transaction);
+                self.senders.entry(transaction.sender_id()).or_default().replacements += 1;
 
                 // also remove the hash
                 replaced_tx = Some((replaced.transaction, replaced.subpool));
@@ -2037,12 +2242,15 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             by_hash: Default::default(),
             txs: Default::default(),
             tx_counter: Default::default(),
+            senders: Default::default(),
+            peers: Default::default(),
             last_seen_block_number: Default::default(),
             last_seen_block_hash: Default::default(),
             pending_fees: Default::default(),
             price_bumps: Default::default(),
             local_transactions_config: Default::default(),
             auths: Default::default(),
+            eviction_policy: None,
             metrics: Default::default(),
         }
     }
@@ -2088,6 +2296,9 @@ pub(crate) enum InsertErr<T: PoolTransaction> {
     ///
     /// The sender can be considered a spammer at this point.
     ExceededSenderTransactionsCapacity { transaction: Arc<ValidPoolTransaction<T>> },
+    /// The transactions received from the network peer of the transaction exceed the limit of the
+    /// [`EvictionPolicy`].
+    ExceededPeerTransactionsCapacity { transaction: Arc<ValidPoolTransaction<T>>, peer: PeerId },
     /// Transaction gas limit exceeds block's gas limit
     TxGasLimitMoreThanAvailableBlockGas {
         transaction: Arc<ValidPoolTransaction<T>>,
@@ -3177,6 +3388,107 @@ mod tests {
         }
     }
 
+    #[test]
+    fn fair_share_rejects_oversized_sender() {
+        let mut f = MockTransactionFactory::default();
+        let policy = crate::eviction::FairShareEvictionPolicy::new(300, usize::MAX);
+        let mut pool = TxPool::new(
+            MockOrdering::default(),
+            PoolConfig { eviction_policy: Some(Arc::new(policy)), ..Default::default() },
+        );
+
+        let sender = address!("0x000000000000000000000000000000000000000a");
+        let mut txs = MockTransactionSet::dependent(sender, 0, 4, TxType::Eip1559).into_vec();
+        for tx in &mut txs {
+            tx.set_size(100);
+        }
+        let oversized = txs.pop().unwrap();
+
+        for tx in txs {
+            pool.add_transaction(f.validated(tx), U256::from(1_000), 0, None).unwrap();
+        }
+
+        let err =
+            pool.add_transaction(f.validated(oversized), U256::from(1_000), 0, None).unwrap_err();
+        assert!(matches!(err.kind, PoolErrorKind::SpammerExceededCapacity(addr) if addr == sender));
+    }
+
+    #[test]
+    fn fair_share_rejects_oversized_peer() {
+        let mut f = MockTransactionFactory::default();
+        let policy = crate::eviction::FairShareEvictionPolicy::new(usize::MAX, 300);
+        let mut pool = TxPool::new(
+            MockOrdering::default(),
+            PoolConfig { eviction_policy: Some(Arc::new(policy)), ..Default::default() },
+        );
+        let peer = PeerId::with_last_byte(1);
+
+        // transactions of different senders above their on-chain nonce, relayed by the same peer
+        let mut from_peer = || {
+            let mut tx = MockTransaction::eip1559().inc_nonce();
+            tx.set_size(100);
+            let mut tx = f.validated(tx);
+            tx.peer = Some(peer);
+            tx
+        };
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            let tx = from_peer();
+            ids.push(*tx.id());
+            pool.add_transaction(tx, U256::from(1_000), 0, None).unwrap();
+        }
+        let err = pool.add_transaction(from_peer(), U256::from(1_000), 0, None).unwrap_err();
+        assert!(matches!(err.kind, PoolErrorKind::PeerExceededCapacity(id) if id == peer));
+
+        // other peers are not affected
+        let mut tx = from_peer();
+        tx.peer = Some(PeerId::with_last_byte(2));
+        pool.add_transaction(tx, U256::from(1_000), 0, None).unwrap();
+
+        // removed transactions free the space of the peer and the sender
+        let sender = ids[0].sender;
+        assert_eq!(pool.all_transactions.senders[&sender].size, 100);
+        pool.remove_transaction(&ids[0]).unwrap();
+        assert_eq!(pool.all_transactions.peers[&peer].size, 200);
+        assert!(!pool.all_transactions.senders.contains_key(&sender));
+        pool.add_transaction(from_peer(), U256::from(1_000), 0, None).unwrap();
+        pool.assert_invariants();
+    }
+
+    #[test]
+    fn fair_share_evicts_spammer_first() {
+        let mut f = MockTransactionFactory::default();
+        let limit = SubPoolLimit::new(usize::MAX, 400);
+        let mut pool = TxPool::new(
+            MockOrdering::default(),
+            PoolConfig {
+                pending_limit: limit,
+                eviction_policy: Some(
+                    Arc::new(crate::eviction::FairShareEvictionPolicy::default()),
+                ),
+                ..Default::default()
+            },
+        );
+
+        // the spammer uses 400 bytes, the other sender 100 bytes, the share is 200 bytes
+        let spammer = address!("0x000000000000000000000000000000000000000a");
+        let other = address!("0x000000000000000000000000000000000000000b");
+        let mut txs = MockTransactionSet::dependent(spammer, 0, 4, TxType::Eip1559).into_vec();
+        txs.push(MockTransaction::eip1559().with_sender(other));
+        for mut tx in txs {
+            tx.set_size(100);
+            pool.add_transaction(f.validated(tx), U256::from(1_000), 0, None).unwrap();
+        }
+        assert_eq!(pool.size().pending, 5);
+
+        // the highest nonce of the spammer is evicted, although all transactions pay the same fee
+        let removed = pool.discard_worst();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].sender(), spammer);
+        assert_eq!(removed[0].nonce(), 3);
+        pool.assert_invariants();
+    }
+
     #[test]
     fn discard_blobs_at_capacity() {
         let mut f = MockTransactionFactory::default();
@@ -504,6 +504,25 @@ impl MockTransaction {
         self
     }
 
+    /// Sets the recipient of the transaction.
+    pub fn set_to(&mut self, val: Address) -> &mut Self {
+        match self {
+            Self::Legacy { to, .. } | Self::Eip2930 { to, .. } | Self::Eip1559 { to, .. } => {
+                *to = val.into();
+            }
+            Self::Eip4844 { to, .. } | Self::Eip7702 { to, .. } => {
+                *to = val;
+            }
+        }
+        self
+    }
+
+    /// Sets the recipient of the transaction using a fluent interface.
+    pub fn with_to(mut self, val: Address) -> Self {
+        self.set_to(val);
+        self
+    }
+
     /// Sets the gas price for the transaction.
     pub const fn with_gas_price(mut self, val: u128) -> Self {
         match self {
@@ -1355,6 +1374,7 @@ impl MockTransactionFactory {
             transaction,
             timestamp: Instant::now(),
             origin,
+            peer: None,
             authority_ids: None,
         }
     }
@@ -52,7 +52,9 @@
 
 use crate::{
     blobstore::BlobStoreError,
+    conditional::MaybeConditionalTransaction,
     error::{InvalidPoolTransactionError, PoolError, PoolResult},
+    inclusion::InclusionPolicyHandle,
     pool::{
         state::SubPool, BestTransactionFilter, NewTransactionEvent, TransactionEvents,
         TransactionListenerKind,
@@ -71,6 +73,7 @@ use alloy_eips::{
     eip7702::SignedAuthorization,
 };
 use alloy_primitives::{Address, Bytes, TxHash, TxKind, B256, U256};
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
 use futures_util::{ready, Stream};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_ethereum_primitives::{PooledTransactionVariant, TransactionSigned};
@@ -121,6 +124,14 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// This tracks the block that the pool has last seen.
     fn block_info(&self) -> BlockInfo;
 
+    /// Returns the inclusion policy that transactions entering the pool are checked against, if
+    /// any.
+    ///
+    /// Payload builders can share it to enforce the same policy on built payloads.
+    fn inclusion_policy(&self) -> Option<InclusionPolicyHandle> {
+        None
+    }
+
     /// Imports an _external_ transaction.
     ///
     /// This is intended to be used by the network to insert incoming transactions received over the
@@ -144,6 +155,23 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         self.add_transactions(TransactionOrigin::External, transactions)
     }
 
+    /// Imports all _external_ transactions that were received from the given peer.
+    ///
+    /// The peer is recorded as the [`ValidPoolTransaction::peer`] of the transactions, which
+    /// [`EvictionPolicy`](crate::eviction::EvictionPolicy)s can bound the pool space of. Pools that
+    /// don't track peers import the transactions like
+    /// [`TransactionPool::add_external_transactions`].
+    ///
+    /// Consumer: P2P
+    fn add_external_transactions_from_peer(
+        &self,
+        peer: PeerId,
+        transactions: Vec<Self::Transaction>,
+    ) -> impl Future<Output = Vec<PoolResult<AddedTransactionOutcome>>> + Send {
+        let _ = peer;
+        self.add_external_transactions(transactions)
+    }
+
     /// Adds an _unvalidated_ transaction into the pool and subscribe to state changes.
     ///
     /// This is the same as [`TransactionPool::add_transaction`] but returns an event stream for the
@@ -1244,6 +1272,9 @@ pub struct EthPooledTransaction<T = TransactionSigned> {
 
     /// The blob side car for this transaction
     pub blob_sidecar: EthBlobTransactionSidecar,
+
+    /// Optional conditional attached to this transaction.
+    pub conditional: Option<Box<TransactionConditional>>,
 }
 
 impl<T: SignedTransaction> EthPooledTransaction<T> {
@@ -1272,7 +1303,7 @@ impl<T: SignedTransaction> EthPooledTransaction<T> {
             blob_sidecar = EthBlobTransactionSidecar::Missing;
         }
 
-        Self { transaction, cost, encoded_length, blob_sidecar }
+        Self { transaction, cost, encoded_length, blob_sidecar, conditional: None }
     }
 
     /// Return the reference to the underlying transaction.
@@ -1281,6 +1312,16 @@ impl<T: SignedTransaction> EthPooledTransaction<T> {
     }
 }
 
+impl<T> MaybeConditionalTransaction for EthPooledTransaction<T> {
+    fn set_conditional(&mut self, conditional: TransactionConditional) {
+        self.conditional = Some(Box::new(conditional))
+    }
+
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        self.conditional.as_deref()
+    }
+}
+
 impl PoolTransaction for EthPooledTransaction {
     type TryFromConsensusError = ValueError<TransactionSigned>;
 
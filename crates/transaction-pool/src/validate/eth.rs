@@ -6,6 +6,7 @@ use crate::{
     error::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
     },
+    inclusion::InclusionPolicyHandle,
     metrics::TxPoolValidationMetrics,
     traits::TransactionOrigin,
     validate::{ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE},
@@ -112,6 +113,11 @@ impl<Client, Tx> EthTransactionValidator<Client, Tx> {
     pub fn max_tx_input_bytes(&self) -> usize {
         self.inner.max_tx_input_bytes
     }
+
+    /// Returns the inclusion policy enforced for acceptance into the pool, if any.
+    pub fn inclusion_policy(&self) -> Option<&InclusionPolicyHandle> {
+        self.inner.inclusion_policy.as_ref()
+    }
 }
 
 impl<Client, Tx> EthTransactionValidator<Client, Tx>
@@ -187,6 +193,10 @@ where
     {
         self.inner.on_new_head_block(new_tip_block.header())
     }
+
+    fn inclusion_policy(&self) -> Option<&InclusionPolicyHandle> {
+        self.inner.inclusion_policy.as_ref()
+    }
 }
 
 /// A [`TransactionValidator`] implementation that validates ethereum transaction.
@@ -233,6 +243,8 @@ pub(crate) struct EthTransactionValidatorInner<Client, T> {
     max_tx_input_bytes: usize,
     /// Maximum gas limit for individual transactions
     max_tx_gas_limit: Option<u64>,
+    /// Address deny-list enforced for acceptance into the pool.
+    inclusion_policy: Option<InclusionPolicyHandle>,
     /// Marker for the transaction type
     _marker: PhantomData<T>,
     /// Metrics for tsx pool validation
@@ -428,6 +440,14 @@ where
             ))
         }
 
+        // Reject transactions touching addresses denied by the inclusion policy, this also applies
+        // to local transactions
+        if let Some(policy) = &self.inclusion_policy {
+            if let Err(err) = policy.current().check_transaction(&transaction) {
+                return Err(TransactionValidationOutcome::Invalid(transaction, err.into()))
+            }
+        }
+
         // determine whether the transaction should be treated as local
         let is_local = self.local_transactions_config.is_local(origin, transaction.sender_ref());
 
@@ -809,6 +829,8 @@ pub struct EthTransactionValidatorBuilder<Client> {
     max_tx_input_bytes: usize,
     /// Maximum gas limit for individual transactions
     max_tx_gas_limit: Option<u64>,
+    /// Address deny-list enforced for acceptance into the pool.
+    inclusion_policy: Option<InclusionPolicyHandle>,
 }
 
 impl<Client> EthTransactionValidatorBuilder<Client> {
@@ -832,6 +854,7 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
             tx_fee_cap: Some(1e18 as u128),
             max_tx_gas_limit: None,
+            inclusion_policy: None,
             // by default all transaction types are allowed
             eip2718: true,
             eip1559: true,
@@ -1007,6 +1030,15 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
         self
     }
 
+    /// Sets the inclusion policy that transactions must satisfy to be accepted into the pool.
+    pub fn with_inclusion_policy(
+        mut self,
+        inclusion_policy: Option<InclusionPolicyHandle>,
+    ) -> Self {
+        self.inclusion_policy = inclusion_policy;
+        self
+    }
+
     /// Builds a the [`EthTransactionValidator`] without spawning validator tasks.
     pub fn build<Tx, S>(self, blob_store: S) -> EthTransactionValidator<Client, Tx>
     where
@@ -1029,6 +1061,7 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
             local_transactions_config,
             max_tx_input_bytes,
             max_tx_gas_limit,
+            inclusion_policy,
             ..
         } = self;
 
@@ -1061,6 +1094,7 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
             local_transactions_config,
             max_tx_input_bytes,
             max_tx_gas_limit,
+            inclusion_policy,
             _marker: Default::default(),
             validation_metrics: TxPoolValidationMetrics::default(),
         };
@@ -1410,6 +1444,40 @@ mod tests {
         assert!(outcome.is_valid());
     }
 
+    #[tokio::test]
+    async fn invalid_on_inclusion_policy_denied_sender() {
+        let transaction = get_transaction();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+
+        let policy = InclusionPolicyHandle::new(Default::default());
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(provider)
+            .with_inclusion_policy(Some(policy.clone()))
+            .build(blob_store.clone());
+
+        let outcome = validator.validate_one(TransactionOrigin::Local, transaction.clone());
+        assert!(outcome.is_valid());
+
+        // policy updates apply to subsequent validations
+        policy.set(crate::inclusion::InclusionPolicy::default().deny_sender(transaction.sender()));
+
+        let pool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        let res = pool.add_transaction(TransactionOrigin::Local, transaction.clone()).await;
+        assert!(res.is_err());
+        assert!(matches!(
+            res.unwrap_err().kind,
+            PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::InclusionPolicy(
+                crate::inclusion::InclusionPolicyViolation::DeniedSender(sender)
+            )) if sender == transaction.sender()
+        ));
+        assert!(pool.get(transaction.hash()).is_none());
+    }
+
     #[tokio::test]
     async fn valid_on_max_tx_gas_limit_within_limit() {
         let transaction = get_transaction();
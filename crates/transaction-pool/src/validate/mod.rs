@@ -3,12 +3,14 @@
 use crate::{
     error::InvalidPoolTransactionError,
     identifier::{SenderId, TransactionId},
+    inclusion::InclusionPolicyHandle,
     traits::{PoolTransaction, TransactionOrigin},
     PriceBumpConfig,
 };
 use alloy_eips::{eip7594::BlobTransactionSidecarVariant, eip7702::SignedAuthorization};
 use alloy_primitives::{Address, TxHash, B256, U256};
 use futures_util::future::Either;
+use reth_network_peers::PeerId;
 use reth_primitives_traits::{Recovered, SealedBlock};
 use std::{fmt, fmt::Debug, future::Future, time::Instant};
 
@@ -236,6 +238,11 @@ pub trait TransactionValidator: Debug + Send + Sync {
         B: Block,
     {
     }
+
+    /// Returns the inclusion policy that transactions are checked against, if any.
+    fn inclusion_policy(&self) -> Option<&InclusionPolicyHandle> {
+        None
+    }
 }
 
 impl<A, B> TransactionValidator for Either<A, B>
@@ -286,6 +293,13 @@ where
             Self::Right(v) => v.on_new_head_block(new_tip_block),
         }
     }
+
+    fn inclusion_policy(&self) -> Option<&InclusionPolicyHandle> {
+        match self {
+            Self::Left(v) => v.inclusion_policy(),
+            Self::Right(v) => v.inclusion_policy(),
+        }
+    }
 }
 
 /// A valid transaction in the pool.
@@ -305,6 +319,8 @@ pub struct ValidPoolTransaction<T: PoolTransaction> {
     pub timestamp: Instant,
     /// Where this transaction originated from.
     pub origin: TransactionOrigin,
+    /// The peer this transaction was received from, if it was imported from the network.
+    pub peer: Option<PeerId>,
     /// The sender ids of the 7702 transaction authorities.
     pub authority_ids: Option<Vec<SenderId>>,
 }
@@ -509,6 +525,7 @@ impl<T: PoolTransaction> Clone for ValidPoolTransaction<T> {
             propagate: self.propagate,
             timestamp: self.timestamp,
             origin: self.origin,
+            peer: self.peer,
             authority_ids: self.authority_ids.clone(),
         }
     }
@@ -520,6 +537,7 @@ impl<T: PoolTransaction> fmt::Debug for ValidPoolTransaction<T> {
             .field("id", &self.transaction_id)
             .field("pragate", &self.propagate)
             .field("origin", &self.origin)
+            .field("peer", &self.peer)
             .field("hash", self.transaction.hash())
             .field("tx", &self.transaction)
             .finish()
@@ -2,6 +2,7 @@
 
 use crate::{
     blobstore::BlobStore,
+    inclusion::InclusionPolicyHandle,
     validate::{EthTransactionValidatorBuilder, TransactionValidatorError},
     EthTransactionValidator, PoolTransaction, TransactionOrigin, TransactionValidationOutcome,
     TransactionValidator,
@@ -250,4 +251,8 @@ where
     {
         self.validator.on_new_head_block(new_tip_block)
     }
+
+    fn inclusion_policy(&self) -> Option<&InclusionPolicyHandle> {
+        self.validator.inclusion_policy()
+    }
 }
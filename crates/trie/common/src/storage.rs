@@ -1,4 +1,4 @@
-use super::{BranchNodeCompact, StoredNibblesSubKey};
+use super::{BranchNodeCompact, Nibbles, StoredNibblesSubKey};
 
 /// Account storage trie node.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -31,3 +31,81 @@ impl reth_codecs::Compact for StorageTrieEntry {
         (this, buf)
     }
 }
+
+/// Trie changeset entry representing the value of a trie node before it was modified.
+///
+/// Used by both the account and storage trie changesets. A missing node means that the node
+/// did not exist before the change.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+pub struct TrieChangeSetsEntry {
+    /// The nibbles of the intermediate node
+    pub nibbles: StoredNibblesSubKey,
+    /// Node value before the change, if any.
+    pub node: Option<BranchNodeCompact>,
+}
+
+impl TrieChangeSetsEntry {
+    /// Returns the entry that marks a block, see [`Self::is_marker`].
+    pub fn marker() -> Self {
+        Self { nibbles: StoredNibblesSubKey(Nibbles::default()), node: None }
+    }
+
+    /// Returns `true` if the entry marks a block instead of recording a node:
+    /// - In the trie changesets, a block whose trie changes were recorded together with the changes
+    ///   of the preceding blocks, e.g. when the trie is updated for a range of blocks at once. The
+    ///   trie can't be reverted to the start of such a block.
+    /// - In the trie snapshots, a block that a snapshot is taken at, so that it can be found even
+    ///   if no node changed since.
+    ///
+    /// The root node is never stored, so the marker never collides with the entry of a node.
+    pub fn is_marker(&self) -> bool {
+        self.nibbles.0.is_empty()
+    }
+}
+
+// NOTE: Same as for `StorageTrieEntry`, the subkey is encoded manually so that
+// `seek_by_key_subkey` works. An absent node is encoded as an empty remainder.
+#[cfg(any(test, feature = "reth-codec"))]
+impl reth_codecs::Compact for TrieChangeSetsEntry {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        let nibbles_len = self.nibbles.to_compact(buf);
+        let node_len = self.node.as_ref().map(|node| node.to_compact(buf)).unwrap_or(0);
+        nibbles_len + node_len
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let (nibbles, buf) = StoredNibblesSubKey::from_compact(buf, 33);
+        if len <= 33 {
+            return (Self { nibbles, node: None }, buf)
+        }
+        let (node, buf) = BranchNodeCompact::from_compact(buf, len - 33);
+        (Self { nibbles, node: Some(node) }, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_codecs::Compact;
+
+    #[test]
+    fn trie_changesets_entry_roundtrip() {
+        let nibbles = StoredNibblesSubKey(Nibbles::from_nibbles_unchecked([0x0a, 0x0b]));
+        let node = BranchNodeCompact::new(0b11, 0b01, 0, vec![], None);
+
+        for entry in [
+            TrieChangeSetsEntry { nibbles: nibbles.clone(), node: None },
+            TrieChangeSetsEntry { nibbles, node: Some(node) },
+            TrieChangeSetsEntry::marker(),
+        ] {
+            let mut buf = Vec::new();
+            let len = entry.to_compact(&mut buf);
+            let (decoded, _) = TrieChangeSetsEntry::from_compact(&buf, len);
+            assert_eq!(decoded, entry);
+        }
+    }
+}
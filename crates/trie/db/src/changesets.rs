@@ -0,0 +1,347 @@
+use alloy_primitives::{
+    map::{B256Map, HashMap},
+    BlockNumber, B256,
+};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::BlockNumberHashedAddress,
+    tables,
+    transaction::DbTx,
+    DatabaseError,
+};
+use reth_trie::{
+    updates::{StorageTrieUpdates, TrieUpdates},
+    BranchNodeCompact, Nibbles, StoredNibblesSubKey, TrieChangeSetsEntry,
+};
+
+/// The interval, in blocks, at which trie snapshots are taken.
+///
+/// A snapshot records the value at its block of every trie node changed since, so reverting the
+/// trie walks the trie changesets up to the next snapshot and the snapshot, instead of the trie
+/// changesets of all blocks up to the tip.
+pub const TRIE_SNAPSHOT_INTERVAL: u64 = 50_400;
+
+/// Extends [`TrieUpdates`] with operations specific for working with trie changesets.
+pub trait DatabaseTrieUpdates<TX>: Sized {
+    /// Returns the lowest block number for which account or storage trie changesets are stored.
+    fn lowest_trie_changesets_block(tx: &TX) -> Result<Option<BlockNumber>, DatabaseError>;
+
+    /// Returns `true` if the trie changesets can revert the trie to the state at the start of the
+    /// given block.
+    ///
+    /// Trie changesets are stored for a contiguous range of blocks up to the tip. Blocks whose
+    /// trie changes were recorded together with the changes of the preceding blocks are marked
+    /// with [`TrieChangeSetsEntry::batch_marker`], and the trie can't be reverted to them.
+    fn can_revert_to(tx: &TX, block_number: BlockNumber) -> Result<bool, DatabaseError>;
+
+    /// Returns the blocks of all trie snapshots, in ascending order.
+    fn trie_snapshots(tx: &TX) -> Result<Vec<BlockNumber>, DatabaseError>;
+
+    /// Returns the block of the first trie snapshot at or after the given block, which
+    /// [`Self::from_reverts`] reverts the trie from.
+    fn trie_snapshot_from(
+        tx: &TX,
+        block_number: BlockNumber,
+    ) -> Result<Option<BlockNumber>, DatabaseError>;
+
+    /// Initializes [`TrieUpdates`] from trie changesets. Iterates over trie node reverts from the
+    /// specified block up to the first trie snapshot after it, or the current tip if there's
+    /// none, followed by the snapshot, and records the value of every node before its first
+    /// change.
+    ///
+    /// Applying the result on top of the current trie yields the trie at the start of the `from`
+    /// block, if [`Self::can_revert_to`] returns `true` for it.
+    fn from_reverts(tx: &TX, from: BlockNumber) -> Result<Self, DatabaseError>;
+}
+
+impl<TX: DbTx> DatabaseTrieUpdates<TX> for TrieUpdates {
+    fn lowest_trie_changesets_block(tx: &TX) -> Result<Option<BlockNumber>, DatabaseError> {
+        let lowest_account = tx
+            .cursor_read::<tables::AccountsTrieChangeSets>()?
+            .first()?
+            .map(|(block_number, _)| block_number);
+        let lowest_storage = tx
+            .cursor_read::<tables::StoragesTrieChangeSets>()?
+            .first()?
+            .map(|(key, _)| key.block_number());
+        Ok(lowest_account.into_iter().chain(lowest_storage).min())
+    }
+
+    fn can_revert_to(tx: &TX, block_number: BlockNumber) -> Result<bool, DatabaseError> {
+        if Self::lowest_trie_changesets_block(tx)?.is_none_or(|lowest| lowest > block_number) {
+            return Ok(false)
+        }
+
+        // the marker sorts before the entries of all nodes
+        let is_batched = tx
+            .cursor_dup_read::<tables::AccountsTrieChangeSets>()?
+            .seek_by_key_subkey(block_number, StoredNibblesSubKey(Nibbles::default()))?
+            .is_some_and(|entry| entry.is_marker());
+        Ok(!is_batched)
+    }
+
+    fn trie_snapshots(tx: &TX) -> Result<Vec<BlockNumber>, DatabaseError> {
+        let mut cursor = tx.cursor_dup_read::<tables::AccountsTrieSnapshots>()?;
+        let mut snapshots = Vec::new();
+        let mut entry = cursor.first()?;
+        while let Some((block_number, _)) = entry {
+            snapshots.push(block_number);
+            entry = cursor.next_no_dup()?;
+        }
+        Ok(snapshots)
+    }
+
+    fn trie_snapshot_from(
+        tx: &TX,
+        block_number: BlockNumber,
+    ) -> Result<Option<BlockNumber>, DatabaseError> {
+        // every snapshot has a marker, so any entry belongs to a snapshot
+        Ok(tx
+            .cursor_read::<tables::AccountsTrieSnapshots>()?
+            .seek(block_number)?
+            .map(|(block_number, _)| block_number))
+    }
+
+    fn from_reverts(tx: &TX, from: BlockNumber) -> Result<Self, DatabaseError> {
+        // The snapshot records the value of every node changed since, so the trie changesets of
+        // the later blocks don't need to be walked.
+        let snapshot = Self::trie_snapshot_from(tx, from)?;
+        let to = snapshot.unwrap_or(BlockNumber::MAX);
+
+        // Iterate over account trie changesets and the snapshot, and record node value before
+        // first occurring change.
+        let mut account_nodes = HashMap::<Nibbles, Option<BranchNodeCompact>>::default();
+        let mut record_account_node = |entry: TrieChangeSetsEntry| {
+            if !entry.is_marker() {
+                account_nodes.entry(entry.nibbles.0).or_insert(entry.node);
+            }
+        };
+        let mut account_changesets_cursor = tx.cursor_read::<tables::AccountsTrieChangeSets>()?;
+        for entry in account_changesets_cursor.walk_range(from..to)? {
+            record_account_node(entry?.1);
+        }
+        if let Some(snapshot) = snapshot {
+            let mut account_snapshots_cursor =
+                tx.cursor_dup_read::<tables::AccountsTrieSnapshots>()?;
+            for entry in account_snapshots_cursor.walk_dup(Some(snapshot), None)? {
+                record_account_node(entry?.1);
+            }
+        }
+
+        // Iterate over storage trie changesets and the snapshot, and record node value before
+        // first occurring change.
+        let mut storage_nodes = B256Map::<HashMap<Nibbles, Option<BranchNodeCompact>>>::default();
+        let mut record_storage_node =
+            |key: BlockNumberHashedAddress, TrieChangeSetsEntry { nibbles, node }| {
+                storage_nodes
+                    .entry(key.hashed_address())
+                    .or_default()
+                    .entry(nibbles.0)
+                    .or_insert(node);
+            };
+        let mut storage_changesets_cursor = tx.cursor_read::<tables::StoragesTrieChangeSets>()?;
+        let storage_range = BlockNumberHashedAddress((from, B256::ZERO))..
+            BlockNumberHashedAddress((to, B256::ZERO));
+        for entry in storage_changesets_cursor.walk_range(storage_range)? {
+            let (key, entry) = entry?;
+            record_storage_node(key, entry);
+        }
+        if let Some(snapshot) = snapshot {
+            let mut storage_snapshots_cursor = tx.cursor_read::<tables::StoragesTrieSnapshots>()?;
+            for entry in storage_snapshots_cursor
+                .walk_range(BlockNumberHashedAddress::range(snapshot..=snapshot))?
+            {
+                let (key, entry) = entry?;
+                record_storage_node(key, entry);
+            }
+        }
+
+        let mut updates = Self::default();
+        for (nibbles, node) in account_nodes {
+            match node {
+                Some(node) => {
+                    updates.account_nodes.insert(nibbles, node);
+                }
+                None => {
+                    updates.removed_nodes.insert(nibbles);
+                }
+            }
+        }
+
+        for (hashed_address, nodes) in storage_nodes {
+            let mut storage_updates = StorageTrieUpdates::default();
+            for (nibbles, node) in nodes {
+                match node {
+                    Some(node) => {
+                        storage_updates.storage_nodes.insert(nibbles, node);
+                    }
+                    None => {
+                        storage_updates.removed_nodes.insert(nibbles);
+                    }
+                }
+            }
+            updates.storage_tries.insert(hashed_address, storage_updates);
+        }
+
+        Ok(updates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db_api::{cursor::DbCursorRW, transaction::DbTxMut};
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    #[test]
+    fn from_reverts_keeps_first_change() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        let path = Nibbles::from_nibbles_unchecked([0x1, 0x2]);
+        let removed = Nibbles::from_nibbles_unchecked([0x3]);
+        let node_at_1 = BranchNodeCompact::new(0b11, 0, 0, vec![], None);
+        let node_at_2 = BranchNodeCompact::new(0b101, 0, 0, vec![], None);
+        let hashed_address = B256::with_last_byte(1);
+
+        let mut cursor = tx.cursor_dup_write::<tables::AccountsTrieChangeSets>().unwrap();
+        for (block_number, nibbles, node) in [
+            (1, path, Some(node_at_1.clone())),
+            (2, path, Some(node_at_2.clone())),
+            (2, removed, None),
+        ] {
+            cursor
+                .upsert(
+                    block_number,
+                    &TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(nibbles), node },
+                )
+                .unwrap();
+        }
+
+        let mut cursor = tx.cursor_dup_write::<tables::StoragesTrieChangeSets>().unwrap();
+        cursor
+            .upsert(
+                BlockNumberHashedAddress((3, hashed_address)),
+                &TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(path), node: None },
+            )
+            .unwrap();
+
+        assert_eq!(TrieUpdates::lowest_trie_changesets_block(tx).unwrap(), Some(1));
+
+        let updates = TrieUpdates::from_reverts(tx, 1).unwrap();
+        assert_eq!(updates.account_nodes.get(&path), Some(&node_at_1));
+        assert!(updates.removed_nodes.contains(&removed));
+        assert!(updates.storage_tries[&hashed_address].removed_nodes.contains(&path));
+
+        let updates = TrieUpdates::from_reverts(tx, 2).unwrap();
+        assert_eq!(updates.account_nodes.get(&path), Some(&node_at_2));
+
+        let updates = TrieUpdates::from_reverts(tx, 3).unwrap();
+        assert!(updates.account_nodes.is_empty());
+        assert!(updates.removed_nodes.is_empty());
+        assert_eq!(updates.storage_tries.len(), 1);
+    }
+
+    #[test]
+    fn can_revert_to_skips_batched_blocks() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        let path = Nibbles::from_nibbles_unchecked([0x1]);
+        let entry = TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(path), node: None };
+
+        // block 5 only changed a storage trie
+        let mut cursor = tx.cursor_dup_write::<tables::StoragesTrieChangeSets>().unwrap();
+        cursor.upsert(BlockNumberHashedAddress((5, B256::with_last_byte(1))), &entry).unwrap();
+
+        // block 6 was recorded together with block 5
+        let mut cursor = tx.cursor_dup_write::<tables::AccountsTrieChangeSets>().unwrap();
+        cursor.upsert(6, &TrieChangeSetsEntry::marker()).unwrap();
+        cursor.upsert(7, &entry).unwrap();
+
+        assert_eq!(TrieUpdates::lowest_trie_changesets_block(tx).unwrap(), Some(5));
+        assert!(!TrieUpdates::can_revert_to(tx, 4).unwrap());
+        assert!(TrieUpdates::can_revert_to(tx, 5).unwrap());
+        assert!(!TrieUpdates::can_revert_to(tx, 6).unwrap());
+        assert!(TrieUpdates::can_revert_to(tx, 7).unwrap());
+
+        let updates = TrieUpdates::from_reverts(tx, 5).unwrap();
+        assert_eq!(updates.removed_nodes.len(), 1);
+        assert!(updates.removed_nodes.contains(&path));
+    }
+
+    #[test]
+    fn from_reverts_stops_at_snapshot() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        let changed_at_1 = Nibbles::from_nibbles_unchecked([0x1]);
+        let changed_at_3 = Nibbles::from_nibbles_unchecked([0x2]);
+        let node_at_1 = BranchNodeCompact::new(0b11, 0, 0, vec![], None);
+        let node_at_2 = BranchNodeCompact::new(0b101, 0, 0, vec![], None);
+        let hashed_address = B256::with_last_byte(1);
+
+        let mut cursor = tx.cursor_dup_write::<tables::AccountsTrieChangeSets>().unwrap();
+        cursor
+            .upsert(
+                1,
+                &TrieChangeSetsEntry {
+                    nibbles: StoredNibblesSubKey(changed_at_1),
+                    node: Some(node_at_1.clone()),
+                },
+            )
+            .unwrap();
+        cursor
+            .upsert(
+                3,
+                &TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(changed_at_3), node: None },
+            )
+            .unwrap();
+
+        // the snapshot at block 2 already recorded the changes of block 3
+        let mut cursor = tx.cursor_dup_write::<tables::AccountsTrieSnapshots>().unwrap();
+        for entry in [
+            TrieChangeSetsEntry::marker(),
+            TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(changed_at_1), node: None },
+            TrieChangeSetsEntry {
+                nibbles: StoredNibblesSubKey(changed_at_3),
+                node: Some(node_at_2.clone()),
+            },
+        ] {
+            cursor.upsert(2, &entry).unwrap();
+        }
+        let mut cursor = tx.cursor_dup_write::<tables::StoragesTrieSnapshots>().unwrap();
+        cursor
+            .upsert(
+                BlockNumberHashedAddress((2, hashed_address)),
+                &TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(changed_at_1), node: None },
+            )
+            .unwrap();
+
+        assert_eq!(TrieUpdates::trie_snapshots(tx).unwrap(), vec![2]);
+        assert_eq!(TrieUpdates::trie_snapshot_from(tx, 1).unwrap(), Some(2));
+        assert_eq!(TrieUpdates::trie_snapshot_from(tx, 2).unwrap(), Some(2));
+        assert_eq!(TrieUpdates::trie_snapshot_from(tx, 3).unwrap(), None);
+
+        // block 1 is reverted with its changeset, and the snapshot for the later blocks
+        let updates = TrieUpdates::from_reverts(tx, 1).unwrap();
+        assert_eq!(updates.account_nodes.get(&changed_at_1), Some(&node_at_1));
+        assert_eq!(updates.account_nodes.get(&changed_at_3), Some(&node_at_2));
+        assert!(!updates.removed_nodes.contains(&changed_at_3));
+        assert!(updates.storage_tries[&hashed_address].removed_nodes.contains(&changed_at_1));
+
+        // block 2 is reverted with the snapshot only
+        let updates = TrieUpdates::from_reverts(tx, 2).unwrap();
+        assert!(updates.removed_nodes.contains(&changed_at_1));
+        assert_eq!(updates.account_nodes.get(&changed_at_3), Some(&node_at_2));
+
+        // block 3 is after the snapshot
+        let updates = TrieUpdates::from_reverts(tx, 3).unwrap();
+        assert!(updates.account_nodes.is_empty());
+        assert!(updates.removed_nodes.contains(&changed_at_3));
+        assert!(updates.storage_tries.is_empty());
+    }
+}
@@ -2,6 +2,7 @@
 
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+mod changesets;
 mod commitment;
 mod hashed_cursor;
 mod prefix_set;
@@ -11,6 +12,7 @@ mod storage;
 mod trie_cursor;
 mod witness;
 
+pub use changesets::{DatabaseTrieUpdates, TRIE_SNAPSHOT_INTERVAL};
 pub use commitment::{MerklePatriciaTrie, StateCommitment};
 pub use hashed_cursor::{
     DatabaseHashedAccountCursor, DatabaseHashedCursorFactory, DatabaseHashedStorageCursor,
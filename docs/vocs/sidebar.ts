@@ -509,7 +509,32 @@ export const sidebar: SidebarItem[] = [
                     },
                     {
                         text: "reth prune",
-                        link: "/cli/reth/prune"
+                        link: "/cli/reth/prune",
+                        collapsed: true,
+                        items: [
+                            {
+                                text: "reth prune reconfigure",
+                                link: "/cli/reth/prune/reconfigure"
+                            }
+                        ]
+                    },
+                    {
+                        text: "reth exex",
+                        link: "/cli/reth/exex",
+                        collapsed: true,
+                        items: [
+                            {
+                                text: "reth exex wal",
+                                link: "/cli/reth/exex/wal",
+                                collapsed: true,
+                                items: [
+                                    {
+                                        text: "reth exex wal inspect",
+                                        link: "/cli/reth/exex/wal/inspect"
+                                    }
+                                ]
+                            }
+                        ]
                     }
                 ]
             }
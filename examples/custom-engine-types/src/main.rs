@@ -50,7 +50,7 @@ use reth_ethereum::{
         },
         EthEvmConfig, EthereumEthApiBuilder,
     },
-    pool::{PoolTransaction, TransactionPool},
+    pool::{conditional::MaybeConditionalTransaction, PoolTransaction, TransactionPool},
     primitives::{Block, RecoveredBlock, SealedBlock},
     provider::{EthStorage, StateProviderFactory},
     rpc::types::engine::ExecutionPayload,
@@ -329,8 +329,10 @@ where
             Primitives = EthPrimitives,
         >,
     >,
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
-        + Unpin
+    Pool: TransactionPool<
+            Transaction: PoolTransaction<Consensus = TransactionSigned>
+                             + MaybeConditionalTransaction,
+        > + Unpin
         + 'static,
 {
     type PayloadBuilder = CustomPayloadBuilder<Pool, Node::Provider>;
@@ -363,7 +365,9 @@ pub struct CustomPayloadBuilder<Pool, Client> {
 impl<Pool, Client> PayloadBuilder for CustomPayloadBuilder<Pool, Client>
 where
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec = ChainSpec> + Clone,
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+    Pool: TransactionPool<
+        Transaction: PoolTransaction<Consensus = TransactionSigned> + MaybeConditionalTransaction,
+    >,
 {
     type Attributes = CustomPayloadBuilderAttributes;
     type BuiltPayload = EthBuiltPayload;
@@ -23,7 +23,7 @@ use reth_ethereum::{
         node::EthereumAddOns,
         EthEngineTypes, EthEvmConfig, EthereumNode,
     },
-    pool::{PoolTransaction, TransactionPool},
+    pool::{conditional::MaybeConditionalTransaction, PoolTransaction, TransactionPool},
     provider::CanonStateSubscriptions,
     EthPrimitives, TransactionSigned,
 };
@@ -46,8 +46,10 @@ where
             Primitives = EthPrimitives,
         >,
     >,
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
-        + Unpin
+    Pool: TransactionPool<
+            Transaction: PoolTransaction<Consensus = TransactionSigned>
+                             + MaybeConditionalTransaction,
+        > + Unpin
         + 'static,
 {
     async fn spawn_payload_builder_service(